@@ -2,34 +2,279 @@
 //!
 //! Handles loading configuration from environment variables.
 
-use std::env;
+use std::{collections::HashMap, env};
+
+use alloy::primitives::Address;
+use reqwest::header::{HeaderName, HeaderValue};
+use rust_decimal::Decimal;
 
 use crate::error::AppError;
-use crate::ethereum::constants::DEFAULT_CHAIN_ID;
+use crate::ethereum::constants::{UniswapAddresses, DEFAULT_CHAIN_ID};
+use crate::types::{ProtocolPreference, Venue};
+
+/// Proxy used for outbound HTTP requests other than RPC, such as token list fetches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HttpProxy {
+    /// Use `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` from the environment.
+    #[default]
+    Environment,
+    /// Connect directly, ignoring any proxy environment variables.
+    Disabled,
+    /// Send every request through this proxy URL, except hosts listed in `NO_PROXY`.
+    Url(String),
+}
+
+impl HttpProxy {
+    /// Apply this proxy setting to an HTTP client builder.
+    ///
+    /// # Errors
+    /// Returns an error if the proxy URL is invalid.
+    pub fn apply(
+        &self,
+        builder: reqwest::ClientBuilder,
+    ) -> Result<reqwest::ClientBuilder, AppError> {
+        match self {
+            Self::Environment => Ok(builder),
+            Self::Disabled => Ok(builder.no_proxy()),
+            Self::Url(url) => {
+                let proxy = reqwest::Proxy::all(url)
+                    .map_err(|e| AppError::Config(format!("Invalid HTTP proxy URL: {}", e)))?
+                    .no_proxy(reqwest::NoProxy::from_env());
+                Ok(builder.proxy(proxy))
+            }
+        }
+    }
+}
+
+/// Default timeout for a tool call in seconds.
+pub const DEFAULT_TOOL_TIMEOUT_SECS: u64 = 60;
+
+/// Default number of concurrent RPC lookups one fan-out request may run.
+pub const DEFAULT_FAN_OUT_CONCURRENCY: usize = 4;
+
+/// Default slippage tolerance floor for swap_tokens, as a percentage (0.05%).
+pub const DEFAULT_MIN_SLIPPAGE_TOLERANCE: Decimal = Decimal::from_parts(5, 0, 0, false, 2);
+
+/// Default largest discrepancy between a swap quote and its cross-check, as a percentage (1%).
+pub const DEFAULT_QUOTE_CROSS_CHECK_TOLERANCE: Decimal = Decimal::ONE;
+
+/// Default price impact percentage above which swap_tokens refuses a swap (50%).
+pub const DEFAULT_MAX_PRICE_IMPACT: Decimal = Decimal::from_parts(50, 0, 0, false, 0);
+
+/// Default number of mnemonic accounts listed at startup.
+pub const DEFAULT_MNEMONIC_PREVIEW_COUNT: u32 = 5;
+
+/// Default maximum number of tokens loaded from the token list.
+pub const DEFAULT_TOKEN_LIST_MAX_TOKENS: usize = 100_000;
 
 /// Application configuration.
 #[derive(Debug, Clone)]
 pub struct Config {
     /// Ethereum JSON-RPC endpoint URL.
     pub rpc_url: String,
-    /// Private key for wallet (hex string with 0x prefix).
+    /// Extra HTTP headers sent with every RPC request, e.g. an `Authorization` token.
+    pub rpc_headers: Vec<(String, String)>,
+    /// Private key for wallet (hex string with 0x prefix). Empty for read-only mode.
     pub private_key: String,
+    /// BIP-39 mnemonic phrase for the wallet, used instead of `private_key`. Empty for none.
+    pub mnemonic: String,
+    /// Index of the mnemonic account to use (default: 0).
+    pub mnemonic_index: u32,
+    /// Address of the mnemonic account to use, instead of picking it by index.
+    pub mnemonic_address: Option<Address>,
+    /// Number of mnemonic accounts listed at startup (default: 5).
+    pub mnemonic_preview_count: u32,
     /// Logging level (default: info).
     pub log_level: String,
     /// Chain ID (default: 1 for Ethereum mainnet).
     pub chain_id: u64,
+    /// Tokens priced at 1.0 in USD (default: the chain's known stablecoins).
+    pub usd_pegged_tokens: Option<Vec<Address>>,
+    /// Token priced at 1.0 in ETH (default: the chain's wrapped native token).
+    pub wrapped_native_token: Option<Address>,
+    /// Background token list refresh interval in seconds (default: disabled).
+    pub token_auto_refresh_secs: Option<u64>,
+    /// Whether the token list is loaded in the background at startup (default: false).
+    pub prewarm_tokens: bool,
+    /// Average block time in seconds (default: the chain's known block time).
+    pub seconds_per_block: Option<u64>,
+    /// Interval in seconds for re-reading the chain ID from the RPC node (default: disabled).
+    pub chain_id_revalidate_secs: Option<u64>,
+    /// Maximum number of tokens loaded from the token list (default: 100,000).
+    pub token_list_max_tokens: usize,
+    /// Minimum pool liquidity for automatic route selection (default: 0, no minimum).
+    pub min_pool_liquidity: u128,
+    /// Timeout for a whole tool call in seconds (default: 60, 0 disables it).
+    pub tool_timeout_secs: u64,
+    /// Per-tool timeouts in seconds by tool name, overriding `tool_timeout_secs`.
+    pub tool_timeout_overrides: HashMap<String, u64>,
+    /// Concurrent RPC lookups one fan-out request may run (default: 4).
+    pub fan_out_concurrency: usize,
+    /// Whether wallet info lists zero token balances (default: false).
+    pub include_zero_balances: bool,
+    /// Blocks behind the latest at which balances and reserves are read (default: 0, latest).
+    pub confirmations: u64,
+    /// Whether trading tools only accept tokens from a curated token list (default: false).
+    pub trusted_tokens_only: bool,
+    /// Tokens flagged as rebasing (default: the chain's known rebasing tokens).
+    pub rebasing_tokens: Option<Vec<Address>>,
+    /// Names of the tools to expose (default: all tools).
+    pub enabled_tools: Option<Vec<String>>,
+    /// Whether prices fall back to the last known price when every source fails (default: false).
+    pub stale_price_fallback: bool,
+    /// Protocol order for automatic swap route selection (default: V3 first).
+    pub protocol_preference: ProtocolPreference,
+    /// Slippage tolerance percentage swap_tokens raises lower requests to (default: 0.05).
+    pub min_slippage_tolerance: Decimal,
+    /// Uniswap-compatible contracts to use (default: the Uniswap mainnet deployments).
+    pub uniswap_addresses: UniswapAddresses,
+    /// Venues V2 routes and prices are quoted on (default: Uniswap and Sushiswap on mainnet).
+    pub v2_venues: Option<Vec<Venue>>,
+    /// Whether swap_tokens cross-checks quotes against pool state by default (default: false).
+    pub quote_cross_check: bool,
+    /// Largest discrepancy, as a percentage, a quote cross-check accepts (default: 1).
+    pub quote_cross_check_tolerance: Decimal,
+    /// Proxy for token list fetches (default: the proxy environment variables).
+    pub http_proxy: HttpProxy,
+    /// Price impact percentage above which swap_tokens refuses a swap (default: 50).
+    pub max_price_impact: Decimal,
+    /// Per-token trade sizes, in whole tokens, Uniswap V3 prices are quoted at,
+    /// on top of the chain's defaults (default: none, 1 token).
+    pub price_reference_amounts: Vec<(Address, Decimal)>,
+    /// Chainlink feeds whose zero and negative answers are accepted (default: none).
+    pub signed_chainlink_feeds: Vec<Address>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            rpc_url: String::new(),
+            rpc_headers: Vec::new(),
+            private_key: String::new(),
+            mnemonic: String::new(),
+            mnemonic_index: 0,
+            mnemonic_address: None,
+            mnemonic_preview_count: DEFAULT_MNEMONIC_PREVIEW_COUNT,
+            log_level: "info".to_string(),
+            chain_id: DEFAULT_CHAIN_ID,
+            usd_pegged_tokens: None,
+            wrapped_native_token: None,
+            token_auto_refresh_secs: None,
+            prewarm_tokens: false,
+            seconds_per_block: None,
+            chain_id_revalidate_secs: None,
+            token_list_max_tokens: DEFAULT_TOKEN_LIST_MAX_TOKENS,
+            min_pool_liquidity: 0,
+            tool_timeout_secs: DEFAULT_TOOL_TIMEOUT_SECS,
+            tool_timeout_overrides: HashMap::new(),
+            fan_out_concurrency: DEFAULT_FAN_OUT_CONCURRENCY,
+            include_zero_balances: false,
+            confirmations: 0,
+            trusted_tokens_only: false,
+            rebasing_tokens: None,
+            enabled_tools: None,
+            stale_price_fallback: false,
+            protocol_preference: ProtocolPreference::V3First,
+            min_slippage_tolerance: DEFAULT_MIN_SLIPPAGE_TOLERANCE,
+            uniswap_addresses: UniswapAddresses::default(),
+            v2_venues: None,
+            quote_cross_check: false,
+            quote_cross_check_tolerance: DEFAULT_QUOTE_CROSS_CHECK_TOLERANCE,
+            http_proxy: HttpProxy::Environment,
+            max_price_impact: DEFAULT_MAX_PRICE_IMPACT,
+            price_reference_amounts: Vec::new(),
+            signed_chainlink_feeds: Vec::new(),
+        }
+    }
 }
 
 impl Config {
+    /// Whether the server runs without a wallet.
+    pub fn is_read_only(&self) -> bool {
+        self.private_key.trim().is_empty() && self.mnemonic.trim().is_empty()
+    }
+
     /// Load configuration from environment variables.
     ///
     /// Required environment variables:
     /// - `ETHEREUM_RPC_URL`: Ethereum JSON-RPC endpoint
-    /// - `ETHEREUM_PRIVATE_KEY`: Private key for wallet (hex)
     ///
     /// Optional environment variables:
+    /// - `ETHEREUM_RPC_HEADERS`: Comma-separated `name:value` headers sent with
+    ///   every RPC request, e.g. `Authorization:Bearer <token>` (default: none)
+    /// - `ETHEREUM_PRIVATE_KEY`: Private key for wallet (hex). Without it the
+    ///   server runs read-only and swap simulations are unavailable.
+    /// - `ETHEREUM_MNEMONIC`: BIP-39 mnemonic phrase to derive the wallet from,
+    ///   instead of `ETHEREUM_PRIVATE_KEY`
+    /// - `ETHEREUM_MNEMONIC_INDEX`: Index of the mnemonic account to use, on the
+    ///   path `m/44'/60'/0'/0/{index}` (default: 0)
+    /// - `ETHEREUM_MNEMONIC_ADDRESS`: Use the mnemonic account with this address
+    ///   instead of picking it by index
+    /// - `ETHEREUM_MNEMONIC_PREVIEW_COUNT`: Number of mnemonic accounts listed in
+    ///   the startup log (default: 5)
     /// - `LOG_LEVEL`: Logging level (default: info)
     /// - `ETHEREUM_CHAIN_ID`: Chain ID (default: 1 for Ethereum mainnet)
+    /// - `ETHEREUM_USD_PEGGED_TOKENS`: Comma-separated stablecoin addresses priced
+    ///   at 1.0 in USD (default: USDC, USDT and DAI on mainnet)
+    /// - `ETHEREUM_WRAPPED_NATIVE_TOKEN`: Address priced at 1.0 in ETH
+    ///   (default: the chain's WETH)
+    /// - `ETHEREUM_TOKEN_AUTO_REFRESH_SECS`: Refresh the token list in the
+    ///   background at this interval (default: disabled, refresh lazily)
+    /// - `ETHEREUM_PREWARM_TOKENS`: Load the token list in the background right
+    ///   after startup instead of on the first lookup (default: false)
+    /// - `ETHEREUM_SECONDS_PER_BLOCK`: Average block time used for block
+    ///   estimates (default: the chain's known block time, 12 on mainnet)
+    /// - `ETHEREUM_CHAIN_ID_REVALIDATE_SECS`: Re-read the RPC node's chain ID at
+    ///   this interval and warn if it changed (default: disabled, read once)
+    /// - `ETHEREUM_TOKEN_LIST_MAX_TOKENS`: Load at most this many tokens from the
+    ///   token list, dropping the rest (default: 100000)
+    /// - `ETHEREUM_MIN_POOL_LIQUIDITY`: Exclude pools with less liquidity from
+    ///   route selection (default: 0, no minimum)
+    /// - `ETHEREUM_TOOL_TIMEOUT_SECS`: Timeout for a whole tool call (default: 60,
+    ///   0 disables it)
+    /// - `ETHEREUM_TOOL_TIMEOUTS`: Per-tool timeouts as `tool=secs` pairs, e.g.
+    ///   `swap_tokens=120,get_balance=10`
+    /// - `ETHEREUM_FAN_OUT_CONCURRENCY`: Concurrent RPC lookups one request that
+    ///   queries many tokens may run (default: 4)
+    /// - `ETHEREUM_INCLUDE_ZERO_BALANCES`: List zero token balances in wallet
+    ///   info instead of only counting them (default: false)
+    /// - `ETHEREUM_CONFIRMATIONS`: Read balances and pool reserves this many
+    ///   blocks behind the latest (default: 0, latest block)
+    /// - `ETHEREUM_TRUSTED_TOKENS_ONLY`: Refuse swaps, quotes and liquidity simulations
+    ///   involving tokens that are not built in or on the Uniswap default token list
+    ///   (default: false)
+    /// - `ETHEREUM_REBASING_TOKENS`: Comma-separated addresses of rebasing tokens
+    ///   flagged in balances and swaps (default: stETH and AMPL on mainnet)
+    /// - `MCP_ENABLED_TOOLS`: Comma-separated names of the tools to expose, e.g.
+    ///   `get_balance,get_token_price` (default: all tools)
+    /// - `ETHEREUM_STALE_PRICE_FALLBACK`: When every price source fails, return
+    ///   the last price fetched for the token, marked stale (default: false)
+    /// - `ETHEREUM_PROTOCOL_PREFERENCE`: Protocol order for swap route selection:
+    ///   `v3_first`, `v2_first` or `best` (default: `v3_first`)
+    /// - `ETHEREUM_MIN_SLIPPAGE_TOLERANCE`: Slippage tolerance percentage that
+    ///   swap_tokens raises lower requests to (default: 0.05, 0 disables it)
+    /// - `UNISWAP_V2_ROUTER_OVERRIDE`, `UNISWAP_V2_FACTORY_OVERRIDE`,
+    ///   `UNISWAP_V3_ROUTER_OVERRIDE`, `UNISWAP_V3_FACTORY_OVERRIDE`,
+    ///   `UNISWAP_V3_QUOTER_OVERRIDE`: Use this contract instead of the Uniswap
+    ///   mainnet deployment, e.g. on a fork or a Uniswap-compatible DEX
+    /// - `ETHEREUM_V2_VENUES`: Comma-separated V2 venues quoted for swaps and
+    ///   prices, `uniswap` and/or `sushiswap`; Sushiswap is only known on mainnet
+    ///   (default: both on mainnet, `uniswap` elsewhere)
+    /// - `ETHEREUM_QUOTE_CROSS_CHECK`: Re-quote every swap_tokens route from pool
+    ///   state and compare the outputs, unless the request says otherwise (default: false)
+    /// - `ETHEREUM_QUOTE_CROSS_CHECK_TOLERANCE`: Discrepancy percentage above which
+    ///   a cross-checked quote carries a warning (default: 1)
+    /// - `ETHEREUM_HTTP_PROXY`: Proxy URL for token list fetches, taking precedence
+    ///   over `HTTP_PROXY`/`HTTPS_PROXY`, or `none` to connect directly (default:
+    ///   the proxy environment variables, honoring `NO_PROXY`)
+    /// - `ETHEREUM_MAX_PRICE_IMPACT`: Price impact percentage above which
+    ///   swap_tokens refuses to return a simulation (default: 50, 100 disables it)
+    /// - `ETHEREUM_PRICE_REFERENCE_AMOUNTS`: Trade sizes Uniswap V3 prices are
+    ///   quoted at as `address=amount` pairs in whole tokens, overriding the chain's
+    ///   defaults (default: 0.01 WBTC on mainnet, 1 token otherwise)
+    /// - `ETHEREUM_SIGNED_CHAINLINK_FEEDS`: Comma-separated Chainlink feed addresses
+    ///   whose zero and negative answers are valid, such as spread or rate feeds
+    ///   (default: none, every answer must be positive)
     pub fn from_env() -> Result<Self, AppError> {
         // Load .env file if present
         let _ = dotenvy::dotenv();
@@ -38,9 +283,49 @@ impl Config {
             AppError::Config("ETHEREUM_RPC_URL environment variable not set".into())
         })?;
 
-        let private_key = env::var("ETHEREUM_PRIVATE_KEY").map_err(|_| {
-            AppError::Config("ETHEREUM_PRIVATE_KEY environment variable not set".into())
-        })?;
+        let rpc_headers = env::var("ETHEREUM_RPC_HEADERS")
+            .ok()
+            .map(|s| parse_rpc_headers("ETHEREUM_RPC_HEADERS", &s))
+            .transpose()?
+            .unwrap_or_default();
+
+        let private_key = env::var("ETHEREUM_PRIVATE_KEY").unwrap_or_default();
+
+        let mnemonic = env::var("ETHEREUM_MNEMONIC").unwrap_or_default();
+        if !mnemonic.trim().is_empty() && !private_key.trim().is_empty() {
+            return Err(AppError::Config(
+                "Set either ETHEREUM_PRIVATE_KEY or ETHEREUM_MNEMONIC, not both".into(),
+            ));
+        }
+
+        let mnemonic_index = env::var("ETHEREUM_MNEMONIC_INDEX")
+            .ok()
+            .map(|s| {
+                s.trim().parse::<u32>().map_err(|_| {
+                    AppError::Config(format!("Invalid ETHEREUM_MNEMONIC_INDEX: {}", s))
+                })
+            })
+            .transpose()?;
+
+        let mnemonic_address = env::var("ETHEREUM_MNEMONIC_ADDRESS")
+            .ok()
+            .map(|s| parse_address("ETHEREUM_MNEMONIC_ADDRESS", &s))
+            .transpose()?;
+        if mnemonic_index.is_some() && mnemonic_address.is_some() {
+            return Err(AppError::Config(
+                "Set either ETHEREUM_MNEMONIC_INDEX or ETHEREUM_MNEMONIC_ADDRESS, not both".into(),
+            ));
+        }
+
+        let mnemonic_preview_count = env::var("ETHEREUM_MNEMONIC_PREVIEW_COUNT")
+            .ok()
+            .map(|s| {
+                s.trim().parse::<u32>().map_err(|_| {
+                    AppError::Config(format!("Invalid ETHEREUM_MNEMONIC_PREVIEW_COUNT: {}", s))
+                })
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_MNEMONIC_PREVIEW_COUNT);
 
         let log_level = env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
 
@@ -49,8 +334,420 @@ impl Config {
             .and_then(|s| s.parse::<u64>().ok())
             .unwrap_or(DEFAULT_CHAIN_ID);
 
-        Ok(Self { rpc_url, private_key, log_level, chain_id })
+        let usd_pegged_tokens = env::var("ETHEREUM_USD_PEGGED_TOKENS")
+            .ok()
+            .map(|s| parse_address_list("ETHEREUM_USD_PEGGED_TOKENS", &s))
+            .transpose()?;
+
+        let wrapped_native_token = env::var("ETHEREUM_WRAPPED_NATIVE_TOKEN")
+            .ok()
+            .map(|s| parse_address("ETHEREUM_WRAPPED_NATIVE_TOKEN", &s))
+            .transpose()?;
+
+        let token_auto_refresh_secs = env::var("ETHEREUM_TOKEN_AUTO_REFRESH_SECS")
+            .ok()
+            .map(|s| parse_refresh_secs("ETHEREUM_TOKEN_AUTO_REFRESH_SECS", &s))
+            .transpose()?
+            .flatten();
+
+        let prewarm_tokens = env::var("ETHEREUM_PREWARM_TOKENS")
+            .ok()
+            .map(|s| parse_bool("ETHEREUM_PREWARM_TOKENS", &s))
+            .transpose()?
+            .unwrap_or(false);
+
+        let seconds_per_block = env::var("ETHEREUM_SECONDS_PER_BLOCK")
+            .ok()
+            .map(|s| parse_block_time("ETHEREUM_SECONDS_PER_BLOCK", &s))
+            .transpose()?;
+
+        let chain_id_revalidate_secs = env::var("ETHEREUM_CHAIN_ID_REVALIDATE_SECS")
+            .ok()
+            .map(|s| parse_refresh_secs("ETHEREUM_CHAIN_ID_REVALIDATE_SECS", &s))
+            .transpose()?
+            .flatten();
+
+        let token_list_max_tokens = env::var("ETHEREUM_TOKEN_LIST_MAX_TOKENS")
+            .ok()
+            .map(|s| match s.trim().parse::<usize>() {
+                Ok(max) if max > 0 => Ok(max),
+                _ => {
+                    Err(AppError::Config(format!("Invalid ETHEREUM_TOKEN_LIST_MAX_TOKENS: {}", s)))
+                }
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_TOKEN_LIST_MAX_TOKENS);
+
+        let min_pool_liquidity = env::var("ETHEREUM_MIN_POOL_LIQUIDITY")
+            .ok()
+            .map(|s| {
+                s.trim().parse::<u128>().map_err(|_| {
+                    AppError::Config(format!("Invalid ETHEREUM_MIN_POOL_LIQUIDITY: {}", s))
+                })
+            })
+            .transpose()?
+            .unwrap_or(0);
+
+        let tool_timeout_secs = env::var("ETHEREUM_TOOL_TIMEOUT_SECS")
+            .ok()
+            .map(|s| parse_secs("ETHEREUM_TOOL_TIMEOUT_SECS", &s))
+            .transpose()?
+            .unwrap_or(DEFAULT_TOOL_TIMEOUT_SECS);
+
+        let tool_timeout_overrides = env::var("ETHEREUM_TOOL_TIMEOUTS")
+            .ok()
+            .map(|s| parse_tool_timeouts("ETHEREUM_TOOL_TIMEOUTS", &s))
+            .transpose()?
+            .unwrap_or_default();
+
+        let fan_out_concurrency = env::var("ETHEREUM_FAN_OUT_CONCURRENCY")
+            .ok()
+            .map(|s| match s.trim().parse::<usize>() {
+                Ok(limit) if limit > 0 => Ok(limit),
+                _ => Err(AppError::Config(format!("Invalid ETHEREUM_FAN_OUT_CONCURRENCY: {}", s))),
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_FAN_OUT_CONCURRENCY);
+
+        let include_zero_balances = env::var("ETHEREUM_INCLUDE_ZERO_BALANCES")
+            .ok()
+            .map(|s| parse_bool("ETHEREUM_INCLUDE_ZERO_BALANCES", &s))
+            .transpose()?
+            .unwrap_or(false);
+
+        let confirmations = env::var("ETHEREUM_CONFIRMATIONS")
+            .ok()
+            .map(|s| {
+                s.trim()
+                    .parse::<u64>()
+                    .map_err(|_| AppError::Config(format!("Invalid ETHEREUM_CONFIRMATIONS: {}", s)))
+            })
+            .transpose()?
+            .unwrap_or(0);
+
+        let trusted_tokens_only = env::var("ETHEREUM_TRUSTED_TOKENS_ONLY")
+            .ok()
+            .map(|s| parse_bool("ETHEREUM_TRUSTED_TOKENS_ONLY", &s))
+            .transpose()?
+            .unwrap_or(false);
+
+        let rebasing_tokens = env::var("ETHEREUM_REBASING_TOKENS")
+            .ok()
+            .map(|s| parse_address_list("ETHEREUM_REBASING_TOKENS", &s))
+            .transpose()?;
+
+        let enabled_tools = env::var("MCP_ENABLED_TOOLS")
+            .ok()
+            .map(|s| parse_tool_list("MCP_ENABLED_TOOLS", &s))
+            .transpose()?;
+
+        let stale_price_fallback = env::var("ETHEREUM_STALE_PRICE_FALLBACK")
+            .ok()
+            .map(|s| parse_bool("ETHEREUM_STALE_PRICE_FALLBACK", &s))
+            .transpose()?
+            .unwrap_or(false);
+
+        let protocol_preference = env::var("ETHEREUM_PROTOCOL_PREFERENCE")
+            .ok()
+            .map(|s| {
+                s.trim().parse::<ProtocolPreference>().map_err(|e| {
+                    AppError::Config(format!("Invalid ETHEREUM_PROTOCOL_PREFERENCE: {}", e))
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let min_slippage_tolerance = env::var("ETHEREUM_MIN_SLIPPAGE_TOLERANCE")
+            .ok()
+            .map(|s| match Decimal::from_str_exact(s.trim()) {
+                Ok(pct) if pct >= Decimal::ZERO && pct <= Decimal::from(50) => Ok(pct),
+                _ => Err(AppError::Config(format!(
+                    "Invalid ETHEREUM_MIN_SLIPPAGE_TOLERANCE: {} (expected a percentage from 0 \
+                     to 50)",
+                    s
+                ))),
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_MIN_SLIPPAGE_TOLERANCE);
+
+        let mut uniswap_addresses = UniswapAddresses::default();
+        for (name, address) in [
+            ("UNISWAP_V2_ROUTER_OVERRIDE", &mut uniswap_addresses.v2_router),
+            ("UNISWAP_V2_FACTORY_OVERRIDE", &mut uniswap_addresses.v2_factory),
+            ("UNISWAP_V3_ROUTER_OVERRIDE", &mut uniswap_addresses.v3_router),
+            ("UNISWAP_V3_FACTORY_OVERRIDE", &mut uniswap_addresses.v3_factory),
+            ("UNISWAP_V3_QUOTER_OVERRIDE", &mut uniswap_addresses.v3_quoter),
+        ] {
+            if let Ok(value) = env::var(name) {
+                *address = parse_contract_address(name, &value)?;
+            }
+        }
+
+        let v2_venues = env::var("ETHEREUM_V2_VENUES")
+            .ok()
+            .map(|s| parse_venue_list("ETHEREUM_V2_VENUES", &s))
+            .transpose()?;
+
+        let quote_cross_check = env::var("ETHEREUM_QUOTE_CROSS_CHECK")
+            .ok()
+            .map(|s| parse_bool("ETHEREUM_QUOTE_CROSS_CHECK", &s))
+            .transpose()?
+            .unwrap_or(false);
+
+        let quote_cross_check_tolerance = env::var("ETHEREUM_QUOTE_CROSS_CHECK_TOLERANCE")
+            .ok()
+            .map(|s| match Decimal::from_str_exact(s.trim()) {
+                Ok(pct) if pct >= Decimal::ZERO && pct <= Decimal::ONE_HUNDRED => Ok(pct),
+                _ => Err(AppError::Config(format!(
+                    "Invalid ETHEREUM_QUOTE_CROSS_CHECK_TOLERANCE: {} (expected a percentage \
+                     from 0 to 100)",
+                    s
+                ))),
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_QUOTE_CROSS_CHECK_TOLERANCE);
+
+        let http_proxy = env::var("ETHEREUM_HTTP_PROXY")
+            .ok()
+            .map(|s| parse_http_proxy("ETHEREUM_HTTP_PROXY", &s))
+            .transpose()?
+            .unwrap_or_default();
+
+        let max_price_impact = env::var("ETHEREUM_MAX_PRICE_IMPACT")
+            .ok()
+            .map(|s| match Decimal::from_str_exact(s.trim()) {
+                Ok(pct) if pct > Decimal::ZERO && pct <= Decimal::ONE_HUNDRED => Ok(pct),
+                _ => Err(AppError::Config(format!(
+                    "Invalid ETHEREUM_MAX_PRICE_IMPACT: {} (expected a percentage above 0, up \
+                     to 100)",
+                    s
+                ))),
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_MAX_PRICE_IMPACT);
+
+        let price_reference_amounts = env::var("ETHEREUM_PRICE_REFERENCE_AMOUNTS")
+            .ok()
+            .map(|s| parse_reference_amounts("ETHEREUM_PRICE_REFERENCE_AMOUNTS", &s))
+            .transpose()?
+            .unwrap_or_default();
+
+        let signed_chainlink_feeds = env::var("ETHEREUM_SIGNED_CHAINLINK_FEEDS")
+            .ok()
+            .map(|s| parse_address_list("ETHEREUM_SIGNED_CHAINLINK_FEEDS", &s))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Self {
+            rpc_url,
+            rpc_headers,
+            private_key,
+            mnemonic,
+            mnemonic_index: mnemonic_index.unwrap_or_default(),
+            mnemonic_address,
+            mnemonic_preview_count,
+            log_level,
+            chain_id,
+            usd_pegged_tokens,
+            wrapped_native_token,
+            token_auto_refresh_secs,
+            prewarm_tokens,
+            seconds_per_block,
+            chain_id_revalidate_secs,
+            token_list_max_tokens,
+            min_pool_liquidity,
+            tool_timeout_secs,
+            tool_timeout_overrides,
+            fan_out_concurrency,
+            include_zero_balances,
+            confirmations,
+            trusted_tokens_only,
+            rebasing_tokens,
+            enabled_tools,
+            stale_price_fallback,
+            protocol_preference,
+            min_slippage_tolerance,
+            uniswap_addresses,
+            v2_venues,
+            quote_cross_check,
+            quote_cross_check_tolerance,
+            http_proxy,
+            max_price_impact,
+            price_reference_amounts,
+            signed_chainlink_feeds,
+        })
+    }
+}
+
+/// Parse a proxy URL, or `none` to disable proxying, from environment variable `name`.
+fn parse_http_proxy(name: &str, value: &str) -> Result<HttpProxy, AppError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(HttpProxy::Environment);
+    }
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(HttpProxy::Disabled);
+    }
+    reqwest::Proxy::all(value)
+        .map_err(|_| AppError::Config(format!("Invalid {}: {}", name, value)))?;
+    Ok(HttpProxy::Url(value.to_string()))
+}
+
+/// Parse a boolean flag from environment variable `name`.
+fn parse_bool(name: &str, value: &str) -> Result<bool, AppError> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => Ok(true),
+        "false" | "0" | "no" => Ok(false),
+        _ => Err(AppError::Config(format!("Invalid {}: {}", name, value))),
+    }
+}
+
+/// Parse an address from environment variable `name`.
+fn parse_address(name: &str, value: &str) -> Result<Address, AppError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| AppError::Config(format!("Invalid address in {}: {}", name, value.trim())))
+}
+
+/// Parse a contract address from environment variable `name`, rejecting the zero address.
+fn parse_contract_address(name: &str, value: &str) -> Result<Address, AppError> {
+    match parse_address(name, value)? {
+        Address::ZERO => Err(AppError::Config(format!("{} must not be the zero address", name))),
+        address => Ok(address),
+    }
+}
+
+/// Parse a non-empty comma-separated venue list from environment variable `name`,
+/// dropping duplicates.
+fn parse_venue_list(name: &str, value: &str) -> Result<Vec<Venue>, AppError> {
+    let mut venues = Vec::new();
+    for venue in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let venue = venue
+            .parse::<Venue>()
+            .map_err(|e| AppError::Config(format!("Invalid {}: {}", name, e)))?;
+        if !venues.contains(&venue) {
+            venues.push(venue);
+        }
     }
+    if venues.is_empty() {
+        return Err(AppError::Config(format!("{} must name at least one venue", name)));
+    }
+    Ok(venues)
+}
+
+/// Parse a number of seconds from environment variable `name`.
+fn parse_secs(name: &str, value: &str) -> Result<u64, AppError> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| AppError::Config(format!("Invalid number of seconds in {}: {}", name, value)))
+}
+
+/// Parse a refresh interval in seconds from environment variable `name`.
+///
+/// Zero disables the refresh.
+fn parse_refresh_secs(name: &str, value: &str) -> Result<Option<u64>, AppError> {
+    let secs = parse_secs(name, value)?;
+    Ok((secs > 0).then_some(secs))
+}
+
+/// Parse an average block time in seconds from environment variable `name`.
+///
+/// Zero is rejected since every block estimate divides by it.
+fn parse_block_time(name: &str, value: &str) -> Result<u64, AppError> {
+    match parse_secs(name, value)? {
+        0 => Err(AppError::Config(format!("{} must be greater than 0", name))),
+        secs => Ok(secs),
+    }
+}
+
+/// Parse comma-separated `tool=secs` pairs from environment variable `name`.
+fn parse_tool_timeouts(name: &str, value: &str) -> Result<HashMap<String, u64>, AppError> {
+    value
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|pair| {
+            let (tool, secs) = pair.split_once('=').ok_or_else(|| {
+                AppError::Config(format!("Expected tool=secs in {}: {}", name, pair.trim()))
+            })?;
+            Ok((tool.trim().to_string(), parse_secs(name, secs)?))
+        })
+        .collect()
+}
+
+/// Parse comma-separated `address=amount` pairs from environment variable `name`.
+///
+/// Amounts are whole tokens and must be positive.
+fn parse_reference_amounts(name: &str, value: &str) -> Result<Vec<(Address, Decimal)>, AppError> {
+    value
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|pair| {
+            let (token, amount) = pair.split_once('=').ok_or_else(|| {
+                AppError::Config(format!("Expected address=amount in {}: {}", name, pair.trim()))
+            })?;
+            match Decimal::from_str_exact(amount.trim()) {
+                Ok(amount) if amount > Decimal::ZERO => Ok((parse_address(name, token)?, amount)),
+                _ => Err(AppError::Config(format!(
+                    "Invalid amount in {}: {} (expected a positive number of tokens)",
+                    name,
+                    amount.trim()
+                ))),
+            }
+        })
+        .collect()
+}
+
+/// Parse a comma-separated list of tool names from environment variable `name`.
+///
+/// At least one name is required. The server checks that each one is a tool
+/// it provides.
+fn parse_tool_list(name: &str, value: &str) -> Result<Vec<String>, AppError> {
+    let tools: Vec<String> =
+        value.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect();
+
+    if tools.is_empty() {
+        return Err(AppError::Config(format!("{} must list at least one tool", name)));
+    }
+    Ok(tools)
+}
+
+/// Parse comma-separated `name:value` HTTP headers from environment variable `name`.
+///
+/// Header names and values are validated here, so a typo fails at startup
+/// rather than on the first RPC request. Values are never echoed in errors.
+fn parse_rpc_headers(name: &str, value: &str) -> Result<Vec<(String, String)>, AppError> {
+    value
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(|pair| {
+            let (header, header_value) = pair.split_once(':').ok_or_else(|| {
+                AppError::Config(format!("Expected name:value headers in {}", name))
+            })?;
+            let (header, header_value) = (header.trim(), header_value.trim());
+            if HeaderName::from_bytes(header.as_bytes()).is_err() {
+                return Err(AppError::Config(format!(
+                    "Invalid header name in {}: {}",
+                    name, header
+                )));
+            }
+            if HeaderValue::from_str(header_value).is_err() {
+                return Err(AppError::Config(format!(
+                    "Invalid value for header {} in {}",
+                    header, name
+                )));
+            }
+            Ok((header.to_string(), header_value.to_string()))
+        })
+        .collect()
+}
+
+/// Parse a comma-separated address list from environment variable `name`.
+///
+/// An empty value yields an empty list.
+fn parse_address_list(name: &str, value: &str) -> Result<Vec<Address>, AppError> {
+    value.split(',').filter(|s| !s.trim().is_empty()).map(|s| parse_address(name, s)).collect()
 }
 
 #[cfg(test)]
@@ -68,6 +765,7 @@ mod tests {
             private_key: "0xkey".to_string(),
             log_level: "info".to_string(),
             chain_id: 1,
+            ..Default::default()
         };
 
         assert_eq!(config.rpc_url, "https://rpc.example.com");
@@ -83,6 +781,7 @@ mod tests {
             private_key: "0xkey".to_string(),
             log_level: "info".to_string(),
             chain_id: 1,
+            ..Default::default()
         };
 
         let cloned = config.clone();
@@ -99,6 +798,7 @@ mod tests {
             private_key: "0xsecret".to_string(),
             log_level: "warn".to_string(),
             chain_id: 1,
+            ..Default::default()
         };
 
         let debug_str = format!("{:?}", config);
@@ -122,6 +822,7 @@ mod tests {
             private_key: "0x1".to_string(),
             log_level: "info".to_string(),
             chain_id: 1,
+            ..Default::default()
         };
         assert_eq!(mainnet.chain_id, 1);
 
@@ -131,6 +832,7 @@ mod tests {
             private_key: "0x2".to_string(),
             log_level: "debug".to_string(),
             chain_id: 11155111,
+            ..Default::default()
         };
         assert_eq!(sepolia.chain_id, 11155111);
 
@@ -140,6 +842,7 @@ mod tests {
             private_key: "0x3".to_string(),
             log_level: "error".to_string(),
             chain_id: 42161,
+            ..Default::default()
         };
         assert_eq!(arbitrum.chain_id, 42161);
     }
@@ -152,6 +855,7 @@ mod tests {
                 private_key: "0x".to_string(),
                 log_level: level.to_string(),
                 chain_id: 1,
+                ..Default::default()
             };
             assert_eq!(config.log_level, level);
         }
@@ -172,6 +876,7 @@ mod tests {
                 private_key: "0x".to_string(),
                 log_level: "info".to_string(),
                 chain_id: 1,
+                ..Default::default()
             };
             assert_eq!(config.rpc_url, url);
         }
@@ -185,6 +890,7 @@ mod tests {
             private_key: "0x1234567890abcdef".to_string(),
             log_level: "info".to_string(),
             chain_id: 1,
+            ..Default::default()
         };
         assert!(config1.private_key.starts_with("0x"));
 
@@ -194,7 +900,206 @@ mod tests {
             private_key: "1234567890abcdef".to_string(),
             log_level: "info".to_string(),
             chain_id: 1,
+            ..Default::default()
         };
         assert!(!config2.private_key.starts_with("0x"));
     }
+
+    #[test]
+    fn test_config_default() {
+        let config = Config::default();
+        assert!(config.rpc_headers.is_empty());
+        assert_eq!(config.log_level, "info");
+        assert_eq!(config.chain_id, DEFAULT_CHAIN_ID);
+        assert!(config.usd_pegged_tokens.is_none());
+        assert!(config.wrapped_native_token.is_none());
+        assert!(config.token_auto_refresh_secs.is_none());
+        assert!(!config.prewarm_tokens);
+        assert!(config.seconds_per_block.is_none());
+        assert!(config.chain_id_revalidate_secs.is_none());
+        assert!(config.mnemonic.is_empty());
+        assert_eq!(config.mnemonic_index, 0);
+        assert!(config.mnemonic_address.is_none());
+        assert_eq!(config.mnemonic_preview_count, DEFAULT_MNEMONIC_PREVIEW_COUNT);
+        assert_eq!(config.token_list_max_tokens, 100_000);
+        assert_eq!(config.min_pool_liquidity, 0);
+        assert_eq!(config.tool_timeout_secs, 60);
+        assert!(config.tool_timeout_overrides.is_empty());
+        assert_eq!(config.fan_out_concurrency, 4);
+        assert_eq!(config.confirmations, 0);
+        assert!(!config.trusted_tokens_only);
+        assert!(config.rebasing_tokens.is_none());
+        assert!(config.enabled_tools.is_none());
+        assert!(!config.stale_price_fallback);
+        assert_eq!(config.protocol_preference, ProtocolPreference::V3First);
+        assert_eq!(config.min_slippage_tolerance, Decimal::new(5, 2));
+        assert!(!config.uniswap_addresses.is_overridden());
+        assert!(config.v2_venues.is_none());
+        assert!(!config.quote_cross_check);
+        assert_eq!(config.quote_cross_check_tolerance, Decimal::ONE);
+        assert_eq!(config.http_proxy, HttpProxy::Environment);
+        assert_eq!(config.max_price_impact, Decimal::from(50));
+    }
+
+    #[test]
+    fn test_config_read_only() {
+        assert!(Config::default().is_read_only());
+        assert!(Config { private_key: "  ".to_string(), ..Default::default() }.is_read_only());
+        assert!(!Config { private_key: "0xkey".to_string(), ..Default::default() }.is_read_only());
+        assert!(!Config { mnemonic: "test test".to_string(), ..Default::default() }.is_read_only());
+    }
+
+    #[test]
+    fn test_parse_contract_address() {
+        let router = parse_contract_address(
+            "UNISWAP_V2_ROUTER_OVERRIDE",
+            " 0xd9e1cE17f2641f24aE83637ab66a2cca9C378B9F ",
+        )
+        .unwrap();
+        assert_eq!(
+            router,
+            "0xd9e1cE17f2641f24aE83637ab66a2cca9C378B9F".parse::<Address>().unwrap()
+        );
+
+        let zero = parse_contract_address("UNISWAP_V2_ROUTER_OVERRIDE", &Address::ZERO.to_string());
+        assert!(matches!(zero, Err(AppError::Config(msg)) if msg.contains("zero address")));
+        assert!(matches!(
+            parse_contract_address("UNISWAP_V3_QUOTER_OVERRIDE", "0x1234"),
+            Err(AppError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_venue_list() {
+        assert_eq!(
+            parse_venue_list("ETHEREUM_V2_VENUES", "sushiswap, Uniswap,sushi").unwrap(),
+            vec![Venue::Sushiswap, Venue::Uniswap]
+        );
+        assert!(matches!(
+            parse_venue_list("ETHEREUM_V2_VENUES", "uniswap,curve"),
+            Err(AppError::Config(msg)) if msg.contains("curve")
+        ));
+        assert!(parse_venue_list("ETHEREUM_V2_VENUES", " , ").is_err());
+    }
+
+    #[test]
+    fn test_parse_refresh_secs() {
+        assert_eq!(parse_refresh_secs("TEST", "3600").unwrap(), Some(3600));
+        assert_eq!(parse_refresh_secs("TEST", " 60 ").unwrap(), Some(60));
+        assert_eq!(parse_refresh_secs("TEST", "0").unwrap(), None);
+        assert!(matches!(parse_refresh_secs("TEST", "1h"), Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn test_parse_block_time() {
+        assert_eq!(parse_block_time("TEST", "12").unwrap(), 12);
+        assert_eq!(parse_block_time("TEST", " 2 ").unwrap(), 2);
+        assert!(matches!(parse_block_time("TEST", "0"), Err(AppError::Config(_))));
+        assert!(matches!(parse_block_time("TEST", "12s"), Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        assert!(parse_bool("TEST", "true").unwrap());
+        assert!(parse_bool("TEST", " YES ").unwrap());
+        assert!(!parse_bool("TEST", "0").unwrap());
+        assert!(matches!(parse_bool("TEST", "maybe"), Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn test_parse_http_proxy() {
+        assert_eq!(
+            parse_http_proxy("TEST", " http://proxy.internal:3128 ").unwrap(),
+            HttpProxy::Url("http://proxy.internal:3128".to_string())
+        );
+        assert_eq!(parse_http_proxy("TEST", "NONE").unwrap(), HttpProxy::Disabled);
+        assert_eq!(parse_http_proxy("TEST", "").unwrap(), HttpProxy::Environment);
+        assert!(matches!(parse_http_proxy("TEST", "http://bad host"), Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn test_parse_tool_timeouts() {
+        let timeouts = parse_tool_timeouts("TEST", "swap_tokens=120, get_balance = 10,").unwrap();
+        assert_eq!(timeouts.len(), 2);
+        assert_eq!(timeouts["swap_tokens"], 120);
+        assert_eq!(timeouts["get_balance"], 10);
+
+        assert!(parse_tool_timeouts("TEST", "").unwrap().is_empty());
+        assert!(matches!(parse_tool_timeouts("TEST", "swap_tokens"), Err(AppError::Config(_))));
+        assert!(matches!(
+            parse_tool_timeouts("TEST", "swap_tokens=soon"),
+            Err(AppError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rpc_headers() {
+        let headers =
+            parse_rpc_headers("TEST", "Authorization: Bearer abc:def , X-Api-Key:123,").unwrap();
+        assert_eq!(
+            headers,
+            vec![
+                ("Authorization".to_string(), "Bearer abc:def".to_string()),
+                ("X-Api-Key".to_string(), "123".to_string()),
+            ]
+        );
+        assert!(parse_rpc_headers("TEST", "").unwrap().is_empty());
+
+        assert!(matches!(parse_rpc_headers("TEST", "Authorization"), Err(AppError::Config(_))));
+        assert!(matches!(parse_rpc_headers("TEST", "Bad Name:x"), Err(AppError::Config(_))));
+        // The value is a secret, so it must not appear in the error
+        let err = parse_rpc_headers("TEST", "Authorization:top\u{7f}secret").unwrap_err();
+        assert!(!err.to_string().contains("secret"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_reference_amounts() {
+        let wbtc = "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599";
+        let amounts =
+            parse_reference_amounts("X", &format!("{}=0.05, 0x{}=100", wbtc, "11".repeat(20)))
+                .unwrap();
+        assert_eq!(amounts.len(), 2);
+        assert_eq!(amounts[0], (wbtc.parse().unwrap(), Decimal::from_str_exact("0.05").unwrap()));
+        assert_eq!(amounts[1], (Address::repeat_byte(0x11), Decimal::from(100)));
+        assert!(parse_reference_amounts("X", "").unwrap().is_empty());
+
+        assert!(parse_reference_amounts("X", wbtc).is_err());
+        assert!(parse_reference_amounts("X", &format!("{}=0", wbtc)).is_err());
+        assert!(parse_reference_amounts("X", &format!("{}=abc", wbtc)).is_err());
+        assert!(parse_reference_amounts("X", "nope=1").is_err());
+    }
+
+    #[test]
+    fn test_parse_tool_list() {
+        let tools = parse_tool_list("TEST", "get_balance, get_token_price,").unwrap();
+        assert_eq!(tools, vec!["get_balance", "get_token_price"]);
+
+        assert!(matches!(parse_tool_list("TEST", " , "), Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn test_parse_address_list() {
+        let tokens = parse_address_list(
+            "TEST",
+            "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48, 0xdAC17F958D2ee523a2206206994597C13D831ec7",
+        )
+        .unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                crate::ethereum::constants::USDC_ADDRESS,
+                crate::ethereum::constants::USDT_ADDRESS
+            ]
+        );
+
+        assert!(parse_address_list("TEST", "").unwrap().is_empty());
+        assert!(parse_address_list("TEST", " , ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_address_list_invalid() {
+        let err = parse_address_list("ETHEREUM_USD_PEGGED_TOKENS", "0xA0b8,USDC").unwrap_err();
+        assert!(matches!(err, AppError::Config(_)));
+        assert!(err.to_string().contains("ETHEREUM_USD_PEGGED_TOKENS"));
+    }
 }
@@ -49,6 +49,10 @@ pub enum AppError {
     #[error("Pool not found for token pair")]
     PoolNotFound,
 
+    /// A caller-specified swap route is invalid or has no pools.
+    #[error("Route not available: {0}")]
+    RouteNotFound(String),
+
     /// Parse error.
     #[error("Parse error: {0}")]
     Parse(String),
@@ -64,6 +68,10 @@ pub enum AppError {
     /// Pending transaction error.
     #[error("Pending transaction error: {0}")]
     PendingTransaction(String),
+
+    /// An operation did not complete in time.
+    #[error("Timed out: {0}")]
+    Timeout(String),
 }
 
 impl From<alloy::transports::TransportError> for AppError {
@@ -102,6 +110,7 @@ impl From<AppError> for McpError {
             AppError::InvalidAddress(_)
             | AppError::TokenNotFound(_)
             | AppError::Parse(_)
+            | AppError::RouteNotFound(_)
             | AppError::NumericOverflow(_) => McpError::invalid_params(err.to_string(), None),
             AppError::Config(_) => McpError::invalid_request(err.to_string(), None),
             _ => McpError::internal_error(err.to_string(), None),
@@ -130,6 +139,12 @@ mod tests {
         assert_eq!(err.to_string(), "Ethereum RPC error: Connection timeout");
     }
 
+    #[test]
+    fn test_app_error_timeout_display() {
+        let err = AppError::Timeout("get_balance did not complete within 60s".to_string());
+        assert_eq!(err.to_string(), "Timed out: get_balance did not complete within 60s");
+    }
+
     #[test]
     fn test_app_error_transport_display() {
         let err = AppError::Transport("Network unreachable".to_string());
@@ -181,6 +196,12 @@ mod tests {
         assert_eq!(err.to_string(), "Pool not found for token pair");
     }
 
+    #[test]
+    fn test_app_error_route_not_found_display() {
+        let err = AppError::RouteNotFound("No Uniswap V2 pair".to_string());
+        assert_eq!(err.to_string(), "Route not available: No Uniswap V2 pair");
+    }
+
     #[test]
     fn test_app_error_parse_display() {
         let err = AppError::Parse("Invalid hex".to_string());
@@ -227,6 +248,11 @@ mod tests {
         let err = AppError::NumericOverflow("overflow".to_string());
         let mcp_err: McpError = err.into();
         assert_eq!(mcp_err.code, ErrorCode::INVALID_PARAMS);
+
+        // RouteNotFound should map to invalid_params
+        let err = AppError::RouteNotFound("no pool".to_string());
+        let mcp_err: McpError = err.into();
+        assert_eq!(mcp_err.code, ErrorCode::INVALID_PARAMS);
     }
 
     #[test]
@@ -1,19 +1,46 @@
 //! Ethereum RPC client.
 
 use alloy::{
+    eips::{eip1559::Eip1559Estimation, BlockId},
     network::Ethereum,
     primitives::{Address, Bytes, U256},
     providers::{Provider, ProviderBuilder, RootProvider},
-    rpc::types::TransactionRequest,
+    rpc::{
+        client::RpcClient,
+        types::{Filter, Log, TransactionRequest},
+    },
 };
-use std::sync::Arc;
-use tokio::sync::OnceCell;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::{
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+use tokio::{sync::OnceCell, task::JoinHandle, time::MissedTickBehavior};
 
 use crate::error::{AppError, Result};
 
 /// Type alias for the HTTP provider.
 pub type HttpProvider = RootProvider<Ethereum>;
 
+/// Block identifier for an optional block number (`None` for the latest block).
+pub fn block_id(block: Option<u64>) -> BlockId {
+    block.map_or_else(BlockId::latest, BlockId::number)
+}
+
+/// Build a header map from `name:value` pairs, marking every value sensitive.
+fn header_map(headers: &[(String, String)]) -> Result<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| AppError::Config(format!("Invalid RPC header name: {}", name)))?;
+        let mut value = HeaderValue::from_str(value)
+            .map_err(|_| AppError::Config(format!("Invalid value for RPC header {}", name)))?;
+        value.set_sensitive(true);
+        map.append(name, value);
+    }
+    Ok(map)
+}
+
 /// Ethereum RPC client wrapper with lazy initialization.
 #[derive(Clone)]
 pub struct EthereumClient {
@@ -23,6 +50,10 @@ pub struct EthereumClient {
     rpc_url: String,
     /// Lazily initialized chain ID.
     chain_id: Arc<OnceCell<u64>>,
+    /// A different chain ID the node reported after `chain_id` was cached.
+    changed_chain_id: Arc<Mutex<Option<u64>>>,
+    /// Blocks behind the latest at which balances and reserves are read (0 = latest).
+    confirmations: u64,
 }
 
 impl EthereumClient {
@@ -31,20 +62,72 @@ impl EthereumClient {
     /// Note: This does NOT make any network calls. The connection is
     /// established lazily when the first operation is performed.
     pub fn new(rpc_url: &str) -> Result<Self> {
-        let url = rpc_url
+        Self::with_headers(rpc_url, &[])
+    }
+
+    /// Create a new Ethereum client sending `headers` with every RPC request.
+    ///
+    /// Use this for providers that authenticate with a header (e.g.
+    /// `Authorization`) instead of an API key in the URL. Header values are
+    /// marked sensitive and never logged.
+    pub fn with_headers(rpc_url: &str, headers: &[(String, String)]) -> Result<Self> {
+        let url: reqwest::Url = rpc_url
             .parse()
             .map_err(|_| AppError::Config(format!("Invalid RPC URL: {}", rpc_url)))?;
 
-        #[allow(deprecated)]
-        let provider = ProviderBuilder::new().on_http(url).root().clone();
+        let provider = if headers.is_empty() {
+            #[allow(deprecated)]
+            ProviderBuilder::new().on_http(url).root().clone()
+        } else {
+            let http = reqwest::Client::builder()
+                .default_headers(header_map(headers)?)
+                .build()
+                .map_err(|e| AppError::Config(format!("Failed to build RPC HTTP client: {}", e)))?;
+            RootProvider::new(RpcClient::new_http_with_client(http, url))
+        };
 
-        tracing::info!(rpc_url = %rpc_url, "Ethereum client created (lazy initialization)");
+        tracing::info!(
+            rpc_url = %rpc_url,
+            rpc_headers = headers.len(),
+            "Ethereum client created (lazy initialization)"
+        );
+
+        Ok(Self::from_provider(provider, rpc_url))
+    }
 
-        Ok(Self {
+    fn from_provider(provider: HttpProvider, rpc_url: &str) -> Self {
+        Self {
             provider: Arc::new(provider),
             rpc_url: rpc_url.to_string(),
             chain_id: Arc::new(OnceCell::new()),
-        })
+            changed_chain_id: Arc::new(Mutex::new(None)),
+            confirmations: 0,
+        }
+    }
+
+    /// Client answering every RPC request from `asserter`, for tests.
+    #[cfg(test)]
+    pub(crate) fn mocked(asserter: alloy::transports::mock::Asserter) -> Self {
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter).root().clone();
+        Self::from_provider(provider, "http://localhost:8545")
+    }
+
+    /// Read balances and reserves `confirmations` blocks behind the latest block.
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Block to read balances and reserves at.
+    ///
+    /// `None` means the latest block and costs no RPC call. Otherwise the block is
+    /// `confirmations` behind the latest, so the data is safe from shallow reorgs.
+    pub async fn confirmed_block(&self) -> Result<Option<u64>> {
+        if self.confirmations == 0 {
+            return Ok(None);
+        }
+        let latest = self.get_block_number().await?;
+        Ok(Some(latest.saturating_sub(self.confirmations)))
     }
 
     /// Get the chain ID (fetches from network on first call).
@@ -59,6 +142,59 @@ impl EthereumClient {
             .copied()
     }
 
+    /// Read the chain ID from the node again and compare it with the cached one.
+    ///
+    /// The cached chain ID is kept either way; a mismatch is logged and reported
+    /// by [`chain_id_change`](Self::chain_id_change) for the rest of the session.
+    /// Returns the chain ID the node reports now.
+    pub async fn revalidate_chain_id(&self) -> Result<u64> {
+        let cached = self.chain_id().await?;
+        let reported = self.provider.get_chain_id().await?;
+        if reported != cached {
+            tracing::warn!(
+                cached_chain_id = cached,
+                reported_chain_id = reported,
+                rpc_url = %self.rpc_url,
+                "RPC endpoint now serves a different chain"
+            );
+            *self.changed_chain_id.lock().unwrap_or_else(|e| e.into_inner()) = Some(reported);
+        }
+        Ok(reported)
+    }
+
+    /// `(cached, reported)` chain IDs if the node ever reported a chain ID other
+    /// than the one cached at the start of the session.
+    pub fn chain_id_change(&self) -> Option<(u64, u64)> {
+        let reported = (*self.changed_chain_id.lock().unwrap_or_else(|e| e.into_inner()))?;
+        Some((*self.chain_id.get()?, reported))
+    }
+
+    /// Spawn a background task that revalidates the chain ID every `interval`.
+    ///
+    /// Failures are logged and retried on the next tick, and the task exits once
+    /// the client is dropped.
+    pub fn spawn_chain_id_revalidation(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let client: Weak<Self> = Arc::downgrade(self);
+        tracing::info!("Revalidating the chain ID every {:?}", interval);
+
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now() + interval;
+            let mut ticker = tokio::time::interval_at(start, interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+
+                let Some(client) = client.upgrade() else {
+                    break;
+                };
+                if let Err(e) = client.revalidate_chain_id().await {
+                    tracing::warn!("Chain ID revalidation failed: {}", e);
+                }
+            }
+        })
+    }
+
     /// Get the underlying provider.
     pub fn provider(&self) -> &HttpProvider {
         &self.provider
@@ -66,12 +202,33 @@ impl EthereumClient {
 
     /// Get native ETH balance for an address.
     pub async fn get_eth_balance(&self, address: Address) -> Result<U256> {
+        self.get_eth_balance_at(address, None).await
+    }
+
+    /// Get native ETH balance for an address at `block` (`None` for the latest block).
+    pub async fn get_eth_balance_at(&self, address: Address, block: Option<u64>) -> Result<U256> {
         self.provider
             .get_balance(address)
+            .block_id(block_id(block))
             .await
             .map_err(|e| AppError::Rpc(format!("Failed to get balance for {}: {}", address, e)))
     }
 
+    /// Get the deployed bytecode at an address (empty for EOAs).
+    pub async fn get_code(&self, address: Address) -> Result<Bytes> {
+        self.provider
+            .get_code_at(address)
+            .await
+            .map_err(|e| AppError::Rpc(format!("Failed to get code for {}: {}", address, e)))
+    }
+
+    /// Read a raw storage slot of a contract.
+    pub async fn get_storage_at(&self, address: Address, slot: U256) -> Result<U256> {
+        self.provider.get_storage_at(address, slot).await.map_err(|e| {
+            AppError::Rpc(format!("Failed to get storage slot {} for {}: {}", slot, address, e))
+        })
+    }
+
     /// Execute a call (simulate transaction without broadcasting).
     pub async fn call(&self, tx: &TransactionRequest) -> Result<Bytes> {
         self.provider
@@ -96,6 +253,32 @@ impl EthereumClient {
             .map_err(|e| AppError::Rpc(format!("Failed to get gas price: {}", e)))
     }
 
+    /// Estimate EIP-1559 fees (max fee and max priority fee per gas).
+    pub async fn estimate_eip1559_fees(&self) -> Result<Eip1559Estimation> {
+        self.provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(|e| AppError::Rpc(format!("Failed to estimate EIP-1559 fees: {}", e)))
+    }
+
+    /// Get the nonce of an address at the latest block (its confirmed transaction count).
+    pub async fn get_latest_nonce(&self, address: Address) -> Result<u64> {
+        self.provider
+            .get_transaction_count(address)
+            .latest()
+            .await
+            .map_err(|e| AppError::Rpc(format!("Failed to get nonce for {}: {}", address, e)))
+    }
+
+    /// Get the next nonce of an address, counting its pending transactions.
+    pub async fn get_pending_nonce(&self, address: Address) -> Result<u64> {
+        self.provider
+            .get_transaction_count(address)
+            .pending()
+            .await
+            .map_err(|e| AppError::Rpc(format!("Failed to get nonce for {}: {}", address, e)))
+    }
+
     /// Get the current block timestamp.
     pub async fn get_block_timestamp(&self) -> Result<u64> {
         let block = self
@@ -106,6 +289,35 @@ impl EthereumClient {
         Ok(block.header.timestamp)
     }
 
+    /// Get the timestamp of block `number`.
+    pub async fn get_block_timestamp_at(&self, number: u64) -> Result<u64> {
+        let block = self
+            .provider
+            .get_block_by_number(alloy::eips::BlockNumberOrTag::Number(number))
+            .await?
+            .ok_or_else(|| AppError::Rpc(format!("Block {} not found", number)))?;
+        Ok(block.header.timestamp)
+    }
+
+    /// Get the latest block number.
+    pub async fn get_block_number(&self) -> Result<u64> {
+        self.provider
+            .get_block_number()
+            .await
+            .map_err(|e| AppError::Rpc(format!("Failed to get block number: {}", e)))
+    }
+
+    /// Fetch event logs matching a filter (`eth_getLogs`).
+    ///
+    /// Providers cap the block range and result size of this call differently,
+    /// so callers should keep the range small.
+    pub async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>> {
+        self.provider
+            .get_logs(filter)
+            .await
+            .map_err(|e| AppError::Rpc(format!("Failed to get logs: {}", e)))
+    }
+
     /// Make a contract call.
     pub async fn call_contract(
         &self,
@@ -122,3 +334,44 @@ impl EthereumClient {
         self.call(&tx).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::transports::mock::Asserter;
+
+    #[tokio::test]
+    async fn test_revalidate_chain_id_keeps_cached_value() {
+        let asserter = Asserter::new();
+        let client = EthereumClient::mocked(asserter.clone());
+
+        asserter.push_success(&"0x1");
+        asserter.push_success(&"0x1");
+        assert_eq!(client.revalidate_chain_id().await.unwrap(), 1);
+        assert_eq!(client.chain_id_change(), None);
+
+        asserter.push_success(&"0xa");
+        assert_eq!(client.revalidate_chain_id().await.unwrap(), 10);
+        assert_eq!(client.chain_id_change(), Some((1, 10)));
+        // The cached chain ID is not replaced
+        assert_eq!(client.chain_id().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_block() {
+        let asserter = Asserter::new();
+
+        // No confirmations reads the latest block without an RPC call
+        let client = EthereumClient::mocked(asserter.clone());
+        assert_eq!(client.confirmed_block().await.unwrap(), None);
+        assert!(asserter.read_q().is_empty());
+
+        let client = EthereumClient::mocked(asserter.clone()).with_confirmations(3);
+        asserter.push_success(&"0x64");
+        assert_eq!(client.confirmed_block().await.unwrap(), Some(97));
+
+        // Never below the genesis block
+        asserter.push_success(&"0x1");
+        assert_eq!(client.confirmed_block().await.unwrap(), Some(0));
+    }
+}
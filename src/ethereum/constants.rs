@@ -2,7 +2,8 @@
 //!
 //! Contains chain IDs and mainnet contract addresses.
 
-use alloy::primitives::{address, Address};
+use alloy::primitives::{address, uint, Address, U256};
+use rust_decimal::Decimal;
 
 // ============================================================================
 // Chain IDs
@@ -17,6 +18,32 @@ pub const SEPOLIA_CHAIN_ID: u64 = 11155111;
 /// Default chain ID (Ethereum Mainnet).
 pub const DEFAULT_CHAIN_ID: u64 = ETHEREUM_MAINNET_CHAIN_ID;
 
+/// Target time between blocks on Ethereum Mainnet and Sepolia, in seconds.
+pub const SECONDS_PER_BLOCK: u64 = 12;
+
+/// Average time between blocks on a chain, in seconds.
+///
+/// Unknown chains are assumed to produce blocks at Ethereum's pace.
+pub fn seconds_per_block(chain_id: u64) -> u64 {
+    match chain_id {
+        // OP Mainnet, Polygon PoS and Base
+        10 | 137 | 8453 => 2,
+        _ => SECONDS_PER_BLOCK,
+    }
+}
+
+/// Chains with built-in contract addresses and tokens.
+pub const SUPPORTED_CHAIN_IDS: [u64; 2] = [ETHEREUM_MAINNET_CHAIN_ID, SEPOLIA_CHAIN_ID];
+
+/// Name of a known network ("mainnet" or "sepolia"), or "unknown".
+pub fn network_name(chain_id: u64) -> &'static str {
+    match chain_id {
+        ETHEREUM_MAINNET_CHAIN_ID => "mainnet",
+        SEPOLIA_CHAIN_ID => "sepolia",
+        _ => "unknown",
+    }
+}
+
 // ============================================================================
 // Core Token Addresses (Ethereum Mainnet)
 // ============================================================================
@@ -33,6 +60,98 @@ pub const WBTC_ADDRESS: Address = address!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2
 /// UNI token address on Ethereum Mainnet.
 pub const UNI_ADDRESS: Address = address!("1f9840a85d5aF5bf1D1762F925BDADdC4201F984");
 
+/// USDT address on Ethereum Mainnet.
+pub const USDT_ADDRESS: Address = address!("dAC17F958D2ee523a2206206994597C13D831ec7");
+
+/// DAI address on Ethereum Mainnet.
+pub const DAI_ADDRESS: Address = address!("6B175474E89094C44Da98b954EedeAC495271d0F");
+
+/// Lido staked ETH (stETH) address on Ethereum Mainnet. Rebases daily.
+pub const STETH_ADDRESS: Address = address!("ae7ab96520DE3A18E5e111B5EaAb095312D7fE84");
+
+/// Lido wrapped staked ETH (wstETH) address on Ethereum Mainnet, the non-rebasing
+/// wrapper of stETH.
+pub const WSTETH_ADDRESS: Address = address!("7f39C581F595B53c5cb19bD0b3f8dA6c935E2Ca0");
+
+/// Ampleforth (AMPL) address on Ethereum Mainnet. Rebases daily.
+pub const AMPL_ADDRESS: Address = address!("D46bA6D942050d489DBd938a2C909A5d5039A161");
+
+// ============================================================================
+// Core Token Addresses (Sepolia)
+// ============================================================================
+
+/// Wrapped Ether (WETH) address on Sepolia.
+pub const SEPOLIA_WETH_ADDRESS: Address = address!("fFf9976782d46CC05630D1f6eBAb18b2324d6B14");
+
+/// USDC address on Sepolia.
+pub const SEPOLIA_USDC_ADDRESS: Address = address!("1c7D4B196Cb0C7B01d743Fbc6116a902379C7238");
+
+/// Wrapped native token for a chain, priced 1:1 in ETH.
+pub fn wrapped_native_token(chain_id: u64) -> Option<Address> {
+    match chain_id {
+        ETHEREUM_MAINNET_CHAIN_ID => Some(WETH_ADDRESS),
+        SEPOLIA_CHAIN_ID => Some(SEPOLIA_WETH_ADDRESS),
+        _ => None,
+    }
+}
+
+/// Canonical address of a core token symbol (case-insensitive) on a chain.
+///
+/// Token lists can carry impersonating tokens that reuse these symbols, so a
+/// symbol resolving elsewhere is worth flagging.
+pub fn canonical_token_address(chain_id: u64, symbol: &str) -> Option<Address> {
+    let symbol = symbol.to_ascii_uppercase();
+    match (chain_id, symbol.as_str()) {
+        (ETHEREUM_MAINNET_CHAIN_ID, "WETH") => Some(WETH_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "USDC") => Some(USDC_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "WBTC") => Some(WBTC_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "UNI") => Some(UNI_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "USDT") => Some(USDT_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "DAI") => Some(DAI_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "STETH") => Some(STETH_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "WSTETH") => Some(WSTETH_ADDRESS),
+        (ETHEREUM_MAINNET_CHAIN_ID, "AMPL") => Some(AMPL_ADDRESS),
+        (SEPOLIA_CHAIN_ID, "WETH") => Some(SEPOLIA_WETH_ADDRESS),
+        (SEPOLIA_CHAIN_ID, "USDC") => Some(SEPOLIA_USDC_ADDRESS),
+        _ => None,
+    }
+}
+
+/// Default USD-pegged stablecoins for a chain, priced at (or near) 1.0 in USD.
+pub fn usd_pegged_tokens(chain_id: u64) -> Vec<Address> {
+    match chain_id {
+        ETHEREUM_MAINNET_CHAIN_ID => vec![USDC_ADDRESS, USDT_ADDRESS, DAI_ADDRESS],
+        SEPOLIA_CHAIN_ID => vec![SEPOLIA_USDC_ADDRESS],
+        _ => Vec::new(),
+    }
+}
+
+/// Default rebasing tokens for a chain, whose balances change without transfers.
+pub fn rebasing_tokens(chain_id: u64) -> Vec<Address> {
+    match chain_id {
+        ETHEREUM_MAINNET_CHAIN_ID => vec![STETH_ADDRESS, AMPL_ADDRESS],
+        _ => Vec::new(),
+    }
+}
+
+/// Default trade sizes, in whole tokens, that Uniswap V3 prices are quoted at
+/// for tokens where one whole token is a poor reference (default: 1 token).
+pub fn price_reference_amounts(chain_id: u64) -> Vec<(Address, Decimal)> {
+    match chain_id {
+        // 0.01 WBTC, so the quote is not sized at a whole bitcoin
+        ETHEREUM_MAINNET_CHAIN_ID => vec![(WBTC_ADDRESS, Decimal::new(1, 2))],
+        _ => Vec::new(),
+    }
+}
+
+/// Non-rebasing wrapper of a rebasing token, if it has one.
+pub fn non_rebasing_wrapper(token: Address) -> Option<Address> {
+    match token {
+        STETH_ADDRESS => Some(WSTETH_ADDRESS),
+        _ => None,
+    }
+}
+
 // ============================================================================
 // Chainlink Price Feed Addresses (Ethereum Mainnet)
 // ============================================================================
@@ -46,6 +165,12 @@ pub const BTC_USD_FEED: Address = address!("F4030086522a5bEEa4988F8cA5B36dbC97Be
 /// Chainlink USDC/USD price feed address on Ethereum Mainnet.
 pub const USDC_USD_FEED: Address = address!("8fFfFfd4AfB6115b954Bd326cbe7B4BA576818f6");
 
+/// Chainlink USDT/USD price feed address on Ethereum Mainnet.
+pub const USDT_USD_FEED: Address = address!("3E7d1eAB13ad0104d2750B8863b489D65364e32D");
+
+/// Chainlink DAI/USD price feed address on Ethereum Mainnet.
+pub const DAI_USD_FEED: Address = address!("Aed0c38402a5d19df6E4c03F4E2DceD6e29c1ee9");
+
 // ============================================================================
 // Uniswap V2 Addresses (Ethereum Mainnet)
 // ============================================================================
@@ -56,6 +181,37 @@ pub const UNISWAP_V2_ROUTER: Address = address!("7a250d5630B4cF539739dF2C5dAcb4c
 /// Uniswap V2 Factory address on Ethereum Mainnet.
 pub const UNISWAP_V2_FACTORY: Address = address!("5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f");
 
+// ============================================================================
+// Sushiswap Addresses (Ethereum Mainnet)
+// ============================================================================
+
+/// Sushiswap V2 Router address on Ethereum Mainnet.
+pub const SUSHISWAP_V2_ROUTER: Address = address!("d9e1cE17f2641f24aE83637ab66a2cca9C378B9F");
+
+/// Sushiswap V2 Factory address on Ethereum Mainnet.
+pub const SUSHISWAP_V2_FACTORY: Address = address!("C0AEe478e3658e2610c5F7A4A2E1777cE9e4f2Ac");
+
+/// Factory and router of a Uniswap V2-compatible deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V2Deployment {
+    /// Factory the pairs are looked up on.
+    pub factory: Address,
+    /// Router swaps and quotes go through.
+    pub router: Address,
+}
+
+/// Sushiswap's V2 deployment on Ethereum Mainnet.
+pub const SUSHISWAP_V2: V2Deployment =
+    V2Deployment { factory: SUSHISWAP_V2_FACTORY, router: SUSHISWAP_V2_ROUTER };
+
+/// Sushiswap's V2 deployment on a chain, if its addresses are known.
+pub fn sushiswap_v2(chain_id: u64) -> Option<V2Deployment> {
+    match chain_id {
+        ETHEREUM_MAINNET_CHAIN_ID => Some(SUSHISWAP_V2),
+        _ => None,
+    }
+}
+
 // ============================================================================
 // Uniswap V3 Addresses (Ethereum Mainnet)
 // ============================================================================
@@ -68,3 +224,56 @@ pub const UNISWAP_V3_FACTORY: Address = address!("1F98431c8aD98523631AE4a59f2673
 
 /// Uniswap V3 Quoter V2 address on Ethereum Mainnet.
 pub const UNISWAP_V3_QUOTER: Address = address!("61fFE014bA17989E743c5F6cB21bF9697530B21e");
+
+/// Uniswap V2/V3 contracts used for pricing, quotes and swaps.
+///
+/// Defaults to the Uniswap mainnet deployments. Any field may point at a fork
+/// or another Uniswap-compatible DEX (e.g., Sushiswap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniswapAddresses {
+    /// V2 router.
+    pub v2_router: Address,
+    /// V2 factory.
+    pub v2_factory: Address,
+    /// V3 SwapRouter.
+    pub v3_router: Address,
+    /// V3 factory.
+    pub v3_factory: Address,
+    /// V3 QuoterV2.
+    pub v3_quoter: Address,
+}
+
+impl UniswapAddresses {
+    /// The Uniswap deployments on Ethereum Mainnet.
+    pub const MAINNET: Self = Self {
+        v2_router: UNISWAP_V2_ROUTER,
+        v2_factory: UNISWAP_V2_FACTORY,
+        v3_router: UNISWAP_V3_ROUTER,
+        v3_factory: UNISWAP_V3_FACTORY,
+        v3_quoter: UNISWAP_V3_QUOTER,
+    };
+
+    /// The V2 factory and router.
+    pub fn v2(&self) -> V2Deployment {
+        V2Deployment { factory: self.v2_factory, router: self.v2_router }
+    }
+
+    /// Whether any address differs from the mainnet deployments.
+    pub fn is_overridden(&self) -> bool {
+        *self != Self::MAINNET
+    }
+}
+
+impl Default for UniswapAddresses {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+// ============================================================================
+// Proxy Storage Slots
+// ============================================================================
+
+/// EIP-1967 implementation slot: `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`.
+pub const EIP1967_IMPLEMENTATION_SLOT: U256 =
+    uint!(0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc_U256);
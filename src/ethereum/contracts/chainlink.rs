@@ -1,12 +1,14 @@
 //! Chainlink price feed contract bindings.
 
-use alloy::sol;
+use alloy::{primitives::Address, sol};
 use std::collections::HashMap;
 
-use super::{USDC_ADDRESS, WBTC_ADDRESS, WETH_ADDRESS};
+use super::{DAI_ADDRESS, USDC_ADDRESS, USDT_ADDRESS, WBTC_ADDRESS, WETH_ADDRESS};
 
 // Re-export Chainlink feed addresses from constants module.
-pub use crate::ethereum::constants::{BTC_USD_FEED, ETH_USD_FEED, USDC_USD_FEED};
+pub use crate::ethereum::constants::{
+    BTC_USD_FEED, DAI_USD_FEED, ETH_USD_FEED, USDC_USD_FEED, USDT_USD_FEED,
+};
 
 // Chainlink Aggregator V3 interface
 sol! {
@@ -29,11 +31,22 @@ sol! {
     }
 }
 
+/// Known Chainlink price feeds and the pair each one prices.
+pub const CHAINLINK_FEED_PAIRS: [(&str, Address); 5] = [
+    ("ETH/USD", ETH_USD_FEED),
+    ("BTC/USD", BTC_USD_FEED),
+    ("USDC/USD", USDC_USD_FEED),
+    ("USDT/USD", USDT_USD_FEED),
+    ("DAI/USD", DAI_USD_FEED),
+];
+
 /// Get known Chainlink price feeds for common tokens.
-pub fn get_chainlink_feeds() -> HashMap<alloy::primitives::Address, alloy::primitives::Address> {
+pub fn get_chainlink_feeds() -> HashMap<Address, Address> {
     let mut feeds = HashMap::new();
     feeds.insert(WETH_ADDRESS, ETH_USD_FEED);
     feeds.insert(WBTC_ADDRESS, BTC_USD_FEED);
     feeds.insert(USDC_ADDRESS, USDC_USD_FEED);
+    feeds.insert(USDT_ADDRESS, USDT_USD_FEED);
+    feeds.insert(DAI_ADDRESS, DAI_USD_FEED);
     feeds
 }
@@ -0,0 +1,67 @@
+//! Pause and blacklist bindings for tokens with known compliance controls.
+//!
+//! These functions are not part of ERC20, so they are only called on tokens
+//! listed in [`compliance_interface`].
+
+use alloy::{primitives::Address, sol};
+
+use super::{USDC_ADDRESS, USDT_ADDRESS};
+use crate::ethereum::constants::SEPOLIA_USDC_ADDRESS;
+
+// Circle FiatToken (USDC) pause and blacklist interface
+sol! {
+    #[sol(rpc)]
+    interface IFiatToken {
+        function paused() external view returns (bool);
+        function isBlacklisted(address account) external view returns (bool);
+    }
+}
+
+// Tether (USDT) pause and blacklist interface
+sol! {
+    #[sol(rpc)]
+    interface ITetherToken {
+        function paused() external view returns (bool);
+        function getBlackListStatus(address maker) external view returns (bool);
+    }
+}
+
+/// Pause and blacklist interface implemented by a known token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComplianceInterface {
+    /// Circle FiatToken: `paused()` and `isBlacklisted(address)`.
+    FiatToken,
+    /// Tether: `paused()` and `getBlackListStatus(address)`.
+    Tether,
+}
+
+/// Compliance interface of `token`, if it is a known token with one.
+pub fn compliance_interface(token: Address) -> Option<ComplianceInterface> {
+    match token {
+        USDC_ADDRESS | SEPOLIA_USDC_ADDRESS => Some(ComplianceInterface::FiatToken),
+        USDT_ADDRESS => Some(ComplianceInterface::Tether),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::constants::DAI_ADDRESS;
+
+    #[test]
+    fn test_compliance_interface_known_tokens() {
+        assert_eq!(compliance_interface(USDC_ADDRESS), Some(ComplianceInterface::FiatToken));
+        assert_eq!(
+            compliance_interface(SEPOLIA_USDC_ADDRESS),
+            Some(ComplianceInterface::FiatToken)
+        );
+        assert_eq!(compliance_interface(USDT_ADDRESS), Some(ComplianceInterface::Tether));
+    }
+
+    #[test]
+    fn test_compliance_interface_unknown_token() {
+        assert_eq!(compliance_interface(DAI_ADDRESS), None);
+        assert_eq!(compliance_interface(Address::ZERO), None);
+    }
+}
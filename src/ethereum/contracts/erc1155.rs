@@ -0,0 +1,15 @@
+//! ERC1155 contract bindings.
+
+use alloy::sol;
+
+// ERC1155 balance interface using Alloy's sol! macro
+sol! {
+    #[sol(rpc)]
+    interface IERC1155 {
+        function balanceOf(address account, uint256 id) external view returns (uint256);
+        function balanceOfBatch(address[] calldata accounts, uint256[] calldata ids)
+            external
+            view
+            returns (uint256[] memory);
+    }
+}
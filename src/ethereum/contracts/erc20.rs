@@ -25,8 +25,10 @@ pub struct TokenMetadata {
     pub name: String,
     /// Token symbol.
     pub symbol: String,
-    /// Token decimals.
+    /// Token decimals (18 when they could not be read).
     pub decimals: u8,
+    /// Whether `decimals` was read from the contract rather than assumed.
+    pub decimals_known: bool,
     /// Token address.
     pub address: Address,
 }
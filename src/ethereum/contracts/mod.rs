@@ -1,9 +1,12 @@
 //! Smart contract bindings.
 
 pub mod chainlink;
+pub mod compliance;
+pub mod erc1155;
 pub mod erc20;
+pub mod selectors;
 pub mod uniswap_v2;
 pub mod uniswap_v3;
 
 // Re-export core token addresses from constants module.
-pub use super::constants::{USDC_ADDRESS, WBTC_ADDRESS, WETH_ADDRESS};
+pub use super::constants::{DAI_ADDRESS, USDC_ADDRESS, USDT_ADDRESS, WBTC_ADDRESS, WETH_ADDRESS};
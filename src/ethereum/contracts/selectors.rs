@@ -0,0 +1,137 @@
+//! Function selector lookup across the known contract bindings.
+//!
+//! The table is built once from the `sol!` bindings, so it always matches the
+//! functions this server can call. Lookups make no network calls.
+
+use std::{collections::BTreeMap, sync::LazyLock};
+
+use super::{
+    chainlink::IAggregatorV3,
+    compliance::{IFiatToken, ITetherToken},
+    erc1155::IERC1155,
+    erc20::IERC20,
+    uniswap_v2::{IUniswapV2Factory, IUniswapV2Pair, IUniswapV2Router02},
+    uniswap_v3::{IQuoterV2, ISwapRouter, IUniswapV3Factory, IUniswapV3Pool},
+};
+
+/// An interface name with the selectors and signatures of its functions, in the
+/// same order.
+type InterfaceFunctions = (&'static str, &'static [[u8; 4]], &'static [&'static str]);
+
+/// Interfaces whose functions are known.
+const KNOWN_INTERFACES: &[InterfaceFunctions] = &[
+    ("IERC20", IERC20::IERC20Calls::SELECTORS, IERC20::IERC20Calls::SIGNATURES),
+    ("IERC1155", IERC1155::IERC1155Calls::SELECTORS, IERC1155::IERC1155Calls::SIGNATURES),
+    (
+        "IUniswapV2Router02",
+        IUniswapV2Router02::IUniswapV2Router02Calls::SELECTORS,
+        IUniswapV2Router02::IUniswapV2Router02Calls::SIGNATURES,
+    ),
+    (
+        "IUniswapV2Factory",
+        IUniswapV2Factory::IUniswapV2FactoryCalls::SELECTORS,
+        IUniswapV2Factory::IUniswapV2FactoryCalls::SIGNATURES,
+    ),
+    (
+        "IUniswapV2Pair",
+        IUniswapV2Pair::IUniswapV2PairCalls::SELECTORS,
+        IUniswapV2Pair::IUniswapV2PairCalls::SIGNATURES,
+    ),
+    (
+        "ISwapRouter",
+        ISwapRouter::ISwapRouterCalls::SELECTORS,
+        ISwapRouter::ISwapRouterCalls::SIGNATURES,
+    ),
+    (
+        "IUniswapV3Factory",
+        IUniswapV3Factory::IUniswapV3FactoryCalls::SELECTORS,
+        IUniswapV3Factory::IUniswapV3FactoryCalls::SIGNATURES,
+    ),
+    (
+        "IUniswapV3Pool",
+        IUniswapV3Pool::IUniswapV3PoolCalls::SELECTORS,
+        IUniswapV3Pool::IUniswapV3PoolCalls::SIGNATURES,
+    ),
+    ("IQuoterV2", IQuoterV2::IQuoterV2Calls::SELECTORS, IQuoterV2::IQuoterV2Calls::SIGNATURES),
+    (
+        "IAggregatorV3",
+        IAggregatorV3::IAggregatorV3Calls::SELECTORS,
+        IAggregatorV3::IAggregatorV3Calls::SIGNATURES,
+    ),
+    ("IFiatToken", IFiatToken::IFiatTokenCalls::SELECTORS, IFiatToken::IFiatTokenCalls::SIGNATURES),
+    (
+        "ITetherToken",
+        ITetherToken::ITetherTokenCalls::SELECTORS,
+        ITetherToken::ITetherTokenCalls::SIGNATURES,
+    ),
+];
+
+/// A function signature and the known interfaces that declare it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownFunction {
+    /// Canonical signature, e.g. `transfer(address,uint256)`.
+    pub signature: &'static str,
+    /// Interfaces declaring the function, in [`KNOWN_INTERFACES`] order.
+    pub interfaces: Vec<&'static str>,
+}
+
+/// Known functions by selector.
+static KNOWN_SELECTORS: LazyLock<BTreeMap<[u8; 4], Vec<KnownFunction>>> = LazyLock::new(|| {
+    let mut map: BTreeMap<[u8; 4], Vec<KnownFunction>> = BTreeMap::new();
+    for (interface, selectors, signatures) in KNOWN_INTERFACES {
+        for (selector, signature) in selectors.iter().zip(signatures.iter()) {
+            let functions = map.entry(*selector).or_default();
+            match functions.iter_mut().find(|f| f.signature == *signature) {
+                Some(function) => function.interfaces.push(interface),
+                None => functions.push(KnownFunction { signature, interfaces: vec![interface] }),
+            }
+        }
+    }
+    map
+});
+
+/// Known functions with `selector`; empty when no known interface declares one.
+///
+/// More than one function is only returned on a selector collision.
+pub fn lookup_selector(selector: [u8; 4]) -> &'static [KnownFunction] {
+    KNOWN_SELECTORS.get(&selector).map(Vec::as_slice).unwrap_or_default()
+}
+
+/// Number of distinct selectors in the table.
+pub fn known_selector_count() -> usize {
+    KNOWN_SELECTORS.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+
+    #[test]
+    fn test_lookup_shared_selector() {
+        let functions = lookup_selector(IERC20::balanceOfCall::SELECTOR);
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].signature, "balanceOf(address)");
+        // The V2 pair is itself an ERC20 token
+        assert!(functions[0].interfaces.contains(&"IERC20"));
+        assert!(functions[0].interfaces.contains(&"IUniswapV2Pair"));
+    }
+
+    #[test]
+    fn test_lookup_router_selector() {
+        let functions = lookup_selector([0x38, 0xed, 0x17, 0x39]);
+
+        assert_eq!(
+            functions[0].signature,
+            "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)"
+        );
+        assert_eq!(functions[0].interfaces, vec!["IUniswapV2Router02"]);
+    }
+
+    #[test]
+    fn test_lookup_unknown_selector() {
+        assert!(lookup_selector([0xde, 0xad, 0xbe, 0xef]).is_empty());
+        assert!(known_selector_count() > 20);
+    }
+}
@@ -7,6 +7,9 @@ pub use crate::ethereum::constants::{UNISWAP_V2_FACTORY, UNISWAP_V2_ROUTER};
 
 // Uniswap V2 Router interface
 sol! {
+    // The generated rpc builders of `addLiquidity` and `removeLiquidity` take more
+    // arguments than clippy allows
+    #[allow(clippy::too_many_arguments)]
     #[sol(rpc)]
     interface IUniswapV2Router02 {
         function factory() external pure returns (address);
@@ -58,6 +61,27 @@ sol! {
             uint deadline
         ) external payable returns (uint[] memory amounts);
 
+        function addLiquidity(
+            address tokenA,
+            address tokenB,
+            uint amountADesired,
+            uint amountBDesired,
+            uint amountAMin,
+            uint amountBMin,
+            address to,
+            uint deadline
+        ) external returns (uint amountA, uint amountB, uint liquidity);
+
+        function removeLiquidity(
+            address tokenA,
+            address tokenB,
+            uint liquidity,
+            uint amountAMin,
+            uint amountBMin,
+            address to,
+            uint deadline
+        ) external returns (uint amountA, uint amountB);
+
         function getAmountsOut(
             uint amountIn,
             address[] calldata path
@@ -86,8 +110,20 @@ sol! {
     interface IUniswapV2Pair {
         function token0() external view returns (address);
         function token1() external view returns (address);
+        function factory() external view returns (address);
+        function totalSupply() external view returns (uint);
+        function balanceOf(address owner) external view returns (uint);
         function getReserves() external view returns (uint112 reserve0, uint112 reserve1, uint32 blockTimestampLast);
         function price0CumulativeLast() external view returns (uint);
         function price1CumulativeLast() external view returns (uint);
+
+        event Swap(
+            address indexed sender,
+            uint amount0In,
+            uint amount1In,
+            uint amount0Out,
+            uint amount1Out,
+            address indexed to
+        );
     }
 }
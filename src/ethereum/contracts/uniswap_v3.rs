@@ -1,6 +1,9 @@
 //! Uniswap V3 contract bindings.
 
-use alloy::sol;
+use alloy::{
+    primitives::{Address, Bytes},
+    sol,
+};
 
 // Re-export Uniswap V3 addresses from constants module.
 pub use crate::ethereum::constants::{UNISWAP_V3_FACTORY, UNISWAP_V3_QUOTER, UNISWAP_V3_ROUTER};
@@ -20,6 +23,51 @@ pub mod fee_tiers {
     pub const ALL_FEES: [u32; 4] = [FEE_LOWEST, FEE_LOW, FEE_MEDIUM, FEE_HIGH];
 }
 
+/// Encode a multi-hop V3 path (`token0 | fee | token1 | fee | token2 ...`).
+///
+/// Every hop uses the same fee tier.
+pub fn encode_path(tokens: &[Address], fee: u32) -> Bytes {
+    let fees = vec![fee; tokens.len().saturating_sub(1)];
+    encode_path_with_fees(tokens, &fees)
+}
+
+/// Encode a multi-hop V3 path with a fee tier per hop.
+///
+/// `fees[i]` is the fee of the pool between `tokens[i]` and `tokens[i + 1]`, so
+/// `fees` must be one shorter than `tokens`.
+pub fn encode_path_with_fees(tokens: &[Address], fees: &[u32]) -> Bytes {
+    debug_assert_eq!(fees.len() + 1, tokens.len(), "one fee per hop");
+
+    let mut encoded = Vec::with_capacity(tokens.len() * 23);
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 {
+            // Fee is a big-endian uint24
+            encoded.extend_from_slice(&fees[i - 1].to_be_bytes()[1..]);
+        }
+        encoded.extend_from_slice(token.as_slice());
+    }
+    Bytes::from(encoded)
+}
+
+/// Decode a V3 path built by [`encode_path_with_fees`] into its tokens and
+/// per-hop fee tiers.
+///
+/// Returns `None` unless `path` holds at least one hop of whole
+/// `fee | token` segments.
+pub fn decode_path(path: &[u8]) -> Option<(Vec<Address>, Vec<u32>)> {
+    if path.len() < 20 + 23 || !(path.len() - 20).is_multiple_of(23) {
+        return None;
+    }
+
+    let mut tokens = vec![Address::from_slice(&path[..20])];
+    let mut fees = Vec::with_capacity((path.len() - 20) / 23);
+    for hop in path[20..].chunks_exact(23) {
+        fees.push(u32::from_be_bytes([0, hop[0], hop[1], hop[2]]));
+        tokens.push(Address::from_slice(&hop[3..]));
+    }
+    Some((tokens, fees))
+}
+
 // Uniswap V3 SwapRouter interface
 sol! {
     #[sol(rpc)]
@@ -85,6 +133,16 @@ sol! {
             uint8 feeProtocol,
             bool unlocked
         );
+
+        event Swap(
+            address indexed sender,
+            address indexed recipient,
+            int256 amount0,
+            int256 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick
+        );
     }
 }
 
@@ -108,5 +166,97 @@ sol! {
                 uint32 initializedTicksCrossed,
                 uint256 gasEstimate
             );
+
+        function quoteExactInput(bytes memory path, uint256 amountIn)
+            external
+            returns (
+                uint256 amountOut,
+                uint160[] memory sqrtPriceX96AfterList,
+                uint32[] memory initializedTicksCrossedList,
+                uint256 gasEstimate
+            );
+
+        struct QuoteExactOutputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint256 amount;
+            uint24 fee;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function quoteExactOutputSingle(QuoteExactOutputSingleParams memory params)
+            external
+            returns (
+                uint256 amountIn,
+                uint160 sqrtPriceX96After,
+                uint32 initializedTicksCrossed,
+                uint256 gasEstimate
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::constants::{UNI_ADDRESS, USDC_ADDRESS, WETH_ADDRESS};
+
+    #[test]
+    fn test_encode_path_single_hop() {
+        let path = encode_path(&[WETH_ADDRESS, USDC_ADDRESS], fee_tiers::FEE_LOW);
+
+        assert_eq!(path.len(), 20 + 3 + 20);
+        assert_eq!(&path[..20], WETH_ADDRESS.as_slice());
+        assert_eq!(&path[20..23], &[0x00, 0x01, 0xf4]); // 500
+        assert_eq!(&path[23..], USDC_ADDRESS.as_slice());
+    }
+
+    #[test]
+    fn test_encode_path_multi_hop() {
+        let tokens = [USDC_ADDRESS, WETH_ADDRESS, USDC_ADDRESS];
+        let path = encode_path(&tokens, fee_tiers::FEE_MEDIUM);
+
+        assert_eq!(path.len(), 20 * 3 + 3 * 2);
+        assert_eq!(&path[20..23], &[0x00, 0x0b, 0xb8]); // 3000
+        assert_eq!(&path[43..46], &[0x00, 0x0b, 0xb8]);
+    }
+
+    #[test]
+    fn test_encode_path_with_mixed_fees() {
+        let tokens = [UNI_ADDRESS, WETH_ADDRESS, USDC_ADDRESS];
+        let path = encode_path_with_fees(&tokens, &[fee_tiers::FEE_MEDIUM, fee_tiers::FEE_LOW]);
+
+        assert_eq!(path.len(), 20 * 3 + 3 * 2);
+        assert_eq!(&path[..20], UNI_ADDRESS.as_slice());
+        assert_eq!(&path[20..23], &[0x00, 0x0b, 0xb8]); // 3000
+        assert_eq!(&path[23..43], WETH_ADDRESS.as_slice());
+        assert_eq!(&path[43..46], &[0x00, 0x01, 0xf4]); // 500
+        assert_eq!(&path[46..], USDC_ADDRESS.as_slice());
+    }
+
+    #[test]
+    fn test_decode_path_round_trip() {
+        let tokens = [UNI_ADDRESS, WETH_ADDRESS, USDC_ADDRESS];
+        let fees = [fee_tiers::FEE_MEDIUM, fee_tiers::FEE_LOW];
+        let path = encode_path_with_fees(&tokens, &fees);
+
+        assert_eq!(decode_path(&path), Some((tokens.to_vec(), fees.to_vec())));
+    }
+
+    #[test]
+    fn test_decode_path_rejects_partial_hops() {
+        let path = encode_path(&[WETH_ADDRESS, USDC_ADDRESS], fee_tiers::FEE_LOW);
+
+        assert_eq!(decode_path(&path[..20]), None);
+        assert_eq!(decode_path(&path[..path.len() - 1]), None);
+        assert_eq!(decode_path(&[]), None);
+    }
+
+    #[test]
+    fn test_encode_path_matches_uniform_fees() {
+        let tokens = [USDC_ADDRESS, WETH_ADDRESS, UNI_ADDRESS];
+        assert_eq!(
+            encode_path(&tokens, fee_tiers::FEE_HIGH),
+            encode_path_with_fees(&tokens, &[fee_tiers::FEE_HIGH, fee_tiers::FEE_HIGH])
+        );
     }
 }
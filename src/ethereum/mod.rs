@@ -5,8 +5,10 @@
 pub mod client;
 pub mod constants;
 pub mod contracts;
+pub mod price_math;
+pub mod quoter;
 pub mod wallet;
 
-pub use client::{EthereumClient, HttpProvider};
+pub use client::{block_id, EthereumClient, HttpProvider};
 pub use constants::*;
-pub use wallet::WalletManager;
+pub use wallet::{MnemonicAccount, WalletManager};
@@ -0,0 +1,410 @@
+//! Big-integer price arithmetic for Uniswap pool prices.
+//!
+//! Uniswap V3 prices span `1.0001^MIN_TICK` to `1.0001^MAX_TICK` (about 2^-128
+//! to 2^128 in raw units), which overflows `Decimal` at one end and rounds to
+//! zero in a fixed number of decimal places at the other. [`PriceRatio`] keeps
+//! a price as an integer fraction instead, so it stays precise across the whole
+//! tick range and only becomes a decimal string at the end.
+
+use alloy::primitives::{uint, U160, U256, U512};
+
+/// Smallest tick of a Uniswap V3 pool.
+pub const MIN_TICK: i32 = -887272;
+
+/// Largest tick of a Uniswap V3 pool.
+pub const MAX_TICK: i32 = 887272;
+
+/// `sqrtPriceX96` at [`MIN_TICK`].
+pub const MIN_SQRT_RATIO: U160 = uint!(4295128739_U160);
+
+/// `sqrtPriceX96` at [`MAX_TICK`].
+pub const MAX_SQRT_RATIO: U160 = uint!(1461446703485210103287273052203988822378723970342_U160);
+
+/// Significant digits kept when formatting prices below 1e-18.
+pub const PRICE_SIGNIFICANT_DIGITS: usize = 18;
+
+/// Decimal places prices are formatted with, at minimum.
+pub const PRICE_DECIMALS: usize = 18;
+
+/// Largest numerator or denominator bit length kept by [`PriceRatio`].
+const RATIO_BITS: usize = 256;
+
+/// `sqrtPriceX96` at `tick`, i.e. `sqrt(1.0001^tick) * 2^96`.
+///
+/// Exact port of Uniswap's `TickMath.getSqrtRatioAtTick`. Returns `None` for a
+/// tick outside [`MIN_TICK`, `MAX_TICK`].
+pub fn sqrt_ratio_at_tick(tick: i32) -> Option<U160> {
+    const FACTORS: [(u32, U256); 19] = [
+        (0x2, uint!(0xfff97272373d413259a46990580e213a_U256)),
+        (0x4, uint!(0xfff2e50f5f656932ef12357cf3c7fdcc_U256)),
+        (0x8, uint!(0xffe5caca7e10e4e61c3624eaa0941cd0_U256)),
+        (0x10, uint!(0xffcb9843d60f6159c9db58835c926644_U256)),
+        (0x20, uint!(0xff973b41fa98c081472e6896dfb254c0_U256)),
+        (0x40, uint!(0xff2ea16466c96a3843ec78b326b52861_U256)),
+        (0x80, uint!(0xfe5dee046a99a2a811c461f1969c3053_U256)),
+        (0x100, uint!(0xfcbe86c7900a88aedcffc83b479aa3a4_U256)),
+        (0x200, uint!(0xf987a7253ac413176f2b074cf7815e54_U256)),
+        (0x400, uint!(0xf3392b0822b70005940c7a398e4b70f3_U256)),
+        (0x800, uint!(0xe7159475a2c29b7443b29c7fa6e889d9_U256)),
+        (0x1000, uint!(0xd097f3bdfd2022b8845ad8f792aa5825_U256)),
+        (0x2000, uint!(0xa9f746462d870fdf8a65dc1f90e061e5_U256)),
+        (0x4000, uint!(0x70d869a156d2a1b890bb3df62baf32f7_U256)),
+        (0x8000, uint!(0x31be135f97d08fd981231505542fcfa6_U256)),
+        (0x10000, uint!(0x9aa508b5b7a84e1c677de54f3e99bc9_U256)),
+        (0x20000, uint!(0x5d6af8dedb81196699c329225ee604_U256)),
+        (0x40000, uint!(0x2216e584f5fa1ea926041bedfe98_U256)),
+        (0x80000, uint!(0x48a170391f7dc42444e8fa2_U256)),
+    ];
+
+    let abs_tick = tick.unsigned_abs();
+    if abs_tick > MAX_TICK.unsigned_abs() {
+        return None;
+    }
+
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        uint!(0xfffcb933bd6fad37aa2d162d1a594001_U256)
+    } else {
+        U256::from(1u8) << 128
+    };
+    for (bit, factor) in FACTORS {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * factor) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Round up from Q128.128 to Q64.96
+    let round_up = !(ratio % U256::from(1u64 << 32)).is_zero();
+    let sqrt_price: U256 = (ratio >> 32) + U256::from(round_up);
+    U160::checked_from_limbs_slice(sqrt_price.as_limbs())
+}
+
+/// Output of a V3 swap computed within the pool's current tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SingleTickQuote {
+    /// Output amount in the output token's smallest unit.
+    pub amount_out: U256,
+    /// How far the swap moves the pool price, in basis points.
+    pub price_move_bps: u32,
+}
+
+/// Quote a V3 exact-input swap as if the whole swap stays in the current tick.
+///
+/// Applies the pool fee to `amount_in`, then moves `sqrt_price_x96` through
+/// `liquidity` as Uniswap's `SqrtPriceMath` does within one tick. Liquidity
+/// changes at initialized ticks are ignored, so the result is only a good
+/// approximation when [`SingleTickQuote::price_move_bps`] is small. Returns
+/// `None` for an empty pool or an output that does not fit in 256 bits.
+pub fn single_tick_amount_out(
+    sqrt_price_x96: U160,
+    liquidity: u128,
+    amount_in: U256,
+    fee_ppm: u32,
+    zero_for_one: bool,
+) -> Option<SingleTickQuote> {
+    let price: U512 = U512::from(sqrt_price_x96);
+    let liquidity: U512 = U512::from(liquidity);
+    if price.is_zero() || liquidity.is_zero() {
+        return None;
+    }
+
+    let fee_ppm: U512 = U512::from(fee_ppm.min(1_000_000));
+    let amount: U512 =
+        U512::from(amount_in) * (U512::from(1_000_000u32) - fee_ppm) / U512::from(1_000_000u32);
+    let scaled_liquidity: U512 = liquidity << 96;
+
+    let (price_after, amount_out) = if zero_for_one {
+        // Token0 in: sqrtP' = L·sqrtP / (L + Δx·sqrtP), Δy = L·(sqrtP − sqrtP')
+        let after = scaled_liquidity * price / (scaled_liquidity + amount * price);
+        (after, (liquidity * (price - after)) >> 96)
+    } else {
+        // Token1 in: sqrtP' = sqrtP + Δy / L, Δx = L·(sqrtP' − sqrtP) / (sqrtP'·sqrtP)
+        let after = price + (amount << 96) / liquidity;
+        (after, scaled_liquidity * (after - price) / after / price)
+    };
+    if price_after.is_zero() || amount_out.bit_len() > 256 {
+        return None;
+    }
+
+    let (before_sq, after_sq) = (price * price, price_after * price_after);
+    let move_bps = before_sq.abs_diff(after_sq) * U512::from(10_000u32) / before_sq;
+    Some(SingleTickQuote {
+        amount_out: amount_out.to(),
+        price_move_bps: u32::try_from(move_bps).unwrap_or(u32::MAX),
+    })
+}
+
+/// Output of a V2 exact-input swap against a pair's reserves.
+///
+/// Port of `UniswapV2Library.getAmountOut`: charges the 0.3% fee on `amount_in`
+/// and applies the constant-product formula. Returns `None` for an empty pair.
+pub fn v2_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> Option<U256> {
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return None;
+    }
+
+    let amount_in_with_fee = U512::from(amount_in) * U512::from(997u32);
+    let numerator = amount_in_with_fee * U512::from(reserve_out);
+    let denominator = U512::from(reserve_in) * U512::from(1000u32) + amount_in_with_fee;
+    Some((numerator / denominator).to())
+}
+
+/// A non-negative price as an integer fraction.
+///
+/// The numerator and denominator are kept below 2^256 by shifting both right
+/// together, which preserves about 77 significant digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceRatio {
+    numerator: U512,
+    denominator: U512,
+}
+
+impl PriceRatio {
+    /// A price of 1.
+    pub const ONE: Self = Self { numerator: uint!(1_U512), denominator: uint!(1_U512) };
+
+    /// `numerator / denominator`, or `None` if the denominator is zero.
+    pub fn new(numerator: U256, denominator: U256) -> Option<Self> {
+        Self::reduced(U512::from(numerator), U512::from(denominator))
+    }
+
+    /// Raw output per raw input of a V3 pool at `sqrt_price_x96`.
+    ///
+    /// `sqrt_price_x96` is `sqrt(token1 / token0) * 2^96`, so the price is
+    /// inverted when swapping token1 for token0. Returns `None` for a zero price.
+    pub fn from_sqrt_price_x96(sqrt_price_x96: U160, input_is_token0: bool) -> Option<Self> {
+        let sqrt_price = U512::from(sqrt_price_x96);
+        let price_x192 = sqrt_price * sqrt_price;
+        let q192 = U512::from(1u8) << 192;
+        if price_x192.is_zero() {
+            return None;
+        }
+        if input_is_token0 {
+            Self::reduced(price_x192, q192)
+        } else {
+            Self::reduced(q192, price_x192)
+        }
+    }
+
+    /// Raw output per raw input of a V3 pool at `tick`.
+    ///
+    /// Returns `None` for a tick outside [`MIN_TICK`, `MAX_TICK`].
+    pub fn from_tick(tick: i32, input_is_token0: bool) -> Option<Self> {
+        Self::from_sqrt_price_x96(sqrt_ratio_at_tick(tick)?, input_is_token0)
+    }
+
+    /// Product of two prices, e.g. to chain the hops of a route.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // Both sides are below 2^256, so the products fit in 512 bits
+        Self::reduced(self.numerator * other.numerator, self.denominator * other.denominator)
+    }
+
+    /// Convert a raw price (output base units per input base unit) to whole
+    /// output tokens per whole input token.
+    ///
+    /// Returns `None` if the decimals differ by too much to represent.
+    pub fn scale_decimals(self, input_decimals: u8, output_decimals: u8) -> Option<Self> {
+        let ten = U512::from(10u8);
+        let (numerator, denominator) = if input_decimals >= output_decimals {
+            let factor = ten.checked_pow(U512::from(input_decimals - output_decimals))?;
+            (self.numerator.checked_mul(factor)?, self.denominator)
+        } else {
+            let factor = ten.checked_pow(U512::from(output_decimals - input_decimals))?;
+            (self.numerator, self.denominator.checked_mul(factor)?)
+        };
+        Self::reduced(numerator, denominator)
+    }
+
+    /// Format as a plain decimal string, rounded down.
+    ///
+    /// Uses at least `min_decimals` decimal places, and more for prices below
+    /// 1 so that `significant_digits` digits survive; trailing zeros are trimmed.
+    pub fn to_decimal_string(&self, min_decimals: usize, significant_digits: usize) -> String {
+        let ten = U512::from(10u8);
+        let integer = self.numerator / self.denominator;
+        let mut remainder = self.numerator % self.denominator;
+
+        let decimals = if integer.is_zero() && !remainder.is_zero() {
+            let mut leading_zeros = 0;
+            let mut scaled = remainder * ten;
+            while scaled < self.denominator {
+                scaled *= ten;
+                leading_zeros += 1;
+            }
+            min_decimals.max(leading_zeros + significant_digits)
+        } else {
+            min_decimals
+        };
+
+        // Long division; the remainder stays below the denominator, so `* 10` fits
+        let mut fraction = String::with_capacity(decimals);
+        for _ in 0..decimals {
+            remainder *= ten;
+            fraction.push_str(&(remainder / self.denominator).to_string());
+            remainder %= self.denominator;
+        }
+
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            integer.to_string()
+        } else {
+            format!("{}.{}", integer, fraction)
+        }
+    }
+
+    /// Shift both parts right until they fit in [`RATIO_BITS`].
+    ///
+    /// Returns `None` if the denominator is (or becomes) zero, i.e. the price is
+    /// too large to represent. A price too small to represent becomes zero.
+    fn reduced(numerator: U512, denominator: U512) -> Option<Self> {
+        let bits = numerator.bit_len().max(denominator.bit_len());
+        let shift = bits.saturating_sub(RATIO_BITS);
+        let denominator = denominator >> shift;
+        (!denominator.is_zero()).then(|| Self { numerator: numerator >> shift, denominator })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(price: PriceRatio) -> String {
+        price.to_decimal_string(PRICE_DECIMALS, PRICE_SIGNIFICANT_DIGITS)
+    }
+
+    #[test]
+    fn test_single_tick_amount_out_both_directions() {
+        // Price 1.0 with deep liquidity: output is the input minus the 0.3% fee
+        let price = U160::from(1u8) << 96;
+        let liquidity = 10u128.pow(24);
+        let amount_in = U256::from(10u64.pow(12));
+
+        for zero_for_one in [true, false] {
+            let quote =
+                single_tick_amount_out(price, liquidity, amount_in, 3000, zero_for_one).unwrap();
+            let expected = U256::from(997_000_000_000u64);
+            assert!(quote.amount_out <= expected && expected - quote.amount_out < U256::from(10));
+            assert_eq!(quote.price_move_bps, 0);
+        }
+    }
+
+    #[test]
+    fn test_single_tick_amount_out_price_move() {
+        // Swapping 1% of the virtual reserve moves the price by about 2%
+        let price = U160::from(1u8) << 96;
+        let quote =
+            single_tick_amount_out(price, 1_000_000, U256::from(10_000u64), 0, true).unwrap();
+        assert_eq!(quote.amount_out, U256::from(9_900u64));
+        assert_eq!(quote.price_move_bps, 197);
+    }
+
+    #[test]
+    fn test_single_tick_amount_out_empty_pool() {
+        let price = U160::from(1u8) << 96;
+        assert_eq!(single_tick_amount_out(price, 0, U256::from(1u8), 3000, true), None);
+        assert_eq!(single_tick_amount_out(U160::ZERO, 1, U256::from(1u8), 3000, true), None);
+    }
+
+    #[test]
+    fn test_v2_amount_out() {
+        // 1 WETH into a 1,000 WETH / 2,000,000 USDC pair
+        let amount_out = v2_amount_out(
+            U256::from(10u64.pow(18)),
+            U256::from(1_000u64) * U256::from(10u64.pow(18)),
+            U256::from(2_000_000u64 * 10u64.pow(6)),
+        )
+        .unwrap();
+        assert_eq!(amount_out, U256::from(1_992_013_962u64));
+
+        assert_eq!(v2_amount_out(U256::from(1u8), U256::ZERO, U256::from(1u8)), None);
+        assert_eq!(v2_amount_out(U256::from(1u8), U256::from(1u8), U256::ZERO), None);
+    }
+
+    #[test]
+    fn test_sqrt_ratio_at_tick_bounds() {
+        assert_eq!(sqrt_ratio_at_tick(0), Some(U160::from(1u8) << 96));
+        assert_eq!(sqrt_ratio_at_tick(MIN_TICK), Some(MIN_SQRT_RATIO));
+        assert_eq!(sqrt_ratio_at_tick(MAX_TICK), Some(MAX_SQRT_RATIO));
+        assert_eq!(sqrt_ratio_at_tick(MIN_TICK - 1), None);
+        assert_eq!(sqrt_ratio_at_tick(MAX_TICK + 1), None);
+    }
+
+    #[test]
+    fn test_sqrt_ratio_at_tick_matches_reference() {
+        // Reference values from TickMath.getSqrtRatioAtTick
+        let cases = [
+            (1, "79232123823359799118286999568"),
+            (-1, "79224201403219477170569942574"),
+            (100, "79625275426524748796330556128"),
+            (-100, "78833030112140176575862854579"),
+            (200000, "1744244129640337381386292603617838"),
+        ];
+        for (tick, expected) in cases {
+            assert_eq!(sqrt_ratio_at_tick(tick).unwrap().to_string(), expected, "tick {}", tick);
+        }
+    }
+
+    #[test]
+    fn test_price_at_extreme_ticks() {
+        let max = PriceRatio::from_tick(MAX_TICK, true).unwrap();
+        assert_eq!(format(max), "340256786836388094070642339899681172762.184831912720469415");
+
+        // The smallest price keeps its significant digits instead of rounding to zero
+        let min = PriceRatio::from_tick(MIN_TICK, true).unwrap();
+        assert_eq!(format(min), "0.0000000000000000000000000000000000000029389568087743112");
+
+        let inverse = PriceRatio::from_tick(MIN_TICK, false).unwrap();
+        assert_eq!(format(inverse), "340256786698763678858396856460488307819.979090561464864775");
+
+        assert_eq!(PriceRatio::from_tick(MAX_TICK + 1, true), None);
+    }
+
+    #[test]
+    fn test_price_with_different_decimals() {
+        // WBTC (8 decimals) is token0 and WETH (18 decimals) token1; 16 WETH per WBTC
+        // is a raw price of 1.6e11 = 400,000^2
+        let sqrt_price = U160::from(400_000u64) << 96;
+
+        let wbtc_to_weth = PriceRatio::from_sqrt_price_x96(sqrt_price, true).unwrap();
+        assert_eq!(format(wbtc_to_weth.scale_decimals(8, 18).unwrap()), "16");
+
+        let weth_to_wbtc = PriceRatio::from_sqrt_price_x96(sqrt_price, false).unwrap();
+        assert_eq!(format(weth_to_wbtc.scale_decimals(18, 8).unwrap()), "0.0625");
+    }
+
+    #[test]
+    fn test_extreme_tick_with_different_decimals() {
+        // Scaling the largest raw price by 18 - 6 decimals stays exact
+        let price = PriceRatio::from_tick(MAX_TICK, true).unwrap().scale_decimals(18, 6).unwrap();
+        assert!(format(price).starts_with("340256786836388094070642339899681172762184831912720"));
+
+        // A decimals gap that cannot be represented is reported, not wrapped
+        assert_eq!(PriceRatio::ONE.scale_decimals(255, 0), None);
+    }
+
+    #[test]
+    fn test_checked_mul_chains_extreme_prices() {
+        let max = PriceRatio::from_tick(MAX_TICK, true).unwrap();
+        let min = PriceRatio::from_tick(MIN_TICK, true).unwrap();
+
+        // MAX_TICK and MIN_TICK are reciprocal up to rounding of the sqrt price
+        let product = format(max.checked_mul(min).unwrap());
+        assert!(product.starts_with("0.99999999") || product.starts_with("1"), "{}", product);
+
+        // Beyond 2^256 the price cannot be represented
+        assert_eq!(max.checked_mul(max).and_then(|p| p.checked_mul(max)), None);
+    }
+
+    #[test]
+    fn test_ratio_formatting() {
+        assert_eq!(format(PriceRatio::ONE), "1");
+        assert_eq!(
+            format(PriceRatio::new(U256::from(1u8), U256::from(3u8)).unwrap()),
+            "0.333333333333333333"
+        );
+        assert_eq!(format(PriceRatio::new(U256::ZERO, U256::from(3u8)).unwrap()), "0");
+        assert_eq!(PriceRatio::new(U256::from(1u8), U256::ZERO), None);
+    }
+}
@@ -0,0 +1,427 @@
+//! Uniswap V3 quoter access.
+//!
+//! QuoterV2's quote functions are not `view`: they execute the swap and revert to
+//! return the result. They must only ever be sent as `eth_call`, never as a
+//! transaction. Depending on the backend, the result comes back either as normal
+//! return data or as revert data on the JSON-RPC error, so both are decoded here.
+
+use alloy::{
+    primitives::{aliases::U24, Address, Bytes, U160, U256},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+    sol_types::{decode_revert_reason, Panic, Revert, SolCall, SolError},
+};
+
+use super::{
+    contracts::uniswap_v3::{encode_path_with_fees, IQuoterV2},
+    HttpProvider,
+};
+use crate::error::{AppError, Result};
+
+/// Quote an exact-input single-pool swap on the `quoter`, returning the output amount.
+pub async fn quote_exact_input_single(
+    provider: &HttpProvider,
+    quoter: Address,
+    token_in: Address,
+    token_out: Address,
+    fee: u32,
+    amount_in: U256,
+) -> Result<U256> {
+    let call = IQuoterV2::quoteExactInputSingleCall {
+        params: IQuoterV2::QuoteExactInputSingleParams {
+            tokenIn: token_in,
+            tokenOut: token_out,
+            amountIn: amount_in,
+            fee: U24::from(fee),
+            sqrtPriceLimitX96: U160::ZERO,
+        },
+    };
+
+    let data = call_quoter(provider, quoter, call.abi_encode()).await?;
+    decode_quote_amount::<IQuoterV2::quoteExactInputSingleCall>(&data, |r| r.amountOut)
+}
+
+/// Quote of an exact-input swap along a multi-hop V3 path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathQuote {
+    /// Output amount of the final hop.
+    pub amount_out: U256,
+    /// Pool price after the swap, one entry per hop.
+    pub sqrt_price_x96_after: Vec<U160>,
+    /// Initialized ticks crossed, one entry per hop.
+    pub initialized_ticks_crossed: Vec<u32>,
+    /// Quoter's gas estimate for the whole path.
+    pub gas_estimate: U256,
+}
+
+/// Quote an exact-input multi-hop swap along `path` at a single fee tier.
+pub async fn quote_exact_input(
+    provider: &HttpProvider,
+    quoter: Address,
+    path: &[Address],
+    fee: u32,
+    amount_in: U256,
+) -> Result<U256> {
+    let fees = vec![fee; path.len().saturating_sub(1)];
+    Ok(quote_exact_input_path(provider, quoter, path, &fees, amount_in).await?.amount_out)
+}
+
+/// Quote an exact-input swap along `path` with a fee tier per hop, in one quoter call.
+///
+/// Each hop is quoted against the pool state left by the previous hop, unlike
+/// chaining single-hop quotes.
+pub async fn quote_exact_input_path(
+    provider: &HttpProvider,
+    quoter: Address,
+    path: &[Address],
+    fees: &[u32],
+    amount_in: U256,
+) -> Result<PathQuote> {
+    if path.len() < 2 || fees.len() + 1 != path.len() {
+        return Err(AppError::RouteNotFound(format!(
+            "invalid V3 path: {} tokens with {} fee tiers",
+            path.len(),
+            fees.len()
+        )));
+    }
+
+    let call = IQuoterV2::quoteExactInputCall {
+        path: encode_path_with_fees(path, fees),
+        amountIn: amount_in,
+    };
+
+    let data = call_quoter(provider, quoter, call.abi_encode()).await?;
+    if let Ok(ret) = IQuoterV2::quoteExactInputCall::abi_decode_returns(&data) {
+        return Ok(PathQuote {
+            amount_out: ret.amountOut,
+            sqrt_price_x96_after: ret.sqrtPriceX96AfterList,
+            initialized_ticks_crossed: ret.initializedTicksCrossedList,
+            gas_estimate: ret.gasEstimate,
+        });
+    }
+
+    // The original Quoter only returns the output amount
+    let amount_out = decode_quote_amount::<IQuoterV2::quoteExactInputCall>(&data, |r| r.amountOut)?;
+    Ok(PathQuote {
+        amount_out,
+        sqrt_price_x96_after: Vec::new(),
+        initialized_ticks_crossed: Vec::new(),
+        gas_estimate: U256::ZERO,
+    })
+}
+
+/// Quote an exact-output single-pool swap, returning the required input amount.
+pub async fn quote_exact_output_single(
+    provider: &HttpProvider,
+    quoter: Address,
+    token_in: Address,
+    token_out: Address,
+    fee: u32,
+    amount_out: U256,
+) -> Result<U256> {
+    let call = IQuoterV2::quoteExactOutputSingleCall {
+        params: IQuoterV2::QuoteExactOutputSingleParams {
+            tokenIn: token_in,
+            tokenOut: token_out,
+            amount: amount_out,
+            fee: U24::from(fee),
+            sqrtPriceLimitX96: U160::ZERO,
+        },
+    };
+
+    let data = call_quoter(provider, quoter, call.abi_encode()).await?;
+    decode_quote_amount::<IQuoterV2::quoteExactOutputSingleCall>(&data, |r| r.amountIn)
+}
+
+/// Send calldata to the `quoter` via `eth_call`, returning either the return data
+/// or the revert data carrying the encoded result.
+async fn call_quoter(provider: &HttpProvider, quoter: Address, calldata: Vec<u8>) -> Result<Bytes> {
+    let tx = TransactionRequest::default().to(quoter).input(Bytes::from(calldata).into());
+
+    match provider.call(tx).await {
+        Ok(data) => Ok(data),
+        Err(e) => match e.as_error_resp().and_then(|payload| payload.as_revert_data()) {
+            // A genuine revert (`Error(string)` / `Panic(uint256)`), not an encoded result
+            Some(data)
+                if data.starts_with(&Revert::SELECTOR) || data.starts_with(&Panic::SELECTOR) =>
+            {
+                Err(AppError::Rpc(format!(
+                    "Quoter call reverted: {}",
+                    decode_revert_reason(&data).unwrap_or_default()
+                )))
+            }
+            Some(data) if !data.is_empty() => Ok(data),
+            _ => Err(AppError::Rpc(format!("Quoter call failed: {}", e))),
+        },
+    }
+}
+
+/// Decode the quoted amount (output for exact-input, input for exact-output)
+/// from quoter return data.
+///
+/// Accepts the full QuoterV2 return tuple, or a bare `uint256` as returned by
+/// the original Quoter's revert payload.
+fn decode_quote_amount<C: SolCall>(
+    data: &[u8],
+    amount: impl Fn(C::Return) -> U256,
+) -> Result<U256> {
+    if let Ok(ret) = C::abi_decode_returns(data) {
+        return Ok(amount(ret));
+    }
+
+    if data.len() == 32 {
+        return Ok(U256::from_be_slice(data));
+    }
+
+    Err(AppError::Parse(format!(
+        "Unexpected quoter response ({} bytes): 0x{}",
+        data.len(),
+        alloy::hex::encode(data)
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::UNISWAP_V3_QUOTER;
+    use alloy::{providers::ProviderBuilder, sol_types::SolValue, transports::mock::Asserter};
+
+    fn mocked_provider(asserter: Asserter) -> HttpProvider {
+        ProviderBuilder::new().connect_mocked_client(asserter).root().clone()
+    }
+
+    fn single_return(amount_out: u64) -> Bytes {
+        (U256::from(amount_out), U160::from(1u64), 2u32, U256::from(100_000u64))
+            .abi_encode_params()
+            .into()
+    }
+
+    /// Push a geth-style "execution reverted" error carrying `data` as revert data.
+    fn push_revert(asserter: &Asserter, data: &Bytes) {
+        asserter.push_failure(
+            serde_json::from_value(serde_json::json!({
+                "code": 3,
+                "message": "execution reverted",
+                "data": data.to_string(),
+            }))
+            .unwrap(),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quote_from_return_data() {
+        let asserter = Asserter::new();
+        asserter.push_success(&single_return(2_500));
+
+        let amount = quote_exact_input_single(
+            &mocked_provider(asserter),
+            UNISWAP_V3_QUOTER,
+            Address::ZERO,
+            Address::ZERO,
+            3000,
+            U256::from(1u64),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(amount, U256::from(2_500u64));
+    }
+
+    #[tokio::test]
+    async fn test_quote_from_revert_data() {
+        let asserter = Asserter::new();
+        push_revert(&asserter, &single_return(1_234));
+
+        let amount = quote_exact_input_single(
+            &mocked_provider(asserter),
+            UNISWAP_V3_QUOTER,
+            Address::ZERO,
+            Address::ZERO,
+            500,
+            U256::from(1u64),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(amount, U256::from(1_234u64));
+    }
+
+    #[tokio::test]
+    async fn test_quote_from_legacy_uint_revert() {
+        let asserter = Asserter::new();
+        let legacy: Bytes = U256::from(42u64).abi_encode().into();
+        push_revert(&asserter, &legacy);
+
+        let amount = quote_exact_input(
+            &mocked_provider(asserter),
+            UNISWAP_V3_QUOTER,
+            &[Address::ZERO, Address::repeat_byte(1), Address::repeat_byte(2)],
+            3000,
+            U256::from(1u64),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(amount, U256::from(42u64));
+    }
+
+    #[tokio::test]
+    async fn test_quote_exact_input_path_outputs() {
+        let asserter = Asserter::new();
+        let ret: Bytes = (
+            U256::from(9_876u64),
+            vec![U160::from(11u64), U160::from(22u64)],
+            vec![3u32, 1u32],
+            U256::from(210_000u64),
+        )
+            .abi_encode_params()
+            .into();
+        asserter.push_success(&ret);
+
+        let quote = quote_exact_input_path(
+            &mocked_provider(asserter),
+            UNISWAP_V3_QUOTER,
+            &[Address::ZERO, Address::repeat_byte(1), Address::repeat_byte(2)],
+            &[3000, 500],
+            U256::from(1u64),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(quote.amount_out, U256::from(9_876u64));
+        assert_eq!(quote.sqrt_price_x96_after, vec![U160::from(11u64), U160::from(22u64)]);
+        assert_eq!(quote.initialized_ticks_crossed, vec![3, 1]);
+        assert_eq!(quote.gas_estimate, U256::from(210_000u64));
+    }
+
+    /// A two-hop path is quoted in one call, so price impact on each hop is
+    /// reflected instead of scaling per-hop unit rates.
+    #[tokio::test]
+    async fn test_quote_exact_input_path_differs_from_per_hop_rates() {
+        let path = [Address::ZERO, Address::repeat_byte(1), Address::repeat_byte(2)];
+        let fees = [3000, 500];
+        let amount_in = U256::from(1_000u64);
+
+        let asserter = Asserter::new();
+        asserter.push_success(&single_return(2));
+        asserter.push_success(&single_return(3));
+        let provider = mocked_provider(asserter.clone());
+        let first = quote_exact_input_single(
+            &provider,
+            UNISWAP_V3_QUOTER,
+            path[0],
+            path[1],
+            fees[0],
+            U256::from(1u64),
+        )
+        .await
+        .unwrap();
+        let second = quote_exact_input_single(
+            &provider,
+            UNISWAP_V3_QUOTER,
+            path[1],
+            path[2],
+            fees[1],
+            U256::from(1u64),
+        )
+        .await
+        .unwrap();
+        let naive = amount_in * first * second;
+
+        let ret: Bytes = (
+            U256::from(5_400u64),
+            vec![U160::from(11u64), U160::from(22u64)],
+            vec![4u32, 2u32],
+            U256::from(210_000u64),
+        )
+            .abi_encode_params()
+            .into();
+        asserter.push_success(&ret);
+        let quote = quote_exact_input_path(&provider, UNISWAP_V3_QUOTER, &path, &fees, amount_in)
+            .await
+            .unwrap();
+
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(encode_path_with_fees(&path, &fees).len(), 20 + 3 + 20 + 3 + 20);
+        assert_eq!(quote.amount_out, U256::from(5_400u64));
+        assert!(quote.amount_out < naive, "{} vs {}", quote.amount_out, naive);
+    }
+
+    #[tokio::test]
+    async fn test_quote_exact_input_path_rejects_fee_mismatch() {
+        let result = quote_exact_input_path(
+            &mocked_provider(Asserter::new()),
+            UNISWAP_V3_QUOTER,
+            &[Address::ZERO, Address::repeat_byte(1), Address::repeat_byte(2)],
+            &[3000],
+            U256::from(1u64),
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::RouteNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_quote_revert_reason_is_error() {
+        let asserter = Asserter::new();
+        let reason: Bytes = Revert::from("SPL").abi_encode().into();
+        push_revert(&asserter, &reason);
+
+        let result = quote_exact_input_single(
+            &mocked_provider(asserter),
+            UNISWAP_V3_QUOTER,
+            Address::ZERO,
+            Address::ZERO,
+            3000,
+            U256::from(1u64),
+        )
+        .await;
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("SPL"), "unexpected error: {}", err);
+    }
+
+    #[tokio::test]
+    async fn test_quote_rpc_error_without_data() {
+        let asserter = Asserter::new();
+        asserter.push_failure_msg("method not allowed");
+
+        let result = quote_exact_input_single(
+            &mocked_provider(asserter),
+            UNISWAP_V3_QUOTER,
+            Address::ZERO,
+            Address::ZERO,
+            3000,
+            U256::from(1u64),
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Rpc(_))));
+    }
+
+    #[tokio::test]
+    async fn test_quote_exact_output_single() {
+        let asserter = Asserter::new();
+        asserter.push_success(&single_return(5_000));
+
+        let amount_in = quote_exact_output_single(
+            &mocked_provider(asserter),
+            UNISWAP_V3_QUOTER,
+            Address::ZERO,
+            Address::ZERO,
+            3000,
+            U256::from(1_000u64),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(amount_in, U256::from(5_000u64));
+    }
+
+    #[test]
+    fn test_decode_quote_amount_rejects_garbage() {
+        let result =
+            decode_quote_amount::<IQuoterV2::quoteExactInputSingleCall>(&[0u8; 5], |r| r.amountOut);
+        assert!(matches!(result, Err(AppError::Parse(_))));
+    }
+}
@@ -1,9 +1,24 @@
 //! Wallet management.
 
-use alloy::{primitives::Address, signers::local::PrivateKeySigner};
+use alloy::{
+    primitives::Address,
+    signers::local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner},
+};
 
 use crate::error::{AppError, Result};
 
+/// Number of mnemonic accounts searched when the account is picked by address.
+pub const MNEMONIC_ADDRESS_SEARCH_LIMIT: u32 = 20;
+
+/// Which account derived from a mnemonic to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MnemonicAccount {
+    /// The account at `m/44'/60'/0'/0/{index}`.
+    Index(u32),
+    /// The account with this address, among the first [`MNEMONIC_ADDRESS_SEARCH_LIMIT`].
+    Address(Address),
+}
+
 /// Wallet manager for transaction signing.
 #[derive(Clone)]
 pub struct WalletManager {
@@ -11,6 +26,8 @@ pub struct WalletManager {
     signer: PrivateKeySigner,
     /// Wallet address.
     address: Address,
+    /// Derivation index when the wallet comes from a mnemonic.
+    derivation_index: Option<u32>,
 }
 
 impl WalletManager {
@@ -28,7 +45,39 @@ impl WalletManager {
 
         tracing::info!(address = %address, "Wallet initialized");
 
-        Ok(Self { signer, address })
+        Ok(Self { signer, address, derivation_index: None })
+    }
+
+    /// Create a wallet manager from a BIP-39 mnemonic phrase.
+    ///
+    /// The first `preview_count` derived addresses are logged with the active one
+    /// marked, so a seed phrase resolving to an unexpected account is easy to spot.
+    /// Neither the phrase nor any key is logged.
+    pub fn from_mnemonic(
+        phrase: &str,
+        account: MnemonicAccount,
+        preview_count: u32,
+    ) -> Result<Self> {
+        let preview = derive_addresses(phrase, preview_count)?;
+
+        let index = match account {
+            MnemonicAccount::Index(index) => index,
+            MnemonicAccount::Address(address) => find_account(phrase, address, &preview)?,
+        };
+        let signer = derive_signer(phrase, index)?;
+        let address = signer.address();
+
+        for (i, derived) in (0..).zip(&preview) {
+            tracing::info!(
+                index = i,
+                address = %derived,
+                active = i == index,
+                "Mnemonic account"
+            );
+        }
+        tracing::info!(address = %address, derivation_index = index, "Wallet initialized from mnemonic");
+
+        Ok(Self { signer, address, derivation_index: Some(index) })
     }
 
     /// Get the wallet address.
@@ -36,6 +85,11 @@ impl WalletManager {
         self.address
     }
 
+    /// Derivation index of the account, when the wallet comes from a mnemonic.
+    pub fn derivation_index(&self) -> Option<u32> {
+        self.derivation_index
+    }
+
     /// Get the signer for transaction signing.
     pub fn signer(&self) -> &PrivateKeySigner {
         &self.signer
@@ -44,10 +98,44 @@ impl WalletManager {
 
 impl std::fmt::Debug for WalletManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("WalletManager").field("address", &self.address).finish()
+        f.debug_struct("WalletManager")
+            .field("address", &self.address)
+            .field("derivation_index", &self.derivation_index)
+            .finish()
     }
 }
 
+/// Derive the signer at `m/44'/60'/0'/0/{index}`.
+fn derive_signer(phrase: &str, index: u32) -> Result<PrivateKeySigner> {
+    MnemonicBuilder::<English>::from_phrase(phrase.trim())
+        .index(index)
+        .and_then(|builder| builder.build())
+        // The underlying error may quote words of the phrase
+        .map_err(|_| AppError::Wallet(format!("Invalid mnemonic or derivation index {}", index)))
+}
+
+/// Addresses of the first `count` accounts derived from a mnemonic.
+fn derive_addresses(phrase: &str, count: u32) -> Result<Vec<Address>> {
+    (0..count).map(|index| derive_signer(phrase, index).map(|signer| signer.address())).collect()
+}
+
+/// Index of the mnemonic account with `address`, reusing the already derived `preview`.
+fn find_account(phrase: &str, address: Address, preview: &[Address]) -> Result<u32> {
+    for index in 0..MNEMONIC_ADDRESS_SEARCH_LIMIT {
+        let derived = match preview.get(index as usize) {
+            Some(derived) => *derived,
+            None => derive_signer(phrase, index)?.address(),
+        };
+        if derived == address {
+            return Ok(index);
+        }
+    }
+    Err(AppError::Wallet(format!(
+        "{} is not among the first {} accounts derived from the mnemonic",
+        address, MNEMONIC_ADDRESS_SEARCH_LIMIT
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,6 +234,60 @@ mod tests {
         assert_ne!(wallet1.address(), wallet2.address());
     }
 
+    // The mnemonic of the Hardhat/Foundry test accounts (DO NOT use in production!)
+    const TEST_MNEMONIC: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_wallet_from_mnemonic_by_index() {
+        let wallet =
+            WalletManager::from_mnemonic(TEST_MNEMONIC, MnemonicAccount::Index(0), 2).unwrap();
+        let reference = WalletManager::from_private_key(TEST_PRIVATE_KEY).unwrap();
+        assert_eq!(wallet.address(), reference.address());
+        assert_eq!(wallet.derivation_index(), Some(0));
+        assert_eq!(reference.derivation_index(), None);
+
+        // Second Hardhat account, outside the preview
+        let wallet =
+            WalletManager::from_mnemonic(TEST_MNEMONIC, MnemonicAccount::Index(1), 0).unwrap();
+        assert_eq!(
+            format!("{:?}", wallet.address()).to_lowercase(),
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+    }
+
+    #[test]
+    fn test_wallet_from_mnemonic_by_address() {
+        let second: Address = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".parse().unwrap();
+        let third: Address = "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC".parse().unwrap();
+
+        let wallet =
+            WalletManager::from_mnemonic(TEST_MNEMONIC, MnemonicAccount::Address(second), 3)
+                .unwrap();
+        assert_eq!(wallet.address(), second);
+        assert_eq!(wallet.derivation_index(), Some(1));
+
+        // Found beyond the preview too
+        let wallet =
+            WalletManager::from_mnemonic(TEST_MNEMONIC, MnemonicAccount::Address(third), 1)
+                .unwrap();
+        assert_eq!(wallet.derivation_index(), Some(2));
+    }
+
+    #[test]
+    fn test_wallet_from_mnemonic_errors() {
+        let unknown = Address::repeat_byte(0x42);
+        let result =
+            WalletManager::from_mnemonic(TEST_MNEMONIC, MnemonicAccount::Address(unknown), 0);
+        assert!(matches!(result, Err(AppError::Wallet(msg)) if msg.contains("first 20 accounts")));
+
+        let result = WalletManager::from_mnemonic(
+            "test test test test test test test test test test test secretword",
+            MnemonicAccount::Index(0),
+            0,
+        );
+        assert!(matches!(result, Err(AppError::Wallet(msg)) if !msg.contains("secretword")));
+    }
+
     #[test]
     fn test_wallet_error_contains_message() {
         let result = WalletManager::from_private_key("invalid_key");
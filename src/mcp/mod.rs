@@ -2,7 +2,19 @@
 //!
 //! Contains the MCP server implementation with tool handlers.
 
+pub mod progress;
 pub mod server;
+pub mod timeout;
 
+pub use progress::ProgressReporter;
 pub use server::EthereumTradingServer;
-pub use server::{GetBalanceInput, GetTokenPriceInput, SwapTokensInput};
+pub use server::{
+    BestSwapInput, CanTradeInput, DecodeSelectorInput, EffectiveRateInput,
+    EstimateDeadlineBlocksInput, ForceRouteInput, GetBalanceInput, GetErc1155BalanceInput,
+    GetExactOutputQuoteInput, GetNonceInput, GetPoolFeeInput, GetTokenPriceInput,
+    GetUsdOutputQuoteInput, GetWalletInfoInput, PoolFeeAprInput, PriceAlertCheckInput,
+    PriceSourceSpreadInput, RecentPoolSwapsInput, SimulateAddLiquidityInput,
+    SimulateRemoveLiquidityInput, SimulateRoundTripPnlInput, SimulateSwapPathInput,
+    SwapPreviewInput, SwapTokensInput, TradeCostBreakdownInput, VerifyTokenInput,
+};
+pub use timeout::ToolTimeouts;
@@ -0,0 +1,147 @@
+//! Progress notifications for long-running tool calls.
+//!
+//! When a `tools/call` request carries a `progressToken`, the call runs inside a
+//! [`ProgressReporter`] scope and batch work reports how many items are done
+//! (e.g., "12/50 tokens priced"). Notifications are throttled to one per
+//! [`MIN_PROGRESS_INTERVAL`], except the final one. Without a token, or when a
+//! tool is called directly, [`report`] does nothing and the client only sees
+//! the final response.
+
+use std::{
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use rmcp::{
+    model::{ProgressNotificationParam, ProgressToken},
+    Peer, RoleServer,
+};
+use tokio::sync::mpsc;
+
+/// Minimum time between two progress notifications of the same call.
+pub const MIN_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+tokio::task_local! {
+    static PROGRESS: ProgressReporter;
+}
+
+/// Sends progress notifications for one tool call.
+#[derive(Debug)]
+pub struct ProgressReporter {
+    /// Notifications waiting to be forwarded to the client, in order.
+    sender: mpsc::UnboundedSender<ProgressNotificationParam>,
+    token: ProgressToken,
+    /// When the last notification was queued.
+    last_sent: Mutex<Option<Instant>>,
+}
+
+impl ProgressReporter {
+    /// Create a reporter forwarding notifications for `token` to `peer`.
+    ///
+    /// Notifications are sent by a background task, so reporting never waits
+    /// on the transport. The task ends when the reporter is dropped.
+    pub fn new(peer: Peer<RoleServer>, token: ProgressToken) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(param) = receiver.recv().await {
+                if let Err(e) = peer.notify_progress(param).await {
+                    tracing::debug!(error = %e, "Failed to send progress notification");
+                    break;
+                }
+            }
+        });
+        Self::with_sender(sender, token)
+    }
+
+    fn with_sender(
+        sender: mpsc::UnboundedSender<ProgressNotificationParam>,
+        token: ProgressToken,
+    ) -> Self {
+        Self { sender, token, last_sent: Mutex::new(None) }
+    }
+
+    /// Run `call` with this reporter receiving its [`report`]s.
+    pub async fn scope<F: Future>(self, call: F) -> F::Output {
+        PROGRESS.scope(self, call).await
+    }
+
+    /// Queue a notification for `done` of `total` items, unless throttled.
+    fn report(&self, done: usize, total: usize, unit: &str) {
+        let now = Instant::now();
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        if !should_send(*last_sent, now, done, total) {
+            return;
+        }
+        *last_sent = Some(now);
+
+        let _ = self.sender.send(ProgressNotificationParam {
+            progress_token: self.token.clone(),
+            progress: done as f64,
+            total: Some(total as f64),
+            message: Some(format!("{}/{} {}", done, total, unit)),
+        });
+    }
+}
+
+/// Report that `done` of `total` items (described by `unit`) are complete.
+///
+/// Does nothing outside a [`ProgressReporter::scope`].
+pub fn report(done: usize, total: usize, unit: &str) {
+    let _ = PROGRESS.try_with(|progress| progress.report(done, total, unit));
+}
+
+/// Whether a notification is due: always for the last item, otherwise at most
+/// once per [`MIN_PROGRESS_INTERVAL`].
+fn should_send(last_sent: Option<Instant>, now: Instant, done: usize, total: usize) -> bool {
+    done >= total || last_sent.is_none_or(|last| now.duration_since(last) >= MIN_PROGRESS_INTERVAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::NumberOrString;
+
+    fn reporter() -> (ProgressReporter, mpsc::UnboundedReceiver<ProgressNotificationParam>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let token = ProgressToken(NumberOrString::Number(1));
+        (ProgressReporter::with_sender(sender, token), receiver)
+    }
+
+    #[test]
+    fn test_should_send_throttles_intermediate_progress() {
+        let start = Instant::now();
+        assert!(should_send(None, start, 1, 10));
+        assert!(!should_send(Some(start), start + Duration::from_millis(10), 2, 10));
+        assert!(should_send(Some(start), start + MIN_PROGRESS_INTERVAL, 3, 10));
+        // The final notification is never throttled
+        assert!(should_send(Some(start), start, 10, 10));
+    }
+
+    #[tokio::test]
+    async fn test_report_in_scope_sends_first_and_last() {
+        let (progress, mut receiver) = reporter();
+
+        progress
+            .scope(async {
+                for done in 1..=50 {
+                    report(done, 50, "tokens priced");
+                }
+            })
+            .await;
+
+        let first = receiver.recv().await.unwrap();
+        assert_eq!(first.progress, 1.0);
+        assert_eq!(first.total, Some(50.0));
+        assert_eq!(first.message.as_deref(), Some("1/50 tokens priced"));
+
+        let last = receiver.recv().await.unwrap();
+        assert_eq!(last.message.as_deref(), Some("50/50 tokens priced"));
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[test]
+    fn test_report_outside_scope_is_noop() {
+        report(1, 2, "tokens priced");
+    }
+}
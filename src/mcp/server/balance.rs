@@ -0,0 +1,284 @@
+//! Balance, wallet and nonce tools.
+
+use std::str::FromStr;
+
+use alloy::primitives::U256;
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+
+use super::{
+    address_schema, optional_address_schema, parse_address, parse_response_format,
+    EthereumTradingServer, EXAMPLE_TOKEN_ADDRESS, EXAMPLE_WALLET_ADDRESS,
+};
+use crate::{
+    mcp::progress,
+    services::token_registry::normalize_symbol,
+    types::{QuoteCurrency, ResponseFormat, WalletInfo},
+};
+
+/// Input parameters for the get_balance tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+#[schemars(
+    example = serde_json::json!({ "address": EXAMPLE_WALLET_ADDRESS }),
+    example = serde_json::json!({
+        "address": EXAMPLE_WALLET_ADDRESS,
+        "token_address": EXAMPLE_TOKEN_ADDRESS,
+    })
+)]
+pub struct GetBalanceInput {
+    /// Wallet address to query (0x...).
+    #[schemars(schema_with = "address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub address: String,
+    /// Optional ERC20 token contract address. If not provided, returns native ETH balance.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_TOKEN_ADDRESS)]
+    pub token_address: Option<String>,
+    /// Optional token symbol (e.g., "USDC"), resolved with the Uniswap Token List, as
+    /// an alternative to token_address. "ETH" returns the native ETH balance.
+    #[serde(default)]
+    #[schemars(example = &"USDC")]
+    pub token_symbol: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved token_symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+    /// Round the human-readable balance to this many decimal places (balance_raw
+    /// stays exact). Defaults to full precision.
+    #[serde(default)]
+    #[schemars(example = 4)]
+    pub display_decimals: Option<u32>,
+    /// Response format: "json" (the full result) or "text" (a concise
+    /// human-readable summary, without provenance). Defaults to "json".
+    #[serde(default)]
+    #[schemars(example = &"text")]
+    pub format: Option<String>,
+}
+
+/// Input parameters for the get_erc1155_balance tool.
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+pub struct GetErc1155BalanceInput {
+    /// Account address to query (0x...).
+    pub address: String,
+    /// ERC1155 contract address (0x...).
+    pub token_address: String,
+    /// Token ID within the contract, decimal or 0x-prefixed hex (e.g., "1").
+    pub token_id: String,
+}
+
+/// Input parameters for the get_wallet_info tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct GetWalletInfoInput {
+    /// If true, include the wallet's native ETH balance. Defaults to false.
+    #[serde(default)]
+    pub include_eth_balance: Option<bool>,
+    /// Token symbols to include balances for (e.g., ["USDC", "WETH"]).
+    #[serde(default)]
+    pub tokens: Vec<String>,
+    /// If set, value each balance and the total in this currency: "USD" or "ETH".
+    #[serde(default)]
+    pub base_currency: Option<String>,
+    /// If true, list tokens with a zero balance; otherwise they are only counted in
+    /// `zero_balance_tokens_skipped`. Defaults to ETHEREUM_INCLUDE_ZERO_BALANCES (false).
+    #[serde(default)]
+    pub include_zero_balances: Option<bool>,
+    /// Response format: "json" (the full result) or "text" (a concise
+    /// human-readable summary, without provenance). Defaults to "json".
+    #[serde(default)]
+    #[schemars(example = &"text")]
+    pub format: Option<String>,
+}
+
+/// Input parameters for the get_nonce tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct GetNonceInput {
+    /// Address to query (0x...). Defaults to the configured wallet.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub address: Option<String>,
+}
+
+#[tool_router(router = balance_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Query ETH and ERC20 token balances for a wallet address.
+    ///
+    /// Returns the balance in both human-readable format (with proper decimals)
+    /// and raw format (smallest unit like wei).
+    #[tool(
+        description = "Query ETH and ERC20 token balances for a wallet address. Pass the token as token_address or as token_symbol (e.g., USDC); the response includes the token's address."
+    )]
+    pub async fn get_balance(
+        &self,
+        Parameters(input): Parameters<GetBalanceInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            address = %input.address,
+            token = ?input.token_address,
+            symbol = ?input.token_symbol,
+            "get_balance called"
+        );
+
+        let address = parse_address(&input.address)?;
+        let format = parse_response_format(input.format.as_deref())?;
+        let mut tokens = self.resolver(input.include_provenance);
+        let token_address = match (&input.token_address, &input.token_symbol) {
+            (Some(_), Some(_)) => {
+                return Err(McpError::invalid_params(
+                    "Set either token_address or token_symbol, not both",
+                    None,
+                ));
+            }
+            (Some(token), None) => Some(parse_address(token)?),
+            (None, Some(symbol)) if normalize_symbol(symbol) == "ETH" => None,
+            (None, Some(symbol)) => Some(tokens.token("token", symbol).await?.address),
+            (None, None) => None,
+        };
+
+        let mut result = self
+            .balance_service
+            .get_balance(address, token_address)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if let Some(decimals) = input.display_decimals {
+            result.round_balance(decimals);
+        }
+
+        if format == ResponseFormat::Text {
+            return Ok(tokens.respond_text(result));
+        }
+        tokens.respond(result)
+    }
+
+    /// Query the balance of one ERC1155 token ID for an account.
+    ///
+    /// Kept separate from get_balance: ERC1155 balances have no decimals, so only
+    /// the raw balance is returned.
+    #[tool(
+        description = "Query the balance of an ERC1155 token ID (e.g., some LP and vault positions) for an account. Returns the raw balance and the token ID."
+    )]
+    pub async fn get_erc1155_balance(
+        &self,
+        Parameters(input): Parameters<GetErc1155BalanceInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            address = %input.address,
+            token = %input.token_address,
+            token_id = %input.token_id,
+            "get_erc1155_balance called"
+        );
+
+        let address = parse_address(&input.address)?;
+        let token_address = parse_address(&input.token_address)?;
+        let token_id = U256::from_str(input.token_id.trim()).map_err(|e| {
+            McpError::invalid_params(format!("Invalid token_id '{}': {}", input.token_id, e), None)
+        })?;
+
+        let result = self
+            .balance_service
+            .get_erc1155_balance(address, token_address, token_id)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// Get the configured wallet's address and, optionally, its balances.
+    ///
+    /// Never exposes the private key. In read-only mode, reports that no wallet
+    /// is configured.
+    #[tool(
+        description = "Get the address of the configured wallet (checksummed) and optionally its ETH balance and the balances of a list of token symbols. Zero token balances are left out and counted unless include_zero_balances is true. Reports when no wallet is configured (read-only mode)."
+    )]
+    pub async fn get_wallet_info(
+        &self,
+        Parameters(input): Parameters<GetWalletInfoInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            include_eth_balance = ?input.include_eth_balance,
+            tokens = ?input.tokens,
+            "get_wallet_info called"
+        );
+
+        let format = parse_response_format(input.format.as_deref())?;
+
+        let Some(wallet_address) = self.wallet_address else {
+            if format == ResponseFormat::Text {
+                return Ok(WalletInfo::not_configured().to_string());
+            }
+            return serde_json::to_string_pretty(&WalletInfo::not_configured())
+                .map_err(|e| McpError::internal_error(e.to_string(), None));
+        };
+
+        let mut resolver = self.resolver(None);
+        let mut tokens = Vec::with_capacity(input.tokens.len());
+        for symbol in &input.tokens {
+            tokens.push(resolver.token("token", symbol).await?.address);
+        }
+
+        let base_currency = input
+            .base_currency
+            .as_deref()
+            .map(QuoteCurrency::from_str)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        let mut result = self
+            .balance_service
+            .get_wallet_info(
+                wallet_address,
+                input.include_eth_balance.unwrap_or(false),
+                &tokens,
+                input.include_zero_balances,
+            )
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        if let Some(base) = base_currency {
+            self.price_service
+                .value_wallet(&mut result, base, |done, total| {
+                    progress::report(done, total, "tokens priced")
+                })
+                .await;
+        }
+
+        if format == ResponseFormat::Text {
+            return Ok(resolver.respond_text(result));
+        }
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// Get the confirmed and pending nonces of an address.
+    ///
+    /// Defaults to the configured wallet; an address is required in read-only mode.
+    #[tool(
+        description = "Get the nonce of the configured wallet or a given address at the latest block and counting pending transactions, with the number of transactions in flight (pending minus latest). Use it to detect stuck or pending transactions before sending a new one."
+    )]
+    pub async fn get_nonce(
+        &self,
+        Parameters(input): Parameters<GetNonceInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(address = ?input.address, "get_nonce called");
+
+        let address = match &input.address {
+            Some(address) => parse_address(address)?,
+            None => self.wallet_address.ok_or_else(|| {
+                McpError::invalid_params(
+                    "No wallet is configured (read-only mode); pass an address",
+                    None,
+                )
+            })?,
+        };
+
+        let result = self
+            .balance_service
+            .get_nonce(address)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+}
@@ -0,0 +1,54 @@
+//! Chain clock tools.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+
+use super::EthereumTradingServer;
+
+/// Input parameters for the estimate_deadline_blocks tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct EstimateDeadlineBlocksInput {
+    /// Deadline as a Unix timestamp in seconds (e.g., 1729001200).
+    pub deadline: u64,
+}
+
+#[tool_router(router = chain_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Compare the RPC node's latest block timestamp with the server's clock.
+    #[tool(
+        description = "Check how stale the RPC node's view of the chain is: compares the latest block's timestamp with the server's clock and reports the lag in seconds and blocks, with a warning above 60 seconds. A lagging or archive-only endpoint explains stale prices and balances and swaps failing their deadline."
+    )]
+    pub async fn rpc_freshness(&self) -> Result<String, McpError> {
+        tracing::info!("rpc_freshness called");
+
+        let result = self
+            .chain_clock_service
+            .rpc_freshness()
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// Estimate how many blocks remain before a deadline expires.
+    #[tool(
+        description = "Estimate how many blocks remain before a swap deadline (Unix seconds) expires. Measures from the latest block's timestamp, since routers check the deadline against the including block, and divides by the chain's average block time (12s on mainnet, configurable with ETHEREUM_SECONDS_PER_BLOCK). Returns the seconds and blocks remaining, the estimated last block that can include the transaction, and whether the deadline has effectively expired."
+    )]
+    pub async fn estimate_deadline_blocks(
+        &self,
+        Parameters(input): Parameters<EstimateDeadlineBlocksInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(deadline = input.deadline, "estimate_deadline_blocks called");
+
+        let result = self
+            .chain_clock_service
+            .estimate_deadline(input.deadline)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+}
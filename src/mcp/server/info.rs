@@ -0,0 +1,166 @@
+//! Server configuration and calldata lookup tools.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+
+use super::EthereumTradingServer;
+use crate::{
+    ethereum::{
+        contracts::{chainlink::CHAINLINK_FEED_PAIRS, selectors::lookup_selector},
+        network_name, sushiswap_v2,
+    },
+    types::{ChainlinkFeedAddress, ContractAddresses, DecodedSelector, SelectorMatch, Venue},
+};
+
+/// Input parameters for the decode_selector tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct DecodeSelectorInput {
+    /// 4-byte function selector or full calldata, as hex (e.g., "0xa9059cbb").
+    pub data: String,
+}
+
+/// Split hex calldata into its 4-byte selector and the number of argument bytes.
+///
+/// The argument byte count is `None` when only a selector is given.
+fn parse_selector(s: &str) -> Result<([u8; 4], Option<usize>), McpError> {
+    let trimmed = s.trim();
+    let hex = trimmed.strip_prefix("0x").or_else(|| trimmed.strip_prefix("0X")).unwrap_or(trimmed);
+    let bytes = alloy::hex::decode(hex)
+        .map_err(|e| McpError::invalid_params(format!("Invalid hex data '{}': {}", s, e), None))?;
+    let Some((selector, arguments)) = bytes.split_first_chunk::<4>() else {
+        return Err(McpError::invalid_params(
+            format!("Data must be at least 4 bytes (a function selector), got {}", bytes.len()),
+            None,
+        ));
+    };
+    Ok((*selector, (!arguments.is_empty()).then_some(arguments.len())))
+}
+
+#[tool_router(router = info_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Get what the server supports with its current configuration.
+    ///
+    /// Makes no network calls.
+    #[tool(
+        description = "Get what the server supports with its current configuration: the quote currencies, the price sources, Uniswap versions and V2 venues available on the network, the supported networks, enabled features (wallet, swap simulation, execution, ETH pricing, trusted tokens only, stale price fallback, protocol preference, confirmations) and the enabled tools."
+    )]
+    pub async fn get_capabilities(&self) -> Result<String, McpError> {
+        tracing::info!("get_capabilities called");
+
+        let result = self.capabilities();
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// Get the contract addresses the server uses on the configured network.
+    ///
+    /// Makes no network calls.
+    #[tool(
+        description = "Get the Uniswap V2/V3 router, factory and quoter, the Sushiswap V2 router and factory (when Sushiswap is a venue), WETH, and Chainlink price feed addresses the server uses, with the configured chain ID and network name. Use it to verify which contracts a swap or price will go through."
+    )]
+    pub async fn get_contract_addresses(&self) -> Result<String, McpError> {
+        tracing::info!("get_contract_addresses called");
+
+        let sushiswap = self
+            .v2_venues
+            .contains(&Venue::Sushiswap)
+            .then(|| sushiswap_v2(self.chain_id))
+            .flatten();
+
+        let result = ContractAddresses {
+            chain_id: self.chain_id,
+            network: network_name(self.chain_id).to_string(),
+            uniswap_v2_router: format!("{:?}", self.uniswap_addresses.v2_router),
+            uniswap_v2_factory: format!("{:?}", self.uniswap_addresses.v2_factory),
+            uniswap_v3_router: format!("{:?}", self.uniswap_addresses.v3_router),
+            uniswap_v3_factory: format!("{:?}", self.uniswap_addresses.v3_factory),
+            uniswap_v3_quoter: format!("{:?}", self.uniswap_addresses.v3_quoter),
+            sushiswap_v2_router: sushiswap.map(|v2| format!("{:?}", v2.router)),
+            sushiswap_v2_factory: sushiswap.map(|v2| format!("{:?}", v2.factory)),
+            weth: self.wrapped_native_token.map(|addr| format!("{:?}", addr)),
+            chainlink_feeds: CHAINLINK_FEED_PAIRS
+                .iter()
+                .map(|(pair, address)| ChainlinkFeedAddress {
+                    pair: pair.to_string(),
+                    address: format!("{:?}", address),
+                })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// Look up a function selector among the known contract ABIs.
+    ///
+    /// Makes no network calls.
+    #[tool(
+        description = "Identify the function a 4-byte selector or full calldata calls, e.g. '0xa9059cbb' is transfer(address,uint256). Looks the selector up in the ABIs this server knows (ERC20, ERC1155, Uniswap V2/V3 routers, factories, pairs and pools, QuoterV2, Chainlink aggregators, USDC/USDT pause and blacklist functions) and returns the matching signature and interfaces, or says clearly that it is unknown. Purely local and fast; arguments are not decoded."
+    )]
+    pub async fn decode_selector(
+        &self,
+        Parameters(input): Parameters<DecodeSelectorInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(data_len = input.data.len(), "decode_selector called");
+
+        let (selector, argument_bytes) = parse_selector(&input.data)?;
+        let selector_hex = format!("0x{}", alloy::hex::encode(selector));
+
+        let functions: Vec<SelectorMatch> = lookup_selector(selector)
+            .iter()
+            .map(|function| SelectorMatch {
+                signature: function.signature.to_string(),
+                interfaces: function.interfaces.iter().map(|i| i.to_string()).collect(),
+            })
+            .collect();
+
+        let result = DecodedSelector {
+            known: !functions.is_empty(),
+            message: functions.is_empty().then(|| {
+                format!(
+                    "Selector {} does not match any function of the contracts this server knows",
+                    selector_hex
+                )
+            }),
+            selector: selector_hex,
+            functions,
+            argument_bytes,
+        };
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bare_selector() {
+        assert_eq!(parse_selector("0xa9059cbb").unwrap(), ([0xa9, 0x05, 0x9c, 0xbb], None));
+        assert_eq!(parse_selector(" 0XA9059CBB ").unwrap(), ([0xa9, 0x05, 0x9c, 0xbb], None));
+    }
+
+    #[test]
+    fn test_parse_selector_from_calldata() {
+        let calldata = format!("0xa9059cbb{}{}", "00".repeat(32), "11".repeat(32));
+        assert_eq!(parse_selector(&calldata).unwrap(), ([0xa9, 0x05, 0x9c, 0xbb], Some(64)));
+        assert_eq!(parse_selector("095ea7b3ff").unwrap(), ([0x09, 0x5e, 0xa7, 0xb3], Some(1)));
+    }
+
+    #[test]
+    fn test_parse_selector_rejects_malformed_hex() {
+        let err = parse_selector("0xa9059cbz").unwrap_err();
+        assert!(err.message.starts_with("Invalid hex data '0xa9059cbz'"), "{}", err.message);
+
+        let err = parse_selector("0xa9059cb").unwrap_err();
+        assert!(err.message.starts_with("Invalid hex data"), "{}", err.message);
+
+        let err = parse_selector("0xa905").unwrap_err();
+        assert!(err.message.contains("at least 4 bytes"), "{}", err.message);
+        assert!(err.message.ends_with("got 2"), "{}", err.message);
+    }
+}
@@ -0,0 +1,194 @@
+//! Uniswap V2 liquidity simulation tools.
+
+use alloy::primitives::U256;
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+
+use super::{
+    optional_address_schema, parse_address, parse_slippage_tolerance, EthereumTradingServer,
+    EXAMPLE_WALLET_ADDRESS,
+};
+use crate::types::{parse_units, AddLiquidityParams, RemoveLiquidityParams};
+
+/// Input parameters for the simulate_add_liquidity tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SimulateAddLiquidityInput {
+    /// First token symbol of the pair (e.g., "WETH").
+    pub token_a: String,
+    /// Second token symbol of the pair (e.g., "USDC").
+    pub token_b: String,
+    /// Most of token_a to deposit (human-readable, e.g., "1.5").
+    #[schemars(example = "1.5")]
+    pub amount_a: String,
+    /// Most of token_b to deposit (human-readable, e.g., "3000").
+    #[schemars(example = "3000")]
+    pub amount_b: String,
+    /// Slippage tolerance percentage as string (e.g., "0.5" for 0.5%), applied to
+    /// both deposited amounts. Default: "0.5".
+    #[serde(default)]
+    #[schemars(example = "0.5", example = "1")]
+    pub slippage_tolerance: Option<String>,
+    /// Receiver of the LP tokens (0x...). Defaults to the configured wallet.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub recipient: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+/// Input parameters for the simulate_remove_liquidity tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SimulateRemoveLiquidityInput {
+    /// First token symbol of the pair (e.g., "WETH").
+    pub token_a: String,
+    /// Second token symbol of the pair (e.g., "USDC").
+    pub token_b: String,
+    /// LP tokens to burn (human-readable, 18 decimals, e.g., "0.0001"). Defaults to
+    /// the owner's whole LP balance.
+    #[serde(default)]
+    #[schemars(example = "0.0001")]
+    pub liquidity: Option<String>,
+    /// Slippage tolerance percentage as string (e.g., "0.5" for 0.5%), applied to
+    /// both withdrawn amounts. Default: "0.5".
+    #[serde(default)]
+    #[schemars(example = "0.5", example = "1")]
+    pub slippage_tolerance: Option<String>,
+    /// Holder of the LP tokens, who also receives the withdrawn tokens (0x...).
+    /// Defaults to the configured wallet.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub owner: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+#[tool_router(router = liquidity_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Simulate adding liquidity to a Uniswap V2 pair.
+    ///
+    /// Computes the deposit and LP tokens from the pair's reserves and supply, and
+    /// builds the unsigned router call. Nothing is sent.
+    #[tool(
+        description = "Simulate adding liquidity to a Uniswap V2 pair. Given the most of each token to deposit, returns the amounts actually deposited at the pair's current ratio, the minimum amounts after slippage, the LP tokens expected to be minted, the resulting share of the pool, and the unsigned addLiquidity transaction for the V2 router. Both tokens must be approved to the router before sending it. Nothing is sent."
+    )]
+    pub async fn simulate_add_liquidity(
+        &self,
+        Parameters(input): Parameters<SimulateAddLiquidityInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            token_a = %input.token_a,
+            token_b = %input.token_b,
+            amount_a = %input.amount_a,
+            amount_b = %input.amount_b,
+            "simulate_add_liquidity called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (a_entry, b_entry) =
+            tokens.pair(("token_a", &input.token_a), ("token_b", &input.token_b)).await?;
+
+        let amount_a_desired = parse_units(&input.amount_a, a_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let amount_b_desired = parse_units(&input.amount_b, b_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        if amount_a_desired == U256::ZERO || amount_b_desired == U256::ZERO {
+            return Err(McpError::invalid_params(
+                "amount_a and amount_b must be greater than zero",
+                None,
+            ));
+        }
+
+        let slippage_tolerance = parse_slippage_tolerance(input.slippage_tolerance.as_deref())?;
+        let recipient = match &input.recipient {
+            Some(recipient) => parse_address(recipient)?,
+            None => self.wallet_address.ok_or_else(|| {
+                McpError::invalid_params(
+                    "No wallet is configured (read-only mode); pass a recipient",
+                    None,
+                )
+            })?,
+        };
+
+        let params = AddLiquidityParams {
+            token_a: a_entry.address,
+            token_b: b_entry.address,
+            amount_a_desired,
+            amount_b_desired,
+            slippage_tolerance,
+            recipient,
+            deadline: None,
+        };
+        let result = self
+            .liquidity_service
+            .simulate_add_liquidity(params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+
+    /// Simulate removing liquidity from a Uniswap V2 pair.
+    ///
+    /// Computes the tokens returned from the pair's reserves and supply, and
+    /// builds the unsigned router call. Nothing is sent.
+    #[tool(
+        description = "Simulate removing liquidity from a Uniswap V2 pair. Given the LP tokens to burn (default: the owner's whole LP balance), returns the tokens expected back at the pair's current reserves, the minimum amounts after slippage, the share of the pool withdrawn, and the unsigned removeLiquidity transaction for the V2 router. The LP tokens must be approved to the router before sending it. Nothing is sent."
+    )]
+    pub async fn simulate_remove_liquidity(
+        &self,
+        Parameters(input): Parameters<SimulateRemoveLiquidityInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            token_a = %input.token_a,
+            token_b = %input.token_b,
+            liquidity = ?input.liquidity,
+            "simulate_remove_liquidity called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (a_entry, b_entry) =
+            tokens.pair(("token_a", &input.token_a), ("token_b", &input.token_b)).await?;
+
+        let liquidity = input
+            .liquidity
+            .as_deref()
+            .map(|liquidity| parse_units(liquidity, 18))
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        if liquidity == Some(U256::ZERO) {
+            return Err(McpError::invalid_params("liquidity must be greater than zero", None));
+        }
+
+        let slippage_tolerance = parse_slippage_tolerance(input.slippage_tolerance.as_deref())?;
+        let owner = match &input.owner {
+            Some(owner) => parse_address(owner)?,
+            None => self.wallet_address.ok_or_else(|| {
+                McpError::invalid_params(
+                    "No wallet is configured (read-only mode); pass an owner",
+                    None,
+                )
+            })?,
+        };
+
+        let params = RemoveLiquidityParams {
+            token_a: a_entry.address,
+            token_b: b_entry.address,
+            liquidity,
+            slippage_tolerance,
+            owner,
+            deadline: None,
+        };
+        let result = self
+            .liquidity_service
+            .simulate_remove_liquidity(params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+}
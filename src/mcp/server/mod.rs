@@ -0,0 +1,979 @@
+//! MCP server implementation.
+
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use alloy::primitives::Address;
+use rmcp::{
+    handler::server::router::tool::ToolRouter,
+    handler::server::tool::ToolCallContext,
+    model::{
+        CallToolRequestParam, CallToolResult, Implementation, ListToolsResult,
+        PaginatedRequestParam, ServerCapabilities, ServerInfo,
+    },
+    schemars,
+    service::RequestContext,
+    ErrorData as McpError, RoleServer, ServerHandler,
+};
+use rust_decimal::Decimal;
+
+use crate::{
+    config::Config,
+    error::AppError,
+    ethereum::{
+        network_name, price_reference_amounts, rebasing_tokens, seconds_per_block, sushiswap_v2,
+        usd_pegged_tokens, wrapped_native_token, EthereumClient, MnemonicAccount, UniswapAddresses,
+        WalletManager, ETHEREUM_MAINNET_CHAIN_ID, SUPPORTED_CHAIN_IDS,
+    },
+    mcp::{progress::ProgressReporter, timeout::ToolTimeouts},
+    services::{
+        BalanceService, ChainClockService, LiquidityService, PoolActivityService, PriceService,
+        SwapService, TokenEntry, TokenRegistry, TokenRegistryTrait, TokenVerificationService,
+        TradeCostService,
+    },
+    types::{
+        format::Warnings, Capabilities, CapabilityFeatures, PriceSource, QuoteCurrency,
+        ResponseFormat, SupportedNetwork, SymbolProvenance, UniswapVersion, Venue, Warning,
+        WarningCode, WithProvenance,
+    },
+};
+
+mod balance;
+mod chain;
+mod info;
+mod liquidity;
+mod pool;
+mod price;
+mod quote;
+mod swap;
+mod tokens;
+mod trade_cost;
+
+pub use balance::{GetBalanceInput, GetErc1155BalanceInput, GetNonceInput, GetWalletInfoInput};
+pub use chain::EstimateDeadlineBlocksInput;
+pub use info::DecodeSelectorInput;
+pub use liquidity::{SimulateAddLiquidityInput, SimulateRemoveLiquidityInput};
+pub use pool::{GetPoolFeeInput, PoolFeeAprInput, RecentPoolSwapsInput};
+pub use price::{GetTokenPriceInput, PriceAlertCheckInput, PriceSourceSpreadInput};
+pub use quote::{GetExactOutputQuoteInput, GetUsdOutputQuoteInput};
+pub use swap::{
+    BestSwapInput, CanTradeInput, ForceRouteInput, SimulateSwapPathInput, SwapPreviewInput,
+    SwapTokensInput,
+};
+pub use tokens::VerifyTokenInput;
+pub use trade_cost::{EffectiveRateInput, SimulateRoundTripPnlInput, TradeCostBreakdownInput};
+
+/// Ethereum Trading MCP Server.
+///
+/// Provides tools for querying balances, prices, and simulating token swaps.
+#[derive(Clone)]
+pub struct EthereumTradingServer {
+    balance_service: BalanceService,
+    chain_clock_service: ChainClockService,
+    price_service: PriceService,
+    swap_service: SwapService,
+    token_verification_service: TokenVerificationService,
+    trade_cost_service: TradeCostService,
+    pool_activity_service: PoolActivityService,
+    liquidity_service: LiquidityService,
+    token_registry: Arc<dyn TokenRegistryTrait>,
+    /// Address of the configured wallet (`None` in read-only mode).
+    wallet_address: Option<Address>,
+    /// Timeouts bounding each tool call.
+    tool_timeouts: ToolTimeouts,
+    /// Whether trading tools refuse tokens not on a curated token list.
+    trusted_tokens_only: bool,
+    /// Slippage tolerance percentage swap_tokens raises lower requests to.
+    min_slippage_tolerance: Decimal,
+    /// Whether swap_tokens cross-checks quotes when a request does not say.
+    quote_cross_check: bool,
+    /// Price impact percentage above which swap_tokens refuses a swap.
+    max_price_impact: Decimal,
+    /// Configured chain ID.
+    chain_id: u64,
+    /// Wrapped native token used for ETH pricing, if known.
+    wrapped_native_token: Option<Address>,
+    /// Uniswap-compatible contracts used for pricing, quotes and swaps.
+    uniswap_addresses: UniswapAddresses,
+    /// Venues V2 routes and prices are quoted on.
+    v2_venues: Vec<Venue>,
+    /// Tools left out of `tool_router` by `MCP_ENABLED_TOOLS`.
+    disabled_tools: Vec<String>,
+    /// Price sources available on the configured network.
+    price_sources: Vec<PriceSource>,
+    /// Configuration-dependent features reported by get_capabilities.
+    features: CapabilityFeatures,
+    tool_router: ToolRouter<Self>,
+}
+
+impl EthereumTradingServer {
+    /// Combine the per-group tool routers into the full tool set.
+    fn tool_router() -> ToolRouter<Self> {
+        Self::balance_router()
+            + Self::chain_router()
+            + Self::info_router()
+            + Self::liquidity_router()
+            + Self::pool_router()
+            + Self::price_router()
+            + Self::quote_router()
+            + Self::swap_router()
+            + Self::tokens_router()
+            + Self::trade_cost_router()
+    }
+
+    /// Create a new Ethereum Trading MCP Server.
+    ///
+    /// Note: This uses lazy initialization - no network calls are made during
+    /// server startup. The Ethereum connection is established when the first
+    /// tool is invoked.
+    pub fn new(config: Config) -> Result<Self, AppError> {
+        tracing::info!("Initializing Ethereum Trading MCP Server");
+
+        // Initialize Ethereum client (lazy - no network call yet)
+        let client = Arc::new(
+            EthereumClient::with_headers(&config.rpc_url, &config.rpc_headers)?
+                .with_confirmations(config.confirmations),
+        );
+
+        // Optionally check that the RPC endpoint keeps serving the same chain
+        if let Some(secs) = config.chain_id_revalidate_secs {
+            if tokio::runtime::Handle::try_current().is_ok() {
+                client.spawn_chain_id_revalidation(Duration::from_secs(secs));
+            } else {
+                tracing::warn!("No async runtime available, chain ID revalidation disabled");
+            }
+        }
+
+        // Initialize wallet (none in read-only mode)
+        let wallet = if config.is_read_only() {
+            tracing::info!("No private key or mnemonic configured, running in read-only mode");
+            None
+        } else if !config.mnemonic.trim().is_empty() {
+            let account = match config.mnemonic_address {
+                Some(address) => MnemonicAccount::Address(address),
+                None => MnemonicAccount::Index(config.mnemonic_index),
+            };
+            Some(WalletManager::from_mnemonic(
+                &config.mnemonic,
+                account,
+                config.mnemonic_preview_count,
+            )?)
+        } else {
+            Some(WalletManager::from_private_key(&config.private_key)?)
+        };
+
+        // Initialize token registry with configured chain ID
+        let token_registry = Arc::new(
+            TokenRegistry::new(config.chain_id)?
+                .with_max_tokens(config.token_list_max_tokens)
+                .with_http_proxy(&config.http_proxy)?,
+        );
+
+        // Optionally load the token list before the first lookup needs it
+        if config.prewarm_tokens {
+            if tokio::runtime::Handle::try_current().is_ok() {
+                token_registry.spawn_prewarm();
+            } else {
+                tracing::warn!("No async runtime available, token list prewarm disabled");
+            }
+        }
+
+        // Optionally keep the token list warm in the background
+        if let Some(secs) = config.token_auto_refresh_secs {
+            if tokio::runtime::Handle::try_current().is_ok() {
+                token_registry.spawn_auto_refresh(Duration::from_secs(secs));
+            } else {
+                tracing::warn!("No async runtime available, token list auto-refresh disabled");
+            }
+        }
+
+        let uniswap = config.uniswap_addresses;
+        if uniswap.is_overridden() {
+            tracing::info!(
+                v2_router = %uniswap.v2_router,
+                v2_factory = %uniswap.v2_factory,
+                v3_router = %uniswap.v3_router,
+                v3_factory = %uniswap.v3_factory,
+                v3_quoter = %uniswap.v3_quoter,
+                "Using overridden Uniswap contract addresses"
+            );
+        }
+
+        // Sushiswap's addresses are only known on mainnet
+        let sushiswap_known = sushiswap_v2(config.chain_id).is_some();
+        let v2_venues = match config.v2_venues {
+            Some(venues) if venues.contains(&Venue::Sushiswap) && !sushiswap_known => {
+                return Err(AppError::Config(format!(
+                    "ETHEREUM_V2_VENUES includes sushiswap, but Sushiswap's addresses are not \
+                     known on chain {}",
+                    config.chain_id
+                )));
+            }
+            Some(venues) => venues,
+            None if sushiswap_known => vec![Venue::Uniswap, Venue::Sushiswap],
+            None => vec![Venue::Uniswap],
+        };
+
+        // Initialize services
+        let balance_service = BalanceService::new(client.clone())
+            .with_fan_out_concurrency(config.fan_out_concurrency)
+            .with_include_zero_balances(config.include_zero_balances)
+            .with_rebasing_tokens(
+                config.rebasing_tokens.unwrap_or_else(|| rebasing_tokens(config.chain_id)),
+            );
+        let chain_clock_service = ChainClockService::new(client.clone()).with_seconds_per_block(
+            config.seconds_per_block.unwrap_or_else(|| seconds_per_block(config.chain_id)),
+        );
+        let native_token =
+            config.wrapped_native_token.or_else(|| wrapped_native_token(config.chain_id));
+        let pegged_tokens =
+            config.usd_pegged_tokens.unwrap_or_else(|| usd_pegged_tokens(config.chain_id));
+
+        // Chainlink feeds are only known on mainnet
+        let mut price_sources = Vec::new();
+        if config.chain_id == ETHEREUM_MAINNET_CHAIN_ID {
+            price_sources.push(PriceSource::Chainlink);
+        }
+        // The quote token itself is always priced at exactly 1
+        price_sources.extend([
+            PriceSource::UniswapV3,
+            PriceSource::UniswapV2,
+            PriceSource::UniswapAggregate,
+            PriceSource::Identity,
+        ]);
+        if !pegged_tokens.is_empty() || native_token.is_some() {
+            price_sources.push(PriceSource::Peg);
+        }
+
+        let price_service = PriceService::new(client.clone(), balance_service.clone())
+            .with_usd_pegged_tokens(pegged_tokens)
+            .with_wrapped_native_token(native_token)
+            .with_stale_price_fallback(config.stale_price_fallback)
+            .with_signed_feeds(config.signed_chainlink_feeds.iter().copied())
+            .with_uniswap_addresses(uniswap)
+            .with_v2_venues(v2_venues.clone())
+            .with_fan_out_concurrency(config.fan_out_concurrency)
+            .with_reference_amounts(
+                price_reference_amounts(config.chain_id)
+                    .into_iter()
+                    .chain(config.price_reference_amounts.iter().copied()),
+            );
+        let wallet_address = wallet.as_ref().map(WalletManager::address);
+        let swap_service = SwapService::new(client.clone(), wallet, balance_service.clone())
+            .with_min_pool_liquidity(config.min_pool_liquidity)
+            .with_protocol_preference(config.protocol_preference)
+            .with_wrapped_native_token(native_token)
+            .with_uniswap_addresses(uniswap)
+            .with_v2_venues(v2_venues.clone())
+            .with_quote_cross_check_tolerance(config.quote_cross_check_tolerance);
+        let trade_cost_service = TradeCostService::new(
+            swap_service.clone(),
+            price_service.clone(),
+            balance_service.clone(),
+        )
+        .with_fan_out_concurrency(config.fan_out_concurrency);
+        let pool_activity_service =
+            PoolActivityService::new(client.clone(), balance_service.clone())
+                .with_uniswap_addresses(uniswap)
+                .with_v2_venues(v2_venues.clone());
+        let liquidity_service = LiquidityService::new(client.clone(), balance_service.clone())
+            .with_uniswap_addresses(uniswap);
+        let token_verification_service = TokenVerificationService::new(client);
+
+        Self::check_tool_names("ETHEREUM_TOOL_TIMEOUTS", config.tool_timeout_overrides.keys())?;
+        let tool_timeouts = config.tool_timeout_overrides.iter().fold(
+            ToolTimeouts::new(Duration::from_secs(config.tool_timeout_secs)),
+            |timeouts, (tool, secs)| timeouts.with_override(tool, Duration::from_secs(*secs)),
+        );
+
+        // Register only the allowed tools when an allowlist is configured
+        let mut tool_router = Self::tool_router();
+        let mut disabled_tools = Vec::new();
+        if let Some(enabled) = &config.enabled_tools {
+            Self::check_tool_names("MCP_ENABLED_TOOLS", enabled)?;
+            for tool in Self::tool_names() {
+                if !enabled.contains(&tool) {
+                    tool_router.remove_route(&tool);
+                    disabled_tools.push(tool);
+                }
+            }
+            tracing::info!(enabled = ?enabled, "Tool allowlist configured");
+        }
+
+        let features = CapabilityFeatures {
+            wallet_configured: wallet_address.is_some(),
+            swap_simulation: wallet_address.is_some(),
+            execution: false,
+            eth_pricing: native_token.is_some(),
+            trusted_tokens_only: config.trusted_tokens_only,
+            stale_price_fallback: config.stale_price_fallback,
+            protocol_preference: config.protocol_preference,
+            confirmations: config.confirmations,
+        };
+
+        tracing::info!("Ethereum Trading MCP Server initialized successfully");
+
+        Ok(Self {
+            balance_service,
+            chain_clock_service,
+            price_service,
+            swap_service,
+            token_verification_service,
+            trade_cost_service,
+            pool_activity_service,
+            liquidity_service,
+            token_registry,
+            wallet_address,
+            tool_timeouts,
+            trusted_tokens_only: config.trusted_tokens_only,
+            min_slippage_tolerance: config.min_slippage_tolerance,
+            quote_cross_check: config.quote_cross_check,
+            max_price_impact: config.max_price_impact,
+            chain_id: config.chain_id,
+            wrapped_native_token: native_token,
+            uniswap_addresses: uniswap,
+            v2_venues,
+            disabled_tools,
+            price_sources,
+            features,
+            tool_router,
+        })
+    }
+
+    /// Names of all tools the server provides, sorted.
+    pub fn tool_names() -> Vec<String> {
+        let mut names: Vec<String> =
+            Self::tool_router().list_all().into_iter().map(|tool| tool.name.into_owned()).collect();
+        names.sort();
+        names
+    }
+
+    /// Reject any name in `tools` that is not a tool the server provides.
+    ///
+    /// `var` is the environment variable the names came from.
+    fn check_tool_names<'a>(
+        var: &str,
+        tools: impl IntoIterator<Item = &'a String>,
+    ) -> Result<(), AppError> {
+        let known = Self::tool_names();
+        match tools.into_iter().find(|tool| !known.contains(tool)) {
+            Some(unknown) => Err(AppError::Config(format!(
+                "Unknown tool in {}: {}. Available tools: {}",
+                var,
+                unknown,
+                known.join(", ")
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Names of the tools this server exposes, sorted.
+    pub fn enabled_tool_names(&self) -> Vec<String> {
+        let mut names: Vec<String> =
+            self.tool_router.list_all().into_iter().map(|tool| tool.name.into_owned()).collect();
+        names.sort();
+        names
+    }
+
+    /// What the server can do with its current configuration.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            chain_id: self.chain_id,
+            network: network_name(self.chain_id).to_string(),
+            supported_networks: SUPPORTED_CHAIN_IDS
+                .iter()
+                .map(|&chain_id| SupportedNetwork {
+                    chain_id,
+                    network: network_name(chain_id).to_string(),
+                })
+                .collect(),
+            quote_currencies: QuoteCurrency::ALL.to_vec(),
+            price_sources: self.price_sources.clone(),
+            swap_protocols: vec![UniswapVersion::V3, UniswapVersion::V2],
+            v2_venues: self.v2_venues.clone(),
+            features: self.features.clone(),
+            enabled_tools: self.enabled_tool_names(),
+        }
+    }
+
+    /// Server instructions, describing the network, pricing and swap setup of
+    /// this instance and naming its enabled tools.
+    fn instructions(&self) -> String {
+        let capabilities = self.capabilities();
+        let features = &capabilities.features;
+        let mut instructions = format!(
+            "Ethereum Trading MCP Server on {} (chain ID {}).",
+            capabilities.network, capabilities.chain_id
+        );
+
+        if !SUPPORTED_CHAIN_IDS.contains(&capabilities.chain_id) {
+            let supported: Vec<String> = capabilities
+                .supported_networks
+                .iter()
+                .map(|n| format!("{} ({})", n.network, n.chain_id))
+                .collect();
+            instructions.push_str(&format!(
+                " This chain has no built-in contract addresses or tokens; only {} do.",
+                supported.join(" and ")
+            ));
+        }
+
+        let quote_currencies: Vec<String> = capabilities
+            .quote_currencies
+            .iter()
+            .filter(|&&currency| currency != QuoteCurrency::ETH || features.eth_pricing)
+            .map(|currency| format!("{:?}", currency))
+            .collect();
+        let price_sources = if capabilities.price_sources.contains(&PriceSource::Chainlink) {
+            "Chainlink feeds and Uniswap pools"
+        } else {
+            "Uniswap pools"
+        };
+        instructions.push_str(&format!(
+            " Prices are quoted in {} from {}.",
+            quote_currencies.join(" or "),
+            price_sources
+        ));
+        if features.stale_price_fallback {
+            instructions
+                .push_str(" A failed price falls back to the last known price, marked stale.");
+        }
+
+        let venues: Vec<String> = self.v2_venues.iter().map(Venue::to_string).collect();
+        instructions.push_str(&format!(
+            " Swaps are routed through Uniswap V3 and V2 pools on {}.",
+            venues.join(" and ")
+        ));
+        if features.swap_simulation {
+            instructions.push_str(
+                " The configured wallet is the default sender and the recipient of simulated \
+                 swaps; execution is off, so nothing is signed or broadcast.",
+            );
+        } else {
+            instructions.push_str(
+                " No wallet is configured, so the server is read-only: quotes and previews work, \
+                 but swap simulations do not, and nothing is ever signed or broadcast.",
+            );
+        }
+        if features.trusted_tokens_only {
+            instructions.push_str(
+                " Swap, quote, trade cost and liquidity tools only accept tokens from a curated \
+                 token list.",
+            );
+        }
+
+        instructions
+            .push_str(" Call get_capabilities to see what the current configuration supports.");
+        let tools = capabilities.enabled_tools.join(", ");
+        if self.disabled_tools.is_empty() {
+            instructions.push_str(&format!(" Tools: {}.", tools));
+        } else {
+            instructions.push_str(&format!(" This deployment only enables: {}.", tools));
+        }
+        instructions
+    }
+
+    /// Refuse a token not on a curated token list when trusted-tokens-only mode is on.
+    fn ensure_trusted(&self, entry: &TokenEntry, requested: &str) -> Result<(), McpError> {
+        if !self.trusted_tokens_only || entry.source.is_curated() {
+            return Ok(());
+        }
+        Err(McpError::invalid_params(
+            format!(
+                "Refusing to trade '{}' ({:?}): it was resolved from {}, which is not a curated \
+                 token list, and ETHEREUM_TRUSTED_TOKENS_ONLY is enabled.",
+                requested, entry.address, entry.source.origin
+            ),
+            None,
+        ))
+    }
+
+    /// Warn when `requested` resolved to a core token symbol away from its canonical address.
+    fn non_canonical_warning(entry: &TokenEntry, requested: &str) -> Option<Warning> {
+        entry.non_canonical().map(|canonical| {
+            Warning::new(
+                WarningCode::NonCanonicalToken,
+                format!(
+                    "'{}' resolved to {:?} ({}), not the canonical {} at {:?}. The token list \
+                     may contain an impersonating token; check the address before trading.",
+                    requested, entry.address, entry.source.origin, entry.symbol, canonical
+                ),
+            )
+        })
+    }
+}
+
+/// Resolves the token symbols named by one tool call.
+///
+/// Every symbol goes through the same lookup, so the unknown-symbol error and the
+/// trusted-tokens check read the same in every tool. The provenance of each symbol and
+/// any non-canonical token warning are collected along the way and attached by
+/// [`TokenResolver::respond`] or [`TokenResolver::respond_text`].
+struct TokenResolver<'a> {
+    server: &'a EthereumTradingServer,
+    /// Whether the resolved tokens are about to be traded.
+    trade: bool,
+    /// Provenance of each resolved symbol, or `None` when it was not requested.
+    provenance: Option<Vec<SymbolProvenance>>,
+    /// Warnings about the resolved symbols.
+    warnings: Vec<Warning>,
+}
+
+impl EthereumTradingServer {
+    /// Start resolving the symbols of a read-only tool call.
+    fn resolver(&self, include_provenance: Option<bool>) -> TokenResolver<'_> {
+        TokenResolver {
+            server: self,
+            trade: false,
+            provenance: include_provenance.unwrap_or(false).then(Vec::new),
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl TokenResolver<'_> {
+    /// Mark the resolved tokens as traded, applying the trusted-tokens check.
+    fn for_trade(mut self) -> Self {
+        self.trade = true;
+        self
+    }
+
+    /// Resolve `symbol`, naming the input field as `label` in the error.
+    async fn token(&mut self, label: &str, symbol: &str) -> Result<TokenEntry, McpError> {
+        let entry = self.server.token_registry.resolve_symbol(symbol).await.ok_or_else(|| {
+            McpError::invalid_params(
+                format!(
+                    "Unknown {} symbol: '{}'. Token not found in Uniswap Token List.",
+                    label, symbol
+                ),
+                None,
+            )
+        })?;
+        if self.trade {
+            self.server.ensure_trusted(&entry, symbol)?;
+        }
+        if let Some(provenance) = &mut self.provenance {
+            provenance.push(entry.provenance(symbol));
+        }
+        self.warnings.extend(EthereumTradingServer::non_canonical_warning(&entry, symbol));
+        Ok(entry)
+    }
+
+    /// Resolve a pair of distinct tokens named by the `a` and `b` input fields.
+    async fn pair(
+        &mut self,
+        (label_a, a): (&str, &str),
+        (label_b, b): (&str, &str),
+    ) -> Result<(TokenEntry, TokenEntry), McpError> {
+        let a_entry = self.token(label_a, a).await?;
+        let b_entry = self.token(label_b, b).await?;
+        if a_entry.address == b_entry.address {
+            return Err(McpError::invalid_params(
+                format!("{} and {} cannot be the same", label_a, label_b),
+                None,
+            ));
+        }
+        Ok((a_entry, b_entry))
+    }
+
+    /// Serialize a tool result with the collected provenance and warnings.
+    ///
+    /// Symbol warnings are appended to the result's `warnings` array, which is added
+    /// if the result has none.
+    fn respond<T: serde::Serialize>(self, result: T) -> Result<String, McpError> {
+        if self.warnings.is_empty() {
+            return to_json_response(result, self.provenance);
+        }
+        let mut value = serde_json::to_value(result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        if let Some(object) = value.as_object_mut() {
+            let warnings =
+                object.entry("warnings").or_insert_with(|| serde_json::Value::Array(Vec::new()));
+            if let Some(warnings) = warnings.as_array_mut() {
+                for warning in self.warnings {
+                    warnings.push(
+                        serde_json::to_value(warning)
+                            .map_err(|e| McpError::internal_error(e.to_string(), None))?,
+                    );
+                }
+            }
+        }
+        to_json_response(value, self.provenance)
+    }
+
+    /// Append the collected warnings to a text summary.
+    fn respond_text(self, summary: impl std::fmt::Display) -> String {
+        format!("{}{}", summary, Warnings(&self.warnings))
+    }
+}
+
+/// Serialize a tool result, attaching symbol provenance when it was requested.
+fn to_json_response<T: serde::Serialize>(
+    result: T,
+    provenance: Option<Vec<SymbolProvenance>>,
+) -> Result<String, McpError> {
+    match provenance {
+        Some(provenance) => serde_json::to_string_pretty(&WithProvenance { result, provenance }),
+        None => serde_json::to_string_pretty(&result),
+    }
+    .map_err(|e| McpError::internal_error(e.to_string(), None))
+}
+
+/// Example wallet address used in tool input schemas.
+const EXAMPLE_WALLET_ADDRESS: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+/// Example token address (USDC) used in tool input schemas.
+const EXAMPLE_TOKEN_ADDRESS: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+/// Pattern of a 0x-prefixed, 20-byte hex address.
+const ADDRESS_PATTERN: &str = "^0x[0-9a-fA-F]{40}$";
+
+/// Schema for a required address field.
+fn address_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": "string",
+        "pattern": ADDRESS_PATTERN,
+    })
+}
+
+/// Schema for an optional address field.
+fn optional_address_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    schemars::json_schema!({
+        "type": ["string", "null"],
+        "pattern": ADDRESS_PATTERN,
+    })
+}
+
+/// Parse and validate an Ethereum address from a string.
+///
+/// Validates:
+/// - Address format (0x + 40 hex characters)
+/// - Basic format checks
+///
+/// # Arguments
+/// * `s` - The address string to parse (with or without 0x prefix)
+///
+/// # Returns
+/// * `Ok(Address)` - If the address is valid
+/// * `Err(McpError)` - If the address is invalid with a descriptive error
+fn parse_address(s: &str) -> Result<Address, McpError> {
+    let trimmed = s.trim();
+
+    // Check for empty input
+    if trimmed.is_empty() {
+        return Err(McpError::invalid_params("Address cannot be empty", None));
+    }
+
+    // Check for correct prefix
+    if !trimmed.starts_with("0x") && !trimmed.starts_with("0X") {
+        return Err(McpError::invalid_params(format!("Address must start with '0x': {}", s), None));
+    }
+
+    // Check length (0x + 40 hex chars = 42 total)
+    if trimmed.len() != 42 {
+        return Err(McpError::invalid_params(
+            format!(
+                "Address must be 42 characters (0x + 40 hex chars), got {}: {}",
+                trimmed.len(),
+                s
+            ),
+            None,
+        ));
+    }
+
+    // Parse the address
+    trimmed.parse::<Address>().map_err(|e| {
+        McpError::invalid_params(format!("Invalid address format '{}': {}", s, e), None)
+    })
+}
+
+/// Parse a slippage tolerance percentage, defaulting to 0.5% and allowing 0-50%.
+fn parse_slippage_tolerance(s: Option<&str>) -> Result<Decimal, McpError> {
+    let slippage_tolerance = s
+        .map(|s| {
+            Decimal::from_str(s).map_err(|e| {
+                McpError::invalid_params(format!("Invalid slippage_tolerance: {}", e), None)
+            })
+        })
+        .transpose()?
+        .unwrap_or(Decimal::new(5, 1)); // Default 0.5%
+
+    // Validate slippage tolerance range (0-50%)
+    if slippage_tolerance < Decimal::ZERO || slippage_tolerance > Decimal::from(50) {
+        return Err(McpError::invalid_params(
+            "slippage_tolerance must be between 0 and 50 (percentage)",
+            None,
+        ));
+    }
+
+    Ok(slippage_tolerance)
+}
+
+/// Parse an optional response format, defaulting to JSON.
+fn parse_response_format(format: Option<&str>) -> Result<ResponseFormat, McpError> {
+    format
+        .map(ResponseFormat::from_str)
+        .transpose()
+        .map_err(|e| McpError::invalid_params(e, None))
+        .map(Option::unwrap_or_default)
+}
+
+impl ServerHandler for EthereumTradingServer {
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let tool = request.name.clone();
+        if self.disabled_tools.iter().any(|disabled| *disabled == tool) {
+            return Err(McpError::invalid_params(
+                format!("tool disabled: '{}' is not in MCP_ENABLED_TOOLS", tool),
+                None,
+            ));
+        }
+        let progress = context
+            .meta
+            .get_progress_token()
+            .map(|token| ProgressReporter::new(context.peer.clone(), token));
+        let call = ToolCallContext::new(self, request, context);
+        let call = self.tool_router.call(call);
+        match progress {
+            Some(progress) => self.tool_timeouts.run(&tool, progress.scope(call)).await,
+            None => self.tool_timeouts.run(&tool, call).await,
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: Default::default(),
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation {
+                name: "ethereum-trading-mcp".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                title: None,
+                icons: None,
+                website_url: None,
+            },
+            instructions: Some(self.instructions()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use rmcp::handler::server::wrapper::Parameters;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{ethereum::contracts::WETH_ADDRESS, services::TokenSource, types::TokenListDiff};
+
+    /// Resolves WETH from the built-in tokens and every other symbol from an uncurated list.
+    struct UncuratedRegistry;
+
+    #[async_trait]
+    impl TokenRegistryTrait for UncuratedRegistry {
+        async fn resolve_symbol(&self, symbol: &str) -> Option<TokenEntry> {
+            let (address, source) = if symbol == "WETH" {
+                (WETH_ADDRESS, TokenSource::builtin())
+            } else {
+                let source = TokenSource {
+                    origin: "https://example.com/tokens.json".to_string(),
+                    list_name: Some("Example".to_string()),
+                    list_version: None,
+                };
+                (Address::repeat_byte(0x11), source)
+            };
+            Some(TokenEntry {
+                address,
+                symbol: symbol.to_string(),
+                name: symbol.to_string(),
+                decimals: 18,
+                chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+                source,
+            })
+        }
+
+        async fn lookup_address(&self, _address: Address) -> Option<TokenEntry> {
+            None
+        }
+
+        async fn token_list_diff(&self) -> crate::error::Result<TokenListDiff> {
+            Err(AppError::Config("no token list".to_string()))
+        }
+    }
+
+    fn server(trusted_tokens_only: bool) -> EthereumTradingServer {
+        let config = Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            trusted_tokens_only,
+            ..Default::default()
+        };
+        let mut server = EthereumTradingServer::new(config).unwrap();
+        server.token_registry = Arc::new(UncuratedRegistry);
+        server
+    }
+
+    fn input<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> Parameters<T> {
+        Parameters(serde_json::from_value(value).unwrap())
+    }
+
+    fn assert_refused(result: Result<String, McpError>) {
+        let err = result.unwrap_err();
+        assert!(err.message.starts_with("Refusing to trade 'SCAM'"), "{}", err.message);
+        assert!(err.message.contains("https://example.com/tokens.json"));
+    }
+
+    #[tokio::test]
+    async fn test_trading_tools_refuse_uncurated_tokens() {
+        let server = server(true);
+
+        assert_refused(
+            server
+                .swap_tokens(input(json!({
+                    "from_token": "WETH",
+                    "to_token": "SCAM",
+                    "amount": "1",
+                })))
+                .await,
+        );
+        assert_refused(
+            server
+                .simulate_swap_path(input(json!({ "tokens": ["WETH", "SCAM"], "amount": "1" })))
+                .await,
+        );
+        assert_refused(
+            server
+                .best_swap(input(json!({
+                    "from_token": "WETH",
+                    "amount": "1",
+                    "to_tokens": ["SCAM"],
+                })))
+                .await,
+        );
+        assert_refused(
+            server
+                .get_usd_output_quote(input(json!({
+                    "from_token": "WETH",
+                    "to_token": "SCAM",
+                    "usd_amount": "100",
+                })))
+                .await,
+        );
+        assert_refused(
+            server
+                .simulate_round_trip_pnl(input(json!({
+                    "from_token": "WETH",
+                    "to_token": "SCAM",
+                    "amount": "1",
+                })))
+                .await,
+        );
+        assert_refused(
+            server
+                .simulate_add_liquidity(input(json!({
+                    "token_a": "SCAM",
+                    "token_b": "WETH",
+                    "amount_a": "1",
+                    "amount_b": "1",
+                })))
+                .await,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolver_attaches_non_canonical_warnings() {
+        let server = server(false);
+
+        let mut tokens = server.resolver(Some(true));
+        tokens.token("token", "WETH").await.unwrap();
+        let response: serde_json::Value =
+            serde_json::from_str(&tokens.respond(json!({ "price": "1" })).unwrap()).unwrap();
+        assert!(response.get("warnings").is_none());
+        assert_eq!(response["provenance"][0]["symbol"], "WETH");
+
+        // The stub resolves USDC away from its canonical address
+        let mut tokens = server.resolver(None);
+        tokens.token("token", "USDC").await.unwrap();
+        let existing = json!({ "code": "pegged_price", "message": "pegged" });
+        let response: serde_json::Value = serde_json::from_str(
+            &tokens.respond(json!({ "price": "1", "warnings": [existing] })).unwrap(),
+        )
+        .unwrap();
+        let warnings = response["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0]["code"], "pegged_price");
+        assert_eq!(warnings[1]["code"], "non_canonical_token");
+
+        let mut tokens = server.resolver(None);
+        tokens.token("token", "USDC").await.unwrap();
+        let text = tokens.respond_text("USDC: $1");
+        assert!(text.starts_with("USDC: $1\nWarning: 'USDC' resolved to"), "{}", text);
+    }
+
+    #[test]
+    fn test_v2_venues_default_by_chain() {
+        use crate::ethereum::SEPOLIA_CHAIN_ID;
+
+        let config = |chain_id, v2_venues| Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            chain_id,
+            v2_venues,
+            ..Default::default()
+        };
+
+        let mainnet = EthereumTradingServer::new(config(1, None)).unwrap();
+        assert_eq!(mainnet.capabilities().v2_venues, vec![Venue::Uniswap, Venue::Sushiswap]);
+
+        let sepolia = EthereumTradingServer::new(config(SEPOLIA_CHAIN_ID, None)).unwrap();
+        assert_eq!(sepolia.capabilities().v2_venues, vec![Venue::Uniswap]);
+
+        // Sushiswap's mainnet addresses are never used on another chain
+        let sushiswap = Some(vec![Venue::Sushiswap]);
+        assert!(matches!(
+            EthereumTradingServer::new(config(SEPOLIA_CHAIN_ID, sushiswap)),
+            Err(AppError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_unknown_enabled_tool_is_rejected() {
+        let config = Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            enabled_tools: Some(vec!["get_balance".to_string(), "send_eth".to_string()]),
+            ..Default::default()
+        };
+
+        let err = EthereumTradingServer::new(config).err().unwrap();
+        assert!(matches!(err, AppError::Config(_)));
+        assert!(err.to_string().contains("send_eth"));
+    }
+
+    #[test]
+    fn test_unknown_timeout_tool_is_rejected() {
+        let config = Config {
+            rpc_url: "http://localhost:8545".to_string(),
+            tool_timeout_overrides: [("swap_token".to_string(), 60)].into(),
+            ..Default::default()
+        };
+
+        let err = EthereumTradingServer::new(config).err().unwrap();
+        assert!(err.to_string().contains("ETHEREUM_TOOL_TIMEOUTS"));
+        assert!(err.to_string().contains("swap_token"));
+    }
+
+    #[test]
+    fn test_price_sources_include_identity() {
+        let config = Config { rpc_url: "http://localhost:8545".to_string(), ..Default::default() };
+        let server = EthereumTradingServer::new(config).unwrap();
+
+        assert!(server.capabilities().price_sources.contains(&PriceSource::Identity));
+    }
+}
@@ -0,0 +1,163 @@
+//! Pool activity and pool fee tools.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+
+use super::{parse_address, EthereumTradingServer};
+use crate::{
+    error::AppError,
+    services::pool_activity::{
+        DEFAULT_FEE_APR_LOOKBACK_BLOCKS, DEFAULT_SWAP_LOOKBACK_BLOCKS, MAX_FEE_APR_LOOKBACK_BLOCKS,
+        MAX_SWAP_LOOKBACK_BLOCKS,
+    },
+};
+
+/// Default number of swaps returned by the recent_pool_swaps tool.
+const DEFAULT_POOL_SWAPS: usize = 20;
+
+/// Maximum number of swaps returned by the recent_pool_swaps tool.
+const MAX_POOL_SWAPS: usize = 100;
+
+/// Input parameters for the recent_pool_swaps tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct RecentPoolSwapsInput {
+    /// Uniswap V2 pair or V3 pool address (0x...).
+    pub pool_address: String,
+    /// Number of recent blocks to search (default: 1000, max: 10000).
+    #[serde(default)]
+    pub blocks: Option<u64>,
+    /// Maximum number of swaps to return, newest first (default: 20, max: 100).
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Input parameters for the pool_fee_apr tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct PoolFeeAprInput {
+    /// Uniswap V2 pair or V3 pool address (0x...).
+    pub pool_address: String,
+    /// Number of recent blocks whose swaps are extrapolated (default: 7200, about a
+    /// day; max: 50400, about a week).
+    #[serde(default)]
+    pub blocks: Option<u64>,
+}
+
+/// Input parameters for the get_pool_fee tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct GetPoolFeeInput {
+    /// First token symbol of the pair (e.g., "WETH").
+    pub token_a: String,
+    /// Second token symbol of the pair (e.g., "USDC").
+    pub token_b: String,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+#[tool_router(router = pool_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Get the most recent swaps of a Uniswap pool.
+    ///
+    /// Reads `Swap` events over a bounded block range, newest first.
+    #[tool(
+        description = "Get recent swaps of a Uniswap V2 pair or V3 pool from its Swap events: direction, amounts in and out, block and transaction. Searches the last 1000 blocks by default (max 10000) and returns up to 20 swaps by default (max 100), newest first."
+    )]
+    pub async fn recent_pool_swaps(
+        &self,
+        Parameters(input): Parameters<RecentPoolSwapsInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            pool = %input.pool_address,
+            blocks = ?input.blocks,
+            limit = ?input.limit,
+            "recent_pool_swaps called"
+        );
+
+        let pool = parse_address(&input.pool_address)?;
+
+        let blocks = input.blocks.unwrap_or(DEFAULT_SWAP_LOOKBACK_BLOCKS);
+        if blocks == 0 || blocks > MAX_SWAP_LOOKBACK_BLOCKS {
+            return Err(McpError::invalid_params(
+                format!("blocks must be between 1 and {}", MAX_SWAP_LOOKBACK_BLOCKS),
+                None,
+            ));
+        }
+
+        let limit = input.limit.unwrap_or(DEFAULT_POOL_SWAPS);
+        if limit == 0 || limit > MAX_POOL_SWAPS {
+            return Err(McpError::invalid_params(
+                format!("limit must be between 1 and {}", MAX_POOL_SWAPS),
+                None,
+            ));
+        }
+
+        let result = self.pool_activity_service.recent_swaps(pool, blocks, limit).await.map_err(
+            |e| match e {
+                AppError::InvalidAddress(_) => McpError::from(e),
+                _ => McpError::internal_error(e.to_string(), None),
+            },
+        )?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// Estimate a pool's annualized fee yield from its recent swaps.
+    ///
+    /// Reads every `Swap` event in the window, so larger windows are slower.
+    #[tool(
+        description = "Estimate the annualized LP fee yield (APR) of a Uniswap V2 pair or V3 pool: the fees paid by its swaps over the last 7200 blocks by default (about a day, max 50400), extrapolated to a year and divided by the pool's current token balances. An estimate from a recent window, not a forecast: the response lists its volume, fees, TVL and assumptions."
+    )]
+    pub async fn pool_fee_apr(
+        &self,
+        Parameters(input): Parameters<PoolFeeAprInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(pool = %input.pool_address, blocks = ?input.blocks, "pool_fee_apr called");
+
+        let pool = parse_address(&input.pool_address)?;
+
+        let blocks = input.blocks.unwrap_or(DEFAULT_FEE_APR_LOOKBACK_BLOCKS);
+        if blocks == 0 || blocks > MAX_FEE_APR_LOOKBACK_BLOCKS {
+            return Err(McpError::invalid_params(
+                format!("blocks must be between 1 and {}", MAX_FEE_APR_LOOKBACK_BLOCKS),
+                None,
+            ));
+        }
+
+        let result =
+            self.pool_activity_service.fee_apr(pool, blocks).await.map_err(|e| match e {
+                AppError::InvalidAddress(_) => McpError::from(e),
+                _ => McpError::internal_error(e.to_string(), None),
+            })?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// List the Uniswap pools of a pair and their trading fees.
+    ///
+    /// Read-only and cheap: only factory lookups, no quotes.
+    #[tool(
+        description = "Get the trading fee of every pool for a token pair: the fixed 0.30% fee of the V2 pair on each configured V2 venue (Uniswap, Sushiswap) where it exists, and the fee tier of each existing V3 pool, with pool addresses. Fees are given in basis points and as a percentage. Read-only; nothing is quoted."
+    )]
+    pub async fn get_pool_fee(
+        &self,
+        Parameters(input): Parameters<GetPoolFeeInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(token_a = %input.token_a, token_b = %input.token_b, "get_pool_fee called");
+
+        let mut tokens = self.resolver(input.include_provenance);
+        let (a_entry, b_entry) =
+            tokens.pair(("token_a", &input.token_a), ("token_b", &input.token_b)).await?;
+
+        let result = self
+            .pool_activity_service
+            .pool_fees(a_entry.address, b_entry.address)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+}
@@ -0,0 +1,271 @@
+//! Token price tools.
+
+use std::str::FromStr;
+
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+use rust_decimal::Decimal;
+
+use super::{parse_response_format, EthereumTradingServer};
+use crate::{
+    services::price::DEFAULT_MAX_SOURCE_SPREAD_BPS,
+    types::{ComparisonOperator, QuoteCurrency, ResponseFormat},
+};
+
+/// Input parameters for the get_token_price tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+#[schemars(
+    example = serde_json::json!({ "token": "WETH" }),
+    example = serde_json::json!({ "token": "UNI", "quote_currency": "ETH", "aggregate": true })
+)]
+pub struct GetTokenPriceInput {
+    /// Token symbol (e.g., "WETH", "USDC", "UNI").
+    #[schemars(example = &"WETH", example = &"USDC", example = &"UNI")]
+    pub token: String,
+    /// Quote currency: "USD" or "ETH". Defaults to "USD".
+    #[serde(default)]
+    #[schemars(example = &"USD", example = &"ETH")]
+    pub quote_currency: Option<String>,
+    /// Quote currencies to price in at once (e.g., ["USD", "ETH"]). Returns a map of
+    /// currency to price instead of a single price. Cannot be combined with
+    /// `quote_currency`.
+    #[serde(default)]
+    #[schemars(example = serde_json::json!(["USD", "ETH"]))]
+    pub quote_currencies: Option<Vec<String>>,
+    /// If true, return a liquidity-weighted average across all Uniswap V2/V3 pools
+    /// instead of the first available source. Defaults to false.
+    #[serde(default)]
+    #[schemars(example = true)]
+    pub aggregate: Option<bool>,
+    /// Trade size in whole tokens that Uniswap V3 prices are quoted at (e.g.,
+    /// "0.01"). The price includes this trade's price impact, so smaller sizes
+    /// track the spot price more closely. Defaults to the server's reference
+    /// amount for the token (1 token unless configured).
+    #[serde(default)]
+    #[schemars(example = &"0.01")]
+    pub reference_amount: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+    /// Response format: "json" (the full result) or "text" (a concise
+    /// human-readable summary, without provenance). Defaults to "json".
+    #[serde(default)]
+    #[schemars(example = &"text")]
+    pub format: Option<String>,
+}
+
+/// Input parameters for the price_alert_check tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct PriceAlertCheckInput {
+    /// Token symbol (e.g., "WETH", "USDC", "UNI").
+    pub token: String,
+    /// Quote currency: "USD" or "ETH". Defaults to "USD".
+    #[serde(default)]
+    pub quote_currency: Option<String>,
+    /// Comparison operator: "==", ">", "<", ">=" or "<=".
+    pub operator: String,
+    /// Threshold price as string (e.g., "3000").
+    pub threshold: String,
+    /// Equality tolerance as a percentage of the threshold (e.g., "0.1" for 0.1%).
+    /// Only used with "==". Default: "0.1".
+    #[serde(default)]
+    pub tolerance: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+/// Input parameters for the price_source_spread tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct PriceSourceSpreadInput {
+    /// Token symbol with a Chainlink feed (e.g., "WETH", "WBTC", "USDC").
+    pub token: String,
+    /// Spread in basis points, either way, above which the prices are flagged
+    /// as diverging. Defaults to 100 (1%).
+    #[serde(default)]
+    pub max_spread_bps: Option<u32>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+#[tool_router(router = price_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Get current token price in USD or ETH.
+    ///
+    /// Fetches prices from on-chain sources (Chainlink oracles or Uniswap pools).
+    /// Token symbols are resolved using Uniswap Token List.
+    #[tool(
+        description = "Get current token price in USD or ETH from on-chain sources, or in both at once with quote_currencies. Supports any token from Uniswap Token List (e.g., WETH, USDC, UNI, LINK, etc.). Uniswap V3 prices are quoted at a reference trade size (1 token by default, 0.01 WBTC) and include that trade's price impact; set reference_amount to change it."
+    )]
+    pub async fn get_token_price(
+        &self,
+        Parameters(input): Parameters<GetTokenPriceInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            token = %input.token,
+            quote = ?input.quote_currency,
+            quotes = ?input.quote_currencies,
+            aggregate = ?input.aggregate,
+            "get_token_price called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance);
+        let token_entry = tokens.token("token", &input.token).await?;
+
+        let aggregate = input.aggregate.unwrap_or(false);
+        let format = parse_response_format(input.format.as_deref())?;
+        let price_service = match &input.reference_amount {
+            Some(amount) => match Decimal::from_str(amount.trim()) {
+                Ok(amount) if amount > Decimal::ZERO => {
+                    self.price_service.clone().with_reference_amount(token_entry.address, amount)
+                }
+                _ => {
+                    return Err(McpError::invalid_params(
+                        format!(
+                            "Invalid reference_amount: '{}'. Must be a positive number of tokens.",
+                            amount
+                        ),
+                        None,
+                    ))
+                }
+            },
+            None => self.price_service.clone(),
+        };
+
+        if let Some(currencies) = &input.quote_currencies {
+            if input.quote_currency.is_some() {
+                return Err(McpError::invalid_params(
+                    "Set either quote_currency or quote_currencies, not both",
+                    None,
+                ));
+            }
+            if currencies.is_empty() {
+                return Err(McpError::invalid_params("quote_currencies must not be empty", None));
+            }
+            let quote_currencies = currencies
+                .iter()
+                .map(|s| s.parse::<QuoteCurrency>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| McpError::invalid_params(e, None))?;
+
+            let result = price_service
+                .get_prices(token_entry.address, &quote_currencies, aggregate)
+                .await
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+            if format == ResponseFormat::Text {
+                return Ok(tokens.respond_text(result));
+            }
+            return tokens.respond(result);
+        }
+
+        let quote_currency = input
+            .quote_currency
+            .as_ref()
+            .map(|s| s.parse::<QuoteCurrency>().map_err(|e| McpError::invalid_params(e, None)))
+            .transpose()?
+            .unwrap_or_default();
+
+        let result = if aggregate {
+            price_service.get_aggregate_price(token_entry.address, quote_currency).await
+        } else {
+            price_service.get_price(token_entry.address, quote_currency).await
+        }
+        .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        if format == ResponseFormat::Text {
+            return Ok(tokens.respond_text(result));
+        }
+        tokens.respond(result)
+    }
+
+    /// Check whether a token's current price meets a threshold condition.
+    ///
+    /// Thin wrapper over get_token_price that performs the comparison server-side.
+    #[tool(
+        description = "Check whether a token's current price meets a condition (e.g., WETH > 3000 USD). Supports ==, >, <, >=, <= with a percentage tolerance for ==. Returns the current price and a boolean 'triggered'. Useful for polling-based strategies."
+    )]
+    pub async fn price_alert_check(
+        &self,
+        Parameters(input): Parameters<PriceAlertCheckInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            token = %input.token,
+            quote = ?input.quote_currency,
+            operator = %input.operator,
+            threshold = %input.threshold,
+            "price_alert_check called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance);
+        let token_entry = tokens.token("token", &input.token).await?;
+
+        let quote_currency = input
+            .quote_currency
+            .as_ref()
+            .map(|s| s.parse::<QuoteCurrency>().map_err(|e| McpError::invalid_params(e, None)))
+            .transpose()?
+            .unwrap_or_default();
+
+        let operator = input
+            .operator
+            .parse::<ComparisonOperator>()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        let threshold = Decimal::from_str(input.threshold.trim())
+            .map_err(|e| McpError::invalid_params(format!("Invalid threshold: {}", e), None))?;
+
+        let tolerance = input
+            .tolerance
+            .as_ref()
+            .map(|s| {
+                Decimal::from_str(s.trim()).map_err(|e| {
+                    McpError::invalid_params(format!("Invalid tolerance: {}", e), None)
+                })
+            })
+            .transpose()?
+            .unwrap_or(Decimal::new(1, 1)); // Default 0.1%
+
+        if tolerance < Decimal::ZERO {
+            return Err(McpError::invalid_params("tolerance must not be negative", None));
+        }
+
+        let result = self
+            .price_service
+            .check_price_alert(token_entry.address, quote_currency, operator, threshold, tolerance)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+
+    /// Compare a token's Chainlink and Uniswap prices.
+    #[tool(
+        description = "Report the spread between a token's Chainlink USD price and its Uniswap USD price (V3, falling back to V2), in basis points. Returns both prices with their sources and flags a spread above max_spread_bps (default 100) with a warning: a large spread points at a stale oracle or a thin or manipulated pool. Only tokens with a known Chainlink feed can be compared."
+    )]
+    pub async fn price_source_spread(
+        &self,
+        Parameters(input): Parameters<PriceSourceSpreadInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(token = %input.token, "price_source_spread called");
+
+        let mut tokens = self.resolver(input.include_provenance);
+        let token_entry = tokens.token("token", &input.token).await?;
+
+        let result = self
+            .price_service
+            .source_spread(
+                token_entry.address,
+                input.max_spread_bps.unwrap_or(DEFAULT_MAX_SOURCE_SPREAD_BPS),
+            )
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+}
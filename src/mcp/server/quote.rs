@@ -0,0 +1,122 @@
+//! Exact-output quote tools.
+
+use std::str::FromStr;
+
+use alloy::primitives::U256;
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+use rust_decimal::Decimal;
+
+use super::EthereumTradingServer;
+use crate::{error::AppError, types::parse_units};
+
+/// Input parameters for the get_exact_output_quote tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct GetExactOutputQuoteInput {
+    /// Input token symbol (e.g., "WETH", "USDC").
+    pub from_token: String,
+    /// Output token symbol (e.g., "WETH", "USDC").
+    pub to_token: String,
+    /// Desired output amount in to_token units (human-readable, e.g., "1000").
+    pub amount_out: String,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+/// Input parameters for the get_usd_output_quote tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct GetUsdOutputQuoteInput {
+    /// Input token symbol (e.g., "WETH", "USDC").
+    pub from_token: String,
+    /// Output token symbol (e.g., "WETH", "USDC").
+    pub to_token: String,
+    /// Desired value of the output in USD (e.g., "500").
+    pub usd_amount: String,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+#[tool_router(router = quote_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Quote the input required to receive an exact output amount.
+    ///
+    /// Read-only: uses the V3 quoter and V2 getAmountsIn, so no wallet balance is needed.
+    #[tool(
+        description = "Quote how much of from_token is needed to receive exactly amount_out of to_token (e.g., how much WETH for exactly 1000 USDC). Checks Uniswap V3 fee tiers and the V2 route on each V2 venue (Uniswap and Sushiswap by default on mainnet), returns the cheapest venue, the required input and the implied price. Read-only."
+    )]
+    pub async fn get_exact_output_quote(
+        &self,
+        Parameters(input): Parameters<GetExactOutputQuoteInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            to = %input.to_token,
+            amount_out = %input.amount_out,
+            "get_exact_output_quote called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        let amount_out = parse_units(&input.amount_out, to_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        if amount_out == U256::ZERO {
+            return Err(McpError::invalid_params("amount_out must be greater than zero", None));
+        }
+
+        let result = self
+            .swap_service
+            .quote_exact_output(from_entry.address, to_entry.address, amount_out)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+
+    /// Quote the input required to receive a USD value of an output token.
+    ///
+    /// Converts the USD value to an output amount at the output token's current
+    /// price, then quotes that amount as an exact output.
+    #[tool(
+        description = "Quote how much of from_token is needed to receive usd_amount worth of to_token (e.g., how much WETH to receive $500 of USDC). Converts the USD value to a to_token amount at its current USD price, then quotes that exact output across Uniswap V3 fee tiers and the V2 venues. Returns the required input, the output amount, the implied price and the implied USD price of from_token. Fails if to_token cannot be priced in USD. Read-only."
+    )]
+    pub async fn get_usd_output_quote(
+        &self,
+        Parameters(input): Parameters<GetUsdOutputQuoteInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            to = %input.to_token,
+            usd_amount = %input.usd_amount,
+            "get_usd_output_quote called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        let usd_amount = Decimal::from_str(input.usd_amount.trim())
+            .map_err(|e| McpError::invalid_params(format!("Invalid usd_amount: {}", e), None))?;
+        if usd_amount <= Decimal::ZERO {
+            return Err(McpError::invalid_params("usd_amount must be greater than zero", None));
+        }
+
+        let result = self
+            .trade_cost_service
+            .quote_usd_output(from_entry.address, to_entry.address, usd_amount)
+            .await
+            .map_err(|e| match e {
+                AppError::Parse(_) => McpError::from(e),
+                _ => McpError::internal_error(e.to_string(), None),
+            })?;
+
+        tokens.respond(result)
+    }
+}
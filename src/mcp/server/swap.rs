@@ -0,0 +1,731 @@
+//! Swap simulation, routing and preview tools.
+
+use std::str::FromStr;
+
+use alloy::primitives::{Address, U256};
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+use rust_decimal::Decimal;
+
+use super::{
+    optional_address_schema, parse_address, parse_response_format, parse_slippage_tolerance,
+    EthereumTradingServer, TokenResolver, EXAMPLE_WALLET_ADDRESS,
+};
+use crate::{
+    error::AppError,
+    ethereum::contracts::uniswap_v3::fee_tiers,
+    mcp::progress,
+    services::{swap::apply_slippage_floor, token_registry::normalize_symbol, TokenEntry},
+    types::{
+        parse_units, ForcedRoute, GasCostToken, PairTradability, ProtocolPreference, QuoteCurrency,
+        ResponseFormat, SwapParams, SwapRankingMetric, TokenInfo, TransactionFormat,
+        UniswapVersion, Venue,
+    },
+};
+
+/// Smallest gas limit accepted by swap_tokens (a plain ETH transfer).
+const MIN_GAS_LIMIT: u64 = 21_000;
+
+/// Largest gas limit accepted by swap_tokens (the mainnet block gas limit).
+const MAX_GAS_LIMIT: u64 = 30_000_000;
+
+/// Maximum number of legs accepted by the simulate_swap_path tool.
+const MAX_SWAP_PATH_LEGS: usize = 5;
+
+/// Maximum number of candidate output tokens accepted by the best_swap tool.
+const MAX_BEST_SWAP_CANDIDATES: usize = 10;
+
+/// Maximum number of slippage levels accepted by the swap_tokens tool.
+const MAX_SLIPPAGE_LEVELS: usize = 10;
+
+/// Explicit route specification for the swap_tokens tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct ForceRouteInput {
+    /// Uniswap protocol version: "V2" or "V3".
+    pub protocol: String,
+    /// Fee tier in hundredths of a bip (100, 500, 3000, 10000). Required for V3.
+    #[serde(default)]
+    pub fee_tier: Option<u32>,
+    /// Intermediary token symbols between from_token and to_token, in order
+    /// (e.g., ["WETH"]). Empty for a direct swap.
+    #[serde(default)]
+    pub via: Vec<String>,
+    /// V2 venue to trade on: "uniswap" or "sushiswap" (default: "uniswap").
+    /// Only valid for V2 routes.
+    #[serde(default)]
+    pub venue: Option<String>,
+}
+
+/// Input parameters for the swap_tokens tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+#[schemars(
+    example = serde_json::json!({ "from_token": "WETH", "to_token": "USDC", "amount": "1.5" }),
+    example = serde_json::json!({
+        "from_token": "USDC",
+        "to_token": "UNI",
+        "amount": "1000",
+        "slippage_tolerance": "1",
+        "force_route": { "protocol": "V3", "fee_tier": 3000, "via": ["WETH"] },
+    })
+)]
+pub struct SwapTokensInput {
+    /// Input token symbol (e.g., "WETH", "USDC").
+    #[schemars(example = &"WETH", example = &"USDC")]
+    pub from_token: String,
+    /// Output token symbol (e.g., "WETH", "USDC").
+    #[schemars(example = &"USDC", example = &"WETH")]
+    pub to_token: String,
+    /// Amount to swap (human-readable, e.g., "1.5").
+    #[schemars(example = "1.5", example = "1000")]
+    pub amount: String,
+    /// Slippage tolerance percentage as string (e.g., "0.5" for 0.5%). Default: "0.5".
+    #[serde(default)]
+    #[schemars(example = "0.1", example = "0.5", example = "1")]
+    pub slippage_tolerance: Option<String>,
+    /// Absolute minimum output in to_token (human-readable, e.g., "2950"). The
+    /// stricter of this and the slippage minimum is sent with the swap, and
+    /// amount_out_minimum_source reports which one applied.
+    #[serde(default)]
+    #[schemars(example = "2950")]
+    pub min_amount_out: Option<String>,
+    /// Optional explicit route. When set, route discovery is skipped and the swap
+    /// fails if any pool along the route does not exist.
+    #[serde(default)]
+    pub force_route: Option<ForceRouteInput>,
+    /// Optional gas limit (21000 to 30000000). When set, it is used instead of
+    /// estimating gas and is included in the returned transaction.
+    #[serde(default)]
+    #[schemars(example = 250_000)]
+    pub gas_limit: Option<u64>,
+    /// Transaction format: "minimal" (to, data, value) or "eip1559", which also
+    /// returns `wallet_transaction`, a complete EIP-1559 transaction (from, chainId,
+    /// gas, fees, nonce) for eth_sendTransaction or a wallet. Defaults to "minimal".
+    #[serde(default)]
+    #[schemars(example = &"minimal", example = &"eip1559")]
+    pub transaction_format: Option<String>,
+    /// Protocol order for route selection: "v3_first" (V2 only if no V3 pool
+    /// qualifies), "v2_first", or "best" (quote both, keep the larger output).
+    /// Defaults to ETHEREUM_PROTOCOL_PREFERENCE ("v3_first"). Cannot be combined
+    /// with force_route.
+    #[serde(default)]
+    #[schemars(example = &"v2_first", example = &"best")]
+    pub protocol_preference: Option<String>,
+    /// Also express the gas cost in "from_token" or "to_token" as gas_cost_in_token.
+    /// Omitted when the token cannot be priced in ETH.
+    #[serde(default)]
+    #[schemars(example = &"to_token")]
+    pub gas_cost_token: Option<String>,
+    /// Simulate as if sent from this address (0x...) instead of the configured wallet.
+    /// Only the eth_call and gas estimate use it; the output still goes to the wallet,
+    /// and nothing is signed or sent.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub from_address: Option<String>,
+    /// If true, keep a slippage_tolerance below the server's floor
+    /// (ETHEREUM_MIN_SLIPPAGE_TOLERANCE, default 0.05%) instead of raising it.
+    /// The minimum output then sits at or near the quote and the real
+    /// transaction will likely revert. Defaults to false.
+    #[serde(default)]
+    pub allow_low_slippage: Option<bool>,
+    /// Extra slippage tolerance percentages (0 to 50, at most 10) to compute the
+    /// minimum output at, returned as slippage_table. Uses the same quote, so
+    /// it costs no extra calls. The floor is not applied to these levels.
+    #[serde(default)]
+    #[schemars(example = &["0.1", "0.5", "1"])]
+    pub slippage_levels: Option<Vec<String>>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+    /// If true, re-quote the selected route from pool state (V2 reserves or V3
+    /// single-tick math) and warn when it disagrees with the quoter or router
+    /// beyond ETHEREUM_QUOTE_CROSS_CHECK_TOLERANCE. Costs a few extra RPC calls.
+    /// Defaults to ETHEREUM_QUOTE_CROSS_CHECK (false).
+    #[serde(default)]
+    pub cross_check_quote: Option<bool>,
+    /// If true, include decoded_transaction: the router function, amountIn,
+    /// amountOutMin, token path, fee tiers, recipient and deadline decoded from
+    /// transaction.data. Defaults to false.
+    #[serde(default)]
+    pub decode_transaction: Option<bool>,
+    /// Response format: "json" (the full result) or "text" (a concise
+    /// human-readable summary, without provenance). Defaults to "json".
+    #[serde(default)]
+    #[schemars(example = &"text")]
+    pub format: Option<String>,
+}
+
+/// Input parameters for the simulate_swap_path tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SimulateSwapPathInput {
+    /// Ordered token symbols to swap through (e.g., ["WETH", "USDC", "UNI"]).
+    /// Each consecutive pair is one leg.
+    pub tokens: Vec<String>,
+    /// Amount of the first token to swap (human-readable, e.g., "1.5").
+    pub amount: String,
+    /// Slippage tolerance percentage applied to every leg (e.g., "0.5"). Default: "0.5".
+    #[serde(default)]
+    pub slippage_tolerance: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+/// Input parameters for the best_swap tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct BestSwapInput {
+    /// Input token symbol (e.g., "WETH").
+    pub from_token: String,
+    /// Amount to swap (human-readable, e.g., "1").
+    pub amount: String,
+    /// Candidate output token symbols (e.g., ["USDC", "UNI", "LINK"]). Maximum 10.
+    pub to_tokens: Vec<String>,
+    /// Ranking metric: "output_usd" (USD value of the output, the default),
+    /// "net_output_usd" (output value minus gas) or "price_impact" (lowest first).
+    #[serde(default)]
+    pub metric: Option<String>,
+    /// Slippage tolerance percentage (e.g., "0.5"). Default: "0.5".
+    #[serde(default)]
+    pub slippage_tolerance: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+/// Input parameters for the can_trade tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct CanTradeInput {
+    /// Input token symbol (e.g., "WETH", "USDC").
+    pub from_token: String,
+    /// Output token symbol (e.g., "WETH", "USDC").
+    pub to_token: String,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+/// Input parameters for the swap_preview tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SwapPreviewInput {
+    /// Input token symbol (e.g., "WETH", "USDC").
+    pub from_token: String,
+    /// Output token symbol (e.g., "WETH", "USDC").
+    pub to_token: String,
+    /// Amount to swap (human-readable, e.g., "1.5").
+    pub amount: String,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+/// Parse the slippage levels of a slippage table, each a percentage from 0 to 50.
+fn parse_slippage_levels(levels: Option<&[String]>) -> Result<Vec<Decimal>, McpError> {
+    let levels = levels.unwrap_or_default();
+    if levels.len() > MAX_SLIPPAGE_LEVELS {
+        return Err(McpError::invalid_params(
+            format!("slippage_levels accepts at most {} levels", MAX_SLIPPAGE_LEVELS),
+            None,
+        ));
+    }
+    levels
+        .iter()
+        .map(|level| {
+            let slippage = Decimal::from_str(level).map_err(|e| {
+                McpError::invalid_params(format!("Invalid slippage level '{}': {}", level, e), None)
+            })?;
+            if slippage < Decimal::ZERO || slippage > Decimal::from(50) {
+                return Err(McpError::invalid_params(
+                    "slippage_levels must be between 0 and 50 (percentage)",
+                    None,
+                ));
+            }
+            Ok(slippage)
+        })
+        .collect()
+}
+
+/// Validate an optional caller-provided gas limit.
+fn parse_gas_limit(gas_limit: Option<u64>) -> Result<Option<u64>, McpError> {
+    match gas_limit {
+        Some(gas) if !(MIN_GAS_LIMIT..=MAX_GAS_LIMIT).contains(&gas) => {
+            Err(McpError::invalid_params(
+                format!("gas_limit must be between {} and {}", MIN_GAS_LIMIT, MAX_GAS_LIMIT),
+                None,
+            ))
+        }
+        _ => Ok(gas_limit),
+    }
+}
+
+/// Reject native ETH as a swap token.
+///
+/// Swaps go through the routers' ERC20 functions and send no ETH, so native
+/// ETH has to be wrapped first (or the output unwrapped afterwards).
+fn reject_native_eth(field: &str, symbol: &str) -> Result<(), McpError> {
+    if normalize_symbol(symbol) != "ETH" {
+        return Ok(());
+    }
+    Err(McpError::invalid_params(
+        format!(
+            "Native ETH cannot be swapped directly: swaps send no ETH value. Use WETH as {} \
+             instead, wrapping ETH before the swap or unwrapping WETH after it.",
+            field
+        ),
+        None,
+    ))
+}
+
+/// Validate a force_route input and resolve its intermediary symbols.
+async fn resolve_forced_route(
+    tokens: &mut TokenResolver<'_>,
+    input: &ForceRouteInput,
+) -> Result<ForcedRoute, McpError> {
+    let protocol =
+        UniswapVersion::from_str(&input.protocol).map_err(|e| McpError::invalid_params(e, None))?;
+
+    match (protocol, input.fee_tier) {
+        (UniswapVersion::V3, None) => {
+            return Err(McpError::invalid_params(
+                "force_route.fee_tier is required for V3 routes",
+                None,
+            ));
+        }
+        (UniswapVersion::V3, Some(fee)) if !fee_tiers::ALL_FEES.contains(&fee) => {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Invalid force_route.fee_tier: {}. Must be one of {:?}",
+                    fee,
+                    fee_tiers::ALL_FEES
+                ),
+                None,
+            ));
+        }
+        (UniswapVersion::V2, Some(_)) => {
+            return Err(McpError::invalid_params(
+                "force_route.fee_tier is only valid for V3 routes",
+                None,
+            ));
+        }
+        _ => {}
+    }
+
+    let venue = match (protocol, &input.venue) {
+        (_, None) => Venue::Uniswap,
+        (UniswapVersion::V3, Some(_)) => {
+            return Err(McpError::invalid_params(
+                "force_route.venue is only valid for V2 routes",
+                None,
+            ));
+        }
+        (UniswapVersion::V2, Some(venue)) => {
+            let venue = Venue::from_str(venue)
+                .map_err(|e| McpError::invalid_params(format!("force_route.venue: {}", e), None))?;
+            if !tokens.server.v2_venues.contains(&venue) {
+                return Err(McpError::invalid_params(
+                    format!(
+                        "force_route.venue {} is not one of the configured V2 venues \
+                         (ETHEREUM_V2_VENUES)",
+                        venue
+                    ),
+                    None,
+                ));
+            }
+            venue
+        }
+    };
+
+    let mut intermediaries = Vec::with_capacity(input.via.len());
+    for symbol in &input.via {
+        intermediaries.push(tokens.token("force_route.via", symbol).await?.address);
+    }
+
+    Ok(ForcedRoute { protocol, fee_tier: input.fee_tier, intermediaries, venue })
+}
+
+#[tool_router(router = swap_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Simulate a token swap on Uniswap V2/V3.
+    ///
+    /// Constructs a real Uniswap transaction and simulates it using eth_call.
+    /// The transaction is NOT executed on-chain.
+    ///
+    /// Returns estimated output amount, gas costs, price impact, and the raw transaction data.
+    #[tool(
+        description = "Simulate a token swap on Uniswap V2/V3 (and Sushiswap V2, when configured) without executing on-chain. Supports any token from Uniswap Token List. Optionally pass force_route to pin the protocol, fee tier and intermediary tokens, protocol_preference \"v2_first\" or \"best\" to change the V3-first route selection, from_address to simulate as another sender, transaction_format \"eip1559\" to also get a complete EIP-1559 transaction for a wallet or external signer, min_amount_out to set an absolute output floor in to_token (the stricter of it and the slippage minimum is used), cross_check_quote to re-quote the route from pool state and flag a disagreeing quote, or decode_transaction to also get the calldata's function and arguments decoded."
+    )]
+    pub async fn swap_tokens(
+        &self,
+        Parameters(input): Parameters<SwapTokensInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            to = %input.to_token,
+            amount = %input.amount,
+            slippage = ?input.slippage_tolerance,
+            force_route = ?input.force_route,
+            "swap_tokens called"
+        );
+
+        reject_native_eth("from_token", &input.from_token)?;
+        reject_native_eth("to_token", &input.to_token)?;
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        // Use decimals from TokenRegistry
+        let amount_in = parse_units(&input.amount, from_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        // Validate amount is not zero
+        if amount_in == U256::ZERO {
+            return Err(McpError::invalid_params("Amount must be greater than zero", None));
+        }
+        let min_amount_out = input
+            .min_amount_out
+            .as_deref()
+            .map(|amount| parse_units(amount, to_entry.decimals))
+            .transpose()
+            .map_err(|e| {
+                McpError::invalid_params(format!("Invalid min_amount_out: {}", e), None)
+            })?;
+
+        let requested_slippage = parse_slippage_tolerance(input.slippage_tolerance.as_deref())?;
+        let (slippage_tolerance, slippage_warning) = if input.allow_low_slippage.unwrap_or(false) {
+            (requested_slippage, None)
+        } else {
+            apply_slippage_floor(requested_slippage, self.min_slippage_tolerance)
+        };
+        let format = parse_response_format(input.format.as_deref())?;
+
+        let transaction_format = input
+            .transaction_format
+            .as_deref()
+            .map(TransactionFormat::from_str)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+
+        let protocol_preference = input
+            .protocol_preference
+            .as_deref()
+            .map(ProtocolPreference::from_str)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let from_address = input.from_address.as_deref().map(parse_address).transpose()?;
+        if from_address.is_some() && transaction_format == TransactionFormat::Eip1559 {
+            return Err(McpError::invalid_params(
+                "from_address only changes the simulation; it cannot be combined with \
+                 transaction_format \"eip1559\", which is signed by the configured wallet",
+                None,
+            ));
+        }
+
+        let gas_cost_token = input
+            .gas_cost_token
+            .as_deref()
+            .map(GasCostToken::from_str)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        if protocol_preference.is_some() && input.force_route.is_some() {
+            return Err(McpError::invalid_params(
+                "protocol_preference cannot be combined with force_route",
+                None,
+            ));
+        }
+
+        let forced_route = match &input.force_route {
+            Some(route) => Some(resolve_forced_route(&mut tokens, route).await?),
+            None => None,
+        };
+
+        let params = SwapParams {
+            from_token: from_entry.address,
+            to_token: to_entry.address,
+            amount_in,
+            slippage_tolerance,
+            deadline: None,
+            forced_route,
+            gas_limit: parse_gas_limit(input.gas_limit)?,
+            transaction_format,
+            protocol_preference,
+            from_address,
+            slippage_levels: parse_slippage_levels(input.slippage_levels.as_deref())?,
+            cross_check_quote: input.cross_check_quote.unwrap_or(self.quote_cross_check),
+            decode_transaction: input.decode_transaction.unwrap_or(false),
+            min_amount_out,
+        };
+
+        let mut result = self.swap_service.simulate_swap(params).await.map_err(|e| match e {
+            AppError::RouteNotFound(_) => McpError::from(e),
+            _ => McpError::internal_error(e.to_string(), None),
+        })?;
+        if let Some(impact) = result.price_impact_above(self.max_price_impact) {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Refusing to swap {} {} for {}: price impact is {}%, above the {}% cap set by \
+                     ETHEREUM_MAX_PRICE_IMPACT. This is almost certainly a mistake or a \
+                     manipulated pool; try a smaller amount or a deeper route.",
+                    input.amount, from_entry.symbol, to_entry.symbol, impact, self.max_price_impact
+                ),
+                None,
+            ));
+        }
+        result.check_parsed_decimals(&from_entry.symbol, from_entry.decimals);
+        result.warnings.extend(slippage_warning);
+
+        // Value the expected and worst-case output; omitted if the output can't be priced
+        match self.price_service.get_price(to_entry.address, QuoteCurrency::USD).await {
+            Ok(info) if !info.stale => match Decimal::from_str(&info.price) {
+                Ok(price) => result.value_output_in_usd(price),
+                Err(e) => tracing::debug!(error = %e, "Invalid USD price for swap output"),
+            },
+            Ok(_) => tracing::debug!("Only a stale USD price for swap output, not valuing it"),
+            Err(e) => tracing::debug!(error = %e, "Could not price swap output in USD"),
+        }
+
+        // Express the gas cost in a traded token; omitted if the token can't be priced
+        if let Some(gas_cost_token) = gas_cost_token {
+            let entry = match gas_cost_token {
+                GasCostToken::FromToken => &from_entry,
+                GasCostToken::ToToken => &to_entry,
+            };
+            match self.price_service.get_price(entry.address, QuoteCurrency::ETH).await {
+                Ok(info) if !info.stale => match Decimal::from_str(&info.price) {
+                    Ok(price) => result.value_gas_in_token(&entry.symbol, entry.decimals, price),
+                    Err(e) => tracing::debug!(error = %e, "Invalid ETH price for gas cost token"),
+                },
+                Ok(_) => tracing::debug!("Only a stale ETH price for gas cost token, not using it"),
+                Err(e) => tracing::debug!(error = %e, "Could not price gas cost token in ETH"),
+            }
+        }
+
+        if format == ResponseFormat::Text {
+            return Ok(tokens.respond_text(result.summary(&from_entry.symbol, &to_entry.symbol)));
+        }
+        tokens.respond(result)
+    }
+
+    /// Simulate a sequence of dependent swaps.
+    ///
+    /// Each leg's expected output is the next leg's input. Stops at the first leg
+    /// that cannot be routed and reports it in the result.
+    #[tool(
+        description = "Simulate a sequence of dependent swaps (e.g., WETH -> USDC -> UNI) where each leg's expected output feeds the next leg. Returns per-leg simulations, the final output and the aggregate gas. Stops early and reports the leg that cannot be routed. Maximum 5 legs."
+    )]
+    pub async fn simulate_swap_path(
+        &self,
+        Parameters(input): Parameters<SimulateSwapPathInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            tokens = ?input.tokens,
+            amount = %input.amount,
+            slippage = ?input.slippage_tolerance,
+            "simulate_swap_path called"
+        );
+
+        if input.tokens.len() < 2 {
+            return Err(McpError::invalid_params(
+                "tokens must contain at least two token symbols",
+                None,
+            ));
+        }
+        if input.tokens.len() > MAX_SWAP_PATH_LEGS + 1 {
+            return Err(McpError::invalid_params(
+                format!("A swap path can have at most {} legs", MAX_SWAP_PATH_LEGS),
+                None,
+            ));
+        }
+
+        let mut resolver = self.resolver(input.include_provenance).for_trade();
+        let mut entries = Vec::with_capacity(input.tokens.len());
+        for symbol in &input.tokens {
+            entries.push(resolver.token("token", symbol).await?);
+        }
+
+        if entries.windows(2).any(|hop| hop[0].address == hop[1].address) {
+            return Err(McpError::invalid_params(
+                "Consecutive tokens in the path cannot be the same",
+                None,
+            ));
+        }
+
+        let amount_in = parse_units(&input.amount, entries[0].decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        if amount_in == U256::ZERO {
+            return Err(McpError::invalid_params("Amount must be greater than zero", None));
+        }
+
+        let slippage_tolerance = parse_slippage_tolerance(input.slippage_tolerance.as_deref())?;
+
+        let tokens: Vec<Address> = entries.iter().map(|entry| entry.address).collect();
+        let result = self
+            .swap_service
+            .simulate_swap_path(&tokens, amount_in, slippage_tolerance)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        resolver.respond(result)
+    }
+
+    /// Rank candidate output tokens for swapping a given input.
+    ///
+    /// Simulates a swap into each candidate concurrently and ranks them by the
+    /// chosen metric. Candidates that cannot be routed or valued come last.
+    #[tool(
+        description = "Find which of several tokens gives the best value for a given input (e.g., 1 WETH into USDC, UNI or LINK). Simulates a swap into each candidate concurrently and ranks them by USD value of the expected output (default), output value after gas, or lowest price impact. Candidates that cannot be routed or priced are listed last with a reason. Maximum 10 candidates."
+    )]
+    pub async fn best_swap(
+        &self,
+        Parameters(input): Parameters<BestSwapInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            amount = %input.amount,
+            to_tokens = ?input.to_tokens,
+            metric = ?input.metric,
+            "best_swap called"
+        );
+
+        if input.to_tokens.is_empty() {
+            return Err(McpError::invalid_params(
+                "to_tokens must contain at least one token symbol",
+                None,
+            ));
+        }
+        if input.to_tokens.len() > MAX_BEST_SWAP_CANDIDATES {
+            return Err(McpError::invalid_params(
+                format!("best_swap accepts at most {} candidates", MAX_BEST_SWAP_CANDIDATES),
+                None,
+            ));
+        }
+
+        let metric = input
+            .metric
+            .as_deref()
+            .map(SwapRankingMetric::from_str)
+            .transpose()
+            .map_err(|e| McpError::invalid_params(e, None))?
+            .unwrap_or_default();
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let from_entry = tokens.token("from_token", &input.from_token).await?;
+
+        let mut to_entries = Vec::with_capacity(input.to_tokens.len());
+        for symbol in &input.to_tokens {
+            let entry = tokens.token("to_tokens", symbol).await?;
+            if entry.address == from_entry.address {
+                return Err(McpError::invalid_params(
+                    format!("to_tokens cannot include from_token ('{}')", symbol),
+                    None,
+                ));
+            }
+            if to_entries.iter().any(|e: &TokenEntry| e.address == entry.address) {
+                return Err(McpError::invalid_params(
+                    format!("Duplicate to_tokens symbol: '{}'", symbol),
+                    None,
+                ));
+            }
+            to_entries.push(entry);
+        }
+
+        let amount_in = parse_units(&input.amount, from_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        if amount_in == U256::ZERO {
+            return Err(McpError::invalid_params("Amount must be greater than zero", None));
+        }
+
+        let slippage_tolerance = parse_slippage_tolerance(input.slippage_tolerance.as_deref())?;
+
+        let result = self
+            .trade_cost_service
+            .best_swap(
+                from_entry.address,
+                to_entries.iter().map(|entry| entry.address).collect(),
+                amount_in,
+                slippage_tolerance,
+                metric,
+                |done, total| progress::report(done, total, "candidates simulated"),
+            )
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+
+    /// Check whether a pair has any route at all.
+    ///
+    /// Read-only: only looks up pools, without quoting them.
+    #[tool(
+        description = "Cheap yes/no check of whether from_token can be swapped for to_token here, before quoting. Looks up a Uniswap V3 pool at every fee tier and, on each V2 venue, a direct pair and a path through WETH, all at once. Returns tradable and the routes that exist (protocol, venue, fee tier, path). Pools are not quoted, so an existing route may still be too thin to fill a trade."
+    )]
+    pub async fn can_trade(
+        &self,
+        Parameters(input): Parameters<CanTradeInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(from = %input.from_token, to = %input.to_token, "can_trade called");
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        let routes = self
+            .swap_service
+            .tradable_routes(from_entry.address, to_entry.address)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        let result = PairTradability::new(
+            TokenInfo::erc20(from_entry.address, from_entry.symbol.clone(), from_entry.decimals),
+            TokenInfo::erc20(to_entry.address, to_entry.symbol.clone(), to_entry.decimals),
+            routes,
+        );
+
+        tokens.respond(result)
+    }
+
+    /// Preview a swap before simulating it.
+    ///
+    /// Read-only: quotes pools but neither estimates gas nor simulates the swap
+    /// transaction, so the result is an estimate.
+    #[tool(
+        description = "Cheap pre-check before swap_tokens: resolves both tokens (addresses, decimals), lists the Uniswap V3 pools and the V2 route on each V2 venue with their liquidity and quotes, picks the route swap_tokens would use with a rough expected output, and says whether a full simulation is likely to succeed (route found, wallet configured, enough balance and router allowance). Only the pools are quoted (read-only eth_calls to the quoter and pairs); the swap transaction itself is neither gas-estimated nor simulated, so the output is an estimate, not a verified simulation."
+    )]
+    pub async fn swap_preview(
+        &self,
+        Parameters(input): Parameters<SwapPreviewInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            to = %input.to_token,
+            amount = %input.amount,
+            "swap_preview called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        let amount_in = parse_units(&input.amount, from_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        if amount_in == U256::ZERO {
+            return Err(McpError::invalid_params("Amount must be greater than zero", None));
+        }
+
+        let result = self
+            .swap_service
+            .preview_swap(from_entry.address, to_entry.address, amount_in)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+}
@@ -0,0 +1,71 @@
+//! Token list and token verification tools.
+
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+
+use super::{parse_address, EthereumTradingServer};
+
+/// Input parameters for the verify_token tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct VerifyTokenInput {
+    /// Token contract address to verify (0x...).
+    pub token_address: String,
+    /// Address to check the token's blacklist for (0x...), for tokens with one
+    /// (USDC, USDT). Defaults to the configured wallet.
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+#[tool_router(router = tokens_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Refresh the token list and report tokens added, removed or changed.
+    ///
+    /// Compares against the snapshot of the previous refresh.
+    #[tool(
+        description = "Refresh the token list and report the tokens added, removed, and changed (symbol or decimals) since the previous refresh. If the token list has not been loaded since startup there is nothing to compare against: the call records a baseline and reports no changes. Use it to detect new listings."
+    )]
+    pub async fn token_list_diff(&self) -> Result<String, McpError> {
+        tracing::info!("token_list_diff called");
+
+        let result = self
+            .token_registry
+            .token_list_diff()
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+
+    /// Verify that an address is a real ERC20 token contract.
+    ///
+    /// Checks for deployed contract code and that the core ERC20 view functions
+    /// respond, returning a report of which checks passed. Also reports whether
+    /// the token is an EIP-1967 upgradeable proxy and, for USDC and USDT, whether
+    /// it is paused or the address is blacklisted.
+    #[tool(
+        description = "Verify that an address is a real ERC20 token contract. Checks for contract code and that decimals(), symbol(), name(), totalSupply() and balanceOf() respond, and flags EIP-1967 upgradeable proxies. For USDC and USDT, also reports whether the token is paused and whether an address (default: the configured wallet) is blacklisted, which makes transfers revert. Use as a basic safety screen before trading an unknown token."
+    )]
+    pub async fn verify_token(
+        &self,
+        Parameters(input): Parameters<VerifyTokenInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(token = %input.token_address, "verify_token called");
+
+        let token_address = parse_address(&input.token_address)?;
+        let holder = match input.address.as_deref() {
+            Some(address) => Some(parse_address(address)?),
+            None => self.wallet_address,
+        };
+
+        let result = self
+            .token_verification_service
+            .verify_token(token_address, holder)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))
+    }
+}
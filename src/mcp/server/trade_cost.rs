@@ -0,0 +1,263 @@
+//! Trade cost, effective rate and round-trip tools.
+
+use std::str::FromStr;
+
+use alloy::primitives::U256;
+use rmcp::{
+    handler::server::wrapper::Parameters, schemars, tool, tool_router, ErrorData as McpError,
+};
+use rust_decimal::Decimal;
+
+use super::{
+    optional_address_schema, parse_address, parse_response_format, parse_slippage_tolerance,
+    EthereumTradingServer, EXAMPLE_WALLET_ADDRESS,
+};
+use crate::types::{parse_units, ResponseFormat, SwapParams};
+
+/// Input parameters for the trade_cost_breakdown tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct TradeCostBreakdownInput {
+    /// Input token symbol (e.g., "WETH", "USDC").
+    pub from_token: String,
+    /// Output token symbol (e.g., "WETH", "USDC").
+    pub to_token: String,
+    /// Amount to swap (human-readable, e.g., "1.5").
+    pub amount: String,
+    /// Simulate as if sent from this address (0x...) instead of the configured wallet.
+    /// Only the eth_call and gas estimate use it; the output still goes to the wallet,
+    /// and nothing is signed or sent.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub from_address: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+    /// Response format: "json" (the full result) or "text" (a concise
+    /// human-readable summary, without provenance). Defaults to "json".
+    #[serde(default)]
+    #[schemars(example = &"text")]
+    pub format: Option<String>,
+}
+
+/// Input parameters for the effective_rate tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct EffectiveRateInput {
+    /// Input token symbol (e.g., "WETH", "USDC").
+    pub from_token: String,
+    /// Output token symbol (e.g., "WETH", "USDC").
+    pub to_token: String,
+    /// Amount to swap (human-readable, e.g., "1.5").
+    pub amount: String,
+    /// Simulate as if sent from this address (0x...) instead of the configured wallet.
+    /// Only the eth_call and gas estimate use it; the output still goes to the wallet,
+    /// and nothing is signed or sent.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub from_address: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+    /// Response format: "json" (the full result) or "text" (a concise
+    /// human-readable summary, without provenance). Defaults to "json".
+    #[serde(default)]
+    #[schemars(example = &"text")]
+    pub format: Option<String>,
+}
+
+/// Input parameters for the simulate_round_trip_pnl tool.
+#[derive(Debug, Clone, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct SimulateRoundTripPnlInput {
+    /// Token to start and end in (e.g., "USDC").
+    pub from_token: String,
+    /// Token bought and then sold back (e.g., "WETH").
+    pub to_token: String,
+    /// Amount of from_token to spend (human-readable, e.g., "1000").
+    pub amount: String,
+    /// Hypothetical pool price of to_token in from_token at exit (e.g., "3500" USDC per
+    /// WETH). Default: the current pool price.
+    #[serde(default)]
+    pub exit_price: Option<String>,
+    /// Slippage tolerance percentage applied to both legs (e.g., "0.5"). Default: "0.5".
+    #[serde(default)]
+    pub slippage_tolerance: Option<String>,
+    /// Simulate as if sent from this address (0x...) instead of the configured wallet.
+    /// Only the eth_call and gas estimate use it; the output still goes to the wallet,
+    /// and nothing is signed or sent.
+    #[serde(default)]
+    #[schemars(schema_with = "optional_address_schema", example = EXAMPLE_WALLET_ADDRESS)]
+    pub from_address: Option<String>,
+    /// If true, include which token list (source URL, name, version) and chain
+    /// resolved each symbol. Defaults to false.
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+#[tool_router(router = trade_cost_router, vis = "pub(super)")]
+impl EthereumTradingServer {
+    /// Estimate the total cost of a swap.
+    ///
+    /// Simulates the swap and values the input, output, AMM fee, price impact and
+    /// gas in USD. USD figures are omitted (with a note) for unpriceable tokens.
+    #[tool(
+        description = "Estimate the total cost of a token swap. Simulates the swap on Uniswap and returns input and output value in USD, the AMM fee, price impact cost, gas cost in USD, the total cost and the net effective rate after gas. USD figures are omitted with a note when a token cannot be priced."
+    )]
+    pub async fn trade_cost_breakdown(
+        &self,
+        Parameters(input): Parameters<TradeCostBreakdownInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            to = %input.to_token,
+            amount = %input.amount,
+            "trade_cost_breakdown called"
+        );
+
+        let format = parse_response_format(input.format.as_deref())?;
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        let amount_in = parse_units(&input.amount, from_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        if amount_in == U256::ZERO {
+            return Err(McpError::invalid_params("Amount must be greater than zero", None));
+        }
+
+        let params = SwapParams {
+            from_token: from_entry.address,
+            to_token: to_entry.address,
+            amount_in,
+            slippage_tolerance: Decimal::new(5, 1),
+            from_address: input.from_address.as_deref().map(parse_address).transpose()?,
+            ..Default::default()
+        };
+
+        let result = self
+            .trade_cost_service
+            .breakdown(params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        if format == ResponseFormat::Text {
+            return Ok(tokens.respond_text(result));
+        }
+        tokens.respond(result)
+    }
+
+    /// Get the all-in exchange rate of a swap at a specific size.
+    ///
+    /// Simulates the swap and returns output per input, fee and price impact
+    /// included, alongside the marginal spot rate, in both directions.
+    #[tool(
+        description = "Get the all-in exchange rate for swapping a specific amount on Uniswap, e.g. '1 WETH = X USDC'. Simulates the swap and returns the rate including the AMM fee and price impact (excluding gas), the marginal spot rate for comparison, and both rates inverted."
+    )]
+    pub async fn effective_rate(
+        &self,
+        Parameters(input): Parameters<EffectiveRateInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            to = %input.to_token,
+            amount = %input.amount,
+            "effective_rate called"
+        );
+
+        let format = parse_response_format(input.format.as_deref())?;
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        let amount_in = parse_units(&input.amount, from_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        if amount_in == U256::ZERO {
+            return Err(McpError::invalid_params("Amount must be greater than zero", None));
+        }
+
+        let params = SwapParams {
+            from_token: from_entry.address,
+            to_token: to_entry.address,
+            amount_in,
+            slippage_tolerance: Decimal::new(5, 1),
+            from_address: input.from_address.as_deref().map(parse_address).transpose()?,
+            ..Default::default()
+        };
+
+        let result = self
+            .trade_cost_service
+            .effective_rate(params)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        if format == ResponseFormat::Text {
+            return Ok(tokens.respond_text(result));
+        }
+        tokens.respond(result)
+    }
+
+    /// Net out buying a token and selling it straight back.
+    ///
+    /// Simulates both legs and reports the gain or loss in the input token after
+    /// both legs' AMM fees, price impact and gas, optionally at a hypothetical
+    /// exit price.
+    #[tool(
+        description = "Evaluate whether a trade is worth it after all costs. Simulates buying to_token with an amount of from_token on Uniswap, then selling the proceeds back, and returns the net gain or loss in from_token and as a percentage, with a breakdown of both legs' AMM fees, price impact and gas. Pass exit_price (from_token per to_token) to evaluate the exit at a hypothetical future price; the default is the current price. Figures that cannot be computed, such as gas when from_token has no ETH price, are omitted with a note."
+    )]
+    pub async fn simulate_round_trip_pnl(
+        &self,
+        Parameters(input): Parameters<SimulateRoundTripPnlInput>,
+    ) -> Result<String, McpError> {
+        tracing::info!(
+            from = %input.from_token,
+            to = %input.to_token,
+            amount = %input.amount,
+            exit_price = ?input.exit_price,
+            "simulate_round_trip_pnl called"
+        );
+
+        let mut tokens = self.resolver(input.include_provenance).for_trade();
+        let (from_entry, to_entry) =
+            tokens.pair(("from_token", &input.from_token), ("to_token", &input.to_token)).await?;
+
+        let amount_in = parse_units(&input.amount, from_entry.decimals)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        if amount_in == U256::ZERO {
+            return Err(McpError::invalid_params("Amount must be greater than zero", None));
+        }
+
+        let exit_price = input
+            .exit_price
+            .as_ref()
+            .map(|s| {
+                Decimal::from_str(s.trim()).map_err(|e| {
+                    McpError::invalid_params(format!("Invalid exit_price: {}", e), None)
+                })
+            })
+            .transpose()?;
+
+        if exit_price.is_some_and(|price| price <= Decimal::ZERO) {
+            return Err(McpError::invalid_params("exit_price must be greater than zero", None));
+        }
+
+        let params = SwapParams {
+            from_token: from_entry.address,
+            to_token: to_entry.address,
+            amount_in,
+            slippage_tolerance: parse_slippage_tolerance(input.slippage_tolerance.as_deref())?,
+            from_address: input.from_address.as_deref().map(parse_address).transpose()?,
+            ..Default::default()
+        };
+
+        let result = self
+            .trade_cost_service
+            .round_trip_pnl(params, exit_price)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        tokens.respond(result)
+    }
+}
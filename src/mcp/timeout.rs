@@ -0,0 +1,135 @@
+//! Per-tool-call timeouts.
+//!
+//! Bounds the end-to-end latency of a tool call, which may chain several RPC
+//! requests, so a slow endpoint cannot hang the MCP client.
+
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use rmcp::ErrorData as McpError;
+
+use crate::{config::DEFAULT_TOOL_TIMEOUT_SECS, error::AppError};
+
+/// Timeout applied to each tool call, with optional per-tool overrides.
+#[derive(Debug, Clone)]
+pub struct ToolTimeouts {
+    /// Timeout for tools without an override (`None` disables it).
+    default: Option<Duration>,
+    /// Per-tool timeouts by tool name (`None` disables it for that tool).
+    overrides: HashMap<String, Option<Duration>>,
+}
+
+impl Default for ToolTimeouts {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_TOOL_TIMEOUT_SECS))
+    }
+}
+
+impl ToolTimeouts {
+    /// Create timeouts with a default of `timeout` (zero disables it).
+    pub fn new(timeout: Duration) -> Self {
+        Self { default: non_zero(timeout), overrides: HashMap::new() }
+    }
+
+    /// Override the timeout of `tool` (zero disables it for that tool).
+    pub fn with_override(mut self, tool: impl Into<String>, timeout: Duration) -> Self {
+        self.overrides.insert(tool.into(), non_zero(timeout));
+        self
+    }
+
+    /// Timeout for `tool`, if any.
+    pub fn for_tool(&self, tool: &str) -> Option<Duration> {
+        self.overrides.get(tool).copied().unwrap_or(self.default)
+    }
+
+    /// Run a call of `tool`, failing with a timeout error if it takes too long.
+    pub async fn run<T, F>(&self, tool: &str, call: F) -> Result<T, McpError>
+    where
+        F: Future<Output = Result<T, McpError>>,
+    {
+        let Some(timeout) = self.for_tool(tool) else {
+            return call.await;
+        };
+
+        match tokio::time::timeout(timeout, call).await {
+            Ok(result) => result,
+            Err(_) => {
+                tracing::warn!(tool = tool, timeout = ?timeout, "Tool call timed out");
+                Err(AppError::Timeout(format!(
+                    "{} did not complete within {:?}; the RPC endpoint may be slow or unreachable",
+                    tool, timeout
+                ))
+                .into())
+            }
+        }
+    }
+}
+
+fn non_zero(timeout: Duration) -> Option<Duration> {
+    (!timeout.is_zero()).then_some(timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_for_tool_uses_override() {
+        let timeouts = ToolTimeouts::new(secs(60)).with_override("swap_tokens", secs(120));
+
+        assert_eq!(timeouts.for_tool("swap_tokens"), Some(secs(120)));
+        assert_eq!(timeouts.for_tool("get_balance"), Some(secs(60)));
+    }
+
+    #[test]
+    fn test_zero_disables_timeout() {
+        let timeouts = ToolTimeouts::new(Duration::ZERO).with_override("get_balance", secs(10));
+        assert_eq!(timeouts.for_tool("swap_tokens"), None);
+        assert_eq!(timeouts.for_tool("get_balance"), Some(secs(10)));
+
+        let timeouts = ToolTimeouts::new(secs(60)).with_override("swap_tokens", Duration::ZERO);
+        assert_eq!(timeouts.for_tool("swap_tokens"), None);
+    }
+
+    #[tokio::test]
+    async fn test_slow_call_times_out() {
+        let timeouts = ToolTimeouts::new(Duration::from_millis(20));
+
+        let result = timeouts
+            .run("swap_tokens", async {
+                tokio::time::sleep(secs(30)).await;
+                Ok("done".to_string())
+            })
+            .await;
+
+        let err = result.unwrap_err();
+        assert!(
+            err.message.contains("swap_tokens did not complete within 20ms"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fast_call_completes() {
+        let timeouts = ToolTimeouts::new(secs(5));
+
+        let result = timeouts.run("get_balance", async { Ok("done".to_string()) }).await;
+
+        assert_eq!(result.unwrap(), "done");
+    }
+
+    #[tokio::test]
+    async fn test_call_error_is_preserved() {
+        let timeouts = ToolTimeouts::new(secs(5));
+
+        let result: Result<String, McpError> = timeouts
+            .run("get_balance", async { Err(McpError::invalid_params("bad input", None)) })
+            .await;
+
+        assert_eq!(result.unwrap_err().message, "bad input");
+    }
+}
@@ -1,27 +1,70 @@
 //! Balance query service.
 
-use alloy::primitives::Address;
-use std::sync::Arc;
+use alloy::primitives::{Address, U256};
+use std::{collections::HashSet, future::Future, sync::Arc};
+use tokio::{sync::Semaphore, task::JoinSet};
 
 use crate::{
-    error::Result,
+    config::DEFAULT_FAN_OUT_CONCURRENCY,
+    error::{AppError, Result},
     ethereum::{
-        contracts::erc20::{TokenMetadata, IERC20},
-        EthereumClient,
+        block_id,
+        contracts::{
+            erc1155::IERC1155,
+            erc20::{TokenMetadata, IERC20},
+        },
+        rebasing_tokens, EthereumClient, DEFAULT_CHAIN_ID,
+    },
+    types::{
+        format_units, BalanceInfo, Erc1155BalanceInfo, NonceInfo, TokenInfo, WalletInfo, Warning,
+        WarningCode,
     },
-    types::{format_units, BalanceInfo, TokenInfo},
 };
 
 /// Service for querying token balances.
 #[derive(Clone)]
 pub struct BalanceService {
     client: Arc<EthereumClient>,
+    /// Maximum concurrent balance lookups within one request.
+    fan_out_concurrency: usize,
+    /// Whether wallet info lists zero token balances by default.
+    include_zero_balances: bool,
+    /// Tokens whose balances change without transfers.
+    rebasing_tokens: HashSet<Address>,
 }
 
 impl BalanceService {
     /// Create a new balance service.
     pub fn new(client: Arc<EthereumClient>) -> Self {
-        Self { client }
+        Self {
+            client,
+            fan_out_concurrency: DEFAULT_FAN_OUT_CONCURRENCY,
+            include_zero_balances: false,
+            rebasing_tokens: rebasing_tokens(DEFAULT_CHAIN_ID).into_iter().collect(),
+        }
+    }
+
+    /// Set how many balance lookups one request may run concurrently.
+    pub fn with_fan_out_concurrency(mut self, limit: usize) -> Self {
+        self.fan_out_concurrency = limit.max(1);
+        self
+    }
+
+    /// Set whether wallet info lists zero token balances when the caller does not say.
+    pub fn with_include_zero_balances(mut self, include: bool) -> Self {
+        self.include_zero_balances = include;
+        self
+    }
+
+    /// Set the tokens flagged as rebasing.
+    pub fn with_rebasing_tokens(mut self, tokens: impl IntoIterator<Item = Address>) -> Self {
+        self.rebasing_tokens = tokens.into_iter().collect();
+        self
+    }
+
+    /// Whether `token` is a rebasing token.
+    pub fn is_rebasing(&self, token: Address) -> bool {
+        self.rebasing_tokens.contains(&token)
     }
 
     /// Get balance for an address.
@@ -33,17 +76,110 @@ impl BalanceService {
         address: Address,
         token_address: Option<Address>,
     ) -> Result<BalanceInfo> {
+        let block = self.client.confirmed_block().await?;
         match token_address {
-            None => self.get_eth_balance(address).await,
-            Some(token) => self.get_erc20_balance(address, token).await,
+            None => self.get_eth_balance(address, block).await,
+            Some(token) => self.get_erc20_balance(address, token, block).await,
         }
     }
 
-    /// Get native ETH balance.
-    async fn get_eth_balance(&self, address: Address) -> Result<BalanceInfo> {
-        tracing::debug!(address = %address, "Querying ETH balance");
+    /// Get the wallet address and, optionally, its ETH and token balances.
+    ///
+    /// Zero token balances are dropped and counted unless `include_zero_balances`
+    /// (or the service default, when `None`) asks for them.
+    pub async fn get_wallet_info(
+        &self,
+        wallet: Address,
+        include_eth_balance: bool,
+        tokens: &[Address],
+        include_zero_balances: Option<bool>,
+    ) -> Result<WalletInfo> {
+        // Read every balance at the same block
+        let block = if include_eth_balance || !tokens.is_empty() {
+            self.client.confirmed_block().await?
+        } else {
+            None
+        };
+
+        let eth_balance = if include_eth_balance {
+            Some(self.get_eth_balance(wallet, block).await?)
+        } else {
+            None
+        };
+
+        let token_balances = fan_out(self.fan_out_concurrency, tokens.to_vec(), |token| {
+            let service = self.clone();
+            async move { service.get_erc20_balance(wallet, token, block).await }
+        })
+        .await?;
+
+        let (token_balances, zero_balance_tokens_skipped) =
+            if include_zero_balances.unwrap_or(self.include_zero_balances) {
+                (token_balances, 0)
+            } else {
+                drop_zero_balances(token_balances)
+            };
+
+        Ok(WalletInfo {
+            configured: true,
+            address: Some(wallet.to_checksum(None)),
+            eth_balance,
+            token_balances,
+            zero_balance_tokens_skipped,
+            base_currency: None,
+            total_value: None,
+            unpriced_tokens: Vec::new(),
+            warnings: Vec::new(),
+            message: None,
+        })
+    }
+
+    /// Get the balance of one ERC1155 token ID.
+    pub async fn get_erc1155_balance(
+        &self,
+        address: Address,
+        token: Address,
+        token_id: U256,
+    ) -> Result<Erc1155BalanceInfo> {
+        tracing::debug!(
+            address = %address,
+            token = %token,
+            token_id = %token_id,
+            "Querying ERC1155 balance"
+        );
+
+        let block = self.client.confirmed_block().await?;
+        let contract = IERC1155::new(token, self.client.provider().clone());
+        let balance = contract.balanceOf(address, token_id).block(block_id(block)).call().await?;
+
+        Ok(Erc1155BalanceInfo {
+            address: format!("{address:?}"),
+            token_address: format!("{token:?}"),
+            token_id: token_id.to_string(),
+            balance_raw: balance.to_string(),
+            block_number: block,
+        })
+    }
+
+    /// Get the confirmed and pending nonces of an address.
+    ///
+    /// Always read at the latest block, regardless of `ETHEREUM_CONFIRMATIONS`,
+    /// since the gap to the pending nonce is what shows transactions in flight.
+    pub async fn get_nonce(&self, address: Address) -> Result<NonceInfo> {
+        tracing::debug!(address = %address, "Querying nonces");
+
+        let (latest, pending) = tokio::try_join!(
+            self.client.get_latest_nonce(address),
+            self.client.get_pending_nonce(address)
+        )?;
+        Ok(NonceInfo::new(address, latest, pending))
+    }
+
+    /// Get native ETH balance at `block` (`None` for the latest block).
+    async fn get_eth_balance(&self, address: Address, block: Option<u64>) -> Result<BalanceInfo> {
+        tracing::debug!(address = %address, block = ?block, "Querying ETH balance");
 
-        let balance = self.client.get_eth_balance(address).await?;
+        let balance = self.client.get_eth_balance_at(address, block).await?;
         let formatted = format_units(balance, 18);
 
         Ok(BalanceInfo {
@@ -51,23 +187,42 @@ impl BalanceService {
             token: TokenInfo::eth(),
             balance: formatted,
             balance_raw: balance.to_string(),
+            block_number: block,
+            value: None,
+            warnings: Vec::new(),
         })
     }
 
-    /// Get ERC20 token balance.
-    async fn get_erc20_balance(&self, address: Address, token: Address) -> Result<BalanceInfo> {
+    /// Get ERC20 token balance at `block` (`None` for the latest block).
+    async fn get_erc20_balance(
+        &self,
+        address: Address,
+        token: Address,
+        block: Option<u64>,
+    ) -> Result<BalanceInfo> {
         tracing::debug!(
             address = %address,
             token = %token,
+            block = ?block,
             "Querying ERC20 balance"
         );
 
         // Get token metadata
-        let metadata = self.get_token_metadata(token).await?;
+        let (metadata, mut warnings) = self.read_token_metadata(token).await;
+        if self.is_rebasing(token) {
+            warnings.push(Warning::new(
+                WarningCode::RebasingToken,
+                format!(
+                    "{} is a rebasing token: its balance changes without transfers, so this \
+                     amount may differ on the next read",
+                    metadata.symbol
+                ),
+            ));
+        }
 
         // Get balance - balanceOf returns U256 directly
         let contract = IERC20::new(token, self.client.provider().clone());
-        let balance = contract.balanceOf(address).call().await?;
+        let balance = contract.balanceOf(address).block(block_id(block)).call().await?;
 
         let formatted = format_units(balance, metadata.decimals);
 
@@ -76,31 +231,131 @@ impl BalanceService {
             token: TokenInfo::erc20(token, metadata.symbol, metadata.decimals),
             balance: formatted,
             balance_raw: balance.to_string(),
+            block_number: block,
+            value: None,
+            warnings,
         })
     }
 
     /// Get token metadata (symbol, decimals).
     pub async fn get_token_metadata(&self, token: Address) -> Result<TokenMetadata> {
+        Ok(self.read_token_metadata(token).await.0)
+    }
+
+    /// Read token metadata, with a warning for each default used in place of
+    /// a failed call.
+    async fn read_token_metadata(&self, token: Address) -> (TokenMetadata, Vec<Warning>) {
         let contract = IERC20::new(token, self.client.provider().clone());
+        let mut warnings = Vec::new();
+        let mut fallback = |field: &str, default: &str| {
+            warnings.push(Warning::new(
+                WarningCode::TokenMetadataFallback,
+                format!("{}() call failed on {:?}; assuming {}", field, token, default),
+            ));
+        };
 
         // Get symbol - returns String directly
-        let symbol = contract.symbol().call().await.unwrap_or_else(|_| "UNKNOWN".to_string());
+        let symbol = contract.symbol().call().await.unwrap_or_else(|_| {
+            fallback("symbol", "UNKNOWN");
+            "UNKNOWN".to_string()
+        });
 
         // Get name - returns String directly
         let name = contract.name().call().await.unwrap_or_else(|_| "Unknown Token".to_string());
 
         // Get decimals - returns u8 directly
-        let decimals = contract.decimals().call().await.unwrap_or(18);
+        let decimals = contract.decimals().call().await.ok();
+        if decimals.is_none() {
+            fallback("decimals", "18");
+        }
+
+        let metadata = TokenMetadata {
+            name,
+            symbol,
+            decimals: decimals.unwrap_or(18),
+            decimals_known: decimals.is_some(),
+            address: token,
+        };
+        (metadata, warnings)
+    }
+}
+
+/// Remove zero balances, returning the rest and how many were removed.
+fn drop_zero_balances(balances: Vec<BalanceInfo>) -> (Vec<BalanceInfo>, usize) {
+    let count = balances.len();
+    let balances: Vec<_> = balances.into_iter().filter(|b| b.balance_raw != "0").collect();
+    let skipped = count - balances.len();
+    (balances, skipped)
+}
+
+/// Run `task` on every item with at most `limit` tasks in flight.
+///
+/// The semaphore is created per call, so one large request cannot take more
+/// than `limit` slots. Results keep the order of `items`; the first error wins.
+pub(crate) async fn fan_out<I, T, F, Fut>(limit: usize, items: Vec<I>, task: F) -> Result<Vec<T>>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    fan_out_with_progress(limit, items, task, |_, _| {}).await
+}
+
+/// [`fan_out`], calling `on_progress(done, total)` as each task completes.
+pub(crate) async fn fan_out_with_progress<I, T, F, Fut>(
+    limit: usize,
+    items: Vec<I>,
+    task: F,
+    on_progress: impl Fn(usize, usize),
+) -> Result<Vec<T>>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(limit.max(1)));
+    let mut tasks = JoinSet::new();
+    let count = items.len();
+    for (index, item) in items.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let future = task(item);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (index, future.await)
+        });
+    }
 
-        Ok(TokenMetadata { name, symbol, decimals, address: token })
+    let mut results: Vec<Option<T>> = std::iter::repeat_with(|| None).take(count).collect();
+    let mut done = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result) =
+            joined.map_err(|e| AppError::Rpc(format!("Fan-out task failed: {}", e)))?;
+        results[index] = Some(result?);
+        done += 1;
+        on_progress(done, count);
     }
+    Ok(results.into_iter().flatten().collect())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ethereum::constants::USDC_ADDRESS;
-    use alloy::primitives::U256;
+    use alloy::transports::mock::Asserter;
+
+    #[tokio::test]
+    async fn test_get_balance_reports_confirmed_block() {
+        let asserter = Asserter::new();
+        let client = EthereumClient::mocked(asserter.clone()).with_confirmations(3);
+        let service = BalanceService::new(Arc::new(client));
+
+        asserter.push_success(&"0x64");
+        asserter.push_success(&"0xde0b6b3a7640000");
+        let balance = service.get_balance(Address::repeat_byte(1), None).await.unwrap();
+
+        assert_eq!(balance.block_number, Some(97));
+        assert_eq!(balance.balance, "1");
+    }
 
     #[test]
     fn test_token_info_eth() {
@@ -128,4 +383,97 @@ mod tests {
         let formatted_usdc = format_units(balance_usdc, 6);
         assert_eq!(formatted_usdc, "1.5");
     }
+
+    #[tokio::test]
+    async fn test_wallet_info_address_only() {
+        let client = Arc::new(EthereumClient::new("http://localhost:8545").unwrap());
+        let service = BalanceService::new(client);
+        let wallet: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse().unwrap();
+
+        // No balances requested, so no RPC calls are made
+        let info = service.get_wallet_info(wallet, false, &[], None).await.unwrap();
+
+        assert!(info.configured);
+        assert_eq!(info.address.as_deref(), Some("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"));
+        assert!(info.eth_balance.is_none());
+        assert!(info.token_balances.is_empty());
+        assert_eq!(info.zero_balance_tokens_skipped, 0);
+        assert!(info.message.is_none());
+    }
+
+    #[test]
+    fn test_drop_zero_balances() {
+        let balance = |symbol: &str, raw: &str| BalanceInfo {
+            address: "0xwallet".to_string(),
+            token: TokenInfo::erc20(USDC_ADDRESS, symbol.to_string(), 6),
+            balance: format_units(U256::from_str_radix(raw, 10).unwrap(), 6),
+            balance_raw: raw.to_string(),
+            block_number: None,
+            value: None,
+            warnings: Vec::new(),
+        };
+
+        let (kept, skipped) =
+            drop_zero_balances(vec![balance("A", "0"), balance("B", "1500000"), balance("C", "0")]);
+
+        assert_eq!(skipped, 2);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].token.symbol, "B");
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_respects_limit_and_order() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let results = fan_out(2, (0..8u64).collect(), |i| {
+            let in_flight = in_flight.clone();
+            let peak = peak.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                // Finish in reverse order to check results are reordered
+                tokio::time::sleep(std::time::Duration::from_millis(20 - 2 * i)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(i * 10)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(results, vec![0, 10, 20, 30, 40, 50, 60, 70]);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_reports_progress() {
+        let reported = std::sync::Mutex::new(Vec::new());
+        let results = fan_out_with_progress(
+            2,
+            vec![1u64, 2, 3],
+            |i| async move { Ok(i) },
+            |done, total| reported.lock().unwrap().push((done, total)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results, vec![1, 2, 3]);
+        assert_eq!(reported.into_inner().unwrap(), vec![(1, 3), (2, 3), (3, 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_propagates_error() {
+        let result = fan_out(4, vec![1u64, 2, 3], |i| async move {
+            if i == 2 {
+                Err(AppError::Rpc("boom".to_string()))
+            } else {
+                Ok(i)
+            }
+        })
+        .await;
+
+        assert!(matches!(result, Err(AppError::Rpc(_))));
+    }
 }
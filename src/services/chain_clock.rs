@@ -0,0 +1,73 @@
+//! Chain clock service.
+//!
+//! Relates the chain's block timestamps to the server's clock and to deadlines.
+
+use std::{sync::Arc, time::SystemTime};
+
+use crate::{
+    error::Result,
+    ethereum::{EthereumClient, SECONDS_PER_BLOCK},
+    types::{DeadlineEstimate, RpcFreshness},
+};
+
+/// Lag behind the server's clock, in seconds, above which the RPC node is
+/// reported as lagging (5 blocks).
+pub const MAX_RPC_LAG_SECS: u64 = 60;
+
+/// Service for reading the chain's clock.
+#[derive(Clone)]
+pub struct ChainClockService {
+    client: Arc<EthereumClient>,
+    /// Average block time of the chain, in seconds.
+    seconds_per_block: u64,
+}
+
+impl ChainClockService {
+    /// Create a new chain clock service.
+    pub fn new(client: Arc<EthereumClient>) -> Self {
+        Self { client, seconds_per_block: SECONDS_PER_BLOCK }
+    }
+
+    /// Set the chain's average block time in seconds.
+    pub fn with_seconds_per_block(mut self, secs: u64) -> Self {
+        self.seconds_per_block = secs.max(1);
+        self
+    }
+
+    /// Estimate how many blocks remain before `deadline` (Unix seconds).
+    pub async fn estimate_deadline(&self, deadline: u64) -> Result<DeadlineEstimate> {
+        let (latest_block, block_timestamp) =
+            tokio::try_join!(self.client.get_block_number(), self.client.get_block_timestamp())?;
+        Ok(DeadlineEstimate::new(deadline, latest_block, block_timestamp, self.seconds_per_block))
+    }
+
+    /// Compare the node's latest block timestamp with the server's clock.
+    pub async fn rpc_freshness(&self) -> Result<RpcFreshness> {
+        let (chain_id, latest_block, block_timestamp) = tokio::try_join!(
+            self.client.chain_id(),
+            self.client.get_block_number(),
+            self.client.get_block_timestamp()
+        )?;
+        let server_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let freshness = RpcFreshness::new(
+            chain_id,
+            latest_block,
+            block_timestamp,
+            server_time,
+            self.seconds_per_block,
+            MAX_RPC_LAG_SECS,
+        );
+        if freshness.lagging {
+            tracing::warn!(
+                lag_secs = freshness.lag_secs,
+                block = latest_block,
+                "RPC node is lagging"
+            );
+        }
+        Ok(freshness)
+    }
+}
@@ -0,0 +1,453 @@
+//! Liquidity service.
+//!
+//! Simulates adding liquidity to and removing it from a Uniswap V2 pair from its
+//! reserves and LP token supply, and builds the unsigned `addLiquidity` and
+//! `removeLiquidity` router calls.
+
+use alloy::{
+    primitives::{Address, U256},
+    sol_types::SolCall,
+};
+use std::{sync::Arc, time::SystemTime};
+
+use crate::{
+    error::{AppError, Result},
+    ethereum::{
+        contracts::uniswap_v2::{IUniswapV2Factory, IUniswapV2Pair, IUniswapV2Router02},
+        EthereumClient, UniswapAddresses,
+    },
+    services::{BalanceService, SwapService},
+    types::{
+        format_units, AddLiquidityParams, AddLiquiditySimulation, RemoveLiquidityParams,
+        RemoveLiquiditySimulation, TokenInfo, TransactionData, Warning, WarningCode,
+    },
+};
+
+/// LP tokens burned by a pair's first deposit (`MINIMUM_LIQUIDITY` in the pair contract).
+const MINIMUM_LIQUIDITY: u64 = 1_000;
+
+/// Decimals of Uniswap V2 LP tokens.
+const LP_TOKEN_DECIMALS: u8 = 18;
+
+/// Decimals of the reported share of pool, in percent.
+const SHARE_DECIMALS: u8 = 6;
+
+/// Get current Unix timestamp.
+fn current_timestamp() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// A V2 pair's reserves, ordered as the caller's token pair, and LP token supply.
+#[derive(Debug, Clone, Copy)]
+struct PairState {
+    /// Pair address (`None` if the pair does not exist).
+    pair: Option<Address>,
+    reserve_a: U256,
+    reserve_b: U256,
+    total_supply: U256,
+}
+
+/// Service for simulating liquidity provision.
+#[derive(Clone)]
+pub struct LiquidityService {
+    client: Arc<EthereumClient>,
+    balance_service: BalanceService,
+    /// Uniswap contracts pairs are read from and liquidity calls are sent to.
+    uniswap: UniswapAddresses,
+}
+
+impl LiquidityService {
+    /// Create a new liquidity service.
+    pub fn new(client: Arc<EthereumClient>, balance_service: BalanceService) -> Self {
+        Self { client, balance_service, uniswap: UniswapAddresses::default() }
+    }
+
+    /// Set the Uniswap contracts pairs are read from and liquidity calls are sent to.
+    pub fn with_uniswap_addresses(mut self, uniswap: UniswapAddresses) -> Self {
+        self.uniswap = uniswap;
+        self
+    }
+
+    /// Simulate adding liquidity to the V2 pair of `params.token_a` and `params.token_b`.
+    ///
+    /// Follows the router's math: the desired amounts are scaled down to the
+    /// pair's current ratio, and the LP tokens minted are the smaller share of
+    /// either reserve (or `sqrt(a * b) - MINIMUM_LIQUIDITY` for a new pair).
+    /// Nothing is sent or simulated on chain.
+    pub async fn simulate_add_liquidity(
+        &self,
+        params: AddLiquidityParams,
+    ) -> Result<AddLiquiditySimulation> {
+        let PairState { pair, reserve_a, reserve_b, total_supply } =
+            self.pair_state(params.token_a, params.token_b).await?;
+
+        let mut warnings = Vec::new();
+        if total_supply.is_zero() {
+            warnings.push(Warning::new(
+                WarningCode::NewPair,
+                "The pair has no liquidity yet: this deposit sets its price, and 1000 LP token \
+                 units are burned",
+            ));
+        }
+
+        let (amount_a, amount_b) = optimal_amounts(
+            params.amount_a_desired,
+            params.amount_b_desired,
+            reserve_a,
+            reserve_b,
+        )?;
+        let liquidity = liquidity_minted(amount_a, amount_b, reserve_a, reserve_b, total_supply)?;
+        let amount_a_min = SwapService::minimum_amount_out(amount_a, params.slippage_tolerance)?;
+        let amount_b_min = SwapService::minimum_amount_out(amount_b, params.slippage_tolerance)?;
+
+        let deadline = params.deadline.unwrap_or_else(|| current_timestamp() + 1200); // 20 minutes
+        let calldata = IUniswapV2Router02::addLiquidityCall {
+            tokenA: params.token_a,
+            tokenB: params.token_b,
+            amountADesired: params.amount_a_desired,
+            amountBDesired: params.amount_b_desired,
+            amountAMin: amount_a_min,
+            amountBMin: amount_b_min,
+            to: params.recipient,
+            deadline: U256::from(deadline),
+        }
+        .abi_encode();
+
+        let token_a = self.token_info(params.token_a).await?;
+        let token_b = self.token_info(params.token_b).await?;
+
+        Ok(AddLiquiditySimulation {
+            pair: pair.map(|pair| format!("{:?}", pair)),
+            reserve_a: format_units(reserve_a, token_a.decimals),
+            reserve_b: format_units(reserve_b, token_b.decimals),
+            amount_a: format_units(amount_a, token_a.decimals),
+            amount_b: format_units(amount_b, token_b.decimals),
+            amount_a_min: format_units(amount_a_min, token_a.decimals),
+            amount_b_min: format_units(amount_b_min, token_b.decimals),
+            lp_tokens: format_units(liquidity, LP_TOKEN_DECIMALS),
+            total_supply: format_units(total_supply, LP_TOKEN_DECIMALS),
+            share_of_pool_percent: format_units(
+                share_of_pool(liquidity, total_supply),
+                SHARE_DECIMALS,
+            ),
+            transaction: TransactionData {
+                to: format!("{:?}", self.uniswap.v2_router),
+                data: format!("0x{}", alloy::hex::encode(calldata)),
+                value: "0".to_string(),
+                gas_limit: None,
+            },
+            token_a,
+            token_b,
+            warnings,
+        })
+    }
+
+    /// Simulate removing liquidity from the V2 pair of `params.token_a` and `params.token_b`.
+    ///
+    /// Burning LP tokens returns the same share of each reserve (`burn` in the
+    /// V2 pair). The withdrawn tokens go to the owner. Nothing is sent or
+    /// simulated on chain.
+    pub async fn simulate_remove_liquidity(
+        &self,
+        params: RemoveLiquidityParams,
+    ) -> Result<RemoveLiquiditySimulation> {
+        let PairState { pair, reserve_a, reserve_b, total_supply } =
+            self.pair_state(params.token_a, params.token_b).await?;
+        let Some(pair) = pair.filter(|_| !total_supply.is_zero()) else {
+            return Err(AppError::PoolNotFound);
+        };
+
+        let contract = IUniswapV2Pair::new(pair, self.client.provider().clone());
+        let lp_balance = contract.balanceOf(params.owner).call().await?;
+        let liquidity = params.liquidity.unwrap_or(lp_balance);
+        if liquidity.is_zero() {
+            return Err(AppError::Wallet(format!(
+                "{} holds no LP tokens of pair {}",
+                params.owner, pair
+            )));
+        }
+
+        let mut warnings = Vec::new();
+        if liquidity > lp_balance {
+            warnings.push(Warning::new(
+                WarningCode::InsufficientBalance,
+                format!(
+                    "The owner holds {} LP tokens, fewer than the {} burned; the transaction \
+                     would revert",
+                    format_units(lp_balance, LP_TOKEN_DECIMALS),
+                    format_units(liquidity, LP_TOKEN_DECIMALS)
+                ),
+            ));
+        }
+
+        let (amount_a, amount_b) =
+            withdrawn_amounts(liquidity, reserve_a, reserve_b, total_supply)?;
+        let amount_a_min = SwapService::minimum_amount_out(amount_a, params.slippage_tolerance)?;
+        let amount_b_min = SwapService::minimum_amount_out(amount_b, params.slippage_tolerance)?;
+
+        let deadline = params.deadline.unwrap_or_else(|| current_timestamp() + 1200); // 20 minutes
+        let calldata = IUniswapV2Router02::removeLiquidityCall {
+            tokenA: params.token_a,
+            tokenB: params.token_b,
+            liquidity,
+            amountAMin: amount_a_min,
+            amountBMin: amount_b_min,
+            to: params.owner,
+            deadline: U256::from(deadline),
+        }
+        .abi_encode();
+
+        let token_a = self.token_info(params.token_a).await?;
+        let token_b = self.token_info(params.token_b).await?;
+
+        Ok(RemoveLiquiditySimulation {
+            pair: format!("{:?}", pair),
+            reserve_a: format_units(reserve_a, token_a.decimals),
+            reserve_b: format_units(reserve_b, token_b.decimals),
+            lp_tokens: format_units(liquidity, LP_TOKEN_DECIMALS),
+            lp_balance: format_units(lp_balance, LP_TOKEN_DECIMALS),
+            total_supply: format_units(total_supply, LP_TOKEN_DECIMALS),
+            share_of_pool_percent: format_units(
+                percent_of(liquidity, total_supply),
+                SHARE_DECIMALS,
+            ),
+            amount_a: format_units(amount_a, token_a.decimals),
+            amount_b: format_units(amount_b, token_b.decimals),
+            amount_a_min: format_units(amount_a_min, token_a.decimals),
+            amount_b_min: format_units(amount_b_min, token_b.decimals),
+            transaction: TransactionData {
+                to: format!("{:?}", self.uniswap.v2_router),
+                data: format!("0x{}", alloy::hex::encode(calldata)),
+                value: "0".to_string(),
+                gas_limit: None,
+            },
+            token_a,
+            token_b,
+            warnings,
+        })
+    }
+
+    /// Read the reserves and LP token supply of the V2 pair of `token_a` and `token_b`.
+    ///
+    /// A pair that does not exist has no reserves and no supply.
+    async fn pair_state(&self, token_a: Address, token_b: Address) -> Result<PairState> {
+        let provider = self.client.provider().clone();
+        let factory = IUniswapV2Factory::new(self.uniswap.v2_factory, provider.clone());
+        let pair: Address = factory.getPair(token_a, token_b).call().await?;
+        if pair == Address::ZERO {
+            return Ok(PairState {
+                pair: None,
+                reserve_a: U256::ZERO,
+                reserve_b: U256::ZERO,
+                total_supply: U256::ZERO,
+            });
+        }
+
+        let contract = IUniswapV2Pair::new(pair, provider);
+        let reserves = contract.getReserves().call().await?;
+        let total_supply = contract.totalSupply().call().await?;
+        let (reserve0, reserve1) = (U256::from(reserves.reserve0), U256::from(reserves.reserve1));
+        let (reserve_a, reserve_b) =
+            if token_a < token_b { (reserve0, reserve1) } else { (reserve1, reserve0) };
+        Ok(PairState { pair: Some(pair), reserve_a, reserve_b, total_supply })
+    }
+
+    /// Token address, symbol and decimals from the token contract.
+    async fn token_info(&self, token: Address) -> Result<TokenInfo> {
+        let metadata = self.balance_service.get_token_metadata(token).await?;
+        Ok(TokenInfo::erc20(token, metadata.symbol, metadata.decimals))
+    }
+}
+
+/// Amounts the router deposits: the desired amounts, with one of them lowered
+/// to match the pair's current ratio (`_addLiquidity` in the V2 router).
+fn optimal_amounts(
+    desired_a: U256,
+    desired_b: U256,
+    reserve_a: U256,
+    reserve_b: U256,
+) -> Result<(U256, U256)> {
+    if reserve_a.is_zero() && reserve_b.is_zero() {
+        return Ok((desired_a, desired_b));
+    }
+    if reserve_a.is_zero() || reserve_b.is_zero() {
+        return Err(AppError::InsufficientLiquidity);
+    }
+
+    let overflow = || AppError::NumericOverflow("Liquidity amounts too large".to_string());
+    let optimal_b = desired_a.checked_mul(reserve_b).ok_or_else(overflow)? / reserve_a;
+    if optimal_b <= desired_b {
+        return Ok((desired_a, optimal_b));
+    }
+    let optimal_a = desired_b.checked_mul(reserve_a).ok_or_else(overflow)? / reserve_b;
+    Ok((optimal_a, desired_b))
+}
+
+/// LP tokens minted for depositing `amount_a` and `amount_b` (`mint` in the V2 pair).
+///
+/// Ignores the protocol fee the pair mints first when the factory's fee switch is on.
+fn liquidity_minted(
+    amount_a: U256,
+    amount_b: U256,
+    reserve_a: U256,
+    reserve_b: U256,
+    total_supply: U256,
+) -> Result<U256> {
+    let overflow = || AppError::NumericOverflow("Liquidity amounts too large".to_string());
+    let liquidity = if total_supply.is_zero() {
+        amount_a
+            .checked_mul(amount_b)
+            .ok_or_else(overflow)?
+            .root(2)
+            .saturating_sub(U256::from(MINIMUM_LIQUIDITY))
+    } else {
+        let from_a = amount_a.checked_mul(total_supply).ok_or_else(overflow)? / reserve_a;
+        let from_b = amount_b.checked_mul(total_supply).ok_or_else(overflow)? / reserve_b;
+        from_a.min(from_b)
+    };
+
+    if liquidity.is_zero() {
+        return Err(AppError::InsufficientLiquidity);
+    }
+    Ok(liquidity)
+}
+
+/// Share of the pair held by `liquidity` newly minted LP tokens, in percent
+/// scaled by `10^SHARE_DECIMALS`.
+fn share_of_pool(liquidity: U256, total_supply: U256) -> U256 {
+    // A first deposit also mints MINIMUM_LIQUIDITY to the zero address
+    let burned = if total_supply.is_zero() { U256::from(MINIMUM_LIQUIDITY) } else { U256::ZERO };
+    percent_of(liquidity, total_supply + burned + liquidity)
+}
+
+/// Tokens returned for burning `liquidity` LP tokens (`burn` in the V2 pair).
+///
+/// Uses the reserves; tokens sent to the pair since its last sync are also paid
+/// out, so the actual amounts can be slightly higher.
+fn withdrawn_amounts(
+    liquidity: U256,
+    reserve_a: U256,
+    reserve_b: U256,
+    total_supply: U256,
+) -> Result<(U256, U256)> {
+    if total_supply.is_zero() {
+        return Err(AppError::InsufficientLiquidity);
+    }
+    let overflow = || AppError::NumericOverflow("Liquidity amounts too large".to_string());
+    let amount_a = liquidity.checked_mul(reserve_a).ok_or_else(overflow)? / total_supply;
+    let amount_b = liquidity.checked_mul(reserve_b).ok_or_else(overflow)? / total_supply;
+    if amount_a.is_zero() || amount_b.is_zero() {
+        return Err(AppError::InsufficientLiquidity);
+    }
+    Ok((amount_a, amount_b))
+}
+
+/// `part / whole` in percent, scaled by `10^SHARE_DECIMALS` and rounded down.
+fn percent_of(part: U256, whole: U256) -> U256 {
+    if whole.is_zero() {
+        return U256::ZERO;
+    }
+    let scale = U256::from(100u8) * U256::from(10u8).pow(U256::from(SHARE_DECIMALS));
+    part.saturating_mul(scale) / whole
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units(value: u64, decimals: u8) -> U256 {
+        U256::from(value) * U256::from(10u8).pow(U256::from(decimals))
+    }
+
+    #[test]
+    fn test_optimal_amounts_follows_reserve_ratio() {
+        // 1 WETH : 2000 USDC
+        let reserve_a = units(1_000, 18);
+        let reserve_b = units(2_000_000, 6);
+
+        // Too much USDC: keep all the WETH
+        let (a, b) = optimal_amounts(units(1, 18), units(2_500, 6), reserve_a, reserve_b).unwrap();
+        assert_eq!((a, b), (units(1, 18), units(2_000, 6)));
+
+        // Too much WETH: keep all the USDC
+        let (a, b) = optimal_amounts(units(2, 18), units(1_000, 6), reserve_a, reserve_b).unwrap();
+        assert_eq!((a, b), (units(1, 18) / U256::from(2), units(1_000, 6)));
+
+        // An empty pair takes the desired amounts as they are
+        let (a, b) = optimal_amounts(units(1, 18), units(5, 6), U256::ZERO, U256::ZERO).unwrap();
+        assert_eq!((a, b), (units(1, 18), units(5, 6)));
+
+        assert!(matches!(
+            optimal_amounts(units(1, 18), units(5, 6), U256::ZERO, reserve_b),
+            Err(AppError::InsufficientLiquidity)
+        ));
+    }
+
+    #[test]
+    fn test_liquidity_minted() {
+        // Depositing 1% of each reserve mints 1% of the supply
+        let supply = units(40, 18);
+        let minted = liquidity_minted(
+            units(10, 18),
+            units(20_000, 6),
+            units(1_000, 18),
+            units(2_000_000, 6),
+            supply,
+        )
+        .unwrap();
+        assert_eq!(minted, supply / U256::from(100));
+        assert_eq!(format_units(share_of_pool(minted, supply), SHARE_DECIMALS), "0.990099");
+
+        // The smaller side decides
+        let minted = liquidity_minted(
+            units(10, 18),
+            units(10_000, 6),
+            units(1_000, 18),
+            units(2_000_000, 6),
+            supply,
+        )
+        .unwrap();
+        assert_eq!(minted, supply / U256::from(200));
+    }
+
+    #[test]
+    fn test_withdrawn_amounts() {
+        // Burning 1% of the supply returns 1% of each reserve
+        let supply = units(40, 18);
+        let (a, b) = withdrawn_amounts(
+            supply / U256::from(100),
+            units(1_000, 18),
+            units(2_000_000, 6),
+            supply,
+        )
+        .unwrap();
+        assert_eq!((a, b), (units(10, 18), units(20_000, 6)));
+        assert_eq!(format_units(percent_of(supply / U256::from(100), supply), SHARE_DECIMALS), "1");
+
+        // Too little to return a whole unit of either token
+        assert!(matches!(
+            withdrawn_amounts(U256::from(1), units(1, 6), units(1, 6), units(40, 18)),
+            Err(AppError::InsufficientLiquidity)
+        ));
+        assert!(matches!(
+            withdrawn_amounts(U256::from(1), U256::ZERO, U256::ZERO, U256::ZERO),
+            Err(AppError::InsufficientLiquidity)
+        ));
+    }
+
+    #[test]
+    fn test_liquidity_minted_first_deposit() {
+        // sqrt(4e18 * 1e18) = 2e18, minus the burned minimum
+        let minted =
+            liquidity_minted(units(4, 18), units(1, 18), U256::ZERO, U256::ZERO, U256::ZERO)
+                .unwrap();
+        assert_eq!(minted, units(2, 18) - U256::from(MINIMUM_LIQUIDITY));
+        assert_eq!(format_units(share_of_pool(minted, U256::ZERO), SHARE_DECIMALS), "99.999999");
+
+        // Too small to cover the burned minimum
+        assert!(matches!(
+            liquidity_minted(U256::from(10), U256::from(10), U256::ZERO, U256::ZERO, U256::ZERO),
+            Err(AppError::InsufficientLiquidity)
+        ));
+    }
+}
@@ -1,11 +1,21 @@
 //! Business logic services module.
 
 pub mod balance;
+pub mod chain_clock;
+pub mod liquidity;
+pub mod pool_activity;
 pub mod price;
 pub mod swap;
 pub mod token_registry;
+pub mod token_verification;
+pub mod trade_cost;
 
 pub use balance::BalanceService;
-pub use price::PriceService;
+pub use chain_clock::ChainClockService;
+pub use liquidity::LiquidityService;
+pub use pool_activity::PoolActivityService;
+pub use price::{AnswerSign, PriceService};
 pub use swap::SwapService;
-pub use token_registry::{TokenEntry, TokenRegistry, TokenRegistryTrait};
+pub use token_registry::{TokenEntry, TokenRegistry, TokenRegistryTrait, TokenSource};
+pub use token_verification::TokenVerificationService;
+pub use trade_cost::TradeCostService;
@@ -0,0 +1,654 @@
+//! Pool activity service.
+//!
+//! Reads recent `Swap` events of a Uniswap V2 pair or V3 pool via `eth_getLogs`,
+//! estimates a pool's fee yield from them, and lists the pools of a token pair
+//! with their trading fees.
+
+use alloy::{
+    primitives::{aliases::U24, Address, U256},
+    rpc::types::{Filter, Log},
+    sol_types::SolEvent,
+};
+use rust_decimal::Decimal;
+use std::{str::FromStr, sync::Arc};
+
+use crate::{
+    error::{AppError, Result},
+    ethereum::{
+        contracts::{
+            erc20::IERC20,
+            uniswap_v2::{IUniswapV2Factory, IUniswapV2Pair},
+            uniswap_v3::{fee_tiers, IUniswapV3Factory, IUniswapV3Pool},
+        },
+        price_math::{PriceRatio, PRICE_DECIMALS, PRICE_SIGNIFICANT_DIGITS},
+        EthereumClient, UniswapAddresses, SECONDS_PER_BLOCK,
+    },
+    services::{swap::v2_deployment, BalanceService},
+    types::{
+        format_units, PairPoolFees, PoolFee, PoolFeeApr, PoolSwap, RecentPoolSwaps, SwapDirection,
+        TokenInfo, UniswapVersion, Venue, V2_POOL_FEE_PPM,
+    },
+};
+
+/// Default number of recent blocks searched for swaps.
+pub const DEFAULT_SWAP_LOOKBACK_BLOCKS: u64 = 1_000;
+
+/// Maximum number of recent blocks searched for swaps.
+pub const MAX_SWAP_LOOKBACK_BLOCKS: u64 = 10_000;
+
+/// Blocks requested per `eth_getLogs` call before any provider limit is hit.
+const LOG_CHUNK_BLOCKS: u64 = 2_000;
+
+/// Smallest chunk tried when the provider rejects a block range.
+const MIN_LOG_CHUNK_BLOCKS: u64 = 50;
+
+/// Default number of recent blocks a fee APR is estimated from (about a day).
+pub const DEFAULT_FEE_APR_LOOKBACK_BLOCKS: u64 = 7_200;
+
+/// Maximum number of recent blocks a fee APR is estimated from (about a week).
+pub const MAX_FEE_APR_LOOKBACK_BLOCKS: u64 = 50_400;
+
+/// Seconds in a 365-day year.
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// A pool's protocol, fee and tokens.
+#[derive(Debug, Clone, Copy)]
+struct PoolInfo {
+    venue: Venue,
+    protocol: UniswapVersion,
+    /// Fee in hundredths of a bip.
+    fee_ppm: u32,
+    token0: Address,
+    token1: Address,
+}
+
+/// Service for reading recent pool activity.
+#[derive(Clone)]
+pub struct PoolActivityService {
+    client: Arc<EthereumClient>,
+    balance_service: BalanceService,
+    /// Uniswap factories pools are looked up on.
+    uniswap: UniswapAddresses,
+    /// V2 venues pairs are looked up on.
+    v2_venues: Vec<Venue>,
+}
+
+impl PoolActivityService {
+    /// Create a new pool activity service.
+    pub fn new(client: Arc<EthereumClient>, balance_service: BalanceService) -> Self {
+        Self {
+            client,
+            balance_service,
+            uniswap: UniswapAddresses::default(),
+            v2_venues: vec![Venue::Uniswap],
+        }
+    }
+
+    /// Set the Uniswap factories pools are looked up on.
+    pub fn with_uniswap_addresses(mut self, uniswap: UniswapAddresses) -> Self {
+        self.uniswap = uniswap;
+        self
+    }
+
+    /// Set the V2 venues pairs are looked up on.
+    pub fn with_v2_venues(mut self, venues: Vec<Venue>) -> Self {
+        self.v2_venues = venues;
+        self
+    }
+
+    /// Get up to `limit` swaps of `pool` from the last `blocks` blocks, newest first.
+    ///
+    /// Logs are fetched newest chunk first, stopping once `limit` swaps are found.
+    /// If the provider rejects a chunk's block range, the chunk is halved and retried.
+    pub async fn recent_swaps(
+        &self,
+        pool: Address,
+        blocks: u64,
+        limit: usize,
+    ) -> Result<RecentPoolSwaps> {
+        tracing::debug!(pool = %pool, blocks = blocks, limit = limit, "Querying recent pool swaps");
+
+        let info = self.pool_info(pool).await?;
+        let token0 = self.token_info(info.token0).await?;
+        let token1 = self.token_info(info.token1).await?;
+
+        let to_block = self.client.get_block_number().await?;
+        let from_block = to_block.saturating_sub(blocks.max(1) - 1);
+
+        let mut swaps = Vec::new();
+        self.for_each_swap_log_chunk(pool, info.protocol, from_block, to_block, |logs| {
+            // Logs come oldest first
+            swaps.extend(
+                logs.iter()
+                    .rev()
+                    .filter_map(|log| decode_swap(log, info.protocol, &token0, &token1)),
+            );
+            swaps.len() < limit
+        })
+        .await?;
+        swaps.truncate(limit);
+
+        Ok(RecentPoolSwaps {
+            pool: format!("{pool:?}"),
+            protocol: info.protocol,
+            token0,
+            token1,
+            from_block,
+            to_block,
+            swaps,
+        })
+    }
+
+    /// Estimate the annualized fee yield of `pool` from its last `blocks` blocks.
+    ///
+    /// Fees are the pool fee applied to the input amount of every `Swap` event in
+    /// the window. They are valued against the pool's current token balances,
+    /// both in token1 at the pool's current price, and extrapolated to a year
+    /// from the window's duration.
+    pub async fn fee_apr(&self, pool: Address, blocks: u64) -> Result<PoolFeeApr> {
+        tracing::debug!(pool = %pool, blocks = blocks, "Estimating pool fee APR");
+
+        let info = self.pool_info(pool).await?;
+        let token0 = self.token_info(info.token0).await?;
+        let token1 = self.token_info(info.token1).await?;
+
+        let to_block = self.client.get_block_number().await?;
+        let from_block = to_block.saturating_sub(blocks.max(1) - 1);
+
+        let mut volume0 = U256::ZERO;
+        let mut volume1 = U256::ZERO;
+        let mut swap_count = 0;
+        self.for_each_swap_log_chunk(pool, info.protocol, from_block, to_block, |logs| {
+            for (direction, amount_in, ..) in
+                logs.iter().filter_map(|log| swap_amounts(log, info.protocol))
+            {
+                match direction {
+                    SwapDirection::Token0ToToken1 => volume0 = volume0.saturating_add(amount_in),
+                    SwapDirection::Token1ToToken0 => volume1 = volume1.saturating_add(amount_in),
+                }
+                swap_count += 1;
+            }
+            true
+        })
+        .await?;
+
+        let provider = self.client.provider().clone();
+        let tvl0 = IERC20::new(info.token0, provider.clone()).balanceOf(pool).call().await?;
+        let tvl1 = IERC20::new(info.token1, provider.clone()).balanceOf(pool).call().await?;
+
+        // token1 per token0, in whole tokens
+        let raw_price = match info.protocol {
+            UniswapVersion::V2 => PriceRatio::new(tvl1, tvl0),
+            UniswapVersion::V3 => {
+                let slot0 = IUniswapV3Pool::new(pool, provider).slot0().call().await?;
+                PriceRatio::from_sqrt_price_x96(slot0.sqrtPriceX96, true)
+            }
+        };
+        let price = raw_price
+            .and_then(|price| price.scale_decimals(token0.decimals, token1.decimals))
+            .and_then(|price| {
+                Decimal::from_str(
+                    &price.to_decimal_string(PRICE_DECIMALS, PRICE_SIGNIFICANT_DIGITS),
+                )
+                .ok()
+            });
+
+        let window_secs = self.window_secs(from_block, to_block).await?;
+
+        let fee_of = |volume: U256| {
+            volume.saturating_mul(U256::from(info.fee_ppm)) / U256::from(1_000_000u32)
+        };
+        let (fees0, fees1) = (fee_of(volume0), fee_of(volume1));
+        let whole = |amount: U256, decimals: u8| {
+            Decimal::from_str(&format_units(amount, decimals)).unwrap_or_default()
+        };
+        let apr = price.and_then(|price| {
+            estimate_fee_apr(
+                (whole(fees0, token0.decimals), whole(fees1, token1.decimals)),
+                (whole(tvl0, token0.decimals), whole(tvl1, token1.decimals)),
+                price,
+                window_secs,
+            )
+        });
+
+        let mut assumptions = vec![
+            format!(
+                "Extrapolates the fees of the last {} blocks ({} seconds) to a year; volume \
+                 varies, so short windows can be far off",
+                to_block - from_block + 1,
+                window_secs
+            ),
+            "TVL is the pool's current token balances, valued in token1 at the pool's current \
+             price; fees are valued the same way"
+                .to_string(),
+        ];
+        if info.protocol == UniswapVersion::V3 {
+            assumptions.push(
+                "V3 fees go only to in-range liquidity, and the balances include out-of-range \
+                 liquidity, so this is a pool-wide average: in-range positions earn more, \
+                 out-of-range positions nothing. Any protocol fee share is not deducted"
+                    .to_string(),
+            );
+        }
+
+        let fee = match info.protocol {
+            UniswapVersion::V2 => PoolFee::v2(info.venue, pool),
+            UniswapVersion::V3 => PoolFee::v3(pool, info.fee_ppm),
+        };
+
+        Ok(PoolFeeApr {
+            fee,
+            from_block,
+            to_block,
+            window_secs,
+            swap_count,
+            volume_token0: format_units(volume0, token0.decimals),
+            volume_token1: format_units(volume1, token1.decimals),
+            fees_token0: format_units(fees0, token0.decimals),
+            fees_token1: format_units(fees1, token1.decimals),
+            tvl_token0: format_units(tvl0, token0.decimals),
+            tvl_token1: format_units(tvl1, token1.decimals),
+            estimated_apr_percent: apr.map(|apr| apr.round_dp(2).normalize().to_string()),
+            token0,
+            token1,
+            assumptions,
+        })
+    }
+
+    /// List the pools of a pair on every V2 venue and Uniswap V3, and their trading fees.
+    ///
+    /// Only factory lookups are made (`getPair` per V2 venue and `getPool` per
+    /// fee tier), so nothing is quoted. A V2 pair always charges 0.30%.
+    pub async fn pool_fees(&self, token_a: Address, token_b: Address) -> Result<PairPoolFees> {
+        let provider = self.client.provider().clone();
+        let mut pools = Vec::new();
+
+        for &venue in &self.v2_venues {
+            let factory = v2_deployment(venue, &self.uniswap).factory;
+            let v2_factory = IUniswapV2Factory::new(factory, provider.clone());
+            let pair: Address = v2_factory.getPair(token_a, token_b).call().await?;
+            if pair != Address::ZERO {
+                pools.push(PoolFee::v2(venue, pair));
+            }
+        }
+
+        let v3_factory = IUniswapV3Factory::new(self.uniswap.v3_factory, provider);
+        for fee in fee_tiers::ALL_FEES {
+            let pool: Address = v3_factory.getPool(token_a, token_b, U24::from(fee)).call().await?;
+            if pool != Address::ZERO {
+                pools.push(PoolFee::v3(pool, fee));
+            }
+        }
+
+        Ok(PairPoolFees {
+            token_a: self.token_info(token_a).await?,
+            token_b: self.token_info(token_b).await?,
+            pools,
+        })
+    }
+
+    /// Identify `pool` as a V2 pair or V3 pool and read its tokens and fee.
+    async fn pool_info(&self, pool: Address) -> Result<PoolInfo> {
+        // V2 pairs and V3 pools share the token0()/token1() selectors
+        let pair = IUniswapV2Pair::new(pool, self.client.provider().clone());
+        let (token0, token1) = match (pair.token0().call().await, pair.token1().call().await) {
+            (Ok(token0), Ok(token1)) => (token0, token1),
+            _ => {
+                return Err(AppError::InvalidAddress(format!(
+                    "{} is not a Uniswap V2 pair or V3 pool",
+                    pool
+                )))
+            }
+        };
+
+        // Only V3 pools have fee()
+        let v3_pool = IUniswapV3Pool::new(pool, self.client.provider().clone());
+        let (protocol, fee_ppm) = match v3_pool.fee().call().await {
+            Ok(fee) => (UniswapVersion::V3, fee.to::<u32>()),
+            Err(_) => (UniswapVersion::V2, V2_POOL_FEE_PPM),
+        };
+
+        // A V2 pair belongs to the venue whose factory created it
+        let venue = match protocol {
+            UniswapVersion::V2 => {
+                let factory: Address = pair.factory().call().await?;
+                self.v2_venues
+                    .iter()
+                    .copied()
+                    .find(|&venue| v2_deployment(venue, &self.uniswap).factory == factory)
+                    .unwrap_or(Venue::Uniswap)
+            }
+            UniswapVersion::V3 => Venue::Uniswap,
+        };
+
+        Ok(PoolInfo { venue, protocol, fee_ppm, token0, token1 })
+    }
+
+    /// Pass the `Swap` logs of `pool` between `from_block` and `to_block` to
+    /// `visit`, one chunk at a time, newest chunk first (logs within a chunk
+    /// oldest first). Stops early when `visit` returns false.
+    ///
+    /// If the provider rejects a chunk's block range, the chunk is halved and retried.
+    async fn for_each_swap_log_chunk(
+        &self,
+        pool: Address,
+        protocol: UniswapVersion,
+        from_block: u64,
+        to_block: u64,
+        mut visit: impl FnMut(&[Log]) -> bool,
+    ) -> Result<()> {
+        let mut chunk = LOG_CHUNK_BLOCKS;
+        let mut end = to_block;
+        loop {
+            let start = end.saturating_sub(chunk - 1).max(from_block);
+            let filter = swap_filter(pool, protocol, start, end);
+
+            let logs = match self.client.get_logs(&filter).await {
+                Ok(logs) => logs,
+                Err(e) if chunk > MIN_LOG_CHUNK_BLOCKS && is_range_limit_error(&e.to_string()) => {
+                    chunk = (chunk / 2).max(MIN_LOG_CHUNK_BLOCKS);
+                    tracing::debug!(chunk = chunk, error = %e, "Log range rejected, retrying smaller");
+                    continue;
+                }
+                Err(e) => {
+                    return Err(AppError::Rpc(format!(
+                        "eth_getLogs failed for blocks {}-{} ({}); the provider may limit log \
+                         queries, try fewer blocks",
+                        start, end, e
+                    )))
+                }
+            };
+
+            if !visit(&logs) || start == from_block {
+                return Ok(());
+            }
+            end = start - 1;
+        }
+    }
+
+    /// Seconds between the timestamps of `from_block` and `to_block`.
+    ///
+    /// Falls back to 12 seconds per block when the timestamps cannot be read.
+    async fn window_secs(&self, from_block: u64, to_block: u64) -> Result<u64> {
+        let blocks = to_block - from_block + 1;
+        let timestamps = (
+            self.client.get_block_timestamp_at(from_block).await,
+            self.client.get_block_timestamp_at(to_block).await,
+        );
+        Ok(match timestamps {
+            (Ok(from), Ok(to)) if to > from => to - from,
+            _ => blocks * SECONDS_PER_BLOCK,
+        })
+    }
+
+    async fn token_info(&self, token: Address) -> Result<TokenInfo> {
+        let metadata = self.balance_service.get_token_metadata(token).await?;
+        Ok(TokenInfo::erc20(token, metadata.symbol, metadata.decimals))
+    }
+}
+
+/// Filter for the `Swap` events of `pool` between `from_block` and `to_block`.
+fn swap_filter(pool: Address, protocol: UniswapVersion, from_block: u64, to_block: u64) -> Filter {
+    let signature = match protocol {
+        UniswapVersion::V2 => IUniswapV2Pair::Swap::SIGNATURE_HASH,
+        UniswapVersion::V3 => IUniswapV3Pool::Swap::SIGNATURE_HASH,
+    };
+    Filter::new().address(pool).event_signature(signature).from_block(from_block).to_block(to_block)
+}
+
+/// Whether an `eth_getLogs` error looks like a provider block range or result limit.
+///
+/// Rate limit errors are not: retrying them with smaller ranges would only send
+/// more requests to a provider that is already throttling.
+fn is_range_limit_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    let rate_limited = ["rate limit", "rate-limit", "ratelimit", "too many requests", "429"]
+        .iter()
+        .any(|pattern| message.contains(pattern));
+    !rate_limited
+        && [
+            "block range",
+            "range is too",
+            "range too",
+            "limited to a",
+            "too many results",
+            "more than",
+            "max results",
+            "response size",
+            "result size",
+        ]
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// A `Swap` event's direction, raw amounts in and out, sender and recipient.
+type SwapAmounts = (SwapDirection, U256, U256, Address, Address);
+
+/// Decode the direction and raw amounts of a `Swap` log.
+fn swap_amounts(log: &Log, protocol: UniswapVersion) -> Option<SwapAmounts> {
+    Some(match protocol {
+        UniswapVersion::V2 => {
+            let event = log.log_decode::<IUniswapV2Pair::Swap>().ok()?.inner.data;
+            if event.amount0In > U256::ZERO {
+                (
+                    SwapDirection::Token0ToToken1,
+                    event.amount0In,
+                    event.amount1Out,
+                    event.sender,
+                    event.to,
+                )
+            } else {
+                (
+                    SwapDirection::Token1ToToken0,
+                    event.amount1In,
+                    event.amount0Out,
+                    event.sender,
+                    event.to,
+                )
+            }
+        }
+        UniswapVersion::V3 => {
+            // Amounts are the pool's balance deltas: positive in, negative out
+            let event = log.log_decode::<IUniswapV3Pool::Swap>().ok()?.inner.data;
+            if event.amount0.is_positive() {
+                (
+                    SwapDirection::Token0ToToken1,
+                    event.amount0.unsigned_abs(),
+                    event.amount1.unsigned_abs(),
+                    event.sender,
+                    event.recipient,
+                )
+            } else {
+                (
+                    SwapDirection::Token1ToToken0,
+                    event.amount1.unsigned_abs(),
+                    event.amount0.unsigned_abs(),
+                    event.sender,
+                    event.recipient,
+                )
+            }
+        }
+    })
+}
+
+/// Annualized fee yield, as a percentage of TVL.
+///
+/// `fees` and `tvl` are (token0, token1) amounts in whole tokens and `price` is
+/// token1 per token0. Returns `None` for an empty pool or window.
+fn estimate_fee_apr(
+    fees: (Decimal, Decimal),
+    tvl: (Decimal, Decimal),
+    price: Decimal,
+    window_secs: u64,
+) -> Option<Decimal> {
+    let fees_value = fees.0.checked_mul(price)?.checked_add(fees.1)?;
+    let tvl_value = tvl.0.checked_mul(price)?.checked_add(tvl.1)?;
+    if tvl_value.is_zero() || window_secs == 0 {
+        return None;
+    }
+    let periods_per_year = Decimal::from(SECONDS_PER_YEAR) / Decimal::from(window_secs);
+    (fees_value / tvl_value).checked_mul(periods_per_year)?.checked_mul(Decimal::ONE_HUNDRED)
+}
+
+/// Decode a `Swap` log into a swap with human-readable amounts.
+fn decode_swap(
+    log: &Log,
+    protocol: UniswapVersion,
+    token0: &TokenInfo,
+    token1: &TokenInfo,
+) -> Option<PoolSwap> {
+    let (direction, amount_in, amount_out, sender, recipient) = swap_amounts(log, protocol)?;
+
+    let (token_in, token_out) = match direction {
+        SwapDirection::Token0ToToken1 => (token0, token1),
+        SwapDirection::Token1ToToken0 => (token1, token0),
+    };
+
+    Some(PoolSwap {
+        block_number: log.block_number,
+        transaction_hash: log.transaction_hash.map(|hash| format!("{hash:?}")),
+        direction,
+        token_in: token_in.symbol.clone(),
+        token_out: token_out.symbol.clone(),
+        amount_in: format_units(amount_in, token_in.decimals),
+        amount_out: format_units(amount_out, token_out.decimals),
+        sender: format!("{sender:?}"),
+        recipient: format!("{recipient:?}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ethereum::constants::{USDC_ADDRESS, WETH_ADDRESS};
+    use alloy::primitives::{aliases::I24, I256, U160};
+
+    fn tokens() -> (TokenInfo, TokenInfo) {
+        (
+            TokenInfo::erc20(USDC_ADDRESS, "USDC".to_string(), 6),
+            TokenInfo::erc20(WETH_ADDRESS, "WETH".to_string(), 18),
+        )
+    }
+
+    fn rpc_log(event: &impl SolEvent) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: Address::repeat_byte(9),
+                data: event.encode_log_data(),
+            },
+            block_number: Some(21_000_000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_v2_swap() {
+        let (usdc, weth) = tokens();
+        // 2500 USDC in, 1 WETH out
+        let log = rpc_log(&IUniswapV2Pair::Swap {
+            sender: Address::repeat_byte(1),
+            amount0In: U256::from(2_500_000_000u64),
+            amount1In: U256::ZERO,
+            amount0Out: U256::ZERO,
+            amount1Out: U256::from(10u64).pow(U256::from(18u64)),
+            to: Address::repeat_byte(2),
+        });
+
+        let swap = decode_swap(&log, UniswapVersion::V2, &usdc, &weth).unwrap();
+
+        assert_eq!(swap.direction, SwapDirection::Token0ToToken1);
+        assert_eq!(swap.token_in, "USDC");
+        assert_eq!(swap.token_out, "WETH");
+        assert_eq!(swap.amount_in, "2500");
+        assert_eq!(swap.amount_out, "1");
+        assert_eq!(swap.block_number, Some(21_000_000));
+        assert_eq!(swap.recipient, format!("{:?}", Address::repeat_byte(2)));
+    }
+
+    #[test]
+    fn test_decode_v3_swap() {
+        let (usdc, weth) = tokens();
+        // 0.5 WETH in, 1250 USDC out
+        let log = rpc_log(&IUniswapV3Pool::Swap {
+            sender: Address::repeat_byte(1),
+            recipient: Address::repeat_byte(2),
+            amount0: I256::try_from(-1_250_000_000i64).unwrap(),
+            amount1: I256::try_from(500_000_000_000_000_000i64).unwrap(),
+            sqrtPriceX96: U160::from(1u64),
+            liquidity: 1,
+            tick: I24::ZERO,
+        });
+
+        let swap = decode_swap(&log, UniswapVersion::V3, &usdc, &weth).unwrap();
+
+        assert_eq!(swap.direction, SwapDirection::Token1ToToken0);
+        assert_eq!(swap.token_in, "WETH");
+        assert_eq!(swap.token_out, "USDC");
+        assert_eq!(swap.amount_in, "0.5");
+        assert_eq!(swap.amount_out, "1250");
+    }
+
+    #[test]
+    fn test_decode_swap_wrong_event() {
+        let (usdc, weth) = tokens();
+        let log = rpc_log(&IUniswapV2Pair::Swap {
+            sender: Address::ZERO,
+            amount0In: U256::from(1u64),
+            amount1In: U256::ZERO,
+            amount0Out: U256::ZERO,
+            amount1Out: U256::from(1u64),
+            to: Address::ZERO,
+        });
+
+        assert!(decode_swap(&log, UniswapVersion::V3, &usdc, &weth).is_none());
+    }
+
+    #[test]
+    fn test_swap_amounts_raw() {
+        let log = rpc_log(&IUniswapV2Pair::Swap {
+            sender: Address::ZERO,
+            amount0In: U256::ZERO,
+            amount1In: U256::from(7u64),
+            amount0Out: U256::from(3u64),
+            amount1Out: U256::ZERO,
+            to: Address::ZERO,
+        });
+
+        let (direction, amount_in, amount_out, ..) =
+            swap_amounts(&log, UniswapVersion::V2).unwrap();
+        assert_eq!(direction, SwapDirection::Token1ToToken0);
+        assert_eq!(amount_in, U256::from(7u64));
+        assert_eq!(amount_out, U256::from(3u64));
+    }
+
+    #[test]
+    fn test_estimate_fee_apr() {
+        // $1000 of fees in a day on $10M of TVL (half in each token at 2500 per token0)
+        let fees = (Decimal::new(2, 1), Decimal::from(500)); // 0.2 token0 = $500
+        let tvl = (Decimal::from(2000), Decimal::from(5_000_000));
+        let apr = estimate_fee_apr(fees, tvl, Decimal::from(2500), 86_400).unwrap();
+
+        // 1000 / 10M * 365 * 100 = 3.65%
+        assert_eq!(apr.round_dp(6), Decimal::new(365, 2));
+    }
+
+    #[test]
+    fn test_estimate_fee_apr_empty_pool_or_window() {
+        let fees = (Decimal::ONE, Decimal::ONE);
+        assert_eq!(estimate_fee_apr(fees, (Decimal::ZERO, Decimal::ZERO), Decimal::ONE, 60), None);
+        assert_eq!(estimate_fee_apr(fees, (Decimal::ONE, Decimal::ONE), Decimal::ONE, 0), None);
+    }
+
+    #[test]
+    fn test_is_range_limit_error() {
+        assert!(is_range_limit_error("query returned more than 10000 results"));
+        assert!(is_range_limit_error("Block range is too large"));
+        assert!(is_range_limit_error("eth_getLogs is limited to a 500 block range"));
+        assert!(is_range_limit_error("Log response size exceeded"));
+        assert!(is_range_limit_error("exceed maximum block range: 5000"));
+        assert!(!is_range_limit_error("connection refused"));
+        assert!(!is_range_limit_error("rate limit exceeded"));
+        assert!(!is_range_limit_error("HTTP error 429 Too Many Requests"));
+        assert!(!is_range_limit_error("Your app has exceeded its compute units per second limit"));
+    }
+}
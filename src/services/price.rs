@@ -1,22 +1,38 @@
 //! Price query service.
 
-use alloy::primitives::{Address, U160, U256};
-use rust_decimal::Decimal;
-use std::{collections::HashMap, sync::Arc, time::SystemTime};
+use alloy::primitives::{aliases::U24, Address, I256, U256};
+use rust_decimal::{
+    prelude::{FromPrimitive, ToPrimitive},
+    Decimal,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
 
 use crate::{
+    config::DEFAULT_FAN_OUT_CONCURRENCY,
     error::{AppError, Result},
     ethereum::{
+        block_id,
         contracts::{
             chainlink::{get_chainlink_feeds, IAggregatorV3},
-            uniswap_v2::{IUniswapV2Factory, IUniswapV2Pair, UNISWAP_V2_FACTORY},
-            uniswap_v3::{fee_tiers, IQuoterV2, UNISWAP_V3_QUOTER},
+            erc20::TokenMetadata,
+            uniswap_v2::{IUniswapV2Factory, IUniswapV2Pair},
+            uniswap_v3::{fee_tiers, IUniswapV3Factory, IUniswapV3Pool},
             WETH_ADDRESS,
         },
-        EthereumClient,
+        price_math::v2_amount_out,
+        quoter, usd_pegged_tokens, EthereumClient, UniswapAddresses, DEFAULT_CHAIN_ID,
+    },
+    services::{balance::fan_out_with_progress, swap::v2_deployment, BalanceService},
+    types::{
+        BalanceInfo, ComparisonOperator, MultiPriceInfo, PriceAlertResult, PriceConfidence,
+        PriceInfo, PriceSource, PriceSourceSpread, QuoteCurrency, TokenInfo, Venue, WalletInfo,
+        Warning, WarningCode,
     },
-    services::BalanceService,
-    types::{PriceInfo, PriceSource, QuoteCurrency, TokenInfo},
 };
 
 /// Get current Unix timestamp in seconds.
@@ -25,18 +41,263 @@ fn current_timestamp() -> u64 {
     SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
 }
 
+/// Warn when an oracle answer is more than half way to the staleness threshold.
+fn oracle_age_warning(age: u64, threshold: u64) -> Option<Warning> {
+    (age > threshold / 2).then(|| {
+        Warning::new(
+            WarningCode::OracleAging,
+            format!(
+                "Chainlink answer is {} seconds old; answers older than {} seconds are rejected",
+                age, threshold
+            ),
+        )
+    })
+}
+
+/// Decimals of the quote token used for Uniswap pricing (USDC = 6, WETH = 18).
+fn quote_token_decimals(token_out: Address) -> u32 {
+    if token_out == crate::ethereum::contracts::USDC_ADDRESS {
+        6
+    } else {
+        18
+    }
+}
+
+/// A price quote from a single Uniswap venue, with its liquidity weight.
+#[derive(Debug, Clone, Copy)]
+struct VenueQuote {
+    /// Price of one input token in the quote token.
+    price: Decimal,
+    /// Pool liquidity `L` (raw units), used as the weight.
+    liquidity: u128,
+}
+
+/// Compute the liquidity-weighted average price across venues.
+///
+/// Weights are normalized against the deepest venue before multiplying so that
+/// large liquidity values cannot overflow `Decimal`.
+fn liquidity_weighted_price(quotes: &[VenueQuote]) -> Option<Decimal> {
+    let max_liquidity = quotes.iter().map(|q| q.liquidity).max().filter(|l| *l > 0)?;
+    let max_liquidity = Decimal::from_u128(max_liquidity)?;
+
+    let mut weighted_sum = Decimal::ZERO;
+    let mut weight_total = Decimal::ZERO;
+    for quote in quotes {
+        let weight = Decimal::from_u128(quote.liquidity)? / max_liquidity;
+        weighted_sum += quote.price * weight;
+        weight_total += weight;
+    }
+
+    if weight_total.is_zero() {
+        return None;
+    }
+    Some(weighted_sum / weight_total)
+}
+
+/// Largest spread between venue prices, as a fraction of the price, that still
+/// rates an aggregate price high confidence (1%).
+const HIGH_CONFIDENCE_MAX_SPREAD: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+/// Largest spread between venue prices that still rates medium confidence (5%).
+const MEDIUM_CONFIDENCE_MAX_SPREAD: Decimal = Decimal::from_parts(5, 0, 0, false, 2);
+
+/// Default spread between Chainlink and Uniswap prices, in basis points, above
+/// which the sources are reported as diverging (1%).
+pub const DEFAULT_MAX_SOURCE_SPREAD_BPS: u32 = 100;
+
+/// Most last known prices kept for the stale price fallback; the oldest is
+/// dropped to make room for a new token.
+const MAX_LAST_PRICES: usize = 1024;
+
+/// Key of a last known price: the token, the quote currency, and whether the
+/// price is the aggregate over venues.
+type LastPriceKey = (Address, QuoteCurrency, bool);
+
+/// Confidence of an aggregate price, from how closely its venues agree.
+///
+/// One venue gives nothing to compare against, so it rates medium.
+fn venue_confidence(quotes: &[VenueQuote], price: Decimal) -> PriceConfidence {
+    if quotes.len() < 2 || price.is_zero() {
+        return PriceConfidence::Medium;
+    }
+
+    let (min, max) =
+        quotes.iter().fold((price, price), |(min, max), q| (min.min(q.price), max.max(q.price)));
+    let spread = (max - min) / price;
+    if spread <= HIGH_CONFIDENCE_MAX_SPREAD {
+        PriceConfidence::High
+    } else if spread <= MEDIUM_CONFIDENCE_MAX_SPREAD {
+        PriceConfidence::Medium
+    } else {
+        PriceConfidence::Low
+    }
+}
+
+/// Confidence of a price read from one V2 pair holding `quote_reserve` whole
+/// quote tokens: medium, or low below $100k (50 ETH) of depth.
+fn reserve_confidence(quote_reserve: Decimal, quote_currency: QuoteCurrency) -> PriceConfidence {
+    let min_reserve = match quote_currency {
+        QuoteCurrency::USD => Decimal::from(100_000),
+        QuoteCurrency::ETH => Decimal::from(50),
+    };
+    if quote_reserve < min_reserve {
+        PriceConfidence::Low
+    } else {
+        PriceConfidence::Medium
+    }
+}
+
 /// Service for fetching token prices.
 #[derive(Clone)]
 pub struct PriceService {
     client: Arc<EthereumClient>,
     balance_service: BalanceService,
     chainlink_feeds: HashMap<Address, Address>,
+    /// Chainlink feeds whose zero and negative answers are valid, such as spreads or rates.
+    signed_feeds: HashSet<Address>,
+    /// Tokens priced at 1.0 in USD (or their Chainlink feed, when one exists).
+    usd_pegged_tokens: HashSet<Address>,
+    /// Token priced at 1.0 in ETH.
+    wrapped_native_token: Option<Address>,
+    /// Whether to return the last known price when every source fails.
+    stale_price_fallback: bool,
+    /// Uniswap contracts pools and quotes are read from.
+    uniswap: UniswapAddresses,
+    /// V2 venues included in the aggregate price.
+    v2_venues: Vec<Venue>,
+    /// Trade size, in whole tokens, Uniswap V3 prices are quoted at (default: 1 token).
+    reference_amounts: HashMap<Address, Decimal>,
+    /// Maximum concurrent price lookups within one request.
+    fan_out_concurrency: usize,
+    /// Last price fetched for each token, quote currency and aggregation, kept
+    /// only while the stale price fallback is enabled.
+    last_prices: Arc<Mutex<BTreeMap<LastPriceKey, PriceInfo>>>,
 }
 
 impl PriceService {
-    /// Create a new price service.
+    /// Create a new price service with the mainnet peg defaults.
     pub fn new(client: Arc<EthereumClient>, balance_service: BalanceService) -> Self {
-        Self { client, balance_service, chainlink_feeds: get_chainlink_feeds() }
+        Self {
+            client,
+            balance_service,
+            chainlink_feeds: get_chainlink_feeds(),
+            signed_feeds: HashSet::new(),
+            usd_pegged_tokens: usd_pegged_tokens(DEFAULT_CHAIN_ID).into_iter().collect(),
+            wrapped_native_token: Some(WETH_ADDRESS),
+            stale_price_fallback: false,
+            uniswap: UniswapAddresses::default(),
+            v2_venues: vec![Venue::Uniswap],
+            reference_amounts: HashMap::new(),
+            fan_out_concurrency: DEFAULT_FAN_OUT_CONCURRENCY,
+            last_prices: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Set the Chainlink feeds whose answers may be zero or negative.
+    pub fn with_signed_feeds(mut self, feeds: impl IntoIterator<Item = Address>) -> Self {
+        self.signed_feeds = feeds.into_iter().collect();
+        self
+    }
+
+    /// Set the tokens priced at 1.0 in USD.
+    pub fn with_usd_pegged_tokens(mut self, tokens: impl IntoIterator<Item = Address>) -> Self {
+        self.usd_pegged_tokens = tokens.into_iter().collect();
+        self
+    }
+
+    /// Set the token priced at 1.0 in ETH (`None` disables the special case).
+    pub fn with_wrapped_native_token(mut self, token: Option<Address>) -> Self {
+        self.wrapped_native_token = token;
+        self
+    }
+
+    /// Set whether a failed price falls back to the last price fetched, marked stale.
+    pub fn with_stale_price_fallback(mut self, enabled: bool) -> Self {
+        self.stale_price_fallback = enabled;
+        self
+    }
+
+    /// Set the Uniswap contracts pools and quotes are read from.
+    pub fn with_uniswap_addresses(mut self, uniswap: UniswapAddresses) -> Self {
+        self.uniswap = uniswap;
+        self
+    }
+
+    /// Set the V2 venues included in the aggregate price.
+    pub fn with_v2_venues(mut self, venues: Vec<Venue>) -> Self {
+        self.v2_venues = venues;
+        self
+    }
+
+    /// Set how many price lookups one request may run concurrently.
+    pub fn with_fan_out_concurrency(mut self, limit: usize) -> Self {
+        self.fan_out_concurrency = limit.max(1);
+        self
+    }
+
+    /// Set the trade sizes, in whole tokens, Uniswap V3 prices are quoted at.
+    ///
+    /// Tokens not listed are quoted at one whole token.
+    pub fn with_reference_amounts(
+        mut self,
+        amounts: impl IntoIterator<Item = (Address, Decimal)>,
+    ) -> Self {
+        self.reference_amounts = amounts.into_iter().collect();
+        self
+    }
+
+    /// Quote `token` at `amount` whole tokens, keeping the other reference amounts.
+    pub fn with_reference_amount(mut self, token: Address, amount: Decimal) -> Self {
+        self.reference_amounts.insert(token, amount);
+        self
+    }
+
+    /// The chain's wrapped native token, if known.
+    pub fn wrapped_native_token(&self) -> Option<Address> {
+        self.wrapped_native_token
+    }
+
+    /// Trade size, in whole tokens, `token` is quoted at.
+    fn reference_amount(&self, token: Address) -> Decimal {
+        self.reference_amounts.get(&token).copied().unwrap_or(Decimal::ONE)
+    }
+
+    /// Token that ETH quotes are priced against: the chain's wrapped native token.
+    fn eth_quote_token(&self) -> Result<Address> {
+        self.wrapped_native_token.ok_or_else(|| {
+            AppError::PriceOracle(
+                "No wrapped native token is known for this chain; set ETHEREUM_WRAPPED_NATIVE_TOKEN"
+                    .to_string(),
+            )
+        })
+    }
+
+    /// Token that Uniswap prices in `quote_currency` are quoted against.
+    ///
+    /// USDC stands in for USD.
+    fn quote_token(&self, quote_currency: QuoteCurrency) -> Result<Address> {
+        match quote_currency {
+            QuoteCurrency::ETH => self.eth_quote_token(),
+            QuoteCurrency::USD => Ok(crate::ethereum::contracts::USDC_ADDRESS),
+        }
+    }
+
+    /// Whether `token` is pegged 1:1 to `quote_currency`.
+    fn is_pegged(&self, token: Address, quote_currency: QuoteCurrency) -> bool {
+        match quote_currency {
+            QuoteCurrency::ETH => self.wrapped_native_token == Some(token),
+            QuoteCurrency::USD => self.usd_pegged_tokens.contains(&token),
+        }
+    }
+
+    /// Chainlink feed used to price `token` in `quote_currency`, if any.
+    ///
+    /// Feeds are only known for USD quotes. WETH maps to the ETH/USD feed.
+    fn chainlink_feed(&self, token: Address, quote_currency: QuoteCurrency) -> Option<Address> {
+        match quote_currency {
+            QuoteCurrency::USD => self.chainlink_feeds.get(&token).copied(),
+            QuoteCurrency::ETH => None,
+        }
     }
 
     /// Get token price in specified quote currency.
@@ -54,45 +315,143 @@ impl PriceService {
         // Get token metadata
         let metadata = self.balance_service.get_token_metadata(token_address).await?;
 
-        // Special case: WETH priced in ETH is always 1:1
-        // (WETH is wrapped ETH, so 1 WETH = 1 ETH)
-        if token_address == WETH_ADDRESS && quote_currency == QuoteCurrency::ETH {
-            return Ok(PriceInfo {
-                token: TokenInfo::erc20(token_address, metadata.symbol.clone(), metadata.decimals),
-                price: "1".to_string(),
-                quote_currency: QuoteCurrency::ETH,
-                source: PriceSource::UniswapV3, // Use V3 as nominal source
-                timestamp: current_timestamp(),
-            });
+        self.price_with_metadata(&metadata, quote_currency).await
+    }
+
+    /// Get a token's price in each of `quote_currencies`.
+    ///
+    /// Token metadata is read once and shared by every quote, and a currency
+    /// listed twice is quoted once. With `aggregate`, each price is the
+    /// liquidity-weighted Uniswap average, as in [`Self::get_aggregate_price`].
+    pub async fn get_prices(
+        &self,
+        token_address: Address,
+        quote_currencies: &[QuoteCurrency],
+        aggregate: bool,
+    ) -> Result<MultiPriceInfo> {
+        tracing::debug!(
+            token = %token_address,
+            quotes = ?quote_currencies,
+            aggregate = aggregate,
+            "Fetching token prices"
+        );
+
+        let metadata = self.balance_service.get_token_metadata(token_address).await?;
+
+        let mut prices = BTreeMap::new();
+        for &quote_currency in quote_currencies {
+            if prices.contains_key(&quote_currency) {
+                continue;
+            }
+            let info = if aggregate {
+                self.aggregate_price_with_metadata(&metadata, quote_currency).await?
+            } else {
+                self.price_with_metadata(&metadata, quote_currency).await?
+            };
+            prices.insert(quote_currency, info.into());
         }
 
-        // Special case: USDC priced in USD is always 1:1
-        // (USDC is the USD proxy, so querying USDC/USDC would fail)
-        if token_address == crate::ethereum::contracts::USDC_ADDRESS
-            && quote_currency == QuoteCurrency::USD
-        {
-            return Ok(PriceInfo {
-                token: TokenInfo::erc20(token_address, metadata.symbol.clone(), metadata.decimals),
-                price: "1".to_string(),
-                quote_currency: QuoteCurrency::USD,
-                source: PriceSource::Chainlink, // Nominal source
-                timestamp: current_timestamp(),
-            });
+        Ok(MultiPriceInfo {
+            token: TokenInfo::erc20(token_address, metadata.symbol, metadata.decimals),
+            prices,
+        })
+    }
+
+    /// Price a token whose metadata has already been read.
+    async fn price_with_metadata(
+        &self,
+        metadata: &TokenMetadata,
+        quote_currency: QuoteCurrency,
+    ) -> Result<PriceInfo> {
+        let price = self.fetch_price(metadata, quote_currency).await;
+        self.with_stale_fallback((metadata.address, quote_currency, false), price)
+    }
+
+    /// Remember a fetched price, or replace a failure with the last price fetched
+    /// under the same `key`.
+    ///
+    /// Does nothing unless the stale price fallback is enabled. The replacement
+    /// is marked `stale` with its age so callers can judge it. At most
+    /// [`MAX_LAST_PRICES`] prices are kept.
+    fn with_stale_fallback(
+        &self,
+        key: LastPriceKey,
+        price: Result<PriceInfo>,
+    ) -> Result<PriceInfo> {
+        if !self.stale_price_fallback {
+            return price;
+        }
+        let (token, quote_currency, _) = key;
+        let mut last_prices = self.last_prices.lock().unwrap_or_else(|e| e.into_inner());
+        match price {
+            Ok(info) => {
+                if !last_prices.contains_key(&key) && last_prices.len() >= MAX_LAST_PRICES {
+                    let oldest = last_prices
+                        .iter()
+                        .min_by_key(|(_, info)| info.timestamp)
+                        .map(|(&oldest, _)| oldest);
+                    if let Some(oldest) = oldest {
+                        last_prices.remove(&oldest);
+                    }
+                }
+                last_prices.insert(key, info.clone());
+                Ok(info)
+            }
+            Err(e) => match last_prices.get(&key) {
+                Some(last) => {
+                    tracing::warn!(
+                        token = %token,
+                        quote = ?quote_currency,
+                        error = %e,
+                        "All price sources failed, returning last known price"
+                    );
+                    Ok(stale_price(last.clone(), current_timestamp(), &e))
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Fetch a token's price from its sources, without the stale fallback.
+    async fn fetch_price(
+        &self,
+        metadata: &TokenMetadata,
+        quote_currency: QuoteCurrency,
+    ) -> Result<PriceInfo> {
+        let token_address = metadata.address;
+
+        // Pegged tokens have no pool against themselves (querying USDC/USDC would
+        // fail), so they are priced from the peg instead
+        if self.is_pegged(token_address, quote_currency) {
+            return self
+                .get_pegged_price(
+                    token_address,
+                    quote_currency,
+                    &metadata.symbol,
+                    metadata.decimals,
+                )
+                .await;
         }
 
         // Try Chainlink first for USD prices
-        if quote_currency == QuoteCurrency::USD {
-            if let Some(feed_address) = self.chainlink_feeds.get(&token_address) {
-                if let Ok(price_info) = self
-                    .get_chainlink_price(
-                        token_address,
-                        *feed_address,
-                        &metadata.symbol,
-                        metadata.decimals,
-                    )
-                    .await
-                {
-                    return Ok(price_info);
+        if let Some(feed_address) = self.chainlink_feed(token_address, quote_currency) {
+            match self
+                .get_chainlink_price(
+                    token_address,
+                    feed_address,
+                    &metadata.symbol,
+                    metadata.decimals,
+                )
+                .await
+            {
+                Ok(price_info) => return Ok(price_info),
+                Err(e) => {
+                    tracing::warn!(
+                        token = %token_address,
+                        feed = %feed_address,
+                        error = %e,
+                        "Chainlink price failed, falling back to Uniswap"
+                    );
                 }
             }
         }
@@ -102,10 +461,439 @@ impl PriceService {
             .await
     }
 
+    /// Value a wallet's balances and their total in `base`.
+    ///
+    /// Every balance is priced in USD, then converted with one ETH/USD rate
+    /// fetched once for the whole wallet, so all values share the same base rate.
+    /// Balances that cannot be priced are listed in `unpriced_tokens`.
+    /// If the ETH/USD rate cannot be fetched, the balances that need it are
+    /// left unvalued and the wallet gets a warning instead of failing.
+    /// Token balances are priced concurrently, at most `fan_out_concurrency` at a
+    /// time, and `on_progress(done, total)` is called as each one is priced.
+    pub async fn value_wallet(
+        &self,
+        wallet: &mut WalletInfo,
+        base: QuoteCurrency,
+        on_progress: impl Fn(usize, usize),
+    ) {
+        let eth_usd = if base == QuoteCurrency::ETH || wallet.eth_balance.is_some() {
+            match self.eth_usd_rate().await {
+                Ok(rate) => Some(rate),
+                Err(e) => {
+                    wallet.warnings.push(Warning::new(
+                        WarningCode::ValueUnavailable,
+                        format!(
+                            "ETH/USD rate is unavailable ({}); balances needing it are not valued",
+                            e
+                        ),
+                    ));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let base_rate = match base {
+            QuoteCurrency::USD => Some(Decimal::ONE),
+            QuoteCurrency::ETH => eth_usd,
+        };
+
+        // Nothing can be valued without a base rate, so skip the lookups
+        let tokens: Vec<Option<Address>> = wallet
+            .token_balances
+            .iter()
+            .map(|balance| {
+                balance.token.address.as_deref().and_then(|address| address.parse().ok())
+            })
+            .map(|token| token.filter(|_| base_rate.is_some()))
+            .collect();
+        let total = tokens.len();
+        let prices = fan_out_with_progress(
+            self.fan_out_concurrency,
+            tokens,
+            |token| {
+                let service = self.clone();
+                async move {
+                    let Some(token) = token else { return Ok(None) };
+                    let price = service.get_price(token, QuoteCurrency::USD).await.ok();
+                    Ok(price.and_then(|info| Decimal::from_str(&info.price).ok()))
+                }
+            },
+            on_progress,
+        )
+        .await;
+        let token_usd_prices = match prices {
+            Ok(prices) => prices,
+            Err(e) => {
+                wallet.warnings.push(Warning::new(
+                    WarningCode::ValueUnavailable,
+                    format!("Token prices are unavailable ({}); token balances are not valued", e),
+                ));
+                vec![None; total]
+            }
+        };
+
+        apply_valuation(wallet, base, base_rate, eth_usd, &token_usd_prices);
+    }
+
+    /// Fetch the ETH/USD rate, rejecting zero.
+    async fn eth_usd_rate(&self) -> Result<Decimal> {
+        let info = self.get_price(self.eth_quote_token()?, QuoteCurrency::USD).await?;
+        match Decimal::from_str(&info.price).map_err(|e| AppError::Parse(e.to_string()))? {
+            rate if rate.is_zero() => {
+                Err(AppError::PriceOracle("Invalid ETH/USD rate".to_string()))
+            }
+            rate => Ok(rate),
+        }
+    }
+
+    /// Check whether the current price satisfies `price <operator> threshold`.
+    ///
+    /// `tolerance` is a percentage of the threshold used for `==` comparisons.
+    pub async fn check_price_alert(
+        &self,
+        token_address: Address,
+        quote_currency: QuoteCurrency,
+        operator: ComparisonOperator,
+        threshold: Decimal,
+        tolerance: Decimal,
+    ) -> Result<PriceAlertResult> {
+        let price = self.get_price(token_address, quote_currency).await?;
+
+        let current = Decimal::from_str(&price.price)
+            .map_err(|e| AppError::Parse(format!("Invalid price '{}': {}", price.price, e)))?;
+        let triggered = operator.evaluate(current, threshold, tolerance);
+
+        tracing::debug!(
+            token = %token_address,
+            price = %current,
+            operator = ?operator,
+            threshold = %threshold,
+            triggered = triggered,
+            "Checked price alert"
+        );
+
+        Ok(PriceAlertResult {
+            price,
+            operator,
+            threshold: threshold.to_string(),
+            tolerance: tolerance.to_string(),
+            triggered,
+        })
+    }
+
+    /// Compare a token's Chainlink USD price with its Uniswap USD price.
+    ///
+    /// Both sources are read concurrently. Spreads above `max_spread_bps`
+    /// (either way) are flagged with a warning.
+    pub async fn source_spread(
+        &self,
+        token_address: Address,
+        max_spread_bps: u32,
+    ) -> Result<PriceSourceSpread> {
+        let metadata = self.balance_service.get_token_metadata(token_address).await?;
+        let feed_address =
+            self.chainlink_feed(token_address, QuoteCurrency::USD).ok_or_else(|| {
+                AppError::PriceOracle(format!(
+                    "No Chainlink feed is known for {} on this network",
+                    metadata.symbol
+                ))
+            })?;
+
+        let (chainlink, uniswap) = tokio::try_join!(
+            self.get_chainlink_price(
+                token_address,
+                feed_address,
+                &metadata.symbol,
+                metadata.decimals
+            ),
+            self.get_uniswap_price(
+                token_address,
+                QuoteCurrency::USD,
+                &metadata.symbol,
+                metadata.decimals
+            ),
+        )?;
+
+        // USDC stands in for USD, so its own Uniswap price is an identity of 1;
+        // price it against the wrapped native token instead
+        let uniswap = if uniswap.source == PriceSource::Identity {
+            self.uniswap_usd_price_via_eth(token_address, &metadata.symbol, metadata.decimals)
+                .await?
+        } else {
+            uniswap
+        };
+
+        let spread = PriceSourceSpread::new(
+            TokenInfo::erc20(token_address, metadata.symbol, metadata.decimals),
+            chainlink,
+            uniswap,
+            max_spread_bps,
+        )
+        .map_err(AppError::PriceOracle)?;
+
+        tracing::debug!(
+            token = %token_address,
+            spread_bps = %spread.spread_bps,
+            diverging = spread.diverging,
+            "Compared Chainlink and Uniswap prices"
+        );
+
+        Ok(spread)
+    }
+
+    /// Uniswap USD price of the USD quote token itself, from its pool price in the
+    /// wrapped native token times Chainlink's ETH/USD.
+    async fn uniswap_usd_price_via_eth(
+        &self,
+        token_address: Address,
+        symbol: &str,
+        decimals: u8,
+    ) -> Result<PriceInfo> {
+        let wrapped = self.eth_quote_token()?;
+        let eth_feed = self.chainlink_feed(wrapped, QuoteCurrency::USD).ok_or_else(|| {
+            AppError::PriceOracle(format!(
+                "{} is the USD quote token and no Chainlink ETH/USD feed is known to price it \
+                 through its ETH pool",
+                symbol
+            ))
+        })?;
+
+        let (in_eth, eth_usd) = tokio::try_join!(
+            self.get_uniswap_price(token_address, QuoteCurrency::ETH, symbol, decimals),
+            self.get_chainlink_price(wrapped, eth_feed, "WETH", 18),
+        )?;
+        price_via_eth(in_eth, &eth_usd)
+    }
+
+    /// Get the liquidity-weighted average price across all Uniswap venues.
+    ///
+    /// Quotes 1 whole token on the V2 pair of each V2 venue (e.g., Uniswap and
+    /// Sushiswap) and on every V3 fee tier, fee and price impact included, then weights
+    /// each venue's price by its pool liquidity `L`:
+    /// - V3: the pool's current in-range `liquidity()`
+    /// - V2: `sqrt(reserve0 * reserve1)`, the equivalent constant-product liquidity
+    ///
+    /// Both measures share the same units, so a thin pool contributes little to the
+    /// result even when its quote diverges. Chainlink is not consulted.
+    pub async fn get_aggregate_price(
+        &self,
+        token_address: Address,
+        quote_currency: QuoteCurrency,
+    ) -> Result<PriceInfo> {
+        tracing::debug!(
+            token = %token_address,
+            quote = ?quote_currency,
+            "Fetching aggregated Uniswap price"
+        );
+
+        let metadata = self.balance_service.get_token_metadata(token_address).await?;
+
+        self.aggregate_price_with_metadata(&metadata, quote_currency).await
+    }
+
+    /// Aggregate price of a token whose metadata has already been read.
+    async fn aggregate_price_with_metadata(
+        &self,
+        metadata: &TokenMetadata,
+        quote_currency: QuoteCurrency,
+    ) -> Result<PriceInfo> {
+        let price = self.fetch_aggregate_price(metadata, quote_currency).await;
+        self.with_stale_fallback((metadata.address, quote_currency, true), price)
+    }
+
+    /// Fetch the aggregate price of a token, without the stale fallback.
+    async fn fetch_aggregate_price(
+        &self,
+        metadata: &TokenMetadata,
+        quote_currency: QuoteCurrency,
+    ) -> Result<PriceInfo> {
+        let token_address = metadata.address;
+
+        // Pegged tokens may have no pools against the quote token; defer to the peg
+        if self.is_pegged(token_address, quote_currency) {
+            return self.fetch_price(metadata, quote_currency).await;
+        }
+
+        let quote_token = self.quote_token(quote_currency)?;
+        if token_address == quote_token {
+            return Ok(identity_price(
+                TokenInfo::erc20(token_address, metadata.symbol.clone(), metadata.decimals),
+                quote_currency,
+            ));
+        }
+
+        let mut quotes =
+            self.get_v3_venue_quotes(token_address, quote_token, metadata.decimals).await;
+        for &venue in &self.v2_venues {
+            let factory = v2_deployment(venue, &self.uniswap).factory;
+            if let Ok(quote) = self
+                .get_v2_venue_quote(factory, token_address, quote_token, metadata.decimals)
+                .await
+            {
+                quotes.push(quote);
+            }
+        }
+
+        tracing::debug!(venues = quotes.len(), "Collected Uniswap venue quotes");
+
+        let price = liquidity_weighted_price(&quotes).ok_or(AppError::PoolNotFound)?;
+
+        Ok(PriceInfo {
+            token: TokenInfo::erc20(token_address, metadata.symbol.clone(), metadata.decimals),
+            price: price.round_dp(18).normalize().to_string(),
+            quote_currency,
+            source: PriceSource::UniswapAggregate,
+            timestamp: current_timestamp(),
+            confidence: Some(venue_confidence(&quotes, price)),
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Quote every V3 fee tier that has a pool with non-zero liquidity.
+    async fn get_v3_venue_quotes(
+        &self,
+        token_in: Address,
+        token_out: Address,
+        token_in_decimals: u8,
+    ) -> Vec<VenueQuote> {
+        let factory =
+            IUniswapV3Factory::new(self.uniswap.v3_factory, self.client.provider().clone());
+        let reference = self.reference_amount(token_in);
+        let Ok(amount_in) = reference_amount_in(reference, token_in_decimals) else {
+            return Vec::new();
+        };
+        let mut quotes = Vec::new();
+
+        for fee in fee_tiers::ALL_FEES {
+            let fee_u24 = U24::from(fee);
+            let pool_address = match factory.getPool(token_in, token_out, fee_u24).call().await {
+                Ok(addr) if addr != Address::ZERO => addr,
+                _ => continue,
+            };
+
+            let pool = IUniswapV3Pool::new(pool_address, self.client.provider().clone());
+            let liquidity = match pool.liquidity().call().await {
+                Ok(l) if l > 0 => l,
+                _ => continue,
+            };
+
+            let Ok(amount_out) = quoter::quote_exact_input_single(
+                self.client.provider(),
+                self.uniswap.v3_quoter,
+                token_in,
+                token_out,
+                fee,
+                amount_in,
+            )
+            .await
+            else {
+                continue;
+            };
+            let Ok(amount_out) = u128::try_from(amount_out) else {
+                continue;
+            };
+            let price = Decimal::from(amount_out)
+                / Decimal::from(10i64.pow(quote_token_decimals(token_out)))
+                / reference;
+
+            quotes.push(VenueQuote { price, liquidity });
+        }
+
+        quotes
+    }
+
+    /// Quote the V2 pair on `factory`, weighting it by `sqrt(reserve0 * reserve1)`.
+    ///
+    /// The pair is quoted for the same reference amount as the V3 pools, fee and
+    /// price impact included, so every venue's price is measured the same way.
+    async fn get_v2_venue_quote(
+        &self,
+        factory: Address,
+        token_in: Address,
+        token_out: Address,
+        token_in_decimals: u8,
+    ) -> Result<VenueQuote> {
+        let factory = IUniswapV2Factory::new(factory, self.client.provider().clone());
+        let pair_address: Address = factory.getPair(token_in, token_out).call().await?;
+
+        if pair_address == Address::ZERO {
+            return Err(AppError::PoolNotFound);
+        }
+
+        let block = self.client.confirmed_block().await?;
+        let pair = IUniswapV2Pair::new(pair_address, self.client.provider().clone());
+        let reserves = pair.getReserves().block(block_id(block)).call().await?;
+        let token0: Address = pair.token0().call().await?;
+
+        let (reserve0, reserve1) = (U256::from(reserves.reserve0), U256::from(reserves.reserve1));
+        let liquidity: u128 = (reserve0 * reserve1).root(2).try_into().map_err(|_| {
+            AppError::NumericOverflow("Uniswap V2 liquidity exceeds u128 range".to_string())
+        })?;
+
+        let (reserve_in, reserve_out) =
+            if token0 == token_in { (reserve0, reserve1) } else { (reserve1, reserve0) };
+        let price = v2_reference_price(
+            self.reference_amount(token_in),
+            token_in_decimals,
+            quote_token_decimals(token_out),
+            reserve_in,
+            reserve_out,
+        )?;
+
+        Ok(VenueQuote { price, liquidity })
+    }
+
+    /// Price a pegged token.
+    ///
+    /// USD-pegged tokens use their Chainlink feed when one is available so small
+    /// deviations from the peg are reported; otherwise the price is exactly 1.
+    async fn get_pegged_price(
+        &self,
+        token_address: Address,
+        quote_currency: QuoteCurrency,
+        symbol: &str,
+        decimals: u8,
+    ) -> Result<PriceInfo> {
+        if quote_currency == QuoteCurrency::USD {
+            if let Some(feed_address) = self.chainlink_feeds.get(&token_address) {
+                match self.get_chainlink_price(token_address, *feed_address, symbol, decimals).await
+                {
+                    Ok(price_info) => return Ok(price_info),
+                    Err(e) => {
+                        tracing::debug!(token = %token_address, error = %e, "Chainlink peg check failed, assuming 1:1");
+                    }
+                }
+            }
+        }
+
+        Ok(PriceInfo {
+            token: TokenInfo::erc20(token_address, symbol.to_string(), decimals),
+            price: "1".to_string(),
+            quote_currency,
+            source: PriceSource::Peg,
+            timestamp: current_timestamp(),
+            confidence: Some(PriceConfidence::Medium),
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: vec![Warning::new(
+                WarningCode::PeggedPrice,
+                format!(
+                    "{} is assumed to trade 1:1 with {:?}; this is not a market price",
+                    symbol, quote_currency
+                ),
+            )],
+        })
+    }
+
     /// Get price from Chainlink oracle.
     ///
     /// Validates that the price data is fresh and positive:
-    /// - Answer must be positive (> 0)
+    /// - Answer must be positive (> 0), unless the feed is configured as signed
     /// - Data must not be stale (answeredInRound >= roundId)
     /// - UpdatedAt must be recent (within 1 hour for most feeds)
     async fn get_chainlink_price(
@@ -137,32 +925,21 @@ impl PriceService {
             .try_into()
             .map_err(|_| AppError::NumericOverflow("updatedAt timestamp overflow".to_string()))?;
         const STALENESS_THRESHOLD: u64 = 3600; // 1 hour
-        if now > updated_at && now - updated_at > STALENESS_THRESHOLD {
+        let age = now.saturating_sub(updated_at);
+        if age > STALENESS_THRESHOLD {
             return Err(AppError::PriceOracle(format!(
                 "Stale Chainlink data: last update was {} seconds ago (threshold: {})",
-                now - updated_at,
-                STALENESS_THRESHOLD
-            )));
-        }
-
-        // 3. Check that answer is positive
-        if round_data.answer.is_negative() || round_data.answer.is_zero() {
-            return Err(AppError::PriceOracle(format!(
-                "Invalid Chainlink answer: {} (must be positive)",
-                round_data.answer
+                age, STALENESS_THRESHOLD
             )));
         }
 
-        // Convert I256 answer to i128 with overflow check
-        let answer_str = round_data.answer.to_string();
-        let answer_i128: i128 = answer_str.parse().map_err(|_| {
-            AppError::NumericOverflow(format!(
-                "Chainlink answer {} exceeds i128 range",
-                round_data.answer
-            ))
-        })?;
-
-        let price = Decimal::from(answer_i128) / Decimal::from(10i64.pow(decimals as u32));
+        // 3. Check the answer's sign and scale it by the feed's decimals
+        let sign = if self.signed_feeds.contains(&feed_address) {
+            AnswerSign::Signed
+        } else {
+            AnswerSign::Positive
+        };
+        let price = chainlink_answer_to_price(round_data.answer, decimals, sign)?;
 
         Ok(PriceInfo {
             token: TokenInfo::erc20(token_address, symbol.to_string(), token_decimals),
@@ -170,6 +947,11 @@ impl PriceService {
             quote_currency: QuoteCurrency::USD,
             source: PriceSource::Chainlink,
             timestamp: current_timestamp(),
+            confidence: Some(PriceConfidence::High),
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: oracle_age_warning(age, STALENESS_THRESHOLD).into_iter().collect(),
         })
     }
 
@@ -181,15 +963,17 @@ impl PriceService {
         symbol: &str,
         decimals: u8,
     ) -> Result<PriceInfo> {
-        // For ETH quote, use WETH pair
+        // For ETH quote, use the wrapped native token pair
         // For USD quote, use USDC pair or WETH->USDC
-        let quote_token = match quote_currency {
-            QuoteCurrency::ETH => WETH_ADDRESS,
-            QuoteCurrency::USD => {
-                // Use USDC as USD proxy
-                crate::ethereum::contracts::USDC_ADDRESS
-            }
-        };
+        let quote_token = self.quote_token(quote_currency)?;
+
+        // The quote token has no pool against itself; it is worth exactly one of itself
+        if token_address == quote_token {
+            return Ok(identity_price(
+                TokenInfo::erc20(token_address, symbol.to_string(), decimals),
+                quote_currency,
+            ));
+        }
 
         // Try V3 first with common fee tiers
         if let Ok(price) = self.get_uniswap_v3_price(token_address, quote_token, decimals).await {
@@ -199,17 +983,40 @@ impl PriceService {
                 quote_currency,
                 source: PriceSource::UniswapV3,
                 timestamp: current_timestamp(),
+                confidence: Some(PriceConfidence::Medium),
+                block_number: None,
+                stale: false,
+                age_secs: None,
+                warnings: Vec::new(),
             });
         }
 
-        // Fall back to V2
-        if let Ok(price) = self.get_uniswap_v2_price(token_address, quote_token, decimals).await {
+        // Fall back to the deepest pair across the V2 venues
+        let block = self.client.confirmed_block().await?;
+        let mut deepest: Option<(Decimal, Decimal)> = None;
+        for &venue in &self.v2_venues {
+            let factory = v2_deployment(venue, &self.uniswap).factory;
+            if let Ok((price, quote_reserve)) = self
+                .get_uniswap_v2_price(factory, token_address, quote_token, decimals, block)
+                .await
+            {
+                if deepest.is_none_or(|(_, deepest_reserve)| quote_reserve > deepest_reserve) {
+                    deepest = Some((price, quote_reserve));
+                }
+            }
+        }
+        if let Some((price, quote_reserve)) = deepest {
             return Ok(PriceInfo {
                 token: TokenInfo::erc20(token_address, symbol.to_string(), decimals),
                 price: price.to_string(),
                 quote_currency,
                 source: PriceSource::UniswapV2,
                 timestamp: current_timestamp(),
+                confidence: Some(reserve_confidence(quote_reserve, quote_currency)),
+                block_number: block,
+                stale: false,
+                age_secs: None,
+                warnings: Vec::new(),
             });
         }
 
@@ -217,41 +1024,42 @@ impl PriceService {
     }
 
     /// Get price from Uniswap V3.
+    ///
+    /// Quotes the token's reference amount, so the price includes that trade's
+    /// price impact and fees: a smaller reference tracks the spot price more
+    /// closely, down to the precision the quote token's decimals allow.
     async fn get_uniswap_v3_price(
         &self,
         token_in: Address,
         token_out: Address,
         token_in_decimals: u8,
     ) -> Result<Decimal> {
-        let quoter = IQuoterV2::new(UNISWAP_V3_QUOTER, self.client.provider().clone());
+        let reference = self.reference_amount(token_in);
+        let amount_in = reference_amount_in(reference, token_in_decimals)?;
 
         // Try each fee tier
         for fee in fee_tiers::ALL_FEES {
-            // Fee tiers are u32, convert to u24 (safe as all fee tiers are < 2^24)
-            let fee_u24 = match u32::try_into(fee) {
-                Ok(f) => f,
-                Err(_) => continue, // Skip invalid fee tiers
-            };
-            let params = IQuoterV2::QuoteExactInputSingleParams {
-                tokenIn: token_in,
-                tokenOut: token_out,
-                amountIn: U256::from(10u64.pow(token_in_decimals as u32)), // 1 token
-                fee: fee_u24,
-                sqrtPriceLimitX96: U160::ZERO,
-            };
-
-            if let Ok(result) = quoter.quoteExactInputSingle(params).call().await {
+            if let Ok(quoted) = quoter::quote_exact_input_single(
+                self.client.provider(),
+                self.uniswap.v3_quoter,
+                token_in,
+                token_out,
+                fee,
+                amount_in,
+            )
+            .await
+            {
                 // Convert to price (assuming 6 decimals for USDC, 18 for WETH)
-                let out_decimals =
-                    if token_out == crate::ethereum::contracts::USDC_ADDRESS { 6 } else { 18 };
+                let out_decimals = quote_token_decimals(token_out);
 
-                let amount_out: u128 = result.amountOut.try_into().map_err(|_| {
+                let amount_out: u128 = quoted.try_into().map_err(|_| {
                     AppError::NumericOverflow(format!(
                         "Uniswap V3 quote amountOut {} exceeds u128 range",
-                        result.amountOut
+                        quoted
                     ))
                 })?;
-                let price = Decimal::from(amount_out) / Decimal::from(10i64.pow(out_decimals));
+                let price =
+                    Decimal::from(amount_out) / Decimal::from(10i64.pow(out_decimals)) / reference;
 
                 return Ok(price);
             }
@@ -260,14 +1068,19 @@ impl PriceService {
         Err(AppError::PoolNotFound)
     }
 
-    /// Get price from Uniswap V2.
+    /// Get price from the reserves of the V2 pair on `factory` at `block` (`None`
+    /// for the latest block).
+    ///
+    /// Returns the price and the pair's `token_out` reserve in whole tokens.
     async fn get_uniswap_v2_price(
         &self,
+        factory: Address,
         token_in: Address,
         token_out: Address,
         token_in_decimals: u8,
-    ) -> Result<Decimal> {
-        let factory = IUniswapV2Factory::new(UNISWAP_V2_FACTORY, self.client.provider().clone());
+        block: Option<u64>,
+    ) -> Result<(Decimal, Decimal)> {
+        let factory = IUniswapV2Factory::new(factory, self.client.provider().clone());
 
         // getPair returns Address directly (tuple with single element)
         let pair_address: Address = factory.getPair(token_in, token_out).call().await?;
@@ -278,7 +1091,7 @@ impl PriceService {
 
         let pair = IUniswapV2Pair::new(pair_address, self.client.provider().clone());
 
-        let reserves = pair.getReserves().call().await?;
+        let reserves = pair.getReserves().block(block_id(block)).call().await?;
         let token0: Address = pair.token0().call().await?;
 
         // Determine which reserve is which
@@ -289,8 +1102,7 @@ impl PriceService {
         };
 
         // Calculate price
-        let out_decimals =
-            if token_out == crate::ethereum::contracts::USDC_ADDRESS { 6 } else { 18 };
+        let out_decimals = quote_token_decimals(token_out);
 
         // Convert U112 reserves to u128 for Decimal with overflow check
         let reserve_in_u128: u128 = reserve_in.try_into().map_err(|_| {
@@ -316,14 +1128,195 @@ impl PriceService {
             * Decimal::from(10i64.pow(token_in_decimals as u32))
             / Decimal::from(reserve_in_u128)
             / Decimal::from(10i64.pow(out_decimals));
+        let quote_reserve =
+            Decimal::from(reserve_out_u128) / Decimal::from(10i64.pow(out_decimals));
+
+        Ok((price, quote_reserve))
+    }
+}
+
+/// Convert a price in ETH to USD at the `eth_usd` price of ETH.
+///
+/// Keeps the ETH price's source and confidence, and the warnings of both.
+fn price_via_eth(in_eth: PriceInfo, eth_usd: &PriceInfo) -> Result<PriceInfo> {
+    let parse = |info: &PriceInfo| {
+        Decimal::from_str(&info.price).map_err(|e| {
+            AppError::PriceOracle(format!("Invalid {} price '{}': {}", info.source, info.price, e))
+        })
+    };
+    let price = parse(&in_eth)?
+        .checked_mul(parse(eth_usd)?)
+        .ok_or_else(|| AppError::NumericOverflow("price in USD overflows".to_string()))?;
+
+    let mut warnings = in_eth.warnings;
+    warnings.extend(eth_usd.warnings.iter().cloned());
+    Ok(PriceInfo {
+        price: price.normalize().to_string(),
+        quote_currency: QuoteCurrency::USD,
+        warnings,
+        ..in_eth
+    })
+}
+
+/// Price of the quote token in its own quote currency: exactly 1.
+fn identity_price(token: TokenInfo, quote_currency: QuoteCurrency) -> PriceInfo {
+    PriceInfo {
+        token,
+        price: "1".to_string(),
+        quote_currency,
+        source: PriceSource::Identity,
+        timestamp: current_timestamp(),
+        confidence: Some(PriceConfidence::High),
+        block_number: None,
+        stale: false,
+        age_secs: None,
+        warnings: Vec::new(),
+    }
+}
+
+/// Raw input amount for a quote of `reference` whole tokens with `decimals`.
+///
+/// Fails when the reference is not positive or rounds to zero raw units.
+fn reference_amount_in(reference: Decimal, decimals: u8) -> Result<U256> {
+    let raw = 10u64
+        .checked_pow(decimals as u32)
+        .and_then(|scale| reference.checked_mul(Decimal::from(scale)))
+        .and_then(|raw| raw.trunc().to_u128());
+    match raw {
+        Some(raw) if raw > 0 => Ok(U256::from(raw)),
+        _ => Err(AppError::PriceOracle(format!(
+            "Reference amount {} is not a positive amount of a {}-decimal token",
+            reference, decimals
+        ))),
+    }
+}
 
-        Ok(price)
+/// Price per token of selling `reference` whole tokens into a V2 pair.
+///
+/// Like a V3 quoter price, it includes the pool fee and the trade's price impact.
+fn v2_reference_price(
+    reference: Decimal,
+    token_in_decimals: u8,
+    out_decimals: u32,
+    reserve_in: U256,
+    reserve_out: U256,
+) -> Result<Decimal> {
+    let amount_in = reference_amount_in(reference, token_in_decimals)?;
+    let amount_out =
+        v2_amount_out(amount_in, reserve_in, reserve_out).ok_or(AppError::InsufficientLiquidity)?;
+    let amount_out: u128 = amount_out.try_into().map_err(|_| {
+        AppError::NumericOverflow(format!("Uniswap V2 amountOut {} exceeds u128 range", amount_out))
+    })?;
+
+    Ok(Decimal::from(amount_out) / Decimal::from(10i64.pow(out_decimals)) / reference)
+}
+
+/// Fill in per-balance values and the total, given USD prices and the base rate
+/// (the price of one unit of `base` in USD).
+///
+/// Without a base rate no balance is valued and there is no total.
+fn apply_valuation(
+    wallet: &mut WalletInfo,
+    base: QuoteCurrency,
+    base_rate: Option<Decimal>,
+    eth_usd: Option<Decimal>,
+    token_usd_prices: &[Option<Decimal>],
+) {
+    let mut total = Decimal::ZERO;
+    let mut unpriced = Vec::new();
+    let mut value = |balance: &mut BalanceInfo, usd_price: Option<Decimal>| {
+        let amount = Decimal::from_str(&balance.balance).ok();
+        match amount.zip(usd_price).and_then(|(a, p)| a.checked_mul(p)).zip(base_rate) {
+            Some((usd, base_rate)) => {
+                let in_base = usd / base_rate;
+                total += in_base;
+                balance.value = Some(format_value(in_base, base));
+            }
+            None => {
+                balance.warnings.push(Warning::new(
+                    WarningCode::ValueUnavailable,
+                    format!("{} could not be priced in {:?}", balance.token.symbol, base),
+                ));
+                unpriced.push(balance.token.symbol.clone());
+            }
+        }
+    };
+
+    if let Some(eth) = wallet.eth_balance.as_mut() {
+        value(eth, eth_usd);
+    }
+    for (balance, usd_price) in wallet.token_balances.iter_mut().zip(token_usd_prices) {
+        value(balance, *usd_price);
+    }
+
+    wallet.base_currency = Some(base);
+    wallet.total_value = base_rate.map(|_| format_value(total, base));
+    wallet.unpriced_tokens = unpriced;
+}
+
+/// Mark a previously fetched price as stale, `now - timestamp` seconds old.
+fn stale_price(mut info: PriceInfo, now: u64, error: &AppError) -> PriceInfo {
+    let age = now.saturating_sub(info.timestamp);
+    info.stale = true;
+    info.age_secs = Some(age);
+    info.confidence = Some(PriceConfidence::Low);
+    info.warnings.push(Warning::new(
+        WarningCode::StalePrice,
+        format!(
+            "Every price source failed ({}); this is the last known price, {} seconds old",
+            error, age
+        ),
+    ));
+    info
+}
+
+/// Which Chainlink answers are accepted as valid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnswerSign {
+    /// Only answers above zero, as for asset prices.
+    #[default]
+    Positive,
+    /// Any answer, including zero and negative ones, as for spread or rate feeds.
+    Signed,
+}
+
+/// Convert a Chainlink answer to a price, scaling by the feed's decimals.
+///
+/// USD feeds report 8 decimals, so an answer of `250012345678` is $2500.12345678.
+/// With [`AnswerSign::Positive`], zero and negative answers are rejected.
+pub fn chainlink_answer_to_price(answer: I256, decimals: u8, sign: AnswerSign) -> Result<Decimal> {
+    if sign == AnswerSign::Positive && (answer.is_negative() || answer.is_zero()) {
+        return Err(AppError::PriceOracle(format!(
+            "Invalid Chainlink answer: {} (must be positive)",
+            answer
+        )));
+    }
+
+    if decimals as u32 > Decimal::MAX_SCALE {
+        return Err(AppError::NumericOverflow(format!(
+            "Chainlink feed decimals {} too large",
+            decimals
+        )));
+    }
+    // Decimal holds a 96-bit mantissa, so bound the answer before scaling it
+    let overflow =
+        || AppError::NumericOverflow(format!("Chainlink answer {} exceeds Decimal range", answer));
+    let value = i128::try_from(answer).map_err(|_| overflow())?;
+    Decimal::try_from_i128_with_scale(value, decimals as u32).map_err(|_| overflow())
+}
+
+/// Format a value in `base`: cents for USD, 8 decimals for ETH.
+fn format_value(value: Decimal, base: QuoteCurrency) -> String {
+    match base {
+        QuoteCurrency::USD => format!("{:.2}", value.round_dp(2)),
+        QuoteCurrency::ETH => value.round_dp(8).normalize().to_string(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy::{primitives::Bytes, sol_types::SolValue, transports::mock::Asserter};
 
     #[test]
     fn test_chainlink_feeds_contains_common_tokens() {
@@ -332,6 +1325,91 @@ mod tests {
         assert!(feeds.contains_key(&WETH_ADDRESS));
     }
 
+    #[test]
+    fn test_weth_usd_uses_eth_usd_feed() {
+        use crate::ethereum::contracts::chainlink::ETH_USD_FEED;
+
+        assert_eq!(get_chainlink_feeds().get(&WETH_ADDRESS), Some(&ETH_USD_FEED));
+
+        // WETH in USD goes to Chainlink, not the peg or Uniswap path
+        let service = test_service();
+        assert!(!service.is_pegged(WETH_ADDRESS, QuoteCurrency::USD));
+        assert_eq!(service.chainlink_feed(WETH_ADDRESS, QuoteCurrency::USD), Some(ETH_USD_FEED));
+        assert_eq!(service.chainlink_feed(WETH_ADDRESS, QuoteCurrency::ETH), None);
+    }
+
+    #[test]
+    fn test_chainlink_answer_to_price() {
+        // ETH/USD feed answers have 8 decimals
+        let price = chainlink_answer_to_price(
+            I256::try_from(250_012_345_678i64).unwrap(),
+            8,
+            AnswerSign::Positive,
+        );
+        assert_eq!(price.unwrap(), Decimal::from_str("2500.12345678").unwrap());
+
+        assert!(matches!(
+            chainlink_answer_to_price(I256::ZERO, 8, AnswerSign::Positive),
+            Err(AppError::PriceOracle(_))
+        ));
+        assert!(matches!(
+            chainlink_answer_to_price(I256::MINUS_ONE, 8, AnswerSign::Positive),
+            Err(AppError::PriceOracle(_))
+        ));
+        assert!(matches!(
+            chainlink_answer_to_price(I256::MAX, 8, AnswerSign::Positive),
+            Err(AppError::NumericOverflow(_))
+        ));
+        assert!(matches!(
+            chainlink_answer_to_price(I256::ONE, 29, AnswerSign::Positive),
+            Err(AppError::NumericOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_chainlink_answer_bounds() {
+        // The largest answer Decimal can hold is 2^96 - 1
+        let max = I256::try_from(i128::from(u64::MAX) << 32 | i128::from(u32::MAX)).unwrap();
+        assert_eq!(chainlink_answer_to_price(max, 0, AnswerSign::Positive).unwrap(), Decimal::MAX);
+        assert!(matches!(
+            chainlink_answer_to_price(max + I256::ONE, 0, AnswerSign::Positive),
+            Err(AppError::NumericOverflow(_))
+        ));
+        assert_eq!(chainlink_answer_to_price(-max, 0, AnswerSign::Signed).unwrap(), Decimal::MIN);
+        assert!(matches!(
+            chainlink_answer_to_price(-max - I256::ONE, 0, AnswerSign::Signed),
+            Err(AppError::NumericOverflow(_))
+        ));
+
+        // Beyond i128 on either side
+        assert!(matches!(
+            chainlink_answer_to_price(I256::MIN, 8, AnswerSign::Signed),
+            Err(AppError::NumericOverflow(_))
+        ));
+        assert!(matches!(
+            chainlink_answer_to_price(I256::MAX, 8, AnswerSign::Signed),
+            Err(AppError::NumericOverflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_chainlink_signed_answers() {
+        assert_eq!(
+            chainlink_answer_to_price(I256::ZERO, 8, AnswerSign::Signed).unwrap(),
+            Decimal::ZERO
+        );
+        assert_eq!(
+            chainlink_answer_to_price(I256::MINUS_ONE, 8, AnswerSign::Signed).unwrap(),
+            Decimal::from_str("-0.00000001").unwrap()
+        );
+        // A rate feed reporting -0.25% with 18 decimals
+        let rate = I256::try_from(-2_500_000_000_000_000i64).unwrap();
+        assert_eq!(
+            chainlink_answer_to_price(rate, 18, AnswerSign::Signed).unwrap(),
+            Decimal::from_str("-0.0025").unwrap()
+        );
+    }
+
     #[test]
     fn test_quote_currency_parsing() {
         assert_eq!("USD".parse::<QuoteCurrency>().unwrap(), QuoteCurrency::USD);
@@ -358,6 +1436,100 @@ mod tests {
         assert_eq!(price, Decimal::from(2));
     }
 
+    #[test]
+    fn test_quote_token_decimals() {
+        assert_eq!(quote_token_decimals(crate::ethereum::contracts::USDC_ADDRESS), 6);
+        assert_eq!(quote_token_decimals(WETH_ADDRESS), 18);
+    }
+
+    #[test]
+    fn test_v2_reference_price_includes_fee_and_impact() {
+        let weth = U256::from(1000u64) * U256::from(10u64).pow(U256::from(18));
+        let usdc = U256::from(3_000_000u64) * U256::from(10u64).pow(U256::from(6));
+
+        let price = v2_reference_price(Decimal::ONE, 18, 6, weth, usdc).unwrap();
+
+        // The reserve spot price is 3000; 0.3% fee and 0.1% impact come off it
+        assert!(price < Decimal::from(2991), "{}", price);
+        assert!(price > Decimal::from(2987), "{}", price);
+
+        assert!(matches!(
+            v2_reference_price(Decimal::ONE, 18, 6, U256::ZERO, usdc),
+            Err(AppError::InsufficientLiquidity)
+        ));
+    }
+
+    #[test]
+    fn test_liquidity_weighted_price_single_venue() {
+        let quotes = [VenueQuote { price: Decimal::from(3000), liquidity: 1_000 }];
+        assert_eq!(liquidity_weighted_price(&quotes), Some(Decimal::from(3000)));
+    }
+
+    #[test]
+    fn test_liquidity_weighted_price_thin_pool_has_little_effect() {
+        // Deep pool at 3000, thin pool (1% of the liquidity) badly skewed at 6000
+        let quotes = [
+            VenueQuote { price: Decimal::from(3000), liquidity: 99_000_000 },
+            VenueQuote { price: Decimal::from(6000), liquidity: 1_000_000 },
+        ];
+        let price = liquidity_weighted_price(&quotes).unwrap();
+
+        // (3000 * 0.99) + (6000 * 0.01) = 3030
+        assert_eq!(price.round_dp(6), Decimal::from(3030));
+    }
+
+    #[test]
+    fn test_liquidity_weighted_price_large_liquidity_no_overflow() {
+        let quotes = [
+            VenueQuote { price: Decimal::from(100_000), liquidity: u128::MAX / 2 },
+            VenueQuote { price: Decimal::from(100_000), liquidity: u128::MAX / 4 },
+        ];
+        // u128::MAX exceeds Decimal range, so this should bail out cleanly
+        assert_eq!(liquidity_weighted_price(&quotes), None);
+
+        let quotes = [
+            VenueQuote { price: Decimal::from(100_000), liquidity: 10u128.pow(27) },
+            VenueQuote { price: Decimal::from(100_000), liquidity: 10u128.pow(26) },
+        ];
+        assert_eq!(liquidity_weighted_price(&quotes).unwrap().round_dp(6), Decimal::from(100_000));
+    }
+
+    #[test]
+    fn test_liquidity_weighted_price_empty_or_zero() {
+        assert_eq!(liquidity_weighted_price(&[]), None);
+
+        let quotes = [VenueQuote { price: Decimal::from(1), liquidity: 0 }];
+        assert_eq!(liquidity_weighted_price(&quotes), None);
+    }
+
+    #[test]
+    fn test_venue_confidence_from_spread() {
+        let venues = |prices: &[i64]| -> Vec<VenueQuote> {
+            prices.iter().map(|p| VenueQuote { price: Decimal::from(*p), liquidity: 1 }).collect()
+        };
+        let price = Decimal::from(1000);
+
+        // A single venue has nothing to agree with
+        assert_eq!(venue_confidence(&venues(&[1000]), price), PriceConfidence::Medium);
+        // 0.5% spread
+        assert_eq!(venue_confidence(&venues(&[998, 1003]), price), PriceConfidence::High);
+        // 3% spread
+        assert_eq!(venue_confidence(&venues(&[985, 1015]), price), PriceConfidence::Medium);
+        // 20% spread
+        assert_eq!(venue_confidence(&venues(&[900, 1100]), price), PriceConfidence::Low);
+    }
+
+    #[test]
+    fn test_reserve_confidence_flags_thin_pools() {
+        let usd = QuoteCurrency::USD;
+        assert_eq!(reserve_confidence(Decimal::from(5_000_000), usd), PriceConfidence::Medium);
+        assert_eq!(reserve_confidence(Decimal::from(20_000), usd), PriceConfidence::Low);
+
+        let eth = QuoteCurrency::ETH;
+        assert_eq!(reserve_confidence(Decimal::from(500), eth), PriceConfidence::Medium);
+        assert_eq!(reserve_confidence(Decimal::from(10), eth), PriceConfidence::Low);
+    }
+
     #[test]
     fn test_weth_eth_special_case_condition() {
         // WETH priced in ETH should be handled as a special case (1:1 ratio)
@@ -372,4 +1544,479 @@ mod tests {
         // WETH + USD quote should NOT trigger special case
         assert!(!(weth == WETH_ADDRESS && quote_usd == QuoteCurrency::ETH));
     }
+
+    fn test_service() -> PriceService {
+        let client = Arc::new(EthereumClient::new("http://localhost:8545").unwrap());
+        PriceService::new(client.clone(), BalanceService::new(client))
+    }
+
+    /// Push a `latestRoundData` answer updated just now, then the feed's decimals.
+    fn push_round(asserter: &Asserter, answer: I256, decimals: u8) {
+        let now = U256::from(current_timestamp());
+        asserter.push_success(&Bytes::from(
+            (U256::from(7u64), answer, now, now, U256::from(7u64)).abi_encode_params(),
+        ));
+        asserter.push_success(&Bytes::from(U256::from(decimals).abi_encode()));
+    }
+
+    #[tokio::test]
+    async fn test_signed_feed_accepts_negative_answer() {
+        let feed = Address::repeat_byte(0x42);
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = PriceService::new(client.clone(), BalanceService::new(client));
+
+        // Rejected from a price feed
+        push_round(&asserter, I256::try_from(-125_000_000i64).unwrap(), 8);
+        let err = service.get_chainlink_price(WETH_ADDRESS, feed, "WETH", 18).await.unwrap_err();
+        assert!(err.to_string().contains("must be positive"));
+
+        // Accepted from a feed configured as signed
+        let service = service.with_signed_feeds([feed]);
+        push_round(&asserter, I256::try_from(-125_000_000i64).unwrap(), 8);
+        let info = service.get_chainlink_price(WETH_ADDRESS, feed, "WETH", 18).await.unwrap();
+        assert_eq!(info.price, "-1.25000000");
+        assert_eq!(info.source, PriceSource::Chainlink);
+
+        push_round(&asserter, I256::ZERO, 8);
+        let info = service.get_chainlink_price(WETH_ADDRESS, feed, "WETH", 18).await.unwrap();
+        assert_eq!(Decimal::from_str(&info.price).unwrap(), Decimal::ZERO);
+    }
+
+    /// Push a V2 pair for `token` against the quote token with the given reserves.
+    fn push_v2_pair(asserter: &Asserter, token: Address, reserve_in: u128, reserve_out: u128) {
+        asserter.push_success(&Bytes::from(Address::repeat_byte(0x22).abi_encode()));
+        asserter.push_success(&Bytes::from(
+            (U256::from(reserve_in), U256::from(reserve_out), 0u32).abi_encode_params(),
+        ));
+        asserter.push_success(&Bytes::from(token.abi_encode()));
+    }
+
+    #[tokio::test]
+    async fn test_v2_fallback_prices_deepest_venue_pair() {
+        let token = Address::repeat_byte(1);
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = PriceService::new(client.clone(), BalanceService::new(client))
+            .with_v2_venues(vec![Venue::Uniswap, Venue::Sushiswap]);
+
+        // No V3 quote at any fee tier
+        for _ in fee_tiers::ALL_FEES {
+            asserter.push_failure_msg("execution reverted");
+        }
+        // A thin Uniswap V2 pair at 1000 USDC, then a deeper Sushiswap pair at 2000 USDC
+        push_v2_pair(&asserter, token, 10u128.pow(18), 1_000 * 10u128.pow(6));
+        push_v2_pair(&asserter, token, 10 * 10u128.pow(18), 20_000 * 10u128.pow(6));
+
+        let info = service.get_uniswap_price(token, QuoteCurrency::USD, "TKN", 18).await.unwrap();
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(Decimal::from_str(&info.price).unwrap(), Decimal::from(2000));
+        assert_eq!(info.source, PriceSource::UniswapV2);
+
+        // A token whose only pair is on Sushiswap
+        let service = service.with_v2_venues(vec![Venue::Sushiswap]);
+        for _ in fee_tiers::ALL_FEES {
+            asserter.push_failure_msg("execution reverted");
+        }
+        push_v2_pair(&asserter, token, 10u128.pow(18), 1_500 * 10u128.pow(6));
+
+        let info = service.get_uniswap_price(token, QuoteCurrency::USD, "TKN", 18).await.unwrap();
+        assert_eq!(Decimal::from_str(&info.price).unwrap(), Decimal::from(1500));
+    }
+
+    #[test]
+    fn test_default_pegs() {
+        use crate::ethereum::contracts::{DAI_ADDRESS, USDC_ADDRESS, USDT_ADDRESS};
+
+        let service = test_service();
+
+        for stablecoin in [USDC_ADDRESS, USDT_ADDRESS, DAI_ADDRESS] {
+            assert!(service.is_pegged(stablecoin, QuoteCurrency::USD));
+            assert!(!service.is_pegged(stablecoin, QuoteCurrency::ETH));
+        }
+        assert!(service.is_pegged(WETH_ADDRESS, QuoteCurrency::ETH));
+        assert!(!service.is_pegged(WETH_ADDRESS, QuoteCurrency::USD));
+    }
+
+    #[test]
+    fn test_peg_overrides() {
+        use crate::ethereum::contracts::{USDC_ADDRESS, USDT_ADDRESS};
+
+        let custom = Address::repeat_byte(0x11);
+        let service = test_service()
+            .with_usd_pegged_tokens([USDT_ADDRESS, custom])
+            .with_wrapped_native_token(None);
+
+        assert!(service.is_pegged(custom, QuoteCurrency::USD));
+        assert!(service.is_pegged(USDT_ADDRESS, QuoteCurrency::USD));
+        assert!(!service.is_pegged(USDC_ADDRESS, QuoteCurrency::USD));
+        assert!(!service.is_pegged(WETH_ADDRESS, QuoteCurrency::ETH));
+    }
+
+    #[test]
+    fn test_eth_quote_token_follows_chain_wrapper() {
+        use crate::ethereum::{wrapped_native_token, SEPOLIA_CHAIN_ID, SEPOLIA_WETH_ADDRESS};
+
+        assert_eq!(test_service().eth_quote_token().unwrap(), WETH_ADDRESS);
+
+        let sepolia =
+            test_service().with_wrapped_native_token(wrapped_native_token(SEPOLIA_CHAIN_ID));
+        assert_eq!(sepolia.eth_quote_token().unwrap(), SEPOLIA_WETH_ADDRESS);
+        assert_eq!(sepolia.wrapped_native_token(), Some(SEPOLIA_WETH_ADDRESS));
+
+        let unknown = test_service().with_wrapped_native_token(None);
+        assert!(unknown.eth_quote_token().unwrap_err().to_string().contains("wrapped native"));
+    }
+
+    #[tokio::test]
+    async fn test_same_token_price_is_exactly_one() {
+        use crate::ethereum::contracts::USDC_ADDRESS;
+
+        // Without the peg, USDC in USD reaches the Uniswap path, where it is the quote token
+        let service = test_service().with_usd_pegged_tokens([]);
+        let usdc = TokenMetadata {
+            name: "USD Coin".to_string(),
+            symbol: "USDC".to_string(),
+            decimals: 6,
+            decimals_known: true,
+            address: USDC_ADDRESS,
+        };
+
+        // Neither path touches the (unreachable) node
+        let info =
+            service.get_uniswap_price(USDC_ADDRESS, QuoteCurrency::USD, "USDC", 6).await.unwrap();
+        assert_eq!(info.price, "1");
+        assert_eq!(info.source, PriceSource::Identity);
+        assert_eq!(info.confidence, Some(PriceConfidence::High));
+        assert!(info.warnings.is_empty());
+
+        let info = service.fetch_aggregate_price(&usdc, QuoteCurrency::USD).await.unwrap();
+        assert_eq!(info.price, "1");
+        assert_eq!(info.source, PriceSource::Identity);
+
+        // The Uniswap path guards ETH quotes the same way
+        let weth = Address::repeat_byte(0x42);
+        let service = test_service().with_wrapped_native_token(Some(weth));
+        let info = service.get_uniswap_price(weth, QuoteCurrency::ETH, "WETH", 18).await.unwrap();
+        assert_eq!(info.price, "1");
+        assert_eq!(info.quote_currency, QuoteCurrency::ETH);
+    }
+
+    #[test]
+    fn test_reference_amounts() {
+        use crate::ethereum::{price_reference_amounts, WBTC_ADDRESS};
+
+        let service = test_service()
+            .with_reference_amounts(price_reference_amounts(crate::ethereum::DEFAULT_CHAIN_ID));
+        assert_eq!(service.reference_amount(WBTC_ADDRESS), Decimal::from_str("0.01").unwrap());
+        assert_eq!(service.reference_amount(WETH_ADDRESS), Decimal::ONE);
+
+        let service = service.with_reference_amount(WETH_ADDRESS, Decimal::from(10));
+        assert_eq!(service.reference_amount(WETH_ADDRESS), Decimal::from(10));
+        assert_eq!(service.reference_amount(WBTC_ADDRESS), Decimal::from_str("0.01").unwrap());
+    }
+
+    #[test]
+    fn test_reference_amount_in() {
+        assert_eq!(reference_amount_in(Decimal::ONE, 18).unwrap(), U256::from(10u64.pow(18)));
+        assert_eq!(
+            reference_amount_in(Decimal::from_str("0.01").unwrap(), 8).unwrap(),
+            U256::from(1_000_000u64)
+        );
+
+        // Zero, negative and sub-unit references have no raw amount to quote
+        assert!(reference_amount_in(Decimal::ZERO, 18).is_err());
+        assert!(reference_amount_in(Decimal::from(-1), 18).is_err());
+        assert!(reference_amount_in(Decimal::from_str("0.001").unwrap(), 2).is_err());
+    }
+
+    fn price_info(price: &str, timestamp: u64) -> PriceInfo {
+        PriceInfo {
+            token: TokenInfo::erc20(WETH_ADDRESS, "WETH".to_string(), 18),
+            price: price.to_string(),
+            quote_currency: QuoteCurrency::USD,
+            source: PriceSource::Chainlink,
+            timestamp,
+            confidence: Some(PriceConfidence::High),
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_price_via_eth() {
+        use crate::ethereum::contracts::USDC_ADDRESS;
+
+        // USDC at 0.000401 WETH in its V3 pool, ETH at $2,500 on Chainlink
+        let in_eth = PriceInfo {
+            token: TokenInfo::erc20(USDC_ADDRESS, "USDC".to_string(), 6),
+            quote_currency: QuoteCurrency::ETH,
+            source: PriceSource::UniswapV3,
+            confidence: Some(PriceConfidence::Medium),
+            ..price_info("0.000401", 1_000)
+        };
+        let mut eth_usd = price_info("2500", 1_000);
+        eth_usd.warnings.push(Warning::new(WarningCode::OracleAging, "aging"));
+
+        let info = price_via_eth(in_eth, &eth_usd).unwrap();
+        assert_eq!(info.price, "1.0025");
+        assert_eq!(info.quote_currency, QuoteCurrency::USD);
+        assert_eq!(info.source, PriceSource::UniswapV3);
+        assert_eq!(info.token.symbol, "USDC");
+        assert_eq!(info.confidence, Some(PriceConfidence::Medium));
+        assert_eq!(info.warnings.len(), 1);
+
+        // Compared against Chainlink's $1, the spread is the pool's real 25 bps
+        let spread = PriceSourceSpread::new(
+            info.token.clone(),
+            price_info("1", 1_000),
+            info,
+            DEFAULT_MAX_SOURCE_SPREAD_BPS,
+        )
+        .unwrap();
+        assert_eq!(spread.spread_bps, "25");
+        assert!(!spread.diverging);
+    }
+
+    #[test]
+    fn test_stale_price() {
+        let info = stale_price(price_info("2500", 1_000), 1_300, &AppError::PoolNotFound);
+
+        assert!(info.stale);
+        assert_eq!(info.age_secs, Some(300));
+        assert_eq!(info.confidence, Some(PriceConfidence::Low));
+        assert_eq!(info.price, "2500");
+        assert_eq!(info.warnings[0].code, WarningCode::StalePrice);
+        assert!(info.warnings[0].message.contains("300 seconds old"));
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["stale"], true);
+        assert_eq!(json["age_secs"], 300);
+    }
+
+    #[test]
+    fn test_stale_fallback_disabled_by_default() {
+        let service = test_service();
+        let key = (WETH_ADDRESS, QuoteCurrency::USD, false);
+
+        let fresh = service.with_stale_fallback(key, Ok(price_info("2500", 1_000)));
+        assert!(!fresh.unwrap().stale);
+        // Nothing is remembered while the fallback is off
+        assert!(service.last_prices.lock().unwrap().is_empty());
+
+        let failed = service.with_stale_fallback(key, Err(AppError::PoolNotFound));
+        assert!(matches!(failed, Err(AppError::PoolNotFound)));
+    }
+
+    #[test]
+    fn test_stale_fallback_returns_last_price() {
+        let service = test_service().with_stale_price_fallback(true);
+        let key = (WETH_ADDRESS, QuoteCurrency::USD, false);
+
+        // Nothing fetched yet, so the error stands
+        let failed = service.with_stale_fallback(key, Err(AppError::PoolNotFound));
+        assert!(failed.is_err());
+
+        service.with_stale_fallback(key, Ok(price_info("2500", 1_000))).unwrap();
+        let stale = service.with_stale_fallback(key, Err(AppError::PoolNotFound));
+        let stale = stale.unwrap();
+        assert!(stale.stale);
+        assert_eq!(stale.price, "2500");
+
+        // Other quote currencies and the aggregate price have their own last price
+        let eth = service.with_stale_fallback(
+            (WETH_ADDRESS, QuoteCurrency::ETH, false),
+            Err(AppError::PoolNotFound),
+        );
+        assert!(eth.is_err());
+        let aggregate = service.with_stale_fallback(
+            (WETH_ADDRESS, QuoteCurrency::USD, true),
+            Err(AppError::PoolNotFound),
+        );
+        assert!(aggregate.is_err());
+    }
+
+    #[test]
+    fn test_stale_fallback_keeps_bounded_prices() {
+        let service = test_service().with_stale_price_fallback(true);
+        let key = |byte: u8| (Address::repeat_byte(byte), QuoteCurrency::USD, false);
+
+        service.with_stale_fallback(key(0), Ok(price_info("1", 500))).unwrap();
+        for i in 1..MAX_LAST_PRICES {
+            let token = Address::from_word(U256::from(i + 1_000).into());
+            service
+                .with_stale_fallback((token, QuoteCurrency::USD, false), Ok(price_info("1", 1_000)))
+                .unwrap();
+        }
+        assert_eq!(service.last_prices.lock().unwrap().len(), MAX_LAST_PRICES);
+
+        // A new token drops the oldest price
+        service.with_stale_fallback(key(1), Ok(price_info("2", 2_000))).unwrap();
+        assert_eq!(service.last_prices.lock().unwrap().len(), MAX_LAST_PRICES);
+        assert!(service.with_stale_fallback(key(0), Err(AppError::PoolNotFound)).is_err());
+        assert!(service.with_stale_fallback(key(1), Err(AppError::PoolNotFound)).is_ok());
+    }
+
+    #[test]
+    fn test_oracle_age_warning() {
+        assert!(oracle_age_warning(600, 3600).is_none());
+        assert!(oracle_age_warning(1800, 3600).is_none());
+
+        let warning = oracle_age_warning(2400, 3600).unwrap();
+        assert_eq!(warning.code, WarningCode::OracleAging);
+        assert!(warning.message.contains("2400 seconds old"));
+    }
+
+    #[test]
+    fn test_chain_aware_peg_defaults() {
+        use crate::ethereum::{wrapped_native_token, SEPOLIA_CHAIN_ID, SEPOLIA_WETH_ADDRESS};
+
+        assert_eq!(wrapped_native_token(DEFAULT_CHAIN_ID), Some(WETH_ADDRESS));
+        assert_eq!(wrapped_native_token(SEPOLIA_CHAIN_ID), Some(SEPOLIA_WETH_ADDRESS));
+        assert_eq!(wrapped_native_token(42161), None);
+
+        assert_eq!(usd_pegged_tokens(DEFAULT_CHAIN_ID).len(), 3);
+        assert!(!usd_pegged_tokens(SEPOLIA_CHAIN_ID).contains(&crate::ethereum::USDC_ADDRESS));
+        assert!(usd_pegged_tokens(42161).is_empty());
+    }
+
+    fn balance(symbol: &str, amount: &str) -> BalanceInfo {
+        BalanceInfo {
+            address: "0xwallet".to_string(),
+            token: TokenInfo {
+                address: Some("0xtoken".to_string()),
+                symbol: symbol.to_string(),
+                decimals: 18,
+            },
+            balance: amount.to_string(),
+            balance_raw: "0".to_string(),
+            block_number: None,
+            value: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    fn wallet() -> WalletInfo {
+        WalletInfo {
+            configured: true,
+            address: Some("0xwallet".to_string()),
+            eth_balance: Some(BalanceInfo { token: TokenInfo::eth(), ..balance("ETH", "2") }),
+            token_balances: vec![balance("USDC", "1000"), balance("NOPRICE", "5")],
+            zero_balance_tokens_skipped: 0,
+            base_currency: None,
+            total_value: None,
+            unpriced_tokens: Vec::new(),
+            warnings: Vec::new(),
+            message: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_value_wallet_prices_tokens_with_progress() {
+        let pegged = Address::repeat_byte(0x11);
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = PriceService::new(client.clone(), BalanceService::new(client))
+            .with_usd_pegged_tokens([pegged])
+            .with_fan_out_concurrency(2);
+
+        // Only the pegged token has an address to price; its metadata is read first
+        asserter.push_success(&Bytes::from("PEG".to_string().abi_encode()));
+        asserter.push_success(&Bytes::from("Pegged".to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(18).abi_encode()));
+
+        let mut pegged_balance = balance("PEG", "3");
+        pegged_balance.token.address = Some(format!("{:?}", pegged));
+        let mut wallet = WalletInfo {
+            eth_balance: None,
+            token_balances: vec![pegged_balance, balance("NOADDR", "5")],
+            ..wallet()
+        };
+        let progress = Mutex::new(Vec::new());
+
+        service
+            .value_wallet(&mut wallet, QuoteCurrency::USD, |done, total| {
+                progress.lock().unwrap().push((done, total))
+            })
+            .await;
+
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(progress.into_inner().unwrap(), vec![(1, 2), (2, 2)]);
+        assert_eq!(wallet.token_balances[0].value.as_deref(), Some("3.00"));
+        assert_eq!(wallet.total_value.as_deref(), Some("3.00"));
+        assert_eq!(wallet.unpriced_tokens, vec!["NOADDR".to_string()]);
+    }
+
+    #[test]
+    fn test_apply_valuation_usd() {
+        let mut wallet = wallet();
+        let eth_usd = Decimal::from(2500);
+
+        apply_valuation(
+            &mut wallet,
+            QuoteCurrency::USD,
+            Some(Decimal::ONE),
+            Some(eth_usd),
+            &[Some(Decimal::ONE), None],
+        );
+
+        assert_eq!(wallet.eth_balance.unwrap().value.as_deref(), Some("5000.00"));
+        assert_eq!(wallet.token_balances[0].value.as_deref(), Some("1000.00"));
+        assert!(wallet.token_balances[1].value.is_none());
+        assert_eq!(wallet.token_balances[1].warnings[0].code, WarningCode::ValueUnavailable);
+        assert!(wallet.token_balances[0].warnings.is_empty());
+        assert_eq!(wallet.total_value.as_deref(), Some("6000.00"));
+        assert_eq!(wallet.base_currency, Some(QuoteCurrency::USD));
+        assert_eq!(wallet.unpriced_tokens, vec!["NOPRICE".to_string()]);
+    }
+
+    #[test]
+    fn test_apply_valuation_eth_uses_one_base_rate() {
+        let mut wallet = wallet();
+        let eth_usd = Decimal::from(2500);
+
+        apply_valuation(
+            &mut wallet,
+            QuoteCurrency::ETH,
+            Some(eth_usd),
+            Some(eth_usd),
+            &[Some(Decimal::ONE), None],
+        );
+
+        assert_eq!(wallet.eth_balance.unwrap().value.as_deref(), Some("2"));
+        assert_eq!(wallet.token_balances[0].value.as_deref(), Some("0.4"));
+        assert_eq!(wallet.total_value.as_deref(), Some("2.4"));
+        assert_eq!(wallet.base_currency, Some(QuoteCurrency::ETH));
+    }
+
+    #[test]
+    fn test_apply_valuation_usd_without_eth_usd() {
+        // Only the ETH balance needs the missing rate
+        let mut wallet = wallet();
+        apply_valuation(
+            &mut wallet,
+            QuoteCurrency::USD,
+            Some(Decimal::ONE),
+            None,
+            &[Some(Decimal::ONE), None],
+        );
+        assert!(wallet.eth_balance.unwrap().value.is_none());
+        assert_eq!(wallet.token_balances[0].value.as_deref(), Some("1000.00"));
+        assert_eq!(wallet.total_value.as_deref(), Some("1000.00"));
+        assert_eq!(wallet.unpriced_tokens, vec!["ETH".to_string(), "NOPRICE".to_string()]);
+    }
+
+    #[test]
+    fn test_apply_valuation_eth_without_eth_usd() {
+        let mut wallet = wallet();
+        apply_valuation(&mut wallet, QuoteCurrency::ETH, None, None, &[None, None]);
+
+        // Nothing can be valued in ETH
+        assert!(wallet.token_balances.iter().all(|b| b.value.is_none()));
+        assert!(wallet.total_value.is_none());
+        assert_eq!(wallet.unpriced_tokens.len(), 3);
+        assert_eq!(wallet.eth_balance.unwrap().warnings[0].code, WarningCode::ValueUnavailable);
+    }
 }
@@ -1,57 +1,339 @@
 //! Swap simulation service.
 
 use alloy::{
+    eips::eip1559::Eip1559Estimation,
     primitives::{aliases::U24, Address, Bytes, U160, U256},
     rpc::types::TransactionRequest,
     sol_types::SolCall,
 };
 use rust_decimal::Decimal;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::SystemTime;
 
 use crate::{
+    config::DEFAULT_QUOTE_CROSS_CHECK_TOLERANCE,
     error::{AppError, Result},
     ethereum::{
         contracts::{
-            uniswap_v2::{
-                IUniswapV2Factory, IUniswapV2Router02, UNISWAP_V2_FACTORY, UNISWAP_V2_ROUTER,
-            },
+            erc20::IERC20,
+            uniswap_v2::{IUniswapV2Factory, IUniswapV2Pair, IUniswapV2Router02},
             uniswap_v3::{
-                fee_tiers, IQuoterV2, ISwapRouter, IUniswapV3Factory, UNISWAP_V3_FACTORY,
-                UNISWAP_V3_QUOTER, UNISWAP_V3_ROUTER,
+                decode_path, encode_path, fee_tiers, ISwapRouter, IUniswapV3Factory, IUniswapV3Pool,
             },
             WETH_ADDRESS,
         },
-        EthereumClient, WalletManager,
+        non_rebasing_wrapper,
+        price_math::{
+            single_tick_amount_out, v2_amount_out, PriceRatio, PRICE_DECIMALS,
+            PRICE_SIGNIFICANT_DIGITS,
+        },
+        quoter, EthereumClient, UniswapAddresses, V2Deployment, WalletManager, SUSHISWAP_V2,
     },
-    services::BalanceService,
+    services::{balance::fan_out, BalanceService},
     types::{
-        format_units, SwapParams, SwapRoute, SwapSimulationResult, TransactionData, UniswapVersion,
+        format_units, parse_units, DecodedTransaction, ExactOutputQuote, ForcedRoute,
+        MinimumOutSource, PreviewVenue, ProtocolPreference, QuoteCrossCheck, QuoteCrossCheckMethod,
+        SkippedPool, SlippageLevel, SwapParams, SwapPathLeg, SwapPathResult, SwapPreview,
+        SwapRoute, SwapSimulationResult, TokenInfo, TransactionData, TransactionFormat,
+        UniswapVersion, Venue, WalletTransaction, Warning, WarningCode, SIMULATION_NOTE,
     },
 };
 
+/// Typical gas used by a single-hop Uniswap V2 swap, used when estimation fails.
+const V2_SWAP_GAS: u64 = 150_000;
+
+/// Typical gas used by a single-hop Uniswap V3 swap, used when estimation fails.
+const V3_SWAP_GAS: u64 = 180_000;
+
+/// Additional gas per extra hop on a V2 route.
+const V2_EXTRA_HOP_GAS: u64 = 60_000;
+
+/// Additional gas per extra hop on a V3 route.
+const V3_EXTRA_HOP_GAS: u64 = 80_000;
+
+/// Last-resort gas value when no protocol-specific estimate applies.
+const DEFAULT_SWAP_GAS: u64 = 200_000;
+
+/// Gas price in wei used when the node's gas price cannot be read (30 gwei).
+const DEFAULT_GAS_PRICE: u128 = 30_000_000_000;
+
+/// Smallest plausible gas estimate (a plain ETH transfer).
+const MIN_PLAUSIBLE_GAS: u64 = 21_000;
+
+/// Largest plausible gas estimate (the mainnet block gas limit).
+const MAX_PLAUSIBLE_GAS: u64 = 30_000_000;
+
+/// Largest plausible gas price in wei (10,000 gwei).
+const MAX_PLAUSIBLE_GAS_PRICE: u128 = 10_000_000_000_000;
+
+/// Price impact, as a percentage, at or above which a swap result carries a warning.
+pub const HIGH_PRICE_IMPACT_PERCENT: u32 = 5;
+
+/// Largest pool price move, in basis points, for which a V3 quote may fall back
+/// to single-tick pool math when the quoter fails (1%).
+pub const MAX_APPROXIMATE_QUOTE_PRICE_MOVE_BPS: u32 = 100;
+
+/// Share of the minimum output, in basis points, that rounding it down to a
+/// whole raw unit may drop before a swap result carries a warning (0.1%).
+pub const PRECISION_LOSS_WARNING_BPS: u32 = 10;
+
+/// Raw output units a spot quote should return for price impact to be measured
+/// against it, so rounding moves its rate by at most 0.1%.
+///
+/// The reference amount is scaled up until its quote reaches this. Only a
+/// 0-decimal output, whose few units cannot be refined, fails below it.
+const MIN_SPOT_OUTPUT: u128 = 1_000;
+
+/// Pick between the best V3 and V2 routes according to `preference`.
+///
+/// Each route carries its output amount; `Best` keeps V3 on a tie.
+fn prefer_route(
+    preference: ProtocolPreference,
+    v3: Option<(SwapRoute, U256)>,
+    v2: Option<(SwapRoute, U256)>,
+) -> Option<(SwapRoute, U256)> {
+    match preference {
+        ProtocolPreference::V3First => v3.or(v2),
+        ProtocolPreference::V2First => v2.or(v3),
+        ProtocolPreference::Best => match (v3, v2) {
+            (Some(v3), Some(v2)) => Some(if v2.1 > v3.1 { v2 } else { v3 }),
+            (v3, v2) => v3.or(v2),
+        },
+    }
+}
+
+/// Pick between the V3 and V2 route selections by `preference`.
+///
+/// When neither protocol has a route, the error of the protocol tried last is
+/// returned (V2 for [`ProtocolPreference::Best`]).
+fn choose_route(
+    preference: ProtocolPreference,
+    v3: Result<(SwapRoute, U256)>,
+    v2: Result<(SwapRoute, U256)>,
+) -> Result<(SwapRoute, U256)> {
+    match preference {
+        ProtocolPreference::V3First => v3.or(v2),
+        ProtocolPreference::V2First => v2.or(v3),
+        ProtocolPreference::Best => match (v3, v2) {
+            (Err(_), Err(e)) => Err(e),
+            (v3, v2) => prefer_route(preference, v3.ok(), v2.ok()).ok_or(AppError::PoolNotFound),
+        },
+    }
+}
+
+/// Raise a `requested` slippage tolerance below `floor` to the floor (both percentages).
+///
+/// A minimum output equal to the exact quote reverts on almost any price
+/// movement, so the raise comes with a warning. A zero floor changes nothing.
+pub fn apply_slippage_floor(requested: Decimal, floor: Decimal) -> (Decimal, Option<Warning>) {
+    if requested >= floor {
+        return (requested, None);
+    }
+    let warning = Warning::new(
+        WarningCode::SlippageRaised,
+        format!(
+            "slippage_tolerance {}% is below the {}% floor and was raised to it; a minimum \
+             output this close to the quote reverts on almost any price movement. Set \
+             allow_low_slippage to keep the requested tolerance.",
+            requested.normalize(),
+            floor.normalize()
+        ),
+    );
+    (floor, Some(warning))
+}
+
 /// Get current Unix timestamp in seconds.
 /// Returns 0 if system time is before Unix epoch (should never happen in practice).
 fn current_timestamp() -> u64 {
     SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
 }
 
+/// Token addresses of a route path, as reported in results.
+fn format_path(path: &[Address]) -> Vec<String> {
+    path.iter().map(|a| format!("{:?}", a)).collect()
+}
+
+/// Pick the V2 route with the largest output among the venues' candidates,
+/// keeping the earlier venue on a tie.
+///
+/// When no venue has a route, the last venue's error is returned.
+fn best_venue_route(
+    candidates: impl IntoIterator<Item = Result<(SwapRoute, U256)>>,
+) -> Result<(SwapRoute, U256)> {
+    let mut best: Option<(SwapRoute, U256)> = None;
+    let mut last_error = AppError::PoolNotFound;
+
+    for candidate in candidates {
+        match candidate {
+            Ok(candidate) => {
+                if best.as_ref().is_none_or(|(_, amount_out)| candidate.1 > *amount_out) {
+                    best = Some(candidate);
+                }
+            }
+            Err(e) => last_error = e,
+        }
+    }
+
+    best.ok_or(last_error)
+}
+
+/// A pool or V2 route passed over during route selection because of low liquidity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ThinPool {
+    protocol: UniswapVersion,
+    venue: Venue,
+    /// Fee tier of a V3 pool (`None` for V2).
+    fee_tier: Option<u32>,
+    amount_out: U256,
+    liquidity: u128,
+}
+
+impl ThinPool {
+    /// A thin Uniswap V3 pool.
+    fn v3(fee_tier: u32, amount_out: U256, liquidity: u128) -> Self {
+        Self {
+            protocol: UniswapVersion::V3,
+            venue: Venue::Uniswap,
+            fee_tier: Some(fee_tier),
+            amount_out,
+            liquidity,
+        }
+    }
+}
+
+/// A V3 fee tier whose pool exists, as seen during route selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct V3Tier {
+    fee_tier: u32,
+    /// Active liquidity.
+    liquidity: u128,
+    /// Quoted output (`None` if the pool was not quoted or the quote failed).
+    amount_out: Option<U256>,
+}
+
+impl V3Tier {
+    /// Whether the pool has no active liquidity, so a quote would be meaningless.
+    fn is_empty(&self) -> bool {
+        self.liquidity == 0
+    }
+}
+
+/// A route whose pools are looked up when checking whether a pair is tradable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RouteCheck {
+    /// A V3 pool at this fee tier.
+    V3(u32),
+    /// A direct V2 pair on this venue.
+    V2(Venue),
+    /// V2 pairs with this intermediary on both sides, on this venue.
+    V2Via(Venue, Address),
+}
+
 /// Service for simulating token swaps.
 #[derive(Clone)]
 pub struct SwapService {
     client: Arc<EthereumClient>,
-    wallet: WalletManager,
+    /// Wallet used as sender and recipient (`None` in read-only mode).
+    wallet: Option<WalletManager>,
     balance_service: BalanceService,
+    /// Pools with less liquidity than this are excluded from route discovery (0 = no minimum).
+    min_pool_liquidity: u128,
+    /// Protocol order for automatic route selection when a request does not set one.
+    protocol_preference: ProtocolPreference,
+    /// Intermediary for V2 routes without a direct pair (`None` for direct pairs only).
+    wrapped_native_token: Option<Address>,
+    /// Uniswap contracts routes are discovered on and swaps are sent to.
+    uniswap: UniswapAddresses,
+    /// Venues V2 routes are quoted on; the one with the largest output is used.
+    v2_venues: Vec<Venue>,
+    /// Largest discrepancy, as a percentage, between a quote and its cross-check.
+    quote_cross_check_tolerance: Decimal,
 }
 
 impl SwapService {
     /// Create a new swap service.
     pub fn new(
         client: Arc<EthereumClient>,
-        wallet: WalletManager,
+        wallet: Option<WalletManager>,
         balance_service: BalanceService,
     ) -> Self {
-        Self { client, wallet, balance_service }
+        Self {
+            client,
+            wallet,
+            balance_service,
+            min_pool_liquidity: 0,
+            protocol_preference: ProtocolPreference::default(),
+            wrapped_native_token: Some(WETH_ADDRESS),
+            uniswap: UniswapAddresses::default(),
+            v2_venues: vec![Venue::Uniswap],
+            quote_cross_check_tolerance: DEFAULT_QUOTE_CROSS_CHECK_TOLERANCE,
+        }
+    }
+
+    /// Set the minimum pool liquidity for automatic route selection.
+    ///
+    /// Liquidity is V3 `liquidity()`, or `sqrt(reserve0 * reserve1)` for V2 pairs.
+    /// Forced routes are not subject to the minimum.
+    pub fn with_min_pool_liquidity(mut self, min_pool_liquidity: u128) -> Self {
+        self.min_pool_liquidity = min_pool_liquidity;
+        self
+    }
+
+    /// Set the default protocol order for automatic route selection.
+    pub fn with_protocol_preference(mut self, preference: ProtocolPreference) -> Self {
+        self.protocol_preference = preference;
+        self
+    }
+
+    /// Set the chain's wrapped native token, used as the V2 routing intermediary
+    /// (`None` limits V2 routes to direct pairs).
+    pub fn with_wrapped_native_token(mut self, token: Option<Address>) -> Self {
+        self.wrapped_native_token = token;
+        self
+    }
+
+    /// Set the Uniswap contracts routes are discovered on and swaps are sent to.
+    pub fn with_uniswap_addresses(mut self, uniswap: UniswapAddresses) -> Self {
+        self.uniswap = uniswap;
+        self
+    }
+
+    /// Set the venues V2 routes are quoted on.
+    pub fn with_v2_venues(mut self, venues: Vec<Venue>) -> Self {
+        self.v2_venues = venues;
+        self
+    }
+
+    /// Set the largest discrepancy, as a percentage, a quote cross-check accepts
+    /// without a warning.
+    pub fn with_quote_cross_check_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.quote_cross_check_tolerance = tolerance;
+        self
+    }
+
+    /// Factory and router of a V2 venue.
+    fn v2(&self, venue: Venue) -> V2Deployment {
+        v2_deployment(venue, &self.uniswap)
+    }
+
+    /// Address of the configured wallet.
+    fn wallet_address(&self) -> Result<Address> {
+        self.wallet.as_ref().map(WalletManager::address).ok_or_else(|| {
+            AppError::Wallet(
+                "No wallet configured (read-only mode); set ETHEREUM_PRIVATE_KEY or \
+                 ETHEREUM_MNEMONIC to simulate swaps"
+                    .to_string(),
+            )
+        })
+    }
+
+    /// Sender of the simulated swap: `params.from_address`, or the wallet.
+    ///
+    /// A wallet is required either way, since it is the swap's recipient.
+    fn simulation_sender(&self, params: &SwapParams) -> Result<Address> {
+        let wallet = self.wallet_address()?;
+        Ok(params.from_address.unwrap_or(wallet))
     }
 
     /// Simulate a token swap.
@@ -61,30 +343,65 @@ impl SwapService {
             to = %params.to_token,
             amount = %params.amount_in,
             slippage = %params.slippage_tolerance,
+            forced_route = ?params.forced_route,
             "Simulating swap"
         );
 
+        // Swaps are simulated from the wallet, so fail fast without one
+        self.wallet_address()?;
+
         // Get token metadata for formatting
         let from_metadata = self.balance_service.get_token_metadata(params.from_token).await?;
         let to_metadata = self.balance_service.get_token_metadata(params.to_token).await?;
 
-        // Try V3 first, then V2
-        let (route, amount_out, tx) = match self.try_v3_swap(&params).await {
-            Ok(result) => result,
-            Err(_) => {
-                // Try V2
-                self.try_v2_swap(&params).await?
-            }
+        let mut thin_pools = Vec::new();
+        let mut approximate_quote = None;
+        let (route, amount_out, mut tx) = match &params.forced_route {
+            // Build exactly the requested route, skipping discovery
+            Some(forced) => self.build_forced_swap(&params, forced).await?,
+            None => self
+                .discover_route(&params, &mut thin_pools, &mut approximate_quote)
+                .await
+                .map_err(|e| match e {
+                    // Only a missing route is explained by the thin pools; keep any other error
+                    AppError::PoolNotFound | AppError::InsufficientLiquidity
+                        if !thin_pools.is_empty() =>
+                    {
+                        AppError::RouteNotFound(format!(
+                            "every pool is below the minimum liquidity of {}",
+                            self.min_pool_liquidity
+                        ))
+                    }
+                    e => e,
+                })?,
         };
 
-        // Calculate minimum output with slippage
-        let slippage_multiplier = Decimal::ONE - params.slippage_tolerance / Decimal::from(100);
-        let amount_out_u128: u128 = amount_out.try_into().map_err(|_| {
-            AppError::NumericOverflow(format!("amount_out {} exceeds u128 range", amount_out))
-        })?;
-        let amount_out_min = Decimal::from(amount_out_u128) * slippage_multiplier;
-        let amount_out_min_u128: u128 = Self::decimal_to_u128(amount_out_min)?;
-        let amount_out_min_u256 = U256::from(amount_out_min_u128);
+        // Report thin pools that would have paid more than the selected route
+        let skipped_pools = Self::better_priced(&thin_pools, amount_out)
+            .map(|pool| SkippedPool {
+                protocol: pool.protocol,
+                venue: pool.venue,
+                fee_tier: pool.fee_tier,
+                amount_out: format_units(pool.amount_out, to_metadata.decimals),
+                liquidity: pool.liquidity.to_string(),
+            })
+            .collect::<Vec<_>>();
+        if !skipped_pools.is_empty() {
+            tracing::info!(
+                skipped = skipped_pools.len(),
+                min_liquidity = self.min_pool_liquidity,
+                "Skipped better-priced pools with low liquidity"
+            );
+        }
+
+        // Simulate with the caller's gas limit so an insufficient limit shows up as a failure
+        if let Some(gas_limit) = params.gas_limit {
+            tx.gas = Some(gas_limit);
+        }
+        check_swap_value(&tx)?;
+
+        // Calculate minimum output with slippage, or the caller's stricter floor
+        let (amount_out_min_u256, minimum_source) = Self::effective_minimum(&params, amount_out)?;
 
         // Simulate the transaction using eth_call to verify it would execute
         let (simulation_success, simulation_error) = match self.simulate_transaction(&tx).await {
@@ -98,22 +415,208 @@ impl SwapService {
             }
         };
 
-        // Estimate gas (may fail if simulation failed, use default in that case)
-        let gas_estimate = self.estimate_gas(&tx).await.unwrap_or(200_000);
-        let gas_price = self.client.get_gas_price().await.unwrap_or(30_000_000_000);
+        // Use the caller's gas limit, or estimate gas (may fail if simulation failed,
+        // fall back to a typical value for the route). The simulation ran first, so
+        // its revert reason explains the failure even when the provider's doesn't.
+        let (gas_estimate, gas_estimate_fallback_reason) = match params.gas_limit {
+            Some(gas_limit) => {
+                tracing::info!(gas_limit = gas_limit, "Using caller-provided gas limit");
+                (gas_limit, None)
+            }
+            None => match self.estimate_gas(&tx).await {
+                Ok(gas) => (gas, None),
+                Err(e) => {
+                    let (gas, fallback) = Self::fallback_gas(&route);
+                    tracing::warn!(error = %e, gas = gas, "Gas estimation failed, using fallback");
+                    let reason =
+                        Self::gas_fallback_reason(&e, simulation_error.as_deref(), &fallback);
+                    (gas, Some(reason))
+                }
+            },
+        };
+        let mut warnings = Vec::new();
+        if let Some((cached, reported)) = self.client.chain_id_change() {
+            warnings.push(Warning::new(
+                WarningCode::ChainIdChanged,
+                format!(
+                    "RPC node now reports chain ID {} but the session started on {}; the \
+                     transaction may target the wrong chain",
+                    reported, cached
+                ),
+            ));
+        }
+        for (token, metadata) in
+            [(params.from_token, &from_metadata), (params.to_token, &to_metadata)]
+        {
+            if self.balance_service.is_rebasing(token) {
+                warnings.push(Self::rebasing_warning(token, &metadata.symbol));
+            }
+        }
+        if let Some(reason) = &gas_estimate_fallback_reason {
+            warnings.push(Warning::new(WarningCode::GasEstimateFallback, reason.clone()));
+        }
+        // Only relevant if the approximated V3 route is the one selected
+        let approximated = route.protocol == UniswapVersion::V3 && approximate_quote.is_some();
+        if route.protocol == UniswapVersion::V3 {
+            warnings.extend(approximate_quote);
+        }
+        match minimum_source {
+            MinimumOutSource::Slippage => warnings.extend(Self::precision_loss_warning(
+                amount_out,
+                params.slippage_tolerance,
+                to_metadata.decimals,
+                &to_metadata.symbol,
+            )),
+            MinimumOutSource::MinAmountOut if amount_out_min_u256 > amount_out => {
+                warnings.push(Warning::new(
+                    WarningCode::MinimumAboveQuote,
+                    format!(
+                        "min_amount_out {} {} is above the expected output {} {}; the swap would \
+                         revert at this price",
+                        format_units(amount_out_min_u256, to_metadata.decimals),
+                        to_metadata.symbol,
+                        format_units(amount_out, to_metadata.decimals),
+                        to_metadata.symbol
+                    ),
+                ));
+            }
+            MinimumOutSource::MinAmountOut => {}
+        }
+        if !skipped_pools.is_empty() {
+            warnings.push(Warning::new(
+                WarningCode::LowLiquidityPoolsSkipped,
+                format!(
+                    "{} better-priced pool(s) skipped for having less than {} liquidity; see skipped_pools",
+                    skipped_pools.len(),
+                    self.min_pool_liquidity
+                ),
+            ));
+        }
+
+        let quote_cross_check = if !params.cross_check_quote {
+            None
+        } else if approximated {
+            // The quote itself came from pool state, so there is nothing independent to compare
+            warnings.push(Warning::new(
+                WarningCode::QuoteCrossCheckUnavailable,
+                "The quote could not be cross-checked: amount_out_expected is already a \
+                 single-tick approximation from pool state",
+            ));
+            None
+        } else {
+            match self.independent_quote(&route, params.amount_in).await {
+                Ok(Some((method, independent))) => {
+                    let check = Self::quote_cross_check(
+                        method,
+                        amount_out,
+                        independent,
+                        self.quote_cross_check_tolerance,
+                        to_metadata.decimals,
+                    );
+                    if !check.within_tolerance {
+                        tracing::warn!(
+                            quoted = %amount_out,
+                            independent = %independent,
+                            discrepancy = %check.discrepancy_percent,
+                            "Quote disagrees with pool state"
+                        );
+                        warnings.push(Warning::new(
+                            WarningCode::QuoteMismatch,
+                            format!(
+                                "Re-quoting the route from pool state gives {} {}, {}% away from \
+                                 amount_out_expected (tolerance {}%); the quote may be unreliable",
+                                check.amount_out,
+                                to_metadata.symbol,
+                                check.discrepancy_percent,
+                                check.tolerance_percent
+                            ),
+                        ));
+                    }
+                    Some(check)
+                }
+                Ok(None) => {
+                    warnings.push(Warning::new(
+                        WarningCode::QuoteCrossCheckUnavailable,
+                        format!(
+                            "The quote could not be cross-checked: the swap moves a V3 pool price \
+                             by more than {} bps, too far for single-tick math",
+                            MAX_APPROXIMATE_QUOTE_PRICE_MOVE_BPS
+                        ),
+                    ));
+                    None
+                }
+                Err(e) => {
+                    warnings.push(Warning::new(
+                        WarningCode::QuoteCrossCheckUnavailable,
+                        format!("The quote could not be cross-checked: {}", e),
+                    ));
+                    None
+                }
+            }
+        };
+
+        let gas_price = match self.client.get_gas_price().await.and_then(plausible_gas_price) {
+            Ok(gas_price) => gas_price,
+            Err(e) => {
+                warnings.push(Warning::new(
+                    WarningCode::GasPriceFallback,
+                    format!("Gas price unavailable ({}); using {} wei", e, DEFAULT_GAS_PRICE),
+                ));
+                DEFAULT_GAS_PRICE
+            }
+        };
 
         // Calculate gas cost in ETH
         let gas_cost_wei = U256::from(gas_estimate) * U256::from(gas_price);
         let gas_cost_eth = format_units(gas_cost_wei, 18);
 
         // Calculate price impact by comparing spot price vs execution price
-        let price_impact =
-            self.calculate_price_impact(&params, amount_out, &route).await.unwrap_or(Decimal::ZERO);
+        let price_impact = match self
+            .calculate_price_impact(&params, amount_out, &route, to_metadata.decimals)
+            .await
+        {
+            Ok(price_impact) => price_impact,
+            Err(e) => {
+                warnings.push(Warning::new(
+                    WarningCode::PriceImpactUnavailable,
+                    format!("Price impact could not be calculated ({}); reported as 0", e),
+                ));
+                Decimal::ZERO
+            }
+        };
+        warnings.extend(Self::price_impact_warning(price_impact));
+
+        let post_trade_price = match self
+            .post_trade_price(
+                &route,
+                params.amount_in,
+                from_metadata.decimals,
+                to_metadata.decimals,
+            )
+            .await
+        {
+            Ok(price) => price,
+            Err(e) => {
+                tracing::debug!(error = %e, "Post-trade price unavailable");
+                None
+            }
+        };
 
         // Format amounts
         let amount_in_formatted = format_units(params.amount_in, from_metadata.decimals);
         let amount_out_formatted = format_units(amount_out, to_metadata.decimals);
         let amount_out_min_formatted = format_units(amount_out_min_u256, to_metadata.decimals);
+        let amm_fee = route.fee_amount(params.amount_in).ok_or_else(|| {
+            AppError::NumericOverflow(format!(
+                "Pool fee on {} over route {:?} cannot be computed",
+                params.amount_in, route
+            ))
+        })?;
+        let amm_fee_amount = format_units(amm_fee, from_metadata.decimals);
+        let slippage_buffer_amount =
+            format_units(amount_out.saturating_sub(amount_out_min_u256), to_metadata.decimals);
+        let slippage_table =
+            Self::slippage_table(amount_out, &params.slippage_levels, to_metadata.decimals)?;
 
         // Build transaction data
         let tx_data = TransactionData {
@@ -124,238 +627,1265 @@ impl SwapService {
                 .map(|d| format!("0x{}", alloy::hex::encode(d)))
                 .unwrap_or_default(),
             value: tx.value.map(|v| v.to_string()).unwrap_or_else(|| "0".to_string()),
+            gas_limit: params.gas_limit.map(|gas| gas.to_string()),
+        };
+        let decoded_transaction = if params.decode_transaction {
+            tx.input.input().and_then(|data| decode_swap_calldata(data))
+        } else {
+            None
+        };
+        let wallet_transaction = match params.transaction_format {
+            TransactionFormat::Minimal => None,
+            TransactionFormat::Eip1559 => Some(self.wallet_transaction(&tx, gas_estimate).await?),
         };
 
         Ok(SwapSimulationResult {
+            executed: false,
+            note: SIMULATION_NOTE.to_string(),
             simulation_success,
             simulation_error,
             amount_in: amount_in_formatted,
+            amount_in_raw: params.amount_in.to_string(),
+            from_token_decimals: from_metadata.decimals_known.then_some(from_metadata.decimals),
             amount_out_expected: amount_out_formatted,
             amount_out_minimum: amount_out_min_formatted,
+            slippage_table,
+            amount_out_expected_usd: None,
+            amount_out_minimum_usd: None,
+            amount_out_minimum_source: minimum_source,
+            amm_fee_amount,
+            slippage_buffer_amount,
             price_impact: price_impact.to_string(),
+            post_trade_price,
             gas_estimate: gas_estimate.to_string(),
+            gas_estimate_is_fallback: gas_estimate_fallback_reason.is_some(),
+            gas_estimate_fallback_reason,
+            gas_limit_is_override: params.gas_limit.is_some(),
             gas_price: gas_price.to_string(),
             gas_cost_eth,
+            gas_cost_in_token: None,
+            gas_cost_token: None,
             route,
+            skipped_pools,
+            quote_cross_checked: quote_cross_check.is_some(),
+            quote_cross_check,
             transaction: tx_data,
+            decoded_transaction,
+            wallet_transaction,
+            warnings,
         })
     }
 
-    /// Try to build a V3 swap.
-    async fn try_v3_swap(
+    /// Complete `tx` into an EIP-1559 transaction with the node's chain ID,
+    /// current fee estimates and the sender's next nonce.
+    async fn wallet_transaction(
         &self,
-        params: &SwapParams,
-    ) -> Result<(SwapRoute, U256, TransactionRequest)> {
-        let factory = IUniswapV3Factory::new(UNISWAP_V3_FACTORY, self.client.provider().clone());
-        let quoter = IQuoterV2::new(UNISWAP_V3_QUOTER, self.client.provider().clone());
+        tx: &TransactionRequest,
+        gas: u64,
+    ) -> Result<WalletTransaction> {
+        let from = self.wallet_address()?;
+        let (chain_id, fees, nonce) = tokio::try_join!(
+            self.client.chain_id(),
+            self.client.estimate_eip1559_fees(),
+            self.client.get_pending_nonce(from),
+        )?;
+        Ok(build_wallet_transaction(tx, from, chain_id, gas, fees, nonce))
+    }
 
-        // Find best fee tier
-        let mut best_fee: Option<u32> = None;
-        let mut best_amount_out = U256::ZERO;
+    /// Simulate a sequence of dependent swaps along `tokens`.
+    ///
+    /// Each leg swaps the previous leg's expected output into the next token.
+    /// Stops at the first leg that cannot be routed and reports it in the result.
+    pub async fn simulate_swap_path(
+        &self,
+        tokens: &[Address],
+        amount_in: U256,
+        slippage_tolerance: Decimal,
+    ) -> Result<SwapPathResult> {
+        if tokens.len() < 2 {
+            return Err(AppError::RouteNotFound(
+                "a swap path needs at least two tokens".to_string(),
+            ));
+        }
+        self.wallet_address()?;
+
+        let mut legs = Vec::with_capacity(tokens.len() - 1);
+        let mut failure = None;
+        let mut leg_amount = amount_in;
+
+        for (index, hop) in tokens.windows(2).enumerate() {
+            let params = SwapParams {
+                from_token: hop[0],
+                to_token: hop[1],
+                amount_in: leg_amount,
+                slippage_tolerance,
+                ..Default::default()
+            };
 
-        for fee in fee_tiers::ALL_FEES {
-            // Check if pool exists - getPool returns Address directly
-            // fee is u32, convert to U24 for the contract call
-            let fee_u24 = U24::from(fee);
-            let pool: Address =
-                factory.getPool(params.from_token, params.to_token, fee_u24).call().await?;
+            let simulation = match self.simulate_swap(params).await {
+                Ok(simulation) => simulation,
+                Err(e) => {
+                    tracing::warn!(leg = index, error = %e, "Swap path leg could not be routed");
+                    failure = Some((index, e.to_string()));
+                    break;
+                }
+            };
 
-            if pool == Address::ZERO {
-                continue;
-            }
+            let from_metadata = self.balance_service.get_token_metadata(hop[0]).await?;
+            let to_metadata = self.balance_service.get_token_metadata(hop[1]).await?;
 
-            // Get quote
-            let quote_params = IQuoterV2::QuoteExactInputSingleParams {
-                tokenIn: params.from_token,
-                tokenOut: params.to_token,
-                amountIn: params.amount_in,
-                fee: fee_u24,
-                sqrtPriceLimitX96: U160::ZERO,
-            };
+            // The next leg spends exactly this leg's expected output
+            leg_amount = parse_units(&simulation.amount_out_expected, to_metadata.decimals)
+                .map_err(AppError::Parse)?;
 
-            if let Ok(result) = quoter.quoteExactInputSingle(quote_params).call().await {
-                if result.amountOut > best_amount_out {
-                    best_amount_out = result.amountOut;
-                    best_fee = Some(fee);
-                }
-            }
+            legs.push(SwapPathLeg {
+                from_token: TokenInfo::erc20(hop[0], from_metadata.symbol, from_metadata.decimals),
+                to_token: TokenInfo::erc20(hop[1], to_metadata.symbol, to_metadata.decimals),
+                simulation,
+            });
         }
 
-        let fee = best_fee.ok_or(AppError::PoolNotFound)?;
+        let first = self.balance_service.get_token_metadata(tokens[0]).await?;
+        let start_token = TokenInfo::erc20(tokens[0], first.symbol, first.decimals);
 
-        if best_amount_out == U256::ZERO {
-            return Err(AppError::InsufficientLiquidity);
-        }
+        Self::summarize_path(format_units(amount_in, first.decimals), start_token, legs, failure)
+    }
 
-        // Build swap transaction
-        let deadline = params.deadline.unwrap_or_else(|| current_timestamp() + 1200); // 20 minutes
+    /// Aggregate per-leg simulations into a path result.
+    ///
+    /// `start_token` is the final token when no leg could be simulated.
+    fn summarize_path(
+        amount_in: String,
+        start_token: TokenInfo,
+        legs: Vec<SwapPathLeg>,
+        failure: Option<(usize, String)>,
+    ) -> Result<SwapPathResult> {
+        let mut total_gas = 0u64;
+        let mut total_gas_cost_wei = U256::ZERO;
+        for leg in &legs {
+            let gas: u64 = leg.simulation.gas_estimate.parse().map_err(|e| {
+                AppError::Parse(format!(
+                    "Invalid gas estimate '{}': {}",
+                    leg.simulation.gas_estimate, e
+                ))
+            })?;
+            let gas_price: U256 = leg.simulation.gas_price.parse().map_err(|e| {
+                AppError::Parse(format!("Invalid gas price '{}': {}", leg.simulation.gas_price, e))
+            })?;
+            total_gas = total_gas.saturating_add(gas);
+            total_gas_cost_wei += U256::from(gas) * gas_price;
+        }
 
-        // Calculate minimum amount out with slippage
-        let slippage_multiplier = Decimal::ONE - params.slippage_tolerance / Decimal::from(100);
-        let best_amount_out_u128: u128 = best_amount_out.try_into().map_err(|_| {
-            AppError::NumericOverflow(format!(
-                "best_amount_out {} exceeds u128 range",
-                best_amount_out
-            ))
-        })?;
-        let min_out = Decimal::from(best_amount_out_u128) * slippage_multiplier;
-        let min_out_u128: u128 = Self::decimal_to_u128(min_out)?;
-        let amount_out_min = U256::from(min_out_u128);
-
-        // Build swap params with fee converted to U24
-        let swap_params = ISwapRouter::ExactInputSingleParams {
-            tokenIn: params.from_token,
-            tokenOut: params.to_token,
-            fee: U24::from(fee),
-            recipient: self.wallet.address(),
-            deadline: U256::from(deadline),
-            amountIn: params.amount_in,
-            amountOutMinimum: amount_out_min,
-            sqrtPriceLimitX96: U160::ZERO,
+        let (final_amount_out, final_token) = match legs.last() {
+            Some(leg) => (leg.simulation.amount_out_expected.clone(), leg.to_token.clone()),
+            None => (amount_in.clone(), start_token),
         };
+        let (failed_leg, error) = failure.unzip();
+
+        Ok(SwapPathResult {
+            amount_in,
+            final_amount_out,
+            final_token,
+            completed: failed_leg.is_none(),
+            all_simulations_succeeded: failed_leg.is_none()
+                && legs.iter().all(|leg| leg.simulation.simulation_success),
+            failed_leg,
+            error,
+            total_gas_estimate: total_gas.to_string(),
+            total_gas_cost_eth: format_units(total_gas_cost_wei, 18),
+            legs,
+        })
+    }
+
+    /// Try to find a direct V3 route.
+    ///
+    /// Pools with zero active liquidity are not quoted. Pools below the minimum
+    /// liquidity are not selected; they are recorded in `thin_pools` instead.
+    ///
+    /// When the quoter fails for every fee tier, the output is approximated from
+    /// pool state instead and `approximate_quote` receives a warning saying so.
+    async fn try_v3_route(
+        &self,
+        params: &SwapParams,
+        thin_pools: &mut Vec<ThinPool>,
+        approximate_quote: &mut Option<Warning>,
+    ) -> Result<(SwapRoute, U256)> {
+        let tiers = self.v3_tiers(params.from_token, params.to_token, params.amount_in).await?;
+        self.v3_route_from_tiers(params, &tiers, thin_pools, approximate_quote).await
+    }
 
-        let calldata = ISwapRouter::exactInputSingleCall { params: swap_params }.abi_encode();
+    /// Select the V3 route among pools already read by [`Self::v3_tiers`].
+    ///
+    /// Only makes RPC calls when the output has to be approximated from pool state.
+    async fn v3_route_from_tiers(
+        &self,
+        params: &SwapParams,
+        tiers: &[V3Tier],
+        thin_pools: &mut Vec<ThinPool>,
+        approximate_quote: &mut Option<Warning>,
+    ) -> Result<(SwapRoute, U256)> {
+        let (fee, best_amount_out) =
+            match Self::select_v3_tier(tiers, self.min_pool_liquidity, thin_pools) {
+                Some(selected) => selected,
+                None => {
+                    let (selected, warning) = self
+                        .approximate_v3_tier(params, tiers, thin_pools)
+                        .await
+                        .ok_or(AppError::PoolNotFound)?;
+                    *approximate_quote = Some(warning);
+                    selected
+                }
+            };
 
-        let tx = TransactionRequest::default()
-            .to(UNISWAP_V3_ROUTER)
-            .input(Bytes::from(calldata).into())
-            .from(self.wallet.address());
+        if best_amount_out == U256::ZERO {
+            return Err(AppError::InsufficientLiquidity);
+        }
 
         let route = SwapRoute {
             protocol: UniswapVersion::V3,
-            path: vec![format!("{:?}", params.from_token), format!("{:?}", params.to_token)],
+            path: format_path(&[params.from_token, params.to_token]),
             fee_tier: Some(fee),
+            venue: Venue::Uniswap,
         };
-
-        Ok((route, best_amount_out, tx))
+        Ok((route, best_amount_out))
     }
 
-    /// Try to build a V2 swap.
-    async fn try_v2_swap(
+    /// Read every existing V3 pool for the pair, quoting those with active liquidity.
+    async fn v3_tiers(
         &self,
-        params: &SwapParams,
-    ) -> Result<(SwapRoute, U256, TransactionRequest)> {
-        let factory = IUniswapV2Factory::new(UNISWAP_V2_FACTORY, self.client.provider().clone());
-        let router = IUniswapV2Router02::new(UNISWAP_V2_ROUTER, self.client.provider().clone());
+        from_token: Address,
+        to_token: Address,
+        amount_in: U256,
+    ) -> Result<Vec<V3Tier>> {
+        let factory =
+            IUniswapV3Factory::new(self.uniswap.v3_factory, self.client.provider().clone());
 
-        // Check if pair exists - getPair returns Address directly
-        let pair: Address = factory.getPair(params.from_token, params.to_token).call().await?;
+        let mut tiers = Vec::new();
+        for fee in fee_tiers::ALL_FEES {
+            // Check if pool exists - getPool returns Address directly
+            // fee is u32, convert to U24 for the contract call
+            let fee_u24 = U24::from(fee);
+            let pool: Address = factory.getPool(from_token, to_token, fee_u24).call().await?;
 
-        if pair == Address::ZERO {
-            // Try routing through WETH
-            let pair_a: Address = factory.getPair(params.from_token, WETH_ADDRESS).call().await?;
-            let pair_b: Address = factory.getPair(WETH_ADDRESS, params.to_token).call().await?;
+            if pool == Address::ZERO {
+                continue;
+            }
 
-            if pair_a == Address::ZERO || pair_b == Address::ZERO {
-                return Err(AppError::PoolNotFound);
+            // Skip quoting pools with no active liquidity
+            let liquidity = IUniswapV3Pool::new(pool, self.client.provider().clone())
+                .liquidity()
+                .call()
+                .await?;
+            let mut tier = V3Tier { fee_tier: fee, liquidity, amount_out: None };
+            if tier.is_empty() {
+                tracing::debug!(fee = fee, "Skipping V3 pool with zero liquidity");
+                tiers.push(tier);
+                continue;
             }
 
-            // Route through WETH
-            return self.build_v2_multihop_swap(params).await;
+            tier.amount_out = quoter::quote_exact_input_single(
+                self.client.provider(),
+                self.uniswap.v3_quoter,
+                from_token,
+                to_token,
+                fee,
+                amount_in,
+            )
+            .await
+            .ok();
+            tiers.push(tier);
         }
 
-        // Get amounts out - returns Vec<U256> directly
-        let path = vec![params.from_token, params.to_token];
-        let amounts: Vec<U256> =
-            router.getAmountsOut(params.amount_in, path.clone()).call().await?;
+        Ok(tiers)
+    }
 
-        let amount_out = amounts[1];
+    /// Approximate the best V3 tier from pool state when the quoter failed for all of them.
+    ///
+    /// Each pool whose quote failed is quoted with single-tick math from its
+    /// `slot0` price and active liquidity. A pool is left out when the swap would
+    /// move its price by more than [`MAX_APPROXIMATE_QUOTE_PRICE_MOVE_BPS`], where
+    /// crossing initialized ticks makes the approximation unreliable.
+    async fn approximate_v3_tier(
+        &self,
+        params: &SwapParams,
+        tiers: &[V3Tier],
+        thin_pools: &mut Vec<ThinPool>,
+    ) -> Option<((u32, U256), Warning)> {
+        let factory =
+            IUniswapV3Factory::new(self.uniswap.v3_factory, self.client.provider().clone());
+        let zero_for_one = params.from_token < params.to_token;
+
+        let mut approximated = Vec::new();
+        for tier in tiers.iter().filter(|tier| !tier.is_empty() && tier.amount_out.is_none()) {
+            let Ok(pool) = factory
+                .getPool(params.from_token, params.to_token, U24::from(tier.fee_tier))
+                .call()
+                .await
+            else {
+                continue;
+            };
+            let Ok(slot0) =
+                IUniswapV3Pool::new(pool, self.client.provider().clone()).slot0().call().await
+            else {
+                continue;
+            };
 
-        if amount_out == U256::ZERO {
-            return Err(AppError::InsufficientLiquidity);
+            let Some(quote) = single_tick_amount_out(
+                slot0.sqrtPriceX96,
+                tier.liquidity,
+                params.amount_in,
+                tier.fee_tier,
+                zero_for_one,
+            ) else {
+                continue;
+            };
+            if quote.price_move_bps > MAX_APPROXIMATE_QUOTE_PRICE_MOVE_BPS {
+                tracing::warn!(
+                    fee = tier.fee_tier,
+                    price_move_bps = quote.price_move_bps,
+                    "V3 quoter failed and the amount is too large to approximate within one tick"
+                );
+                continue;
+            }
+            approximated.push(V3Tier { amount_out: Some(quote.amount_out), ..*tier });
         }
 
-        // Build swap transaction
-        let deadline = params.deadline.unwrap_or_else(|| current_timestamp() + 1200);
+        let (fee, amount_out) =
+            Self::select_v3_tier(&approximated, self.min_pool_liquidity, thin_pools)?;
+        tracing::warn!(fee = fee, "V3 quoter failed; using a single-tick approximation");
+        let warning = Warning::new(
+            WarningCode::ApproximateQuote,
+            format!(
+                "The V3 quoter failed, so amount_out_expected is approximated from the {} fee \
+                 tier pool's current price and active liquidity, assuming the swap stays within \
+                 the current tick; the eth_call simulation still checks it",
+                fee
+            ),
+        );
+        Some(((fee, amount_out), warning))
+    }
 
-        // Calculate minimum amount out with slippage
-        let slippage_multiplier = Decimal::ONE - params.slippage_tolerance / Decimal::from(100);
-        let amount_out_u128: u128 = amount_out.try_into().map_err(|_| {
-            AppError::NumericOverflow(format!("amount_out {} exceeds u128 range", amount_out))
-        })?;
-        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
-        let min_out_u128: u128 = Self::decimal_to_u128(min_out)?;
-        let amount_out_min = U256::from(min_out_u128);
+    /// Pick the quoted tier with the most output.
+    ///
+    /// Tiers quoting exactly the same output are tie-broken toward the lower fee,
+    /// whatever order they are listed in, so selection is reproducible. Empty and
+    /// unquoted pools are ignored. Pools below `min_liquidity` are recorded in
+    /// `thin_pools` instead of being selected.
+    fn select_v3_tier(
+        tiers: &[V3Tier],
+        min_liquidity: u128,
+        thin_pools: &mut Vec<ThinPool>,
+    ) -> Option<(u32, U256)> {
+        let mut best: Option<(u32, U256)> = None;
+        for tier in tiers.iter().filter(|tier| !tier.is_empty()) {
+            let Some(amount_out) = tier.amount_out else { continue };
+
+            if min_liquidity > 0 && tier.liquidity < min_liquidity {
+                tracing::debug!(
+                    fee = tier.fee_tier,
+                    liquidity = tier.liquidity,
+                    "Skipping thin V3 pool"
+                );
+                thin_pools.push(ThinPool::v3(tier.fee_tier, amount_out, tier.liquidity));
+                continue;
+            }
 
-        let calldata = IUniswapV2Router02::swapExactTokensForTokensCall {
-            amountIn: params.amount_in,
-            amountOutMin: amount_out_min,
-            path,
-            to: self.wallet.address(),
-            deadline: U256::from(deadline),
+            let better = match best {
+                None => !amount_out.is_zero(),
+                Some((best_fee, best_amount_out)) => {
+                    amount_out > best_amount_out
+                        || (amount_out == best_amount_out && tier.fee_tier < best_fee)
+                }
+            };
+            if better {
+                best = Some((tier.fee_tier, amount_out));
+            }
         }
-        .abi_encode();
+        best
+    }
 
-        let tx = TransactionRequest::default()
-            .to(UNISWAP_V2_ROUTER)
-            .input(Bytes::from(calldata).into())
-            .from(self.wallet.address());
+    /// Warn that a swapped token rebases, recommending its wrapper if it has one.
+    fn rebasing_warning(token: Address, symbol: &str) -> Warning {
+        let recommendation = non_rebasing_wrapper(token)
+            .map(|wrapper| {
+                format!("; consider swapping its non-rebasing wrapper {:?} instead", wrapper)
+            })
+            .unwrap_or_default();
+        Warning::new(
+            WarningCode::RebasingToken,
+            format!(
+                "{} is a rebasing token: balances change without transfers, so the amount \
+                 received may differ from the quote{}",
+                symbol, recommendation
+            ),
+        )
+    }
 
-        let route = SwapRoute {
-            protocol: UniswapVersion::V2,
-            path: vec![format!("{:?}", params.from_token), format!("{:?}", params.to_token)],
-            fee_tier: None,
-        };
+    /// Warn when the price impact reaches [`HIGH_PRICE_IMPACT_PERCENT`].
+    fn price_impact_warning(price_impact: Decimal) -> Option<Warning> {
+        (price_impact >= Decimal::from(HIGH_PRICE_IMPACT_PERCENT)).then(|| {
+            Warning::new(
+                WarningCode::HighPriceImpact,
+                format!(
+                    "Price impact is {}%; consider a smaller trade or a deeper pool",
+                    price_impact
+                ),
+            )
+        })
+    }
 
-        Ok((route, amount_out, tx))
+    /// Thin pools that quoted more output than the selected route.
+    fn better_priced(thin_pools: &[ThinPool], amount_out: U256) -> impl Iterator<Item = &ThinPool> {
+        thin_pools.iter().filter(move |pool| pool.amount_out > amount_out)
     }
 
-    /// Build a V2 swap routing through WETH.
-    async fn build_v2_multihop_swap(
+    /// Select a route automatically and build its swap transaction.
+    async fn discover_route(
         &self,
         params: &SwapParams,
+        thin_pools: &mut Vec<ThinPool>,
+        approximate_quote: &mut Option<Warning>,
     ) -> Result<(SwapRoute, U256, TransactionRequest)> {
-        let router = IUniswapV2Router02::new(UNISWAP_V2_ROUTER, self.client.provider().clone());
+        let (route, amount_out) = self.select_route(params, thin_pools, approximate_quote).await?;
+        let path = Self::route_path(&route)?;
+        match (route.protocol, route.fee_tier) {
+            (UniswapVersion::V3, Some(fee)) => self.build_v3_swap(params, &path, fee, amount_out),
+            (UniswapVersion::V3, None) => {
+                Err(AppError::RouteNotFound("selected V3 route has no fee tier".to_string()))
+            }
+            (UniswapVersion::V2, _) => self.build_v2_swap(params, route.venue, path, amount_out),
+        }
+    }
+
+    /// Select the route a swap of `params` takes, trying protocols in the preferred
+    /// order. Only quotes the route; no transaction is built.
+    ///
+    /// With [`ProtocolPreference::Best`] both protocols are tried concurrently
+    /// and the route with the larger output wins.
+    async fn select_route(
+        &self,
+        params: &SwapParams,
+        thin_pools: &mut Vec<ThinPool>,
+        approximate_quote: &mut Option<Warning>,
+    ) -> Result<(SwapRoute, U256)> {
+        let preference = params.protocol_preference.unwrap_or(self.protocol_preference);
+        match preference {
+            ProtocolPreference::V3First => {
+                match self.try_v3_route(params, thin_pools, approximate_quote).await {
+                    Ok(result) => Ok(result),
+                    Err(_) => self.try_v2_route(params, thin_pools).await,
+                }
+            }
+            ProtocolPreference::V2First => match self.try_v2_route(params, thin_pools).await {
+                Ok(result) => Ok(result),
+                Err(_) => self.try_v3_route(params, thin_pools, approximate_quote).await,
+            },
+            ProtocolPreference::Best => {
+                let mut thin_v2_routes = Vec::new();
+                let (v3, v2) = tokio::join!(
+                    self.try_v3_route(params, thin_pools, approximate_quote),
+                    self.try_v2_route(params, &mut thin_v2_routes)
+                );
+                thin_pools.extend(thin_v2_routes);
+                choose_route(preference, v3, v2)
+            }
+        }
+    }
+
+    /// Try to find a V2 route on every V2 venue, keeping the largest output.
+    ///
+    /// On equal outputs the earlier venue wins. Fails with the last venue's
+    /// error when no venue has a route. Routes below the minimum liquidity are
+    /// recorded in `thin_pools` instead.
+    async fn try_v2_route(
+        &self,
+        params: &SwapParams,
+        thin_pools: &mut Vec<ThinPool>,
+    ) -> Result<(SwapRoute, U256)> {
+        let mut candidates = Vec::with_capacity(self.v2_venues.len());
+        for &venue in &self.v2_venues {
+            let candidate = self.try_v2_venue_route(params, venue, thin_pools).await;
+            if let Err(e) = &candidate {
+                tracing::debug!(venue = %venue, error = %e, "No V2 route on venue");
+            }
+            candidates.push(candidate);
+        }
+
+        best_venue_route(candidates)
+    }
 
-        let path = vec![params.from_token, WETH_ADDRESS, params.to_token];
-        let amounts: Vec<U256> =
-            router.getAmountsOut(params.amount_in, path.clone()).call().await?;
+    /// Try to find a V2 route on one venue.
+    ///
+    /// A route below the minimum liquidity is quoted, recorded in `thin_pools`
+    /// and not selected.
+    async fn try_v2_venue_route(
+        &self,
+        params: &SwapParams,
+        venue: Venue,
+        thin_pools: &mut Vec<ThinPool>,
+    ) -> Result<(SwapRoute, U256)> {
+        let path = self.find_v2_path(venue, params.from_token, params.to_token).await?;
+
+        let liquidity = match self.min_pool_liquidity {
+            0 => None,
+            _ => Some(self.v2_path_liquidity(venue, &path).await?),
+        };
 
-        let amount_out = amounts[2];
+        let amount_out = self.get_v2_quote(venue, &path, params.amount_in).await?;
+        self.v2_venue_route(venue, &path, liquidity, amount_out, thin_pools)
+    }
 
+    /// Select the V2 route on `venue` from its path, liquidity (`None` if not
+    /// read) and quoted output.
+    ///
+    /// A route below the minimum liquidity is recorded in `thin_pools` instead.
+    fn v2_venue_route(
+        &self,
+        venue: Venue,
+        path: &[Address],
+        liquidity: Option<u128>,
+        amount_out: U256,
+        thin_pools: &mut Vec<ThinPool>,
+    ) -> Result<(SwapRoute, U256)> {
         if amount_out == U256::ZERO {
             return Err(AppError::InsufficientLiquidity);
         }
 
-        let deadline = params.deadline.unwrap_or_else(|| current_timestamp() + 1200);
+        if let Some(liquidity) = liquidity.filter(|&liquidity| liquidity < self.min_pool_liquidity)
+        {
+            tracing::debug!(venue = %venue, liquidity = liquidity, "Skipping thin V2 route");
+            thin_pools.push(ThinPool {
+                protocol: UniswapVersion::V2,
+                venue,
+                fee_tier: None,
+                amount_out,
+                liquidity,
+            });
+            return Err(AppError::InsufficientLiquidity);
+        }
 
-        let slippage_multiplier = Decimal::ONE - params.slippage_tolerance / Decimal::from(100);
-        let amount_out_u128: u128 = amount_out.try_into().map_err(|_| {
-            AppError::NumericOverflow(format!(
-                "multihop amount_out {} exceeds u128 range",
-                amount_out
-            ))
-        })?;
-        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
-        let min_out_u128: u128 = Self::decimal_to_u128(min_out)?;
-        let amount_out_min = U256::from(min_out_u128);
+        let route = SwapRoute {
+            protocol: UniswapVersion::V2,
+            path: format_path(path),
+            fee_tier: None,
+            venue,
+        };
+        Ok((route, amount_out))
+    }
+
+    /// Liquidity of the thinnest pair along a V2 path, as `sqrt(reserve0 * reserve1)`.
+    async fn v2_path_liquidity(&self, venue: Venue, path: &[Address]) -> Result<u128> {
+        let factory =
+            IUniswapV2Factory::new(self.v2(venue).factory, self.client.provider().clone());
+
+        let mut thinnest = u128::MAX;
+        for hop in path.windows(2) {
+            let pair_address: Address = factory.getPair(hop[0], hop[1]).call().await?;
+            let pair = IUniswapV2Pair::new(pair_address, self.client.provider().clone());
+            let reserves = pair.getReserves().call().await?;
+
+            let k = U256::from(reserves.reserve0) * U256::from(reserves.reserve1);
+            let liquidity: u128 = k.root(2).try_into().unwrap_or(u128::MAX);
+            thinnest = thinnest.min(liquidity);
+        }
+
+        Ok(thinnest)
+    }
+
+    /// Find a V2 path on `venue`: the direct pair if it exists, otherwise routed
+    /// through the chain's wrapped native token.
+    async fn find_v2_path(
+        &self,
+        venue: Venue,
+        from_token: Address,
+        to_token: Address,
+    ) -> Result<Vec<Address>> {
+        let factory =
+            IUniswapV2Factory::new(self.v2(venue).factory, self.client.provider().clone());
+
+        // Check if pair exists - getPair returns Address directly
+        let pair: Address = factory.getPair(from_token, to_token).call().await?;
+
+        if pair == Address::ZERO {
+            let path =
+                self.v2_intermediary_path(from_token, to_token).ok_or(AppError::PoolNotFound)?;
+            let pair_a: Address = factory.getPair(path[0], path[1]).call().await?;
+            let pair_b: Address = factory.getPair(path[1], path[2]).call().await?;
+
+            if pair_a == Address::ZERO || pair_b == Address::ZERO {
+                return Err(AppError::PoolNotFound);
+            }
+
+            return Ok(path);
+        }
+
+        Ok(vec![from_token, to_token])
+    }
+
+    /// The V2 path through the wrapped native token, tried when there is no direct pair.
+    fn v2_intermediary_path(&self, from_token: Address, to_token: Address) -> Option<Vec<Address>> {
+        let wrapped = self.wrapped_native_token?;
+        (wrapped != from_token && wrapped != to_token).then(|| vec![from_token, wrapped, to_token])
+    }
+
+    /// Quote the input required to receive exactly `amount_out` of `to_token`.
+    ///
+    /// Checks every V3 fee tier and the V2 route on each V2 venue, and picks the
+    /// venue that needs the least input.
+    pub async fn quote_exact_output(
+        &self,
+        from_token: Address,
+        to_token: Address,
+        amount_out: U256,
+    ) -> Result<ExactOutputQuote> {
+        tracing::info!(
+            from = %from_token,
+            to = %to_token,
+            amount_out = %amount_out,
+            "Quoting exact output"
+        );
+
+        let from_metadata = self.balance_service.get_token_metadata(from_token).await?;
+        let to_metadata = self.balance_service.get_token_metadata(to_token).await?;
+
+        let factory =
+            IUniswapV3Factory::new(self.uniswap.v3_factory, self.client.provider().clone());
+        let mut best: Option<(SwapRoute, U256)> = None;
+
+        for fee in fee_tiers::ALL_FEES {
+            let pool: Address =
+                factory.getPool(from_token, to_token, U24::from(fee)).call().await?;
+            if pool == Address::ZERO {
+                continue;
+            }
+
+            let Ok(amount_in) = quoter::quote_exact_output_single(
+                self.client.provider(),
+                self.uniswap.v3_quoter,
+                from_token,
+                to_token,
+                fee,
+                amount_out,
+            )
+            .await
+            else {
+                continue;
+            };
+
+            let route = SwapRoute {
+                protocol: UniswapVersion::V3,
+                path: vec![format!("{:?}", from_token), format!("{:?}", to_token)],
+                fee_tier: Some(fee),
+                venue: Venue::Uniswap,
+            };
+            best = Self::cheaper_quote(best, route, amount_in);
+        }
+
+        for &venue in &self.v2_venues {
+            let Ok(path) = self.find_v2_path(venue, from_token, to_token).await else {
+                continue;
+            };
+            if let Ok(amount_in) = self.get_v2_amount_in(venue, &path, amount_out).await {
+                let route = SwapRoute {
+                    protocol: UniswapVersion::V2,
+                    path: format_path(&path),
+                    fee_tier: None,
+                    venue,
+                };
+                best = Self::cheaper_quote(best, route, amount_in);
+            }
+        }
+
+        let (route, amount_in) = best.ok_or(AppError::PoolNotFound)?;
+
+        let amount_in_formatted = format_units(amount_in, from_metadata.decimals);
+        let amount_out_formatted = format_units(amount_out, to_metadata.decimals);
+        let price = Self::implied_price(&amount_in_formatted, &amount_out_formatted)?;
+
+        Ok(ExactOutputQuote {
+            from_token: TokenInfo::erc20(from_token, from_metadata.symbol, from_metadata.decimals),
+            to_token: TokenInfo::erc20(to_token, to_metadata.symbol, to_metadata.decimals),
+            amount_in: amount_in_formatted,
+            amount_in_raw: amount_in.to_string(),
+            amount_out: amount_out_formatted,
+            amount_out_raw: amount_out.to_string(),
+            price: price.to_string(),
+            route,
+        })
+    }
+
+    /// List the routes that exist for a pair, without quoting them.
+    ///
+    /// Looks up a V3 pool at every fee tier and, on each V2 venue, a direct pair
+    /// and a path through the wrapped native token. All lookups run concurrently.
+    pub async fn tradable_routes(
+        &self,
+        from_token: Address,
+        to_token: Address,
+    ) -> Result<Vec<SwapRoute>> {
+        let mut checks: Vec<RouteCheck> =
+            fee_tiers::ALL_FEES.iter().map(|&fee| RouteCheck::V3(fee)).collect();
+        for &venue in &self.v2_venues {
+            checks.push(RouteCheck::V2(venue));
+            if let Some(path) = self.v2_intermediary_path(from_token, to_token) {
+                checks.push(RouteCheck::V2Via(venue, path[1]));
+            }
+        }
+
+        let routes = fan_out(checks.len(), checks, |check| {
+            let service = self.clone();
+            async move { service.check_route(check, from_token, to_token).await }
+        })
+        .await?;
+
+        Ok(routes.into_iter().flatten().collect())
+    }
+
+    /// The route for `check`, if its pools exist.
+    async fn check_route(
+        &self,
+        check: RouteCheck,
+        from_token: Address,
+        to_token: Address,
+    ) -> Result<Option<SwapRoute>> {
+        let provider = self.client.provider().clone();
+        let (protocol, path, fee_tier, venue) = match check {
+            RouteCheck::V3(fee) => {
+                let factory = IUniswapV3Factory::new(self.uniswap.v3_factory, provider);
+                let pool: Address =
+                    factory.getPool(from_token, to_token, U24::from(fee)).call().await?;
+                if pool == Address::ZERO {
+                    return Ok(None);
+                }
+                (UniswapVersion::V3, vec![from_token, to_token], Some(fee), Venue::Uniswap)
+            }
+            RouteCheck::V2(venue) => {
+                let factory = IUniswapV2Factory::new(self.v2(venue).factory, provider);
+                let pair: Address = factory.getPair(from_token, to_token).call().await?;
+                if pair == Address::ZERO {
+                    return Ok(None);
+                }
+                (UniswapVersion::V2, vec![from_token, to_token], None, venue)
+            }
+            RouteCheck::V2Via(venue, via) => {
+                let factory = IUniswapV2Factory::new(self.v2(venue).factory, provider);
+                let (call_a, call_b) =
+                    (factory.getPair(from_token, via), factory.getPair(via, to_token));
+                let (pair_a, pair_b) = tokio::join!(call_a.call(), call_b.call());
+                if pair_a? == Address::ZERO || pair_b? == Address::ZERO {
+                    return Ok(None);
+                }
+                (UniswapVersion::V2, vec![from_token, via, to_token], None, venue)
+            }
+        };
+
+        Ok(Some(SwapRoute { protocol, path: format_path(&path), fee_tier, venue }))
+    }
+
+    /// Preview a swap without simulating it.
+    ///
+    /// Picks the route with the same selection `simulate_swap` uses and checks the
+    /// wallet's balance and router allowance. The pools are quoted, but the swap
+    /// transaction is neither gas-estimated nor simulated.
+    pub async fn preview_swap(
+        &self,
+        from_token: Address,
+        to_token: Address,
+        amount_in: U256,
+    ) -> Result<SwapPreview> {
+        tracing::info!(from = %from_token, to = %to_token, amount = %amount_in, "Previewing swap");
+
+        let from_metadata = self.balance_service.get_token_metadata(from_token).await?;
+        let to_metadata = self.balance_service.get_token_metadata(to_token).await?;
+
+        // Every V3 pool and V2 venue, for the listing
+        let tiers = self.v3_tiers(from_token, to_token, amount_in).await?;
+        let mut venues: Vec<PreviewVenue> = tiers
+            .iter()
+            .map(|tier| PreviewVenue {
+                protocol: UniswapVersion::V3,
+                venue: Venue::Uniswap,
+                fee_tier: Some(tier.fee_tier),
+                path: format_path(&[from_token, to_token]),
+                liquidity: Some(tier.liquidity.to_string()),
+                amount_out: tier.amount_out.map(|out| format_units(out, to_metadata.decimals)),
+            })
+            .collect();
+        let mut thin_pools = Vec::new();
+        let mut v2_candidates = Vec::with_capacity(self.v2_venues.len());
+        for &venue in &self.v2_venues {
+            let path = match self.find_v2_path(venue, from_token, to_token).await {
+                Ok(path) => path,
+                Err(AppError::PoolNotFound) => {
+                    v2_candidates.push(Err(AppError::PoolNotFound));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let liquidity = self.v2_path_liquidity(venue, &path).await?;
+            let amount_out =
+                self.get_v2_quote(venue, &path, amount_in).await.ok().filter(|out| !out.is_zero());
+            v2_candidates.push(match amount_out {
+                Some(out) => {
+                    self.v2_venue_route(venue, &path, Some(liquidity), out, &mut thin_pools)
+                }
+                None => Err(AppError::InsufficientLiquidity),
+            });
+            venues.push(PreviewVenue {
+                protocol: UniswapVersion::V2,
+                venue,
+                fee_tier: None,
+                path: format_path(&path),
+                liquidity: Some(liquidity.to_string()),
+                amount_out: amount_out.map(|out| format_units(out, to_metadata.decimals)),
+            });
+        }
+
+        // The route swap_tokens would pick, selected from the pools read above
+        let params = SwapParams { from_token, to_token, amount_in, ..Default::default() };
+        let v3 = self.v3_route_from_tiers(&params, &tiers, &mut thin_pools, &mut None).await;
+        let best = match choose_route(self.protocol_preference, v3, best_venue_route(v2_candidates))
+        {
+            Ok(best) => Some(best),
+            Err(AppError::PoolNotFound | AppError::InsufficientLiquidity) => None,
+            Err(e) => return Err(e),
+        };
+
+        // The router pulls the input from the wallet, so check balance and allowance
+        let (balance, allowance) = match (&best, &self.wallet) {
+            (Some((route, _)), Some(wallet)) => {
+                let spender = match route.protocol {
+                    UniswapVersion::V2 => self.v2(route.venue).router,
+                    UniswapVersion::V3 => self.uniswap.v3_router,
+                };
+                let token = IERC20::new(from_token, self.client.provider().clone());
+                (
+                    token.balanceOf(wallet.address()).call().await.ok(),
+                    token.allowance(wallet.address(), spender).call().await.ok(),
+                )
+            }
+            _ => (None, None),
+        };
+
+        let issues = Self::preview_issues(
+            &from_metadata.symbol,
+            amount_in,
+            best.is_some(),
+            self.wallet.is_some(),
+            balance,
+            allowance,
+        );
+        let (route, amount_out_estimate) = match best {
+            Some((route, out)) => (Some(route), Some(format_units(out, to_metadata.decimals))),
+            None => (None, None),
+        };
+
+        Ok(SwapPreview {
+            from_token: TokenInfo::erc20(from_token, from_metadata.symbol, from_metadata.decimals),
+            to_token: TokenInfo::erc20(to_token, to_metadata.symbol, to_metadata.decimals),
+            amount_in: format_units(amount_in, from_metadata.decimals),
+            amount_in_raw: amount_in.to_string(),
+            venues,
+            route,
+            amount_out_estimate,
+            likely_to_succeed: issues.is_empty(),
+            issues,
+        })
+    }
+
+    /// Reasons a full simulation is likely to fail, given what a preview could check.
+    ///
+    /// A balance or allowance that could not be read is not reported.
+    fn preview_issues(
+        symbol: &str,
+        amount_in: U256,
+        has_route: bool,
+        has_wallet: bool,
+        balance: Option<U256>,
+        allowance: Option<U256>,
+    ) -> Vec<String> {
+        let mut issues = Vec::new();
+        if !has_route {
+            issues.push("No Uniswap V2 or V3 route with liquidity for this pair".to_string());
+        }
+        if !has_wallet {
+            issues.push(
+                "No wallet configured (read-only mode); swap_tokens needs ETHEREUM_PRIVATE_KEY \
+                 or ETHEREUM_MNEMONIC"
+                    .to_string(),
+            );
+        }
+        if balance.is_some_and(|balance| balance < amount_in) {
+            issues.push(format!("Wallet {} balance is below the input amount", symbol));
+        }
+        if allowance.is_some_and(|allowance| allowance < amount_in) {
+            issues.push(format!("Router allowance for {} is below the input amount", symbol));
+        }
+        issues
+    }
+
+    /// Keep whichever quote requires less input (ignoring zero quotes).
+    fn cheaper_quote(
+        best: Option<(SwapRoute, U256)>,
+        route: SwapRoute,
+        amount_in: U256,
+    ) -> Option<(SwapRoute, U256)> {
+        match best {
+            _ if amount_in == U256::ZERO => best,
+            Some((_, best_in)) if best_in <= amount_in => best,
+            _ => Some((route, amount_in)),
+        }
+    }
+
+    /// Input tokens paid per output token, from human-readable amounts.
+    fn implied_price(amount_in: &str, amount_out: &str) -> Result<Decimal> {
+        let amount_in = Decimal::from_str(amount_in)
+            .map_err(|e| AppError::Parse(format!("Invalid amount '{}': {}", amount_in, e)))?;
+        let amount_out = Decimal::from_str(amount_out)
+            .map_err(|e| AppError::Parse(format!("Invalid amount '{}': {}", amount_out, e)))?;
+
+        if amount_out.is_zero() {
+            return Ok(Decimal::ZERO);
+        }
+
+        Ok((amount_in / amount_out).normalize())
+    }
+
+    /// Build a caller-specified route, verifying every hop has a pool.
+    async fn build_forced_swap(
+        &self,
+        params: &SwapParams,
+        forced: &ForcedRoute,
+    ) -> Result<(SwapRoute, U256, TransactionRequest)> {
+        let path = Self::forced_route_path(params, forced)?;
+
+        match forced.protocol {
+            UniswapVersion::V2 => {
+                let venue = forced.venue;
+                if !self.v2_venues.contains(&venue) {
+                    return Err(AppError::RouteNotFound(format!(
+                        "{} is not one of the configured V2 venues",
+                        venue
+                    )));
+                }
+
+                let factory =
+                    IUniswapV2Factory::new(self.v2(venue).factory, self.client.provider().clone());
+                for hop in path.windows(2) {
+                    let pair: Address = factory.getPair(hop[0], hop[1]).call().await?;
+                    if pair == Address::ZERO {
+                        return Err(AppError::RouteNotFound(format!(
+                            "No {} V2 pair for {:?} -> {:?}",
+                            venue, hop[0], hop[1]
+                        )));
+                    }
+                }
+
+                let amount_out = self.get_v2_quote(venue, &path, params.amount_in).await?;
+                if amount_out == U256::ZERO {
+                    return Err(AppError::InsufficientLiquidity);
+                }
+
+                self.build_v2_swap(params, venue, path, amount_out)
+            }
+            UniswapVersion::V3 => {
+                let fee = forced.fee_tier.ok_or_else(|| {
+                    AppError::RouteNotFound("A fee tier is required for a V3 route".to_string())
+                })?;
+
+                let factory =
+                    IUniswapV3Factory::new(self.uniswap.v3_factory, self.client.provider().clone());
+                for hop in path.windows(2) {
+                    let pool: Address =
+                        factory.getPool(hop[0], hop[1], U24::from(fee)).call().await?;
+                    if pool == Address::ZERO {
+                        return Err(AppError::RouteNotFound(format!(
+                            "No Uniswap V3 pool for {:?} -> {:?} at fee tier {}",
+                            hop[0], hop[1], fee
+                        )));
+                    }
+                }
+
+                let amount_out = self.get_v3_quote(&path, params.amount_in, fee).await?;
+                if amount_out == U256::ZERO {
+                    return Err(AppError::InsufficientLiquidity);
+                }
+
+                self.build_v3_swap(params, &path, fee, amount_out)
+            }
+        }
+    }
+
+    /// Expand a forced route into the full token path and validate its shape.
+    fn forced_route_path(params: &SwapParams, forced: &ForcedRoute) -> Result<Vec<Address>> {
+        let mut path = Vec::with_capacity(forced.intermediaries.len() + 2);
+        path.push(params.from_token);
+        path.extend(forced.intermediaries.iter().copied());
+        path.push(params.to_token);
+
+        for (i, token) in path.iter().enumerate() {
+            if path[..i].contains(token) {
+                return Err(AppError::RouteNotFound(format!(
+                    "Token {:?} appears more than once in the route",
+                    token
+                )));
+            }
+        }
+
+        if forced.protocol == UniswapVersion::V3 {
+            if let Some(fee) = forced.fee_tier {
+                if !fee_tiers::ALL_FEES.contains(&fee) {
+                    return Err(AppError::RouteNotFound(format!(
+                        "Unsupported V3 fee tier {} (expected one of {:?})",
+                        fee,
+                        fee_tiers::ALL_FEES
+                    )));
+                }
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Build a V3 swap along `path`, using `exactInputSingle` for one hop and
+    /// `exactInput` for multi-hop paths.
+    fn build_v3_swap(
+        &self,
+        params: &SwapParams,
+        path: &[Address],
+        fee: u32,
+        amount_out: U256,
+    ) -> Result<(SwapRoute, U256, TransactionRequest)> {
+        // Build swap transaction
+        let deadline = params.deadline.unwrap_or_else(|| current_timestamp() + 1200); // 20 minutes
+
+        // Calculate minimum amount out with slippage, or the caller's stricter floor
+        let (amount_out_min, _) = Self::effective_minimum(params, amount_out)?;
+
+        let calldata = if path.len() == 2 {
+            // Build swap params with fee converted to U24
+            let swap_params = ISwapRouter::ExactInputSingleParams {
+                tokenIn: params.from_token,
+                tokenOut: params.to_token,
+                fee: U24::from(fee),
+                recipient: self.wallet_address()?,
+                deadline: U256::from(deadline),
+                amountIn: params.amount_in,
+                amountOutMinimum: amount_out_min,
+                sqrtPriceLimitX96: U160::ZERO,
+            };
+
+            ISwapRouter::exactInputSingleCall { params: swap_params }.abi_encode()
+        } else {
+            let swap_params = ISwapRouter::ExactInputParams {
+                path: encode_path(path, fee),
+                recipient: self.wallet_address()?,
+                deadline: U256::from(deadline),
+                amountIn: params.amount_in,
+                amountOutMinimum: amount_out_min,
+            };
+
+            ISwapRouter::exactInputCall { params: swap_params }.abi_encode()
+        };
+
+        let tx = TransactionRequest::default()
+            .to(self.uniswap.v3_router)
+            .input(Bytes::from(calldata).into())
+            .value(U256::ZERO)
+            .from(self.simulation_sender(params)?);
+
+        let route = SwapRoute {
+            protocol: UniswapVersion::V3,
+            path: format_path(path),
+            fee_tier: Some(fee),
+            venue: Venue::Uniswap,
+        };
+
+        Ok((route, amount_out, tx))
+    }
+
+    /// Build a V2 swap on `venue` along `path` (direct or multi-hop).
+    fn build_v2_swap(
+        &self,
+        params: &SwapParams,
+        venue: Venue,
+        path: Vec<Address>,
+        amount_out: U256,
+    ) -> Result<(SwapRoute, U256, TransactionRequest)> {
+        // Build swap transaction
+        let deadline = params.deadline.unwrap_or_else(|| current_timestamp() + 1200);
+
+        // Calculate minimum amount out with slippage, or the caller's stricter floor
+        let (amount_out_min, _) = Self::effective_minimum(params, amount_out)?;
 
         let calldata = IUniswapV2Router02::swapExactTokensForTokensCall {
             amountIn: params.amount_in,
             amountOutMin: amount_out_min,
             path: path.clone(),
-            to: self.wallet.address(),
+            to: self.wallet_address()?,
             deadline: U256::from(deadline),
         }
         .abi_encode();
 
         let tx = TransactionRequest::default()
-            .to(UNISWAP_V2_ROUTER)
+            .to(self.v2(venue).router)
             .input(Bytes::from(calldata).into())
-            .from(self.wallet.address());
+            .value(U256::ZERO)
+            .from(self.simulation_sender(params)?);
 
         let route = SwapRoute {
             protocol: UniswapVersion::V2,
-            path: path.iter().map(|a| format!("{:?}", a)).collect(),
+            path: format_path(&path),
             fee_tier: None,
+            venue,
         };
 
         Ok((route, amount_out, tx))
     }
 
-    /// Estimate gas for a transaction.
+    /// Apply slippage tolerance (a percentage) to an expected output amount.
+    ///
+    /// Computed in integers as `amount_out * (100 - slippage) / 100`, rounded down,
+    /// so fractional basis points (e.g. 0.005%) lose no precision and the minimum
+    /// never rounds above the exact value.
+    pub(crate) fn minimum_amount_out(
+        amount_out: U256,
+        slippage_tolerance: Decimal,
+    ) -> Result<U256> {
+        let (numerator, denominator) = Self::slippage_fraction(slippage_tolerance)?;
+
+        amount_out.checked_mul(numerator).map(|product| product / denominator).ok_or_else(|| {
+            AppError::NumericOverflow(format!(
+                "amount_out {} is too large to apply slippage",
+                amount_out
+            ))
+        })
+    }
+
+    /// The minimum output at each slippage level, in the order given.
+    fn slippage_table(
+        amount_out: U256,
+        levels: &[Decimal],
+        decimals: u8,
+    ) -> Result<Vec<SlippageLevel>> {
+        levels
+            .iter()
+            .map(|&slippage| {
+                let min_out = Self::minimum_amount_out(amount_out, slippage)?;
+                Ok(SlippageLevel {
+                    slippage: slippage.normalize().to_string(),
+                    min_out: format_units(min_out, decimals),
+                })
+            })
+            .collect()
+    }
+
+    /// The minimum output sent with a swap: the slippage minimum, raised to the
+    /// caller's absolute `min_amount_out` when that is stricter.
+    fn effective_minimum(
+        params: &SwapParams,
+        amount_out: U256,
+    ) -> Result<(U256, MinimumOutSource)> {
+        let slippage_min = Self::minimum_amount_out(amount_out, params.slippage_tolerance)?;
+        Ok(match params.min_amount_out {
+            Some(floor) if floor > slippage_min => (floor, MinimumOutSource::MinAmountOut),
+            _ => (slippage_min, MinimumOutSource::Slippage),
+        })
+    }
+
+    /// The share of the output kept after slippage, `(100 - slippage) / 100`,
+    /// as an exact integer fraction.
+    fn slippage_fraction(slippage_tolerance: Decimal) -> Result<(U256, U256)> {
+        if slippage_tolerance < Decimal::ZERO || slippage_tolerance > Decimal::ONE_HUNDRED {
+            return Err(AppError::Parse(format!(
+                "Invalid slippage tolerance {}%: must be between 0 and 100",
+                slippage_tolerance
+            )));
+        }
+
+        // slippage / 100 = mantissa / (100 * 10^scale)
+        let denominator =
+            U256::from(100u8) * U256::from(10u8).pow(U256::from(slippage_tolerance.scale()));
+        let numerator = denominator - U256::from(slippage_tolerance.mantissa().unsigned_abs());
+        Ok((numerator, denominator))
+    }
+
+    /// Warn when rounding the minimum output down to a whole raw unit drops more
+    /// than [`PRECISION_LOSS_WARNING_BPS`] of its exact value.
+    ///
+    /// This happens for small amounts of low-decimal tokens: 1.50 of a 2-decimal
+    /// token at 0.5% slippage has an exact minimum of 1.4925, sent as 1.49.
+    fn precision_loss_warning(
+        amount_out: U256,
+        slippage_tolerance: Decimal,
+        decimals: u8,
+        symbol: &str,
+    ) -> Option<Warning> {
+        let (numerator, denominator) = Self::slippage_fraction(slippage_tolerance).ok()?;
+        let product = amount_out.checked_mul(numerator)?;
+        let remainder = product % denominator;
+        // remainder / product is the dropped share of the exact minimum
+        if product.is_zero()
+            || remainder * U256::from(10_000u32)
+                <= product.saturating_mul(U256::from(PRECISION_LOSS_WARNING_BPS))
+        {
+            return None;
+        }
+
+        // Both fit in u128 here: product is under 1000x the remainder, which is below denominator
+        let lost_percent = (Decimal::from(u128::try_from(remainder).ok()?) * Decimal::ONE_HUNDRED
+            / Decimal::from(u128::try_from(product).ok()?))
+        .round_dp(2);
+        Some(Warning::new(
+            WarningCode::PrecisionLoss,
+            format!(
+                "amount_out_minimum is rounded down to {} {} ({} decimals), {}% below the exact \
+                 minimum; consider a larger amount",
+                format_units(product / denominator, decimals),
+                symbol,
+                decimals,
+                lost_percent
+            ),
+        ))
+    }
+
+    /// Pick a fallback gas value for a route, returning it with a description.
+    ///
+    /// Uses a typical per-protocol value scaled by hop count, or the hard default
+    /// if the route has no hops.
+    fn fallback_gas(route: &SwapRoute) -> (u64, String) {
+        let hops = route.path.len().saturating_sub(1) as u64;
+        if hops == 0 {
+            return (DEFAULT_SWAP_GAS, format!("hard default of {} gas", DEFAULT_SWAP_GAS));
+        }
+
+        let (base, per_hop) = match route.protocol {
+            UniswapVersion::V2 => (V2_SWAP_GAS, V2_EXTRA_HOP_GAS),
+            UniswapVersion::V3 => (V3_SWAP_GAS, V3_EXTRA_HOP_GAS),
+        };
+        let gas = base + per_hop * (hops - 1);
+
+        (gas, format!("typical {:?} value of {} gas for {} hop(s)", route.protocol, gas, hops))
+    }
+
+    /// Explain why gas estimation fell back, preferring the simulation's revert reason.
+    fn gas_fallback_reason(
+        estimate_error: &AppError,
+        simulation_error: Option<&str>,
+        fallback: &str,
+    ) -> String {
+        match simulation_error {
+            Some(revert) => format!(
+                "Gas estimation failed because the swap would revert ({}); using {}",
+                revert, fallback
+            ),
+            None => format!("Gas estimation failed ({}); using {}", estimate_error, fallback),
+        }
+    }
+
+    /// Estimate gas for a transaction, rejecting implausible estimates.
     async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<u64> {
-        self.client.estimate_gas(tx).await
+        self.client.estimate_gas(tx).await.and_then(plausible_gas_estimate)
     }
 
     /// Simulate a transaction using eth_call to verify it would execute successfully.
@@ -394,39 +1924,265 @@ impl SwapService {
         }
     }
 
-    /// Calculate approximate price impact by comparing spot price vs execution price.
+    /// Token addresses along a route.
+    fn route_path(route: &SwapRoute) -> Result<Vec<Address>> {
+        route
+            .path
+            .iter()
+            .map(|a| a.parse::<Address>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Parse(format!("Invalid route path: {}", e)))
+    }
+
+    /// Pool mid price after swapping `amount_in` along `route`, in output tokens
+    /// per input token with the fee excluded.
     ///
-    /// Price impact measures how much the trade size affects the execution price.
-    /// A higher price impact means the trade is moving the market more significantly.
-    async fn calculate_price_impact(
+    /// V3 uses the quoter's `sqrtPriceX96After` for each hop; V2 applies each hop's
+    /// amounts to the pair reserves. Returns `None` if the quoter does not report
+    /// post-swap prices.
+    async fn post_trade_price(
         &self,
-        params: &SwapParams,
-        amount_out: U256,
         route: &SwapRoute,
-    ) -> Result<Decimal> {
-        // Use a small reference amount to approximate the spot price
-        // This gives us the "marginal" exchange rate without significant price impact
-        let reference_amount = Self::calculate_reference_amount(params.amount_in);
+        amount_in: U256,
+        from_decimals: u8,
+        to_decimals: u8,
+    ) -> Result<Option<String>> {
+        let path = Self::route_path(route)?;
+        let hops = path.len().saturating_sub(1);
 
-        let spot_output = match route.protocol {
+        let hop_prices = match route.protocol {
             UniswapVersion::V3 => {
-                self.get_v3_quote(params, reference_amount, route.fee_tier).await?
+                let fees = vec![route.fee_tier.unwrap_or(3000); hops];
+                let quote = quoter::quote_exact_input_path(
+                    self.client.provider(),
+                    self.uniswap.v3_quoter,
+                    &path,
+                    &fees,
+                    amount_in,
+                )
+                .await?;
+                if quote.sqrt_price_x96_after.len() != hops {
+                    return Ok(None);
+                }
+                path.windows(2)
+                    .zip(&quote.sqrt_price_x96_after)
+                    .map(|(hop, sqrt_price)| {
+                        PriceRatio::from_sqrt_price_x96(*sqrt_price, hop[0] < hop[1])
+                    })
+                    .collect::<Option<Vec<_>>>()
+            }
+            UniswapVersion::V2 => {
+                let v2 = self.v2(route.venue);
+                let router = IUniswapV2Router02::new(v2.router, self.client.provider().clone());
+                let factory = IUniswapV2Factory::new(v2.factory, self.client.provider().clone());
+                let amounts = validate_v2_amounts(
+                    router.getAmountsOut(amount_in, path.clone()).call().await?,
+                    &path,
+                )?;
+
+                let mut prices = Vec::with_capacity(hops);
+                for (i, hop) in path.windows(2).enumerate() {
+                    let pair_address: Address = factory.getPair(hop[0], hop[1]).call().await?;
+                    let pair = IUniswapV2Pair::new(pair_address, self.client.provider().clone());
+                    let reserves = pair.getReserves().call().await?;
+                    let (reserve_in, reserve_out) = if hop[0] < hop[1] {
+                        (U256::from(reserves.reserve0), U256::from(reserves.reserve1))
+                    } else {
+                        (U256::from(reserves.reserve1), U256::from(reserves.reserve0))
+                    };
+                    prices.push(v2_hop_price(
+                        reserve_in + amounts[i],
+                        reserve_out.saturating_sub(amounts[i + 1]),
+                    ));
+                }
+                prices.into_iter().collect::<Option<Vec<_>>>()
             }
-            UniswapVersion::V2 => self.get_v2_quote(params, reference_amount).await?,
         };
 
-        // Calculate rates (output per unit of input)
-        // spot_rate = spot_output / reference_amount
-        // execution_rate = amount_out / amount_in
-        //
-        // Price impact = (1 - execution_rate / spot_rate) * 100
-        //              = (1 - (amount_out * reference_amount) / (spot_output * amount_in)) * 100
+        Ok(hop_prices.and_then(|prices| chain_hop_prices(&prices, from_decimals, to_decimals)))
+    }
 
-        // Convert U256 values to u128 with overflow checking
+    /// Re-quote `route` from pool state, bypassing the quoter and router.
+    ///
+    /// V2 hops apply the constant-product formula to each pair's reserves; V3 hops
+    /// apply single-tick math to each pool's `slot0` price and active liquidity.
+    /// Returns `None` when a V3 hop would move its pool price by more than
+    /// [`MAX_APPROXIMATE_QUOTE_PRICE_MOVE_BPS`], where crossing initialized ticks
+    /// makes single-tick math unreliable.
+    async fn independent_quote(
+        &self,
+        route: &SwapRoute,
+        amount_in: U256,
+    ) -> Result<Option<(QuoteCrossCheckMethod, U256)>> {
+        let path = Self::route_path(route)?;
+        let mut amount = amount_in;
+
+        match route.protocol {
+            UniswapVersion::V2 => {
+                let factory = IUniswapV2Factory::new(
+                    self.v2(route.venue).factory,
+                    self.client.provider().clone(),
+                );
+                for hop in path.windows(2) {
+                    let pair_address: Address = factory.getPair(hop[0], hop[1]).call().await?;
+                    if pair_address == Address::ZERO {
+                        return Err(AppError::PoolNotFound);
+                    }
+                    let pair = IUniswapV2Pair::new(pair_address, self.client.provider().clone());
+                    let reserves = pair.getReserves().call().await?;
+                    let (reserve_in, reserve_out) = if hop[0] < hop[1] {
+                        (U256::from(reserves.reserve0), U256::from(reserves.reserve1))
+                    } else {
+                        (U256::from(reserves.reserve1), U256::from(reserves.reserve0))
+                    };
+                    amount = v2_amount_out(amount, reserve_in, reserve_out)
+                        .ok_or(AppError::InsufficientLiquidity)?;
+                }
+                Ok(Some((QuoteCrossCheckMethod::V2Reserves, amount)))
+            }
+            UniswapVersion::V3 => {
+                let fee = route.fee_tier.unwrap_or(3000);
+                let factory =
+                    IUniswapV3Factory::new(self.uniswap.v3_factory, self.client.provider().clone());
+                for hop in path.windows(2) {
+                    let pool_address: Address =
+                        factory.getPool(hop[0], hop[1], U24::from(fee)).call().await?;
+                    if pool_address == Address::ZERO {
+                        return Err(AppError::PoolNotFound);
+                    }
+                    let pool = IUniswapV3Pool::new(pool_address, self.client.provider().clone());
+                    let slot0 = pool.slot0().call().await?;
+                    let liquidity = pool.liquidity().call().await?;
+
+                    let Some(quote) = single_tick_amount_out(
+                        slot0.sqrtPriceX96,
+                        liquidity,
+                        amount,
+                        fee,
+                        hop[0] < hop[1],
+                    ) else {
+                        return Err(AppError::InsufficientLiquidity);
+                    };
+                    if quote.price_move_bps > MAX_APPROXIMATE_QUOTE_PRICE_MOVE_BPS {
+                        return Ok(None);
+                    }
+                    amount = quote.amount_out;
+                }
+                Ok(Some((QuoteCrossCheckMethod::V3SingleTick, amount)))
+            }
+        }
+    }
+
+    /// Compare a quoted output with an independent quote of the same route.
+    fn quote_cross_check(
+        method: QuoteCrossCheckMethod,
+        quoted: U256,
+        independent: U256,
+        tolerance: Decimal,
+        decimals: u8,
+    ) -> QuoteCrossCheck {
+        let discrepancy = Self::quote_discrepancy_percent(quoted, independent);
+        QuoteCrossCheck {
+            method,
+            amount_out: format_units(independent, decimals),
+            discrepancy_percent: discrepancy.normalize().to_string(),
+            tolerance_percent: tolerance.normalize().to_string(),
+            within_tolerance: discrepancy <= tolerance,
+        }
+    }
+
+    /// Difference between `quoted` and `independent` as a percentage of `quoted`,
+    /// to 4 decimal places.
+    fn quote_discrepancy_percent(quoted: U256, independent: U256) -> Decimal {
+        if quoted.is_zero() {
+            return if independent.is_zero() { Decimal::ZERO } else { Decimal::ONE_HUNDRED };
+        }
+        // Percentage scaled by 10^4
+        let scaled = quoted.abs_diff(independent) * U256::from(1_000_000u32) / quoted;
+        i128::try_from(scaled)
+            .ok()
+            .and_then(|scaled| Decimal::try_from_i128_with_scale(scaled, 4).ok())
+            .unwrap_or(Decimal::MAX)
+    }
+
+    /// Calculate approximate price impact by comparing spot price vs execution price.
+    ///
+    /// Price impact measures how much the trade size affects the execution price.
+    /// A higher price impact means the trade is moving the market more significantly.
+    async fn calculate_price_impact(
+        &self,
+        params: &SwapParams,
+        amount_out: U256,
+        route: &SwapRoute,
+        to_decimals: u8,
+    ) -> Result<Decimal> {
+        // Use a small reference amount to approximate the spot price
+        // This gives us the "marginal" exchange rate without significant price impact
+        let mut reference_amount = Self::calculate_reference_amount(params.amount_in);
+        let max_reference = params.amount_in / U256::from(10);
+
+        // Quote the reference amount along the same path the swap uses
+        let path = Self::route_path(route)?;
+        let mut spot_output = self.quote_route(route, &path, reference_amount).await?;
+
+        // A small trade into a coarse token (e.g. 8 decimals) quotes only a few raw
+        // units at 0.1%; grow the reference until its rate is measurable
+        while spot_output < U256::from(MIN_SPOT_OUTPUT)
+            && reference_amount * U256::from(10) <= max_reference
+        {
+            reference_amount *= U256::from(10);
+            spot_output = self.quote_route(route, &path, reference_amount).await?;
+        }
+
+        let min_spot_output = if to_decimals == 0 { MIN_SPOT_OUTPUT } else { 1 };
+        Self::price_impact_from_quotes(
+            params.amount_in,
+            amount_out,
+            reference_amount,
+            spot_output,
+            min_spot_output,
+        )
+    }
+
+    /// Quote `amount_in` along `path` on the route's protocol and venue.
+    async fn quote_route(
+        &self,
+        route: &SwapRoute,
+        path: &[Address],
+        amount_in: U256,
+    ) -> Result<U256> {
+        match route.protocol {
+            UniswapVersion::V3 => {
+                self.get_v3_quote(path, amount_in, route.fee_tier.unwrap_or(3000)).await
+            }
+            UniswapVersion::V2 => self.get_v2_quote(route.venue, path, amount_in).await,
+        }
+    }
+
+    /// Price impact percentage of trading `amount_in` for `amount_out`, against the
+    /// spot rate of `reference_amount` quoted for `spot_output` (all raw units).
+    ///
+    /// Fails when the spot output is below `min_spot_output`, too small to measure
+    /// a rate from, instead of reporting a meaningless impact.
+    fn price_impact_from_quotes(
+        amount_in: U256,
+        amount_out: U256,
+        reference_amount: U256,
+        spot_output: U256,
+        min_spot_output: u128,
+    ) -> Result<Decimal> {
+        // Calculate rates (output per unit of input)
+        // spot_rate = spot_output / reference_amount
+        // execution_rate = amount_out / amount_in
+        //
+        // Price impact = (1 - execution_rate / spot_rate) * 100
+        //              = (1 - (amount_out * reference_amount) / (spot_output * amount_in)) * 100
+
+        // Convert U256 values to u128 with overflow checking
         // For price impact calculation, overflow indicates extremely large values
         // which would likely result in very high price impact anyway
-        let amount_in_u128: u128 = params.amount_in.try_into().map_err(|_| {
-            AppError::NumericOverflow(format!("amount_in {} exceeds u128 range", params.amount_in))
+        let amount_in_u128: u128 = amount_in.try_into().map_err(|_| {
+            AppError::NumericOverflow(format!("amount_in {} exceeds u128 range", amount_in))
         })?;
         let amount_out_u128: u128 = amount_out.try_into().map_err(|_| {
             AppError::NumericOverflow(format!("amount_out {} exceeds u128 range", amount_out))
@@ -442,9 +2198,16 @@ impl SwapService {
         })?;
 
         // Avoid division by zero
-        if spot_output_u128 == 0 || amount_in_u128 == 0 {
+        if amount_in_u128 == 0 {
             return Ok(Decimal::ZERO);
         }
+        // A spot output of a few raw units rounds its rate by more than the impact measured
+        if spot_output_u128 < min_spot_output {
+            return Err(AppError::PriceOracle(format!(
+                "spot quote returned {} raw output units, too few to measure a rate from",
+                spot_output_u128
+            )));
+        }
 
         // Use high precision decimals for the calculation
         // Calculate rate_ratio = (amount_out / amount_in) / (spot_output / reference)
@@ -494,356 +2257,1664 @@ impl SwapService {
         }
     }
 
-    /// Convert a Decimal to u128 with overflow checking.
-    /// Truncates to integer and validates it fits in u128.
-    fn decimal_to_u128(value: Decimal) -> Result<u128> {
-        let truncated = value.trunc();
-        // Decimal's to_string for truncated value should be a valid integer
-        truncated
-            .to_string()
-            .parse::<u128>()
-            .map_err(|_| AppError::NumericOverflow(format!("Decimal {} exceeds u128 range", value)))
+    /// Get a V3 quote along a token path (single or multi-hop) at one fee tier.
+    async fn get_v3_quote(&self, path: &[Address], amount_in: U256, fee: u32) -> Result<U256> {
+        if path.len() == 2 {
+            quoter::quote_exact_input_single(
+                self.client.provider(),
+                self.uniswap.v3_quoter,
+                path[0],
+                path[1],
+                fee,
+                amount_in,
+            )
+            .await
+        } else {
+            quoter::quote_exact_input(
+                self.client.provider(),
+                self.uniswap.v3_quoter,
+                path,
+                fee,
+                amount_in,
+            )
+            .await
+        }
     }
 
-    /// Get a V3 quote for a given amount.
-    async fn get_v3_quote(
+    /// Get a V2 quote on `venue` along a token path.
+    async fn get_v2_quote(&self, venue: Venue, path: &[Address], amount_in: U256) -> Result<U256> {
+        let router = IUniswapV2Router02::new(self.v2(venue).router, self.client.provider().clone());
+
+        let amounts = router.getAmountsOut(amount_in, path.to_vec()).call().await?;
+        let amounts = validate_v2_amounts(amounts, path)?;
+        amounts.last().copied().ok_or(AppError::InsufficientLiquidity)
+    }
+
+    /// Get the V2 input required on `venue` along a token path for an exact output.
+    async fn get_v2_amount_in(
         &self,
-        params: &SwapParams,
-        amount_in: U256,
-        fee_tier: Option<u32>,
+        venue: Venue,
+        path: &[Address],
+        amount_out: U256,
     ) -> Result<U256> {
-        let quoter = IQuoterV2::new(UNISWAP_V3_QUOTER, self.client.provider().clone());
+        let router = IUniswapV2Router02::new(self.v2(venue).router, self.client.provider().clone());
+
+        let amounts = router.getAmountsIn(amount_out, path.to_vec()).call().await?;
+        let amounts = validate_v2_amounts(amounts, path)?;
+        amounts.first().copied().ok_or(AppError::InsufficientLiquidity)
+    }
+}
+
+/// Factory and router of a V2 `venue`, given the configured Uniswap addresses.
+pub fn v2_deployment(venue: Venue, uniswap: &UniswapAddresses) -> V2Deployment {
+    match venue {
+        Venue::Uniswap => uniswap.v2(),
+        Venue::Sushiswap => SUSHISWAP_V2,
+    }
+}
+
+/// Reject a gas estimate no transaction could use, so a misbehaving provider
+/// triggers the fallback instead of producing a nonsensical gas cost.
+fn plausible_gas_estimate(gas: u64) -> Result<u64> {
+    if !(MIN_PLAUSIBLE_GAS..=MAX_PLAUSIBLE_GAS).contains(&gas) {
+        tracing::warn!(gas = gas, "Provider returned an implausible gas estimate");
+        return Err(AppError::Rpc(format!(
+            "Implausible gas estimate {} (expected {} to {})",
+            gas, MIN_PLAUSIBLE_GAS, MAX_PLAUSIBLE_GAS
+        )));
+    }
+    Ok(gas)
+}
+
+/// Reject a zero or absurdly high gas price from the provider.
+fn plausible_gas_price(gas_price: u128) -> Result<u128> {
+    if gas_price == 0 || gas_price > MAX_PLAUSIBLE_GAS_PRICE {
+        tracing::warn!(gas_price = gas_price, "Provider returned an implausible gas price");
+        return Err(AppError::Rpc(format!(
+            "Implausible gas price {} wei (expected 1 to {})",
+            gas_price, MAX_PLAUSIBLE_GAS_PRICE
+        )));
+    }
+    Ok(gas_price)
+}
+
+/// Check that a V2 router `getAmountsOut`/`getAmountsIn` result has one amount
+/// per path token, so `amounts[0]` is the input and the last amount the output.
+fn validate_v2_amounts(amounts: Vec<U256>, path: &[Address]) -> Result<Vec<U256>> {
+    if path.len() < 2 || amounts.len() != path.len() {
+        return Err(AppError::Rpc(format!(
+            "V2 router returned {} amounts for a path of {} tokens",
+            amounts.len(),
+            path.len()
+        )));
+    }
+    Ok(amounts)
+}
+
+/// Build an EIP-1559 transaction object from a swap transaction request.
+fn build_wallet_transaction(
+    tx: &TransactionRequest,
+    from: Address,
+    chain_id: u64,
+    gas: u64,
+    fees: Eip1559Estimation,
+    nonce: u64,
+) -> WalletTransaction {
+    WalletTransaction {
+        tx_type: "0x2".to_string(),
+        from: format!("{:?}", from),
+        to: tx.to.and_then(|t| t.to().map(|addr| format!("{:?}", addr))).unwrap_or_default(),
+        data: tx.input.input().map(|d| format!("0x{}", alloy::hex::encode(d))).unwrap_or_default(),
+        value: format!("{:#x}", tx.value.unwrap_or_default()),
+        chain_id: format!("{:#x}", chain_id),
+        gas: format!("{:#x}", gas),
+        max_fee_per_gas: format!("{:#x}", fees.max_fee_per_gas),
+        max_priority_fee_per_gas: format!("{:#x}", fees.max_priority_fee_per_gas),
+        nonce: format!("{:#x}", nonce),
+    }
+}
+
+/// Check that a swap transaction sends no ETH.
+///
+/// Every route swaps an ERC20 input (native ETH is swapped as WETH), so the
+/// router is never owed ETH; a non-zero `value` would be lost or revert.
+fn check_swap_value(tx: &TransactionRequest) -> Result<()> {
+    match tx.value {
+        Some(value) if !value.is_zero() => Err(AppError::SimulationFailed(format!(
+            "Swap transaction sends {} wei of ETH, but the input is an ERC20 token",
+            value
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Decode swap router calldata built by [`SwapService`].
+///
+/// Returns `None` for calldata of any other function.
+fn decode_swap_calldata(data: &[u8]) -> Option<DecodedTransaction> {
+    let selector: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    let (function, amount_in, amount_out_min, path, fee_tiers, recipient, deadline) = match selector
+    {
+        ISwapRouter::exactInputSingleCall::SELECTOR => {
+            let call = ISwapRouter::exactInputSingleCall::abi_decode(data).ok()?.params;
+            (
+                "exactInputSingle",
+                call.amountIn,
+                call.amountOutMinimum,
+                vec![call.tokenIn, call.tokenOut],
+                vec![call.fee.to::<u32>()],
+                call.recipient,
+                call.deadline,
+            )
+        }
+        ISwapRouter::exactInputCall::SELECTOR => {
+            let call = ISwapRouter::exactInputCall::abi_decode(data).ok()?.params;
+            let (path, fees) = decode_path(&call.path)?;
+            (
+                "exactInput",
+                call.amountIn,
+                call.amountOutMinimum,
+                path,
+                fees,
+                call.recipient,
+                call.deadline,
+            )
+        }
+        IUniswapV2Router02::swapExactTokensForTokensCall::SELECTOR => {
+            let call = IUniswapV2Router02::swapExactTokensForTokensCall::abi_decode(data).ok()?;
+            (
+                "swapExactTokensForTokens",
+                call.amountIn,
+                call.amountOutMin,
+                call.path,
+                Vec::new(),
+                call.to,
+                call.deadline,
+            )
+        }
+        _ => return None,
+    };
+
+    Some(DecodedTransaction {
+        function: function.to_string(),
+        selector: format!("0x{}", alloy::hex::encode(selector)),
+        amount_in: amount_in.to_string(),
+        amount_out_min: amount_out_min.to_string(),
+        path: format_path(&path),
+        fee_tiers,
+        recipient: format!("{:?}", recipient),
+        deadline: deadline.saturating_to(),
+    })
+}
+
+/// Mid price of a V2 pair, as raw output per raw input.
+fn v2_hop_price(reserve_in: U256, reserve_out: U256) -> Option<PriceRatio> {
+    PriceRatio::new(reserve_out, reserve_in)
+}
+
+/// Multiply per-hop raw prices into a human-readable price of the whole path.
+fn chain_hop_prices(
+    hop_prices: &[PriceRatio],
+    from_decimals: u8,
+    to_decimals: u8,
+) -> Option<String> {
+    let price = hop_prices
+        .iter()
+        .try_fold(PriceRatio::ONE, |acc, price| acc.checked_mul(*price))?
+        .scale_decimals(from_decimals, to_decimals)?;
+    Some(price.to_decimal_string(PRICE_DECIMALS, PRICE_SIGNIFICANT_DIGITS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ethereum::UNISWAP_V3_ROUTER, types::format_units};
+    use alloy::{primitives::Bytes, sol_types::SolValue, transports::mock::Asserter};
+
+    #[test]
+    fn test_plausible_gas_values() {
+        assert_eq!(plausible_gas_estimate(150_000).unwrap(), 150_000);
+        assert_eq!(plausible_gas_estimate(MIN_PLAUSIBLE_GAS).unwrap(), MIN_PLAUSIBLE_GAS);
+        assert_eq!(plausible_gas_estimate(MAX_PLAUSIBLE_GAS).unwrap(), MAX_PLAUSIBLE_GAS);
+        assert!(matches!(plausible_gas_estimate(0), Err(AppError::Rpc(_))));
+        assert!(matches!(plausible_gas_estimate(MAX_PLAUSIBLE_GAS + 1), Err(AppError::Rpc(_))));
+
+        assert_eq!(plausible_gas_price(DEFAULT_GAS_PRICE).unwrap(), DEFAULT_GAS_PRICE);
+        assert!(matches!(plausible_gas_price(0), Err(AppError::Rpc(_))));
+        // A gas price decoded from the wrong encoding is typically enormous
+        assert!(matches!(plausible_gas_price(u64::MAX as u128), Err(AppError::Rpc(_))));
+    }
+
+    #[test]
+    fn test_validate_v2_amounts() {
+        let path = [Address::repeat_byte(1), Address::repeat_byte(2), Address::repeat_byte(3)];
+        let amounts = vec![U256::from(100), U256::from(50), U256::from(25)];
+        assert_eq!(validate_v2_amounts(amounts.clone(), &path).unwrap(), amounts);
+
+        assert!(matches!(
+            validate_v2_amounts(vec![U256::from(100), U256::from(50)], &path),
+            Err(AppError::Rpc(_))
+        ));
+        assert!(matches!(validate_v2_amounts(Vec::new(), &[]), Err(AppError::Rpc(_))));
+    }
+
+    #[test]
+    fn test_apply_slippage_floor() {
+        let floor = Decimal::new(5, 2); // 0.05%
+
+        let (slippage, warning) = apply_slippage_floor(Decimal::ZERO, floor);
+        assert_eq!(slippage, floor);
+        let warning = warning.unwrap();
+        assert_eq!(warning.code, WarningCode::SlippageRaised);
+        assert!(warning.message.contains("0% is below the 0.05% floor"));
+
+        assert_eq!(apply_slippage_floor(Decimal::new(5, 1), floor), (Decimal::new(5, 1), None));
+        assert_eq!(apply_slippage_floor(floor, floor), (floor, None));
+        assert_eq!(apply_slippage_floor(Decimal::ZERO, Decimal::ZERO), (Decimal::ZERO, None));
+    }
+
+    #[test]
+    fn test_slippage_calculation() {
+        let amount_out = U256::from(1_000_000u64); // 1 USDC
+        let slippage = Decimal::new(5, 1); // 0.5%
+
+        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
+        let amount_out_u128: u128 = amount_out.to_string().parse().unwrap();
+        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+
+        // 0.5% slippage means minimum is 99.5% of original
+        let expected = Decimal::from(995_000u64); // 0.995 * 1_000_000
+        assert_eq!(min_out, expected);
+    }
+
+    fn test_service() -> SwapService {
+        let client = Arc::new(EthereumClient::new("http://localhost:8545").unwrap());
+        SwapService::new(client.clone(), None, BalanceService::new(client))
+    }
+
+    #[test]
+    fn test_v2_intermediary_uses_chain_wrapper() {
+        use crate::ethereum::{
+            contracts::USDC_ADDRESS, wrapped_native_token, ETHEREUM_MAINNET_CHAIN_ID,
+            SEPOLIA_CHAIN_ID, SEPOLIA_WETH_ADDRESS,
+        };
+
+        let token = Address::repeat_byte(0x11);
+
+        let mainnet = test_service()
+            .with_wrapped_native_token(wrapped_native_token(ETHEREUM_MAINNET_CHAIN_ID));
+        assert_eq!(
+            mainnet.v2_intermediary_path(token, USDC_ADDRESS),
+            Some(vec![token, WETH_ADDRESS, USDC_ADDRESS])
+        );
+
+        let sepolia =
+            test_service().with_wrapped_native_token(wrapped_native_token(SEPOLIA_CHAIN_ID));
+        assert_eq!(
+            sepolia.v2_intermediary_path(token, USDC_ADDRESS),
+            Some(vec![token, SEPOLIA_WETH_ADDRESS, USDC_ADDRESS])
+        );
+
+        // A custom wrapper (e.g., WMATIC) is used as configured
+        let wmatic = Address::repeat_byte(0x22);
+        let custom = test_service().with_wrapped_native_token(Some(wmatic));
+        assert_eq!(custom.v2_intermediary_path(token, USDC_ADDRESS).unwrap()[1], wmatic);
+        assert_eq!(custom.v2_intermediary_path(wmatic, USDC_ADDRESS), None);
+
+        // Unknown chains without a configured wrapper only use direct pairs
+        let unknown = test_service().with_wrapped_native_token(wrapped_native_token(42161));
+        assert_eq!(unknown.v2_intermediary_path(token, USDC_ADDRESS), None);
+    }
+
+    #[test]
+    fn test_deadline_default() {
+        let now = current_timestamp();
+        let deadline = now + 1200; // 20 minutes
+
+        // Deadline should be 20 minutes (1200 seconds) in the future
+        assert_eq!(deadline - now, 1200);
+    }
+
+    #[test]
+    fn test_gas_cost_calculation() {
+        let gas_estimate: u64 = 150_000;
+        let gas_price: u128 = 30_000_000_000; // 30 gwei
+
+        let gas_cost_wei = U256::from(gas_estimate) * U256::from(gas_price);
+        let gas_cost_eth = format_units(gas_cost_wei, 18);
+
+        // 150,000 * 30 gwei = 4,500,000 gwei = 0.0045 ETH
+        assert_eq!(gas_cost_eth, "0.0045");
+    }
+
+    #[test]
+    fn test_swap_route_creation() {
+        let route = SwapRoute {
+            protocol: UniswapVersion::V3,
+            path: vec!["0xToken1".to_string(), "0xToken2".to_string()],
+            fee_tier: Some(3000),
+            venue: Venue::Uniswap,
+        };
+
+        assert_eq!(route.protocol, UniswapVersion::V3);
+        assert_eq!(route.path.len(), 2);
+        assert_eq!(route.fee_tier, Some(3000));
+    }
+
+    // ============================================================================
+    // current_timestamp Tests
+    // ============================================================================
+
+    #[test]
+    fn test_current_timestamp_is_reasonable() {
+        let ts = current_timestamp();
+        // Should be after Jan 1, 2024 (1704067200)
+        assert!(ts > 1704067200, "Timestamp should be after 2024");
+        // Should be before year 2100 (4102444800)
+        assert!(ts < 4102444800, "Timestamp should be before 2100");
+    }
+
+    #[test]
+    fn test_current_timestamp_increases() {
+        let ts1 = current_timestamp();
+        // Small delay to ensure time passes
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let ts2 = current_timestamp();
+        // ts2 should be >= ts1 (might be equal if within same second)
+        assert!(ts2 >= ts1);
+    }
+
+    // ============================================================================
+    // calculate_reference_amount Tests
+    // ============================================================================
+
+    #[test]
+    fn test_calculate_reference_amount_normal() {
+        // 1000 tokens -> 0.1% = 1 token reference
+        let amount = U256::from(1_000_000_000_000_000_000_000u128); // 1000 * 10^18
+        let reference = SwapService::calculate_reference_amount(amount);
+
+        // Should be 0.1% = 1 token worth
+        let expected = amount / U256::from(1000);
+        assert_eq!(reference, expected);
+    }
+
+    #[test]
+    fn test_calculate_reference_amount_minimum() {
+        // Very small amount should use minimum (1000)
+        let amount = U256::from(100u64);
+        let reference = SwapService::calculate_reference_amount(amount);
+
+        // Should be capped at amount itself (since 100 < 1000)
+        assert_eq!(reference, amount);
+    }
+
+    #[test]
+    fn test_calculate_reference_amount_large() {
+        // Very large amount - 10% cap
+        let amount = U256::from(1_000_000_000_000_000_000_000_000u128); // 1M tokens
+        let reference = SwapService::calculate_reference_amount(amount);
+
+        // 0.1% of 1M = 1000, which should be between min and max
+        let expected = amount / U256::from(1000);
+        let max_reference = amount / U256::from(10);
+
+        assert!(reference <= max_reference);
+        assert_eq!(reference, expected);
+    }
+
+    #[test]
+    fn test_calculate_reference_amount_boundary() {
+        // Test at boundary where 0.1% equals minimum
+        let min_reference = U256::from(1_000u64);
+        let amount = min_reference * U256::from(1000); // 1,000,000
+
+        let reference = SwapService::calculate_reference_amount(amount);
+        assert_eq!(reference, min_reference);
+    }
+
+    // ============================================================================
+    // Slippage Calculation Tests
+    // ============================================================================
+
+    #[test]
+    fn test_slippage_1_percent() {
+        let amount_out = U256::from(1_000_000_000u64); // 1B units
+        let slippage = Decimal::from(1); // 1%
+
+        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
+        let amount_out_u128: u128 = amount_out.try_into().unwrap();
+        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+
+        // 1% slippage = 99% of original
+        let expected = Decimal::from(990_000_000u64);
+        assert_eq!(min_out, expected);
+    }
+
+    #[test]
+    fn test_slippage_5_percent() {
+        let amount_out = U256::from(100_000_000u64);
+        let slippage = Decimal::from(5); // 5%
+
+        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
+        let amount_out_u128: u128 = amount_out.try_into().unwrap();
+        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+
+        // 5% slippage = 95% of original
+        let expected = Decimal::from(95_000_000u64);
+        assert_eq!(min_out, expected);
+    }
+
+    #[test]
+    fn test_slippage_small() {
+        let amount_out = U256::from(1_000_000u64);
+        let slippage = Decimal::new(1, 1); // 0.1%
+
+        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
+        let amount_out_u128: u128 = amount_out.try_into().unwrap();
+        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+
+        // 0.1% slippage = 99.9% of original
+        let expected = Decimal::from(999_000u64);
+        assert_eq!(min_out, expected);
+    }
+
+    // ============================================================================
+    // Gas Cost Calculation Tests
+    // ============================================================================
+
+    #[test]
+    fn test_gas_cost_high_gas_price() {
+        let gas_estimate: u64 = 200_000;
+        let gas_price: u128 = 100_000_000_000; // 100 gwei
+
+        let gas_cost_wei = U256::from(gas_estimate) * U256::from(gas_price);
+        let gas_cost_eth = format_units(gas_cost_wei, 18);
+
+        // 200,000 * 100 gwei = 20,000,000 gwei = 0.02 ETH
+        assert_eq!(gas_cost_eth, "0.02");
+    }
+
+    #[test]
+    fn test_gas_cost_low_gas_price() {
+        let gas_estimate: u64 = 100_000;
+        let gas_price: u128 = 5_000_000_000; // 5 gwei
+
+        let gas_cost_wei = U256::from(gas_estimate) * U256::from(gas_price);
+        let gas_cost_eth = format_units(gas_cost_wei, 18);
+
+        // 100,000 * 5 gwei = 500,000 gwei = 0.0005 ETH
+        assert_eq!(gas_cost_eth, "0.0005");
+    }
+
+    // ============================================================================
+    // SwapRoute Tests
+    // ============================================================================
+
+    #[test]
+    fn test_swap_route_v2_direct() {
+        let route = SwapRoute {
+            protocol: UniswapVersion::V2,
+            path: vec!["WETH".to_string(), "USDC".to_string()],
+            fee_tier: None,
+            venue: Venue::Uniswap,
+        };
+
+        assert_eq!(route.protocol, UniswapVersion::V2);
+        assert_eq!(route.path.len(), 2);
+        assert!(route.fee_tier.is_none());
+    }
+
+    #[test]
+    fn test_swap_route_v2_multihop() {
+        let route = SwapRoute {
+            protocol: UniswapVersion::V2,
+            path: vec!["TOKEN".to_string(), "WETH".to_string(), "USDC".to_string()],
+            fee_tier: None,
+            venue: Venue::Uniswap,
+        };
+
+        assert_eq!(route.path.len(), 3);
+    }
+
+    #[test]
+    fn test_swap_route_v3_fee_tiers() {
+        // Test all common V3 fee tiers
+        for fee in [100, 500, 3000, 10000] {
+            let route = SwapRoute {
+                protocol: UniswapVersion::V3,
+                path: vec!["A".to_string(), "B".to_string()],
+                fee_tier: Some(fee),
+                venue: Venue::Uniswap,
+            };
+            assert_eq!(route.fee_tier, Some(fee));
+        }
+    }
+
+    // ============================================================================
+    // Deadline Tests
+    // ============================================================================
+
+    #[test]
+    fn test_deadline_20_minutes() {
+        let now = current_timestamp();
+        let deadline = now + 1200; // 20 minutes
+
+        assert_eq!(deadline - now, 1200);
+        assert!(deadline > now);
+    }
+
+    #[test]
+    fn test_deadline_custom() {
+        let custom_deadline = 1800000000u64;
+        let params = SwapParams {
+            from_token: Address::ZERO,
+            to_token: Address::ZERO,
+            amount_in: U256::ZERO,
+            slippage_tolerance: Decimal::ONE,
+            deadline: Some(custom_deadline),
+            ..Default::default()
+        };
+
+        assert_eq!(params.deadline, Some(custom_deadline));
+    }
+
+    #[test]
+    fn test_minimum_amount_out() {
+        let min = SwapService::minimum_amount_out(U256::from(1_000_000u64), Decimal::ONE).unwrap();
+        assert_eq!(min, U256::from(990_000u64));
+
+        let min = SwapService::minimum_amount_out(U256::from(1_000u64), Decimal::ZERO).unwrap();
+        assert_eq!(min, U256::from(1_000u64));
+    }
+
+    #[test]
+    fn test_minimum_amount_out_fractional_basis_points() {
+        let amount_out = U256::from(1_000_000_000u64);
+
+        // 0.05% = 5 bps
+        let slippage = Decimal::from_str("0.05").unwrap();
+        let min = SwapService::minimum_amount_out(amount_out, slippage).unwrap();
+        assert_eq!(min, U256::from(999_500_000u64));
+
+        // 0.005% = 0.5 bps
+        let slippage = Decimal::from_str("0.005").unwrap();
+        let min = SwapService::minimum_amount_out(amount_out, slippage).unwrap();
+        assert_eq!(min, U256::from(999_950_000u64));
+
+        // Trailing zeros do not change the result
+        let slippage = Decimal::from_str("0.0050").unwrap();
+        let min = SwapService::minimum_amount_out(amount_out, slippage).unwrap();
+        assert_eq!(min, U256::from(999_950_000u64));
+    }
+
+    #[test]
+    fn test_minimum_amount_out_rounds_down() {
+        // 12345 * (1 - 0.00005) = 12344.38275
+        let slippage = Decimal::from_str("0.005").unwrap();
+        let min = SwapService::minimum_amount_out(U256::from(12_345u64), slippage).unwrap();
+        assert_eq!(min, U256::from(12_344u64));
+    }
+
+    #[test]
+    fn test_minimum_amount_out_large_amount_exact() {
+        // 29 significant digits: more than a Decimal product can hold exactly
+        let amount_out = U256::from_str("12345678901234567890123456789").unwrap();
+        let slippage = Decimal::from_str("0.005").unwrap();
+        let min = SwapService::minimum_amount_out(amount_out, slippage).unwrap();
+
+        let expected = amount_out * U256::from(1_999_900u64) / U256::from(2_000_000u64);
+        assert_eq!(min, expected);
+        assert_eq!(min, U256::from_str("12345061617289506161728950616").unwrap());
+    }
+
+    #[test]
+    fn test_minimum_amount_out_invalid_slippage() {
+        let amount_out = U256::from(1_000u64);
+        assert!(SwapService::minimum_amount_out(amount_out, Decimal::NEGATIVE_ONE).is_err());
+        assert!(SwapService::minimum_amount_out(amount_out, Decimal::from(101)).is_err());
+        assert_eq!(
+            SwapService::minimum_amount_out(amount_out, Decimal::ONE_HUNDRED).unwrap(),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn test_slippage_table_values() {
+        // 2500 USDC expected
+        let amount_out = U256::from(2_500_000_000u64);
+        let levels = [Decimal::new(1, 1), Decimal::new(50, 2), Decimal::ONE, Decimal::ZERO];
+        let table = SwapService::slippage_table(amount_out, &levels, 6).unwrap();
+
+        let rows: Vec<(&str, &str)> =
+            table.iter().map(|level| (level.slippage.as_str(), level.min_out.as_str())).collect();
+        assert_eq!(rows, vec![("0.1", "2497.5"), ("0.5", "2487.5"), ("1", "2475"), ("0", "2500")]);
+        assert!(SwapService::slippage_table(amount_out, &[], 6).unwrap().is_empty());
+        assert!(SwapService::slippage_table(amount_out, &[Decimal::from(101)], 6).is_err());
+    }
+
+    #[test]
+    fn test_effective_minimum_takes_stricter_limit() {
+        let amount_out = U256::from(1_000_000u64);
+        let mut params = SwapParams { slippage_tolerance: Decimal::ONE, ..Default::default() };
+
+        // Slippage alone: 1% below the quote
+        assert_eq!(
+            SwapService::effective_minimum(&params, amount_out).unwrap(),
+            (U256::from(990_000u64), MinimumOutSource::Slippage)
+        );
+
+        // A looser absolute floor leaves the slippage minimum in place
+        params.min_amount_out = Some(U256::from(980_000u64));
+        assert_eq!(
+            SwapService::effective_minimum(&params, amount_out).unwrap(),
+            (U256::from(990_000u64), MinimumOutSource::Slippage)
+        );
+
+        // A stricter one replaces it, even above the quote
+        params.min_amount_out = Some(U256::from(995_000u64));
+        assert_eq!(
+            SwapService::effective_minimum(&params, amount_out).unwrap(),
+            (U256::from(995_000u64), MinimumOutSource::MinAmountOut)
+        );
+        params.min_amount_out = Some(U256::from(1_100_000u64));
+        assert_eq!(
+            SwapService::effective_minimum(&params, amount_out).unwrap().1,
+            MinimumOutSource::MinAmountOut
+        );
+    }
+
+    #[test]
+    fn test_simulation_sender_prefers_from_address() {
+        let client = Arc::new(EthereumClient::new("http://localhost:8545").unwrap());
+        let wallet = WalletManager::from_private_key(
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let wallet_address = wallet.address();
+        let service = SwapService::new(client.clone(), Some(wallet), BalanceService::new(client));
+
+        let params = SwapParams::default();
+        assert_eq!(service.simulation_sender(&params).unwrap(), wallet_address);
+
+        let other = Address::repeat_byte(7);
+        let params = SwapParams { from_address: Some(other), ..Default::default() };
+        assert_eq!(service.simulation_sender(&params).unwrap(), other);
+    }
+
+    #[test]
+    fn test_token_swaps_send_no_value() {
+        let client = Arc::new(EthereumClient::new("http://localhost:8545").unwrap());
+        let wallet = WalletManager::from_private_key(
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        let service = SwapService::new(client.clone(), Some(wallet), BalanceService::new(client));
+        let params = SwapParams {
+            from_token: Address::repeat_byte(1),
+            to_token: Address::repeat_byte(2),
+            amount_in: U256::from(10u64).pow(U256::from(18)),
+            slippage_tolerance: Decimal::ONE,
+            ..Default::default()
+        };
+
+        let (_, _, tx) = service
+            .build_v3_swap(&params, &[params.from_token, params.to_token], 500, U256::from(1000))
+            .unwrap();
+        assert_eq!(tx.value, Some(U256::ZERO));
+        assert!(check_swap_value(&tx).is_ok());
+
+        // A value on a token-in swap is rejected; an unset value is none sent
+        assert!(matches!(
+            check_swap_value(&tx.clone().value(U256::from(1))),
+            Err(AppError::SimulationFailed(_))
+        ));
+        assert!(check_swap_value(&TransactionRequest::default()).is_ok());
+    }
+
+    #[test]
+    fn test_simulation_sender_requires_wallet() {
+        let params =
+            SwapParams { from_address: Some(Address::repeat_byte(7)), ..Default::default() };
+        assert!(matches!(test_service().simulation_sender(&params), Err(AppError::Wallet(_))));
+    }
+
+    #[test]
+    fn test_best_venue_route() {
+        let candidate = |venue, amount_out: u64| {
+            let route =
+                SwapRoute { protocol: UniswapVersion::V2, path: Vec::new(), fee_tier: None, venue };
+            Ok((route, U256::from(amount_out)))
+        };
+
+        // The larger output wins, whichever venue is listed first
+        let (route, amount_out) =
+            best_venue_route([candidate(Venue::Uniswap, 100), candidate(Venue::Sushiswap, 101)])
+                .unwrap();
+        assert_eq!(route.venue, Venue::Sushiswap);
+        assert_eq!(amount_out, U256::from(101u64));
+
+        // The earlier venue wins a tie
+        let (route, _) =
+            best_venue_route([candidate(Venue::Uniswap, 100), candidate(Venue::Sushiswap, 100)])
+                .unwrap();
+        assert_eq!(route.venue, Venue::Uniswap);
+
+        // A venue without a route is skipped
+        let (route, _) =
+            best_venue_route([Err(AppError::PoolNotFound), candidate(Venue::Sushiswap, 1)])
+                .unwrap();
+        assert_eq!(route.venue, Venue::Sushiswap);
+
+        // Without any route, the last venue's error is returned
+        let result =
+            best_venue_route([Err(AppError::PoolNotFound), Err(AppError::InsufficientLiquidity)]);
+        assert!(matches!(result, Err(AppError::InsufficientLiquidity)));
+    }
+
+    #[test]
+    fn test_forced_route_path_includes_intermediaries() {
+        let from = Address::repeat_byte(1);
+        let via = Address::repeat_byte(2);
+        let to = Address::repeat_byte(3);
+        let params = SwapParams { from_token: from, to_token: to, ..Default::default() };
+        let forced = ForcedRoute {
+            protocol: UniswapVersion::V2,
+            fee_tier: None,
+            intermediaries: vec![via],
+            venue: Venue::Uniswap,
+        };
+
+        let path = SwapService::forced_route_path(&params, &forced).unwrap();
+        assert_eq!(path, vec![from, via, to]);
+    }
+
+    #[test]
+    fn test_forced_route_path_rejects_repeated_token() {
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(3);
+        let params = SwapParams { from_token: from, to_token: to, ..Default::default() };
+        let forced = ForcedRoute {
+            protocol: UniswapVersion::V2,
+            fee_tier: None,
+            intermediaries: vec![to],
+            venue: Venue::Uniswap,
+        };
+
+        let result = SwapService::forced_route_path(&params, &forced);
+        assert!(matches!(result, Err(AppError::RouteNotFound(_))));
+    }
+
+    #[test]
+    fn test_forced_route_path_rejects_unknown_fee_tier() {
+        let params = SwapParams {
+            from_token: Address::repeat_byte(1),
+            to_token: Address::repeat_byte(3),
+            ..Default::default()
+        };
+        let forced = ForcedRoute {
+            protocol: UniswapVersion::V3,
+            fee_tier: Some(1234),
+            intermediaries: vec![],
+            venue: Venue::Uniswap,
+        };
+
+        let result = SwapService::forced_route_path(&params, &forced);
+        assert!(matches!(result, Err(AppError::RouteNotFound(_))));
+    }
+
+    #[test]
+    fn test_fallback_gas_per_protocol() {
+        let route = |protocol, hops: usize| SwapRoute {
+            protocol,
+            path: vec![String::new(); hops + 1],
+            fee_tier: None,
+            venue: Venue::Uniswap,
+        };
+
+        assert_eq!(SwapService::fallback_gas(&route(UniswapVersion::V2, 1)).0, V2_SWAP_GAS);
+        assert_eq!(SwapService::fallback_gas(&route(UniswapVersion::V3, 1)).0, V3_SWAP_GAS);
+        assert_eq!(
+            SwapService::fallback_gas(&route(UniswapVersion::V2, 2)).0,
+            V2_SWAP_GAS + V2_EXTRA_HOP_GAS
+        );
+        assert_eq!(
+            SwapService::fallback_gas(&route(UniswapVersion::V3, 3)).0,
+            V3_SWAP_GAS + 2 * V3_EXTRA_HOP_GAS
+        );
+    }
+
+    #[test]
+    fn test_fallback_gas_hard_default() {
+        let route = SwapRoute {
+            protocol: UniswapVersion::V3,
+            path: vec![],
+            fee_tier: None,
+            venue: Venue::Uniswap,
+        };
+        let (gas, reason) = SwapService::fallback_gas(&route);
+
+        assert_eq!(gas, DEFAULT_SWAP_GAS);
+        assert!(reason.contains("hard default"));
+    }
+
+    #[test]
+    fn test_gas_fallback_reason_uses_revert_reason() {
+        let error = AppError::Rpc("execution reverted".to_string());
+
+        let reason = SwapService::gas_fallback_reason(
+            &error,
+            Some("Token transfer failed - check token approval"),
+            "typical V3 value of 180000 gas for 1 hop(s)",
+        );
+        assert!(reason.contains("would revert (Token transfer failed - check token approval)"));
+        assert!(reason.ends_with("using typical V3 value of 180000 gas for 1 hop(s)"));
+
+        let reason = SwapService::gas_fallback_reason(&error, None, "hard default of 200000 gas");
+        assert!(reason.starts_with("Gas estimation failed ("));
+        assert!(reason.contains("execution reverted"));
+    }
+
+    #[test]
+    fn test_cheaper_quote_prefers_lower_input() {
+        let route = |fee| SwapRoute {
+            protocol: UniswapVersion::V3,
+            path: vec![],
+            fee_tier: fee,
+            venue: Venue::Uniswap,
+        };
+
+        let best = SwapService::cheaper_quote(None, route(Some(500)), U256::from(100u64));
+        let best = SwapService::cheaper_quote(best, route(Some(3000)), U256::from(90u64));
+        let best = SwapService::cheaper_quote(best, route(Some(10000)), U256::from(95u64));
+
+        let (route, amount_in) = best.unwrap();
+        assert_eq!(route.fee_tier, Some(3000));
+        assert_eq!(amount_in, U256::from(90u64));
+    }
+
+    #[test]
+    fn test_cheaper_quote_ignores_zero() {
+        let route = SwapRoute {
+            protocol: UniswapVersion::V2,
+            path: vec![],
+            fee_tier: None,
+            venue: Venue::Uniswap,
+        };
+        assert!(SwapService::cheaper_quote(None, route, U256::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_implied_price() {
+        let price = SwapService::implied_price("0.4", "1000").unwrap();
+        assert_eq!(price, Decimal::from_str("0.0004").unwrap());
+
+        let price = SwapService::implied_price("1", "0").unwrap();
+        assert_eq!(price, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_quote_discrepancy_percent() {
+        let quoted = U256::from(2_000_000u64);
+        assert_eq!(SwapService::quote_discrepancy_percent(quoted, quoted), Decimal::ZERO);
+        assert_eq!(
+            SwapService::quote_discrepancy_percent(quoted, U256::from(1_990_000u64)),
+            Decimal::new(5, 1)
+        );
+        // Symmetric: a higher independent quote is just as suspicious
+        assert_eq!(
+            SwapService::quote_discrepancy_percent(quoted, U256::from(2_010_000u64)),
+            Decimal::new(5, 1)
+        );
+        assert_eq!(
+            SwapService::quote_discrepancy_percent(U256::ZERO, U256::from(1u8)),
+            Decimal::ONE_HUNDRED
+        );
+    }
 
-        let fee = fee_tier.unwrap_or(3000); // Default to 0.3% tier
-        let fee_u24 = U24::from(fee);
+    #[test]
+    fn test_quote_cross_check_tolerance() {
+        let quoted = U256::from(2_000_000_000u64);
+
+        let close = SwapService::quote_cross_check(
+            QuoteCrossCheckMethod::V2Reserves,
+            quoted,
+            U256::from(1_990_000_000u64),
+            Decimal::ONE,
+            6,
+        );
+        assert_eq!(close.amount_out, "1990");
+        assert_eq!(close.discrepancy_percent, "0.5");
+        assert_eq!(close.tolerance_percent, "1");
+        assert!(close.within_tolerance);
+
+        let far = SwapService::quote_cross_check(
+            QuoteCrossCheckMethod::V3SingleTick,
+            quoted,
+            U256::from(1_900_000_000u64),
+            Decimal::ONE,
+            6,
+        );
+        assert_eq!(far.discrepancy_percent, "5");
+        assert!(!far.within_tolerance);
+    }
 
-        let quote_params = IQuoterV2::QuoteExactInputSingleParams {
-            tokenIn: params.from_token,
-            tokenOut: params.to_token,
-            amountIn: amount_in,
-            fee: fee_u24,
-            sqrtPriceLimitX96: U160::ZERO,
+    #[test]
+    fn test_prefer_route() {
+        let route = |protocol, amount_out: u64| {
+            let route =
+                SwapRoute { protocol, path: Vec::new(), fee_tier: None, venue: Venue::Uniswap };
+            Some((route, U256::from(amount_out)))
         };
+        let protocol = |chosen: Option<(SwapRoute, U256)>| chosen.map(|(r, _)| r.protocol);
+        let v3 = || route(UniswapVersion::V3, 100);
+        let v2 = || route(UniswapVersion::V2, 120);
 
-        let result = quoter.quoteExactInputSingle(quote_params).call().await?;
-        Ok(result.amountOut)
-    }
+        assert_eq!(
+            protocol(prefer_route(ProtocolPreference::V3First, v3(), v2())),
+            Some(UniswapVersion::V3)
+        );
+        assert_eq!(
+            protocol(prefer_route(ProtocolPreference::V2First, v3(), v2())),
+            Some(UniswapVersion::V2)
+        );
+        assert_eq!(
+            protocol(prefer_route(ProtocolPreference::Best, v3(), v2())),
+            Some(UniswapVersion::V2)
+        );
 
-    /// Get a V2 quote for a given amount.
-    async fn get_v2_quote(&self, params: &SwapParams, amount_in: U256) -> Result<U256> {
-        let router = IUniswapV2Router02::new(UNISWAP_V2_ROUTER, self.client.provider().clone());
+        // Each order falls back to the other protocol
+        assert_eq!(
+            protocol(prefer_route(ProtocolPreference::V3First, None, v2())),
+            Some(UniswapVersion::V2)
+        );
+        assert_eq!(
+            protocol(prefer_route(ProtocolPreference::V2First, v3(), None)),
+            Some(UniswapVersion::V3)
+        );
+        assert_eq!(protocol(prefer_route(ProtocolPreference::Best, None, None)), None);
 
-        // Try direct path first
-        let path = vec![params.from_token, params.to_token];
-        match router.getAmountsOut(amount_in, path).call().await {
-            Ok(amounts) => Ok(amounts[1]),
-            Err(_) => {
-                // Try routing through WETH
-                let path_via_weth = vec![params.from_token, WETH_ADDRESS, params.to_token];
-                let amounts = router.getAmountsOut(amount_in, path_via_weth).call().await?;
-                Ok(amounts[2])
-            }
-        }
+        // Best keeps V3 on a tie
+        let tie = route(UniswapVersion::V2, 100);
+        assert_eq!(
+            protocol(prefer_route(ProtocolPreference::Best, v3(), tie)),
+            Some(UniswapVersion::V3)
+        );
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::format_units;
 
     #[test]
-    fn test_slippage_calculation() {
-        let amount_out = U256::from(1_000_000u64); // 1 USDC
-        let slippage = Decimal::new(5, 1); // 0.5%
+    fn test_select_v3_tier_skips_empty_pool() {
+        let tiers = [
+            // Exists but has no active liquidity, so it was never quoted
+            V3Tier { fee_tier: 500, liquidity: 0, amount_out: None },
+            V3Tier { fee_tier: 3000, liquidity: 1_000_000, amount_out: Some(U256::from(990u64)) },
+        ];
+        let mut thin_pools = Vec::new();
+
+        let selected = SwapService::select_v3_tier(&tiers, 0, &mut thin_pools);
+
+        assert_eq!(selected, Some((3000, U256::from(990u64))));
+        assert!(thin_pools.is_empty());
+    }
 
-        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
-        let amount_out_u128: u128 = amount_out.to_string().parse().unwrap();
-        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+    #[test]
+    fn test_select_v3_tier_equal_quotes_prefer_lower_fee() {
+        let quote = Some(U256::from(1_000u64));
+        let medium = V3Tier { fee_tier: 3000, liquidity: 1_000_000, amount_out: quote };
+        let low = V3Tier { fee_tier: 500, liquidity: 10, amount_out: quote };
+        let mut thin_pools = Vec::new();
+
+        // The lower fee wins regardless of the order the tiers are listed in
+        for tiers in [[medium, low], [low, medium]] {
+            let selected = SwapService::select_v3_tier(&tiers, 0, &mut thin_pools);
+            assert_eq!(selected, Some((500, U256::from(1_000u64))));
+        }
 
-        // 0.5% slippage means minimum is 99.5% of original
-        let expected = Decimal::from(995_000u64); // 0.995 * 1_000_000
-        assert_eq!(min_out, expected);
+        // A strictly larger quote still beats a lower fee
+        let better = V3Tier { amount_out: Some(U256::from(1_001u64)), ..medium };
+        let selected = SwapService::select_v3_tier(&[low, better], 0, &mut thin_pools);
+        assert_eq!(selected, Some((3000, U256::from(1_001u64))));
     }
 
     #[test]
-    fn test_deadline_default() {
-        let now = current_timestamp();
-        let deadline = now + 1200; // 20 minutes
+    fn test_select_v3_tier_only_empty_pools() {
+        let tiers = [V3Tier { fee_tier: 500, liquidity: 0, amount_out: None }];
+        let mut thin_pools = Vec::new();
 
-        // Deadline should be 20 minutes (1200 seconds) in the future
-        assert_eq!(deadline - now, 1200);
+        assert_eq!(SwapService::select_v3_tier(&tiers, 0, &mut thin_pools), None);
     }
 
     #[test]
-    fn test_gas_cost_calculation() {
-        let gas_estimate: u64 = 150_000;
-        let gas_price: u128 = 30_000_000_000; // 30 gwei
+    fn test_select_v3_tier_min_liquidity() {
+        let tiers = [
+            V3Tier { fee_tier: 100, liquidity: 5, amount_out: Some(U256::from(1_010u64)) },
+            V3Tier { fee_tier: 500, liquidity: 20, amount_out: Some(U256::from(1_005u64)) },
+            V3Tier { fee_tier: 3000, liquidity: 100, amount_out: Some(U256::from(1_000u64)) },
+        ];
+        let mut thin_pools = Vec::new();
+
+        let selected = SwapService::select_v3_tier(&tiers, 50, &mut thin_pools);
+
+        assert_eq!(selected, Some((3000, U256::from(1_000u64))));
+        assert_eq!(thin_pools.len(), 2);
+        assert_eq!(thin_pools[0].fee_tier, Some(100));
+        assert_eq!(thin_pools[1].liquidity, 20);
+    }
 
-        let gas_cost_wei = U256::from(gas_estimate) * U256::from(gas_price);
-        let gas_cost_eth = format_units(gas_cost_wei, 18);
+    /// Push the symbol, name and decimals of a token.
+    fn push_metadata(asserter: &Asserter, symbol: &str, decimals: u64) {
+        asserter.push_success(&Bytes::from(symbol.to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(symbol.to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(decimals).abi_encode()));
+    }
 
-        // 150,000 * 30 gwei = 4,500,000 gwei = 0.0045 ETH
-        assert_eq!(gas_cost_eth, "0.0045");
+    #[tokio::test]
+    async fn test_preview_selects_from_listed_venues() {
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = SwapService::new(client.clone(), None, BalanceService::new(client))
+            .with_protocol_preference(ProtocolPreference::Best);
+        let pair = Address::repeat_byte(0x22);
+
+        push_metadata(&asserter, "AAA", 0);
+        push_metadata(&asserter, "BBB", 0);
+        // One V3 pool at the lowest fee tier, with liquidity and a quote
+        asserter.push_success(&Bytes::from(Address::repeat_byte(0x33).abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(1_000u64).abi_encode()));
+        asserter.push_success(&Bytes::from(
+            (U256::from(900u64), U256::from(1u64), 1u32, U256::from(100_000u64))
+                .abi_encode_params(),
+        ));
+        for _ in 1..fee_tiers::ALL_FEES.len() {
+            asserter.push_success(&Bytes::from(Address::ZERO.abi_encode()));
+        }
+        // The V2 pair: path, liquidity and quote
+        asserter.push_success(&Bytes::from(pair.abi_encode()));
+        asserter.push_success(&Bytes::from(pair.abi_encode()));
+        asserter.push_success(&Bytes::from(
+            (U256::from(10_000u64), U256::from(10_000u64), 0u32).abi_encode_params(),
+        ));
+        asserter.push_success(&Bytes::from(
+            vec![U256::from(1_000u64), U256::from(950u64)].abi_encode(),
+        ));
+
+        let preview = service
+            .preview_swap(Address::repeat_byte(1), Address::repeat_byte(3), U256::from(1_000u64))
+            .await
+            .unwrap();
+
+        // The route is selected without quoting the pools again
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(preview.venues.len(), 2);
+        let route = preview.route.unwrap();
+        assert_eq!(route.protocol, UniswapVersion::V2);
+        assert_eq!(preview.amount_out_estimate.as_deref(), Some("950"));
     }
 
-    #[test]
-    fn test_swap_route_creation() {
-        let route = SwapRoute {
-            protocol: UniswapVersion::V3,
-            path: vec!["0xToken1".to_string(), "0xToken2".to_string()],
-            fee_tier: Some(3000),
-        };
+    #[tokio::test]
+    async fn test_preview_rpc_failure_is_an_error() {
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = SwapService::new(client.clone(), None, BalanceService::new(client));
 
-        assert_eq!(route.protocol, UniswapVersion::V3);
-        assert_eq!(route.path.len(), 2);
-        assert_eq!(route.fee_tier, Some(3000));
-    }
+        push_metadata(&asserter, "AAA", 18);
+        push_metadata(&asserter, "BBB", 18);
+        for _ in fee_tiers::ALL_FEES {
+            asserter.push_success(&Bytes::from(Address::ZERO.abi_encode()));
+        }
+        asserter.push_failure_msg("header not found");
 
-    // ============================================================================
-    // current_timestamp Tests
-    // ============================================================================
+        let result = service
+            .preview_swap(Address::repeat_byte(1), Address::repeat_byte(3), U256::from(1_000u64))
+            .await;
+        assert!(result.unwrap_err().to_string().contains("header not found"));
+    }
 
     #[test]
-    fn test_current_timestamp_is_reasonable() {
-        let ts = current_timestamp();
-        // Should be after Jan 1, 2024 (1704067200)
-        assert!(ts > 1704067200, "Timestamp should be after 2024");
-        // Should be before year 2100 (4102444800)
-        assert!(ts < 4102444800, "Timestamp should be before 2100");
+    fn test_preview_issues_clear() {
+        let amount = U256::from(1_000u64);
+        let issues =
+            SwapService::preview_issues("USDC", amount, true, true, Some(amount), Some(U256::MAX));
+        assert!(issues.is_empty());
+
+        // Unreadable balance and allowance are not reported
+        assert!(SwapService::preview_issues("USDC", amount, true, true, None, None).is_empty());
     }
 
     #[test]
-    fn test_current_timestamp_increases() {
-        let ts1 = current_timestamp();
-        // Small delay to ensure time passes
-        std::thread::sleep(std::time::Duration::from_millis(10));
-        let ts2 = current_timestamp();
-        // ts2 should be >= ts1 (might be equal if within same second)
-        assert!(ts2 >= ts1);
+    fn test_preview_issues_reported() {
+        let amount = U256::from(1_000u64);
+
+        let issues = SwapService::preview_issues("USDC", amount, false, false, None, None);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].contains("No Uniswap V2 or V3 route"));
+        assert!(issues[1].contains("read-only mode"));
+
+        let issues = SwapService::preview_issues(
+            "USDC",
+            amount,
+            true,
+            true,
+            Some(U256::from(999u64)),
+            Some(U256::ZERO),
+        );
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].contains("USDC balance"));
+        assert!(issues[1].contains("allowance"));
     }
 
-    // ============================================================================
-    // calculate_reference_amount Tests
-    // ============================================================================
-
     #[test]
-    fn test_calculate_reference_amount_normal() {
-        // 1000 tokens -> 0.1% = 1 token reference
-        let amount = U256::from(1_000_000_000_000_000_000_000u128); // 1000 * 10^18
-        let reference = SwapService::calculate_reference_amount(amount);
+    fn test_price_impact_warning() {
+        assert!(SwapService::price_impact_warning(Decimal::from_str("4.99").unwrap()).is_none());
 
-        // Should be 0.1% = 1 token worth
-        let expected = amount / U256::from(1000);
-        assert_eq!(reference, expected);
+        let warning = SwapService::price_impact_warning(Decimal::from(5)).unwrap();
+        assert_eq!(warning.code, WarningCode::HighPriceImpact);
+        assert!(warning.message.contains("5%"));
     }
 
     #[test]
-    fn test_calculate_reference_amount_minimum() {
-        // Very small amount should use minimum (1000)
-        let amount = U256::from(100u64);
-        let reference = SwapService::calculate_reference_amount(amount);
-
-        // Should be capped at amount itself (since 100 < 1000)
-        assert_eq!(reference, amount);
+    fn test_precision_loss_warning_two_decimal_token() {
+        let slippage = Decimal::from_str("0.5").unwrap();
+
+        // 1.50 at 0.5%: exact minimum 1.4925, sent as 1.49 (0.17% lower)
+        let warning =
+            SwapService::precision_loss_warning(U256::from(150u32), slippage, 2, "GUSD").unwrap();
+        assert_eq!(warning.code, WarningCode::PrecisionLoss);
+        assert!(warning.message.contains("1.49 GUSD"));
+        assert!(warning.message.contains("0.17%"));
+
+        // 10000.00 at 0.5%: the rounding is negligible
+        assert!(SwapService::precision_loss_warning(U256::from(1_000_000u32), slippage, 2, "GUSD")
+            .is_none());
+
+        // An exact minimum needs no rounding
+        assert!(
+            SwapService::precision_loss_warning(U256::from(200u32), slippage, 2, "GUSD").is_none()
+        );
+        assert!(
+            SwapService::precision_loss_warning(U256::from(3u32), Decimal::ZERO, 0, "X").is_none()
+        );
+        assert!(SwapService::precision_loss_warning(U256::ZERO, slippage, 2, "GUSD").is_none());
     }
 
     #[test]
-    fn test_calculate_reference_amount_large() {
-        // Very large amount - 10% cap
-        let amount = U256::from(1_000_000_000_000_000_000_000_000u128); // 1M tokens
-        let reference = SwapService::calculate_reference_amount(amount);
+    fn test_precision_loss_warning_zero_decimal_token() {
+        // 7 units at 1%: exact minimum 6.93, sent as 6 (13.42% lower)
+        let warning =
+            SwapService::precision_loss_warning(U256::from(7u32), Decimal::ONE, 0, "X").unwrap();
+        assert!(warning.message.contains("6 X"));
+        assert!(warning.message.contains("13.42%"));
+    }
 
-        // 0.1% of 1M = 1000, which should be between min and max
-        let expected = amount / U256::from(1000);
-        let max_reference = amount / U256::from(10);
+    #[test]
+    fn test_price_impact_from_quotes() {
+        let impact = |amount_in: u64, amount_out: u64, reference: u64, spot: u64| {
+            SwapService::price_impact_from_quotes(
+                U256::from(amount_in),
+                U256::from(amount_out),
+                U256::from(reference),
+                U256::from(spot),
+                1,
+            )
+        };
 
-        assert!(reference <= max_reference);
-        assert_eq!(reference, expected);
+        // Spot rate 2 per unit, execution rate 1.9 per unit
+        assert_eq!(impact(1_000_000, 1_900_000, 1_000, 2_000).unwrap(), Decimal::from(5));
+        // A better execution than spot is not negative impact
+        assert_eq!(impact(1_000_000, 2_100_000, 1_000, 2_000).unwrap(), Decimal::ZERO);
+        assert_eq!(impact(0, 0, 0, 2_000).unwrap(), Decimal::ZERO);
+        // A spot quote of nothing has no rate
+        assert!(impact(1_000_000, 1_900_000, 1_000, 0).is_err());
     }
 
     #[test]
-    fn test_calculate_reference_amount_boundary() {
-        // Test at boundary where 0.1% equals minimum
-        let min_reference = U256::from(1_000u64);
-        let amount = min_reference * U256::from(1000); // 1,000,000
+    fn test_price_impact_eight_decimal_output() {
+        // 10 USDC for 0.00009 WBTC (9,000 sats): the reference grown to 1 USDC
+        // quotes 1,000 sats, enough to measure a 10% impact
+        assert_eq!(
+            SwapService::price_impact_from_quotes(
+                U256::from(10_000_000u64),
+                U256::from(9_000u64),
+                U256::from(1_000_000u64),
+                U256::from(1_000u64),
+                1,
+            )
+            .unwrap(),
+            Decimal::from(10)
+        );
 
-        let reference = SwapService::calculate_reference_amount(amount);
-        assert_eq!(reference, min_reference);
+        // A 1 USDC trade caps the reference at 0.1 USDC (100 sats): coarse, but an
+        // 8-decimal output is still measured rather than refused
+        assert_eq!(
+            SwapService::price_impact_from_quotes(
+                U256::from(1_000_000u64),
+                U256::from(1_000u64),
+                U256::from(100_000u64),
+                U256::from(100u64),
+                1,
+            )
+            .unwrap(),
+            Decimal::ZERO
+        );
     }
 
-    // ============================================================================
-    // decimal_to_u128 Tests
-    // ============================================================================
-
     #[test]
-    fn test_decimal_to_u128_whole_number() {
-        let value = Decimal::from(1000);
-        let result = SwapService::decimal_to_u128(value).unwrap();
-        assert_eq!(result, 1000u128);
+    fn test_price_impact_zero_decimal_output() {
+        // 10 USDC for 8 units of a 0-decimal token: the 0.01 USDC spot quote
+        // returns 0 units, which cannot be turned into a rate
+        assert!(SwapService::price_impact_from_quotes(
+            U256::from(10_000_000u64),
+            U256::from(8u64),
+            U256::from(10_000u64),
+            U256::ZERO,
+            MIN_SPOT_OUTPUT,
+        )
+        .is_err());
+
+        // A few units are still too coarse for a 0-decimal output
+        assert!(SwapService::price_impact_from_quotes(
+            U256::from(10_000_000u64),
+            U256::from(8u64),
+            U256::from(1_000_000u64),
+            U256::from(1u64),
+            MIN_SPOT_OUTPUT,
+        )
+        .is_err());
+
+        // Selling a 0-decimal token: the spot quote is the whole 8 units, and the
+        // USDC output is fine-grained enough to measure
+        assert_eq!(
+            SwapService::price_impact_from_quotes(
+                U256::from(8u64),
+                U256::from(9_500_000u64),
+                U256::from(8u64),
+                U256::from(10_000_000u64),
+                1,
+            )
+            .unwrap(),
+            Decimal::from(5)
+        );
     }
 
     #[test]
-    fn test_decimal_to_u128_truncates_decimals() {
-        let value = Decimal::new(12345, 2); // 123.45
-        let result = SwapService::decimal_to_u128(value).unwrap();
-        assert_eq!(result, 123u128); // Truncated
+    fn test_zero_decimal_swap_quote_formatting() {
+        // 1,000 USDC bought 3 units of a 0-decimal token
+        let amount_out = U256::from(3u64);
+        assert_eq!(format_units(amount_out, 0), "3");
+
+        // 0.5% of 3 units cannot be represented, so the minimum rounds down to 2
+        let slippage = Decimal::from_str("0.5").unwrap();
+        let min_out = SwapService::minimum_amount_out(amount_out, slippage).unwrap();
+        assert_eq!(min_out, U256::from(2u64));
+        assert_eq!(format_units(min_out, 0), "2");
+        assert_eq!(format_units(amount_out - min_out, 0), "1");
+        let warning = SwapService::precision_loss_warning(amount_out, slippage, 0, "ITEM").unwrap();
+        assert!(warning.message.contains("2 ITEM (0 decimals)"));
+
+        // A single unit has no room for slippage at all
+        let min_out = SwapService::minimum_amount_out(U256::from(1u64), slippage).unwrap();
+        assert_eq!(min_out, U256::ZERO);
+        // ...unless none is allowed
+        let min_out = SwapService::minimum_amount_out(U256::from(1u64), Decimal::ZERO).unwrap();
+        assert_eq!(min_out, U256::from(1u64));
     }
 
     #[test]
-    fn test_decimal_to_u128_zero() {
-        let value = Decimal::ZERO;
-        let result = SwapService::decimal_to_u128(value).unwrap();
-        assert_eq!(result, 0u128);
+    fn test_post_trade_price_v3_sqrt_price() {
+        // sqrtPriceX96 = 2 * 2^96: token1 / token0 = 4 in raw units
+        let two = U160::from(2u8) << 96;
+        let forward = PriceRatio::from_sqrt_price_x96(two, true).unwrap();
+        let backward = PriceRatio::from_sqrt_price_x96(two, false).unwrap();
+        assert_eq!(chain_hop_prices(&[forward], 18, 18).as_deref(), Some("4"));
+        assert_eq!(chain_hop_prices(&[backward], 18, 18).as_deref(), Some("0.25"));
+
+        assert_eq!(PriceRatio::from_sqrt_price_x96(U160::ZERO, true), None);
     }
 
     #[test]
-    fn test_decimal_to_u128_max_valid() {
-        // Large but valid u128 value
-        let value = Decimal::from(u64::MAX);
-        let result = SwapService::decimal_to_u128(value).unwrap();
-        assert_eq!(result, u64::MAX as u128);
+    fn test_post_trade_price_v2_reserves() {
+        // 1,000 WETH and 2,500,000 USDC in the pair after the trade
+        let price = v2_hop_price(
+            U256::from(1_000u64) * U256::from(10u64).pow(U256::from(18)),
+            U256::from(2_500_000u64) * U256::from(1_000_000u64),
+        )
+        .unwrap();
+        assert_eq!(chain_hop_prices(&[price], 18, 6).as_deref(), Some("2500"));
+
+        assert_eq!(v2_hop_price(U256::ZERO, U256::from(1u8)), None);
     }
 
-    // ============================================================================
-    // Slippage Calculation Tests
-    // ============================================================================
+    #[test]
+    fn test_post_trade_price_multi_hop() {
+        // USDC -> WETH at 1/2500, then WETH -> DAI at 2400
+        let usdc_weth = v2_hop_price(
+            U256::from(2_500_000u64) * U256::from(1_000_000u64),
+            U256::from(1_000u64) * U256::from(10u64).pow(U256::from(18)),
+        )
+        .unwrap();
+        let weth_dai = PriceRatio::new(U256::from(2400u16), U256::from(1u8)).unwrap();
+
+        let price = chain_hop_prices(&[usdc_weth, weth_dai], 6, 18).unwrap();
+        assert_eq!(price, "0.96");
+    }
 
     #[test]
-    fn test_slippage_1_percent() {
-        let amount_out = U256::from(1_000_000_000u64); // 1B units
-        let slippage = Decimal::from(1); // 1%
+    fn test_post_trade_price_tiny_price_keeps_precision() {
+        // SHIB-like token (18 decimals) worth 1e-8 WBTC (8 decimals): a raw price of
+        // 1e-18 that a fixed 18-decimal format would round to zero
+        let price = v2_hop_price(U256::from(10u64).pow(U256::from(18)), U256::from(1u8)).unwrap();
+        assert_eq!(chain_hop_prices(&[price], 18, 8).as_deref(), Some("0.00000001"));
+
+        let price =
+            v2_hop_price(U256::from(3u64) * U256::from(10u64).pow(U256::from(30)), U256::from(1u8))
+                .unwrap();
+        assert_eq!(
+            chain_hop_prices(&[price], 18, 18).as_deref(),
+            Some("0.000000000000000000000000000000333333333333333333")
+        );
+    }
 
-        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
-        let amount_out_u128: u128 = amount_out.try_into().unwrap();
-        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+    #[test]
+    fn test_decode_swap_calldata_exact_input_single() {
+        use crate::ethereum::contracts::USDC_ADDRESS;
+
+        let recipient = Address::repeat_byte(0x11);
+        let calldata = ISwapRouter::exactInputSingleCall {
+            params: ISwapRouter::ExactInputSingleParams {
+                tokenIn: WETH_ADDRESS,
+                tokenOut: USDC_ADDRESS,
+                fee: U24::from(3000),
+                recipient,
+                deadline: U256::from(1_800_000_000u64),
+                amountIn: U256::from(10u64).pow(U256::from(18)),
+                amountOutMinimum: U256::from(2_990_000_000u64),
+                sqrtPriceLimitX96: U160::ZERO,
+            },
+        }
+        .abi_encode();
 
-        // 1% slippage = 99% of original
-        let expected = Decimal::from(990_000_000u64);
-        assert_eq!(min_out, expected);
+        let decoded = decode_swap_calldata(&calldata).unwrap();
+        assert_eq!(decoded.function, "exactInputSingle");
+        assert_eq!(decoded.selector, "0x414bf389");
+        assert_eq!(decoded.amount_in, "1000000000000000000");
+        assert_eq!(decoded.amount_out_min, "2990000000");
+        assert_eq!(
+            decoded.path,
+            vec![format!("{:?}", WETH_ADDRESS), format!("{:?}", USDC_ADDRESS)]
+        );
+        assert_eq!(decoded.fee_tiers, vec![3000]);
+        assert_eq!(decoded.recipient, format!("{:?}", recipient));
+        assert_eq!(decoded.deadline, 1_800_000_000);
     }
 
     #[test]
-    fn test_slippage_5_percent() {
-        let amount_out = U256::from(100_000_000u64);
-        let slippage = Decimal::from(5); // 5%
-
-        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
-        let amount_out_u128: u128 = amount_out.try_into().unwrap();
-        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+    fn test_decode_swap_calldata_exact_input() {
+        use crate::ethereum::{constants::UNI_ADDRESS, contracts::USDC_ADDRESS};
+
+        let calldata = ISwapRouter::exactInputCall {
+            params: ISwapRouter::ExactInputParams {
+                path: encode_path(&[UNI_ADDRESS, WETH_ADDRESS, USDC_ADDRESS], 3000),
+                recipient: Address::repeat_byte(0x11),
+                deadline: U256::from(1_800_000_000u64),
+                amountIn: U256::from(5u64),
+                amountOutMinimum: U256::from(4u64),
+            },
+        }
+        .abi_encode();
 
-        // 5% slippage = 95% of original
-        let expected = Decimal::from(95_000_000u64);
-        assert_eq!(min_out, expected);
+        let decoded = decode_swap_calldata(&calldata).unwrap();
+        assert_eq!(decoded.function, "exactInput");
+        assert_eq!(decoded.path.len(), 3);
+        assert_eq!(decoded.path[1], format!("{:?}", WETH_ADDRESS));
+        assert_eq!(decoded.fee_tiers, vec![3000, 3000]);
     }
 
     #[test]
-    fn test_slippage_small() {
-        let amount_out = U256::from(1_000_000u64);
-        let slippage = Decimal::new(1, 1); // 0.1%
+    fn test_decode_swap_calldata_v2() {
+        use crate::ethereum::contracts::USDC_ADDRESS;
 
-        let slippage_multiplier = Decimal::ONE - slippage / Decimal::from(100);
-        let amount_out_u128: u128 = amount_out.try_into().unwrap();
-        let min_out = Decimal::from(amount_out_u128) * slippage_multiplier;
+        let calldata = IUniswapV2Router02::swapExactTokensForTokensCall {
+            amountIn: U256::from(1_000_000u64),
+            amountOutMin: U256::from(300u64),
+            path: vec![USDC_ADDRESS, WETH_ADDRESS],
+            to: Address::repeat_byte(0x22),
+            deadline: U256::from(1_800_000_000u64),
+        }
+        .abi_encode();
 
-        // 0.1% slippage = 99.9% of original
-        let expected = Decimal::from(999_000u64);
-        assert_eq!(min_out, expected);
+        let decoded = decode_swap_calldata(&calldata).unwrap();
+        assert_eq!(decoded.function, "swapExactTokensForTokens");
+        assert_eq!(decoded.selector, "0x38ed1739");
+        assert_eq!(decoded.amount_in, "1000000");
+        assert_eq!(decoded.amount_out_min, "300");
+        assert!(decoded.fee_tiers.is_empty());
+        assert_eq!(decoded.recipient, format!("{:?}", Address::repeat_byte(0x22)));
+
+        // Calldata of other functions is not decoded
+        assert!(decode_swap_calldata(&[0xa9, 0x05, 0x9c, 0xbb]).is_none());
+        assert!(decode_swap_calldata(&calldata[..3]).is_none());
     }
 
-    // ============================================================================
-    // Gas Cost Calculation Tests
-    // ============================================================================
-
     #[test]
-    fn test_gas_cost_high_gas_price() {
-        let gas_estimate: u64 = 200_000;
-        let gas_price: u128 = 100_000_000_000; // 100 gwei
-
-        let gas_cost_wei = U256::from(gas_estimate) * U256::from(gas_price);
-        let gas_cost_eth = format_units(gas_cost_wei, 18);
+    fn test_build_wallet_transaction() {
+        let from = Address::repeat_byte(1);
+        let tx = TransactionRequest::default()
+            .from(from)
+            .to(UNISWAP_V3_ROUTER)
+            .input(Bytes::from(vec![0xab, 0xcd]).into())
+            .value(U256::from(10u64).pow(U256::from(18)));
+        let fees = Eip1559Estimation {
+            max_fee_per_gas: 30_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+        };
 
-        // 200,000 * 100 gwei = 20,000,000 gwei = 0.02 ETH
-        assert_eq!(gas_cost_eth, "0.02");
+        let wallet_tx = build_wallet_transaction(&tx, from, 1, 250_000, fees, 7);
+
+        assert_eq!(wallet_tx.tx_type, "0x2");
+        assert_eq!(wallet_tx.from, format!("{:?}", from));
+        assert_eq!(wallet_tx.to, format!("{:?}", UNISWAP_V3_ROUTER));
+        assert_eq!(wallet_tx.data, "0xabcd");
+        assert_eq!(wallet_tx.value, "0xde0b6b3a7640000");
+        assert_eq!(wallet_tx.chain_id, "0x1");
+        assert_eq!(wallet_tx.gas, "0x3d090");
+        assert_eq!(wallet_tx.max_fee_per_gas, "0x6fc23ac00");
+        assert_eq!(wallet_tx.max_priority_fee_per_gas, "0x3b9aca00");
+        assert_eq!(wallet_tx.nonce, "0x7");
+
+        let json = serde_json::to_value(&wallet_tx).unwrap();
+        assert_eq!(json["type"], "0x2");
+        assert_eq!(json["chainId"], "0x1");
+        assert_eq!(json["maxPriorityFeePerGas"], "0x3b9aca00");
+
+        // Swaps that spend a token send no ETH
+        let tx = TransactionRequest::default().to(UNISWAP_V3_ROUTER);
+        assert_eq!(build_wallet_transaction(&tx, from, 1, 21_000, fees, 0).value, "0x0");
     }
 
     #[test]
-    fn test_gas_cost_low_gas_price() {
-        let gas_estimate: u64 = 100_000;
-        let gas_price: u128 = 5_000_000_000; // 5 gwei
+    fn test_rebasing_warning_recommends_wrapper() {
+        use crate::ethereum::constants::{AMPL_ADDRESS, STETH_ADDRESS, WSTETH_ADDRESS};
 
-        let gas_cost_wei = U256::from(gas_estimate) * U256::from(gas_price);
-        let gas_cost_eth = format_units(gas_cost_wei, 18);
+        let warning = SwapService::rebasing_warning(STETH_ADDRESS, "stETH");
+        assert_eq!(warning.code, WarningCode::RebasingToken);
+        assert!(warning.message.contains(&format!("{:?}", WSTETH_ADDRESS)));
 
-        // 100,000 * 5 gwei = 500,000 gwei = 0.0005 ETH
-        assert_eq!(gas_cost_eth, "0.0005");
+        let warning = SwapService::rebasing_warning(AMPL_ADDRESS, "AMPL");
+        assert!(warning.message.starts_with("AMPL is a rebasing token"));
+        assert!(!warning.message.contains("wrapper"));
     }
 
-    // ============================================================================
-    // SwapRoute Tests
-    // ============================================================================
-
-    #[test]
-    fn test_swap_route_v2_direct() {
-        let route = SwapRoute {
-            protocol: UniswapVersion::V2,
-            path: vec!["WETH".to_string(), "USDC".to_string()],
-            fee_tier: None,
+    #[tokio::test]
+    async fn test_thin_v2_route_is_recorded() {
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = SwapService::new(client.clone(), None, BalanceService::new(client))
+            .with_min_pool_liquidity(1_000);
+        let params = SwapParams {
+            from_token: Address::repeat_byte(1),
+            to_token: Address::repeat_byte(3),
+            amount_in: U256::from(100u64),
+            ..Default::default()
         };
 
-        assert_eq!(route.protocol, UniswapVersion::V2);
-        assert_eq!(route.path.len(), 2);
-        assert!(route.fee_tier.is_none());
+        // getPair for the path, getPair and getReserves for its liquidity, then the quote
+        let pair = Address::repeat_byte(0x22);
+        asserter.push_success(&Bytes::from(pair.abi_encode()));
+        asserter.push_success(&Bytes::from(pair.abi_encode()));
+        asserter.push_success(&Bytes::from(
+            (U256::from(10u64), U256::from(10u64), 0u32).abi_encode_params(),
+        ));
+        asserter
+            .push_success(&Bytes::from(vec![U256::from(100u64), U256::from(95u64)].abi_encode()));
+
+        let mut thin_pools = Vec::new();
+        let result = service.try_v2_venue_route(&params, Venue::Sushiswap, &mut thin_pools).await;
+
+        assert!(matches!(result, Err(AppError::InsufficientLiquidity)));
+        assert_eq!(
+            thin_pools,
+            vec![ThinPool {
+                protocol: UniswapVersion::V2,
+                venue: Venue::Sushiswap,
+                fee_tier: None,
+                amount_out: U256::from(95u64),
+                liquidity: 10,
+            }]
+        );
     }
 
-    #[test]
-    fn test_swap_route_v2_multihop() {
-        let route = SwapRoute {
-            protocol: UniswapVersion::V2,
-            path: vec!["TOKEN".to_string(), "WETH".to_string(), "USDC".to_string()],
-            fee_tier: None,
-        };
+    #[tokio::test]
+    async fn test_v3_liquidity_read_failure_is_an_error() {
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = SwapService::new(client.clone(), None, BalanceService::new(client));
 
-        assert_eq!(route.path.len(), 3);
+        // The first fee tier's pool exists, but its liquidity cannot be read
+        asserter.push_success(&Bytes::from(Address::repeat_byte(0x33).abi_encode()));
+        asserter.push_failure_msg("header not found");
+
+        let result = service
+            .v3_tiers(Address::repeat_byte(1), Address::repeat_byte(3), U256::from(100u64))
+            .await;
+        assert!(result.unwrap_err().to_string().contains("header not found"));
     }
 
-    #[test]
-    fn test_swap_route_v3_fee_tiers() {
-        // Test all common V3 fee tiers
-        for fee in [100, 500, 3000, 10000] {
-            let route = SwapRoute {
-                protocol: UniswapVersion::V3,
-                path: vec!["A".to_string(), "B".to_string()],
-                fee_tier: Some(fee),
-            };
-            assert_eq!(route.fee_tier, Some(fee));
+    #[tokio::test]
+    async fn test_exact_output_quote_uses_to_token_decimals() {
+        let asserter = Asserter::new();
+        let client = Arc::new(EthereumClient::mocked(asserter.clone()));
+        let service = SwapService::new(client.clone(), None, BalanceService::new(client));
+
+        // symbol, name and decimals of each token
+        for (symbol, decimals) in [("WETH", 18u64), ("USDC", 6u64)] {
+            asserter.push_success(&Bytes::from(symbol.to_string().abi_encode()));
+            asserter.push_success(&Bytes::from(symbol.to_string().abi_encode()));
+            asserter.push_success(&Bytes::from(U256::from(decimals).abi_encode()));
         }
+        // No V3 pool at any fee tier, then a direct V2 pair and its getAmountsIn
+        for _ in fee_tiers::ALL_FEES {
+            asserter.push_success(&Bytes::from(Address::ZERO.abi_encode()));
+        }
+        asserter.push_success(&Bytes::from(Address::repeat_byte(0x22).abi_encode()));
+        let amount_in = U256::from(500_000_000_000_000u64);
+        let amount_out = U256::from(1_500_000u64);
+        asserter.push_success(&Bytes::from(vec![amount_in, amount_out].abi_encode()));
+
+        let quote = service
+            .quote_exact_output(Address::repeat_byte(1), Address::repeat_byte(3), amount_out)
+            .await
+            .unwrap();
+
+        assert!(asserter.read_q().is_empty());
+        assert_eq!(quote.amount_out, "1.5");
+        assert_eq!(quote.amount_out_raw, "1500000");
+        assert_eq!(quote.amount_in, "0.0005");
+        assert_eq!(quote.to_token.decimals, 6);
+        assert_eq!(quote.route.protocol, UniswapVersion::V2);
     }
 
-    // ============================================================================
-    // Deadline Tests
-    // ============================================================================
+    #[test]
+    fn test_better_priced_thin_pools() {
+        let thin_pools = [
+            ThinPool::v3(100, U256::from(1_010u64), 5),
+            ThinPool::v3(10000, U256::from(900u64), 7),
+        ];
+
+        let better: Vec<_> =
+            SwapService::better_priced(&thin_pools, U256::from(1_000u64)).collect();
+        assert_eq!(better, vec![&thin_pools[0]]);
+
+        assert_eq!(SwapService::better_priced(&thin_pools, U256::from(2_000u64)).count(), 0);
+    }
+
+    fn path_leg(from: &str, to: &str, amount_out: &str, gas: &str, success: bool) -> SwapPathLeg {
+        SwapPathLeg {
+            from_token: TokenInfo::erc20(Address::ZERO, from.to_string(), 18),
+            to_token: TokenInfo::erc20(Address::ZERO, to.to_string(), 18),
+            simulation: SwapSimulationResult {
+                simulation_success: success,
+                amount_out_expected: amount_out.to_string(),
+                amount_out_minimum: amount_out.to_string(),
+                gas_estimate: gas.to_string(),
+                gas_price: "10000000000".to_string(),
+                gas_cost_eth: "0".to_string(),
+                route: SwapRoute {
+                    protocol: UniswapVersion::V2,
+                    path: vec![],
+                    fee_tier: None,
+                    venue: Venue::Uniswap,
+                },
+                ..SwapSimulationResult::fixture()
+            },
+        }
+    }
 
     #[test]
-    fn test_deadline_20_minutes() {
-        let now = current_timestamp();
-        let deadline = now + 1200; // 20 minutes
+    fn test_summarize_path_completed() {
+        let start = TokenInfo::erc20(Address::ZERO, "WETH".to_string(), 18);
+        let legs = vec![
+            path_leg("WETH", "USDC", "3000", "150000", true),
+            path_leg("USDC", "UNI", "400", "180000", false),
+        ];
+
+        let result = SwapService::summarize_path("1".to_string(), start, legs, None).unwrap();
+
+        assert!(result.completed);
+        assert!(!result.all_simulations_succeeded);
+        assert_eq!(result.final_amount_out, "400");
+        assert_eq!(result.final_token.symbol, "UNI");
+        assert_eq!(result.total_gas_estimate, "330000");
+        // 330000 gas * 10 gwei
+        assert_eq!(result.total_gas_cost_eth, "0.0033");
+        assert!(result.failed_leg.is_none());
+    }
 
-        assert_eq!(deadline - now, 1200);
-        assert!(deadline > now);
+    #[test]
+    fn test_summarize_path_stopped_early() {
+        let start = TokenInfo::erc20(Address::ZERO, "WETH".to_string(), 18);
+        let legs = vec![path_leg("WETH", "USDC", "3000", "150000", true)];
+        let failure = Some((1, "Pool not found for token pair".to_string()));
+
+        let result = SwapService::summarize_path("1".to_string(), start, legs, failure).unwrap();
+
+        assert!(!result.completed);
+        assert!(!result.all_simulations_succeeded);
+        assert_eq!(result.failed_leg, Some(1));
+        assert_eq!(result.final_amount_out, "3000");
+        assert_eq!(result.final_token.symbol, "USDC");
+        assert_eq!(result.legs.len(), 1);
     }
 
     #[test]
-    fn test_deadline_custom() {
-        let custom_deadline = 1800000000u64;
-        let params = SwapParams {
-            from_token: Address::ZERO,
-            to_token: Address::ZERO,
-            amount_in: U256::ZERO,
-            slippage_tolerance: Decimal::ONE,
-            deadline: Some(custom_deadline),
-        };
+    fn test_summarize_path_first_leg_failed() {
+        let start = TokenInfo::erc20(Address::ZERO, "WETH".to_string(), 18);
+        let failure = Some((0, "Pool not found for token pair".to_string()));
 
-        assert_eq!(params.deadline, Some(custom_deadline));
+        let result = SwapService::summarize_path("1".to_string(), start, vec![], failure).unwrap();
+
+        assert_eq!(result.final_amount_out, "1");
+        assert_eq!(result.final_token.symbol, "WETH");
+        assert_eq!(result.total_gas_estimate, "0");
+        assert_eq!(result.total_gas_cost_eth, "0");
     }
 }
@@ -9,14 +9,20 @@ use std::time::{Duration, Instant};
 
 use alloy::primitives::Address;
 use async_trait::async_trait;
+use icu_normalizer::ComposingNormalizerBorrowed;
 use serde::Deserialize;
 use tokio::sync::{RwLock, Semaphore};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
 use tracing::{info, warn};
 
+use crate::config::{HttpProxy, DEFAULT_TOKEN_LIST_MAX_TOKENS};
 use crate::error::{AppError, Result};
 use crate::ethereum::constants::{
-    ETHEREUM_MAINNET_CHAIN_ID, UNI_ADDRESS, USDC_ADDRESS, WBTC_ADDRESS, WETH_ADDRESS,
+    canonical_token_address, ETHEREUM_MAINNET_CHAIN_ID, UNI_ADDRESS, USDC_ADDRESS, WBTC_ADDRESS,
+    WETH_ADDRESS,
 };
+use crate::types::{ChangedToken, ListedToken, SymbolProvenance, TokenListDiff};
 
 // ============================================================================
 // Token List Sources
@@ -31,6 +37,19 @@ pub const ONE_INCH_TOKEN_LIST_URL: &str = "https://tokens.1inch.eth.limo";
 /// Default cache TTL (24 hours).
 pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(86400);
 
+/// Largest token list response body accepted (32 MiB).
+///
+/// The Uniswap default list is well under 1 MiB; this only stops a misbehaving
+/// or malicious source from exhausting memory before the token cap applies.
+pub const MAX_TOKEN_LIST_BYTES: usize = 32 * 1024 * 1024;
+
+/// Extra cache lookups for a token still missing after the refresh forced by
+/// a cache miss, to pick up a concurrent refresh that completes just after it.
+const MISS_RETRY_ATTEMPTS: u32 = 2;
+
+/// Delay before each of the [`MISS_RETRY_ATTEMPTS`] lookups.
+const MISS_RETRY_DELAY: Duration = Duration::from_millis(100);
+
 // ============================================================================
 // Token List Types (following tokenlists.org schema)
 // ============================================================================
@@ -54,11 +73,31 @@ pub struct TokenListToken {
     pub logo_uri: Option<String>,
 }
 
+/// Token list version (semantic versioning, per tokenlists.org schema).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct TokenListVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Patch version.
+    pub patch: u32,
+}
+
+impl std::fmt::Display for TokenListVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 /// Token list response from API.
 #[derive(Debug, Clone, Deserialize)]
 pub struct TokenListResponse {
     /// List name.
     pub name: String,
+    /// List version (optional).
+    #[serde(default)]
+    pub version: Option<TokenListVersion>,
     /// List of tokens.
     pub tokens: Vec<TokenListToken>,
 }
@@ -67,6 +106,41 @@ pub struct TokenListResponse {
 // Cached Token Entry
 // ============================================================================
 
+/// Origin label for built-in fallback tokens.
+pub const BUILTIN_TOKEN_SOURCE: &str = "builtin";
+
+/// Where a token entry was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenSource {
+    /// Token list URL, or [`BUILTIN_TOKEN_SOURCE`] for built-in fallback tokens.
+    pub origin: String,
+    /// Token list name, if loaded from a list.
+    pub list_name: Option<String>,
+    /// Token list version, if the list declares one.
+    pub list_version: Option<TokenListVersion>,
+}
+
+impl TokenSource {
+    /// Source for built-in fallback tokens.
+    pub fn builtin() -> Self {
+        Self { origin: BUILTIN_TOKEN_SOURCE.to_string(), list_name: None, list_version: None }
+    }
+
+    /// Whether this source is curated: the built-in tokens or the Uniswap default list.
+    pub fn is_curated(&self) -> bool {
+        self.origin == BUILTIN_TOKEN_SOURCE || self.origin == UNISWAP_TOKEN_LIST_URL
+    }
+
+    /// Source for tokens loaded from a remote token list.
+    pub fn token_list(url: &str, list: &TokenListResponse) -> Self {
+        Self {
+            origin: url.to_string(),
+            list_name: Some(list.name.clone()),
+            list_version: list.version,
+        }
+    }
+}
+
 /// A token entry with parsed address.
 #[derive(Debug, Clone)]
 pub struct TokenEntry {
@@ -80,6 +154,29 @@ pub struct TokenEntry {
     pub decimals: u8,
     /// Chain ID.
     pub chain_id: u64,
+    /// Where this entry was loaded from.
+    pub source: TokenSource,
+}
+
+impl TokenEntry {
+    /// Describe how `requested` resolved to this entry.
+    pub fn provenance(&self, requested: &str) -> SymbolProvenance {
+        SymbolProvenance {
+            symbol: requested.to_string(),
+            address: format!("{:?}", self.address),
+            chain_id: self.chain_id,
+            source: self.source.origin.clone(),
+            list_name: self.source.list_name.clone(),
+            list_version: self.source.list_version.map(|v| v.to_string()),
+        }
+    }
+
+    /// Canonical address of this entry's symbol, if it is a core token and this
+    /// entry is somewhere else.
+    pub fn non_canonical(&self) -> Option<Address> {
+        canonical_token_address(self.chain_id, &self.symbol)
+            .filter(|&canonical| canonical != self.address)
+    }
 }
 
 // ============================================================================
@@ -109,6 +206,12 @@ pub trait TokenRegistryTrait: Send + Sync {
     /// # Returns
     /// Token entry if found, None otherwise.
     async fn lookup_address(&self, address: Address) -> Option<TokenEntry>;
+
+    /// Refresh the token list and report what changed since the previous refresh.
+    ///
+    /// # Returns
+    /// Tokens added, removed and changed since the previous snapshot.
+    async fn token_list_diff(&self) -> Result<TokenListDiff>;
 }
 
 // ============================================================================
@@ -123,11 +226,27 @@ struct CacheState {
     by_address: HashMap<(u64, Address), TokenEntry>,
     /// Last update timestamp.
     last_updated: Option<Instant>,
+    /// Tokens loaded by the last token list refresh.
+    list_snapshot: Option<ListSnapshot>,
+}
+
+/// Tokens loaded from one token list refresh, kept to diff against the next.
+#[derive(Clone)]
+struct ListSnapshot {
+    /// Where the tokens were loaded from.
+    source: TokenSource,
+    /// Loaded tokens by address.
+    tokens: HashMap<Address, TokenEntry>,
 }
 
 impl CacheState {
     fn new() -> Self {
-        Self { by_symbol: HashMap::new(), by_address: HashMap::new(), last_updated: None }
+        Self {
+            by_symbol: HashMap::new(),
+            by_address: HashMap::new(),
+            last_updated: None,
+            list_snapshot: None,
+        }
     }
 
     fn is_expired(&self, ttl: Duration) -> bool {
@@ -139,11 +258,70 @@ impl CacheState {
 
     /// Insert a token entry into both indexes.
     fn insert(&mut self, entry: TokenEntry) {
-        let symbol_key = (entry.chain_id, entry.symbol.to_uppercase());
+        let symbol_key = (entry.chain_id, normalize_symbol(&entry.symbol));
         let address_key = (entry.chain_id, entry.address);
         self.by_symbol.insert(symbol_key, entry.clone());
         self.by_address.insert(address_key, entry);
     }
+
+    /// Insert the tokens of `list` deployed on `chain_id`, at most `max_tokens`.
+    ///
+    /// Tokens with unparseable addresses are skipped. Returns the number of
+    /// tokens inserted; tokens past the cap are dropped with a warning. The
+    /// inserted tokens replace the list snapshot.
+    fn load_token_list(
+        &mut self,
+        list: TokenListResponse,
+        source: &TokenSource,
+        chain_id: u64,
+        max_tokens: usize,
+    ) -> usize {
+        let mut tokens = HashMap::new();
+        let mut dropped = 0;
+
+        for token in list.tokens {
+            // Only include tokens for our target chain
+            if token.chain_id != chain_id {
+                continue;
+            }
+
+            if tokens.len() >= max_tokens {
+                dropped += 1;
+                continue;
+            }
+
+            // Parse address
+            let address = match token.address.parse::<Address>() {
+                Ok(addr) => addr,
+                Err(e) => {
+                    warn!("Invalid token address {}: {}", token.address, e);
+                    continue;
+                }
+            };
+
+            let entry = TokenEntry {
+                address,
+                symbol: token.symbol,
+                name: token.name,
+                decimals: token.decimals,
+                chain_id: token.chain_id,
+                source: source.clone(),
+            };
+            self.insert(entry.clone());
+            tokens.insert(address, entry);
+        }
+
+        if dropped > 0 {
+            warn!(
+                "Token list '{}' has more than {} tokens for chain {}, dropped {}",
+                list.name, max_tokens, chain_id, dropped
+            );
+        }
+
+        let count = tokens.len();
+        self.list_snapshot = Some(ListSnapshot { source: source.clone(), tokens });
+        count
+    }
 }
 
 /// Token Registry with caching support.
@@ -152,6 +330,7 @@ impl CacheState {
 /// - Remote fetching from Uniswap Token Lists
 /// - In-memory caching with 24-hour TTL
 /// - Auto-refresh on cache miss
+/// - Optional periodic background refresh
 /// - Concurrent refresh protection (only one refresh at a time)
 pub struct TokenRegistry {
     /// HTTP client for fetching token lists.
@@ -162,6 +341,8 @@ pub struct TokenRegistry {
     chain_id: u64,
     /// Cache TTL.
     cache_ttl: Duration,
+    /// Maximum number of tokens loaded from the token list.
+    max_tokens: usize,
     /// Cached token data.
     cache: Arc<RwLock<CacheState>>,
     /// Semaphore to prevent concurrent cache refreshes.
@@ -194,16 +375,12 @@ impl TokenRegistry {
         token_list_url: String,
         cache_ttl: Duration,
     ) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| AppError::Transport(format!("Failed to create HTTP client: {}", e)))?;
-
         let registry = Self {
-            client,
+            client: http_client(&HttpProxy::Environment)?,
             token_list_url,
             chain_id,
             cache_ttl,
+            max_tokens: DEFAULT_TOKEN_LIST_MAX_TOKENS,
             cache: Arc::new(RwLock::new(CacheState::new())),
             refresh_semaphore: Semaphore::new(1),
         };
@@ -216,6 +393,21 @@ impl TokenRegistry {
         Ok(registry)
     }
 
+    /// Fetch the token list through `proxy` instead of the proxy environment variables.
+    ///
+    /// # Errors
+    /// Returns an error if the proxy URL is invalid or the HTTP client cannot be created.
+    pub fn with_http_proxy(mut self, proxy: &HttpProxy) -> Result<Self> {
+        self.client = http_client(proxy)?;
+        Ok(self)
+    }
+
+    /// Set the maximum number of tokens loaded from the token list.
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens.max(1);
+        self
+    }
+
     /// Pre-populate cache with well-known mainnet tokens.
     /// These serve as fallbacks when remote token list is unavailable.
     fn populate_fallback_tokens(&self) {
@@ -226,6 +418,7 @@ impl TokenRegistry {
                 name: "Wrapped Ether".to_string(),
                 decimals: 18,
                 chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+                source: TokenSource::builtin(),
             },
             TokenEntry {
                 address: USDC_ADDRESS,
@@ -233,6 +426,7 @@ impl TokenRegistry {
                 name: "USD Coin".to_string(),
                 decimals: 6,
                 chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+                source: TokenSource::builtin(),
             },
             TokenEntry {
                 address: WBTC_ADDRESS,
@@ -240,6 +434,7 @@ impl TokenRegistry {
                 name: "Wrapped BTC".to_string(),
                 decimals: 8,
                 chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+                source: TokenSource::builtin(),
             },
             TokenEntry {
                 address: UNI_ADDRESS,
@@ -247,6 +442,7 @@ impl TokenRegistry {
                 name: "Uniswap".to_string(),
                 decimals: 18,
                 chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+                source: TokenSource::builtin(),
             },
         ];
 
@@ -290,6 +486,88 @@ impl TokenRegistry {
         Ok(())
     }
 
+    /// Refresh the token list and report what changed since the previous refresh.
+    ///
+    /// Compares against the snapshot of the last refresh, whether a lookup, the
+    /// background task or an earlier diff triggered it. Without one (the first
+    /// refresh since startup) nothing is reported as added, removed or changed.
+    pub async fn refresh_with_diff(&self) -> Result<TokenListDiff> {
+        let _permit =
+            self.refresh_semaphore.acquire().await.map_err(|_| {
+                AppError::Transport("Failed to acquire refresh semaphore".to_string())
+            })?;
+
+        let previous = self.cache.read().await.list_snapshot.clone();
+        self.refresh().await?;
+        let cache_guard = self.cache.read().await;
+        let current = cache_guard.list_snapshot.as_ref().ok_or_else(|| {
+            AppError::Transport("Token list refresh kept no snapshot".to_string())
+        })?;
+
+        Ok(diff_snapshots(previous.as_ref(), current))
+    }
+
+    /// Refresh the token cache while holding the refresh semaphore.
+    async fn refresh_exclusive(&self) -> Result<usize> {
+        let _permit =
+            self.refresh_semaphore.acquire().await.map_err(|_| {
+                AppError::Transport("Failed to acquire refresh semaphore".to_string())
+            })?;
+        self.refresh().await
+    }
+
+    /// Spawn a background task that refreshes the cache every `interval`.
+    ///
+    /// Keeps the cache warm so no lookup pays the refresh latency after the TTL
+    /// expires. Refreshes hold the refresh semaphore, failures are logged and
+    /// retried on the next tick, and the task exits once the registry is dropped.
+    pub fn spawn_auto_refresh(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let registry = Arc::downgrade(self);
+        info!("Auto-refreshing token list every {:?}", interval);
+
+        tokio::spawn(async move {
+            // Skip the immediate first tick: the first lookup populates the cache
+            let start = tokio::time::Instant::now() + interval;
+            let mut ticker = tokio::time::interval_at(start, interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+
+                let Some(registry) = registry.upgrade() else {
+                    break;
+                };
+                if let Err(e) = registry.refresh_exclusive().await {
+                    warn!("Background token list refresh failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Spawn a background task that loads the token list once, right away.
+    ///
+    /// Moves the download off the first lookup's critical path. The refresh is
+    /// skipped if a lookup already loaded the list, and a failure is logged and
+    /// left for the next lookup to retry.
+    pub fn spawn_prewarm(self: &Arc<Self>) -> JoinHandle<()> {
+        let registry = Arc::downgrade(self);
+        info!("Prewarming token list in the background");
+
+        tokio::spawn(async move {
+            let Some(registry) = registry.upgrade() else {
+                return;
+            };
+            let started = Instant::now();
+            match registry.ensure_fresh().await {
+                Ok(()) => {
+                    let (tokens, _) = registry.cache_stats().await;
+                    info!("Prewarmed token list with {} tokens in {:?}", tokens, started.elapsed());
+                }
+                Err(e) => warn!("Token list prewarm failed, the first lookup will retry: {}", e),
+            }
+        })
+    }
+
     /// Refresh the token cache from remote source.
     ///
     /// # Returns
@@ -311,49 +589,71 @@ impl TokenRegistry {
             )));
         }
 
-        let token_list: TokenListResponse = response
-            .json()
-            .await
+        let body = read_capped_body(response, MAX_TOKEN_LIST_BYTES).await?;
+        let token_list: TokenListResponse = serde_json::from_slice(&body)
             .map_err(|e| AppError::Parse(format!("Failed to parse token list: {}", e)))?;
+        drop(body);
+        let source = TokenSource::token_list(&self.token_list_url, &token_list);
+        let list_name = token_list.name.clone();
+        let list_version = token_list.version;
 
         let mut cache_guard = self.cache.write().await;
-        let mut count = 0;
+        let count =
+            cache_guard.load_token_list(token_list, &source, self.chain_id, self.max_tokens);
 
-        for token in token_list.tokens {
-            // Only include tokens for our target chain
-            if token.chain_id != self.chain_id {
-                continue;
-            }
+        cache_guard.last_updated = Some(Instant::now());
+        info!(
+            "Loaded {} tokens for chain {} from '{}' (version {})",
+            count,
+            self.chain_id,
+            list_name,
+            list_version.map(|v| v.to_string()).unwrap_or_else(|| "unknown".to_string())
+        );
 
-            // Parse address
-            let address = match token.address.parse::<Address>() {
-                Ok(addr) => addr,
-                Err(e) => {
-                    warn!("Invalid token address {}: {}", token.address, e);
-                    continue;
-                }
-            };
+        Ok(count)
+    }
 
-            let entry = TokenEntry {
-                address,
-                symbol: token.symbol.clone(),
-                name: token.name,
-                decimals: token.decimals,
-                chain_id: token.chain_id,
-            };
+    /// Look a token up in the cache, refreshing the token list on a miss.
+    ///
+    /// Another task's refresh (a lookup, the background refresh or a diff) may
+    /// land just after ours, so a token still missing, or missing because our
+    /// refresh failed, is looked up again up to [`MISS_RETRY_ATTEMPTS`] times,
+    /// each after a short delay and any refresh in flight.
+    async fn lookup_with_refresh(
+        &self,
+        what: &str,
+        find: impl Fn(&CacheState) -> Option<TokenEntry>,
+    ) -> Option<TokenEntry> {
+        // First, ensure cache is fresh
+        if let Err(e) = self.ensure_fresh().await {
+            warn!("Failed to refresh token list: {}", e);
+        }
 
-            let symbol_key = (token.chain_id, token.symbol.to_uppercase());
-            let address_key = (token.chain_id, address);
+        if let Some(entry) = find(&*self.cache.read().await) {
+            return Some(entry);
+        }
 
-            cache_guard.by_symbol.insert(symbol_key, entry.clone());
-            cache_guard.by_address.insert(address_key, entry);
-            count += 1;
+        // Not found - force refresh and retry
+        info!("{} not found in cache, forcing refresh", what);
+        if let Err(e) = self.refresh().await {
+            warn!("Failed to refresh token list on cache miss: {}", e);
         }
 
-        cache_guard.last_updated = Some(Instant::now());
-        info!("Loaded {} tokens for chain {}", count, self.chain_id);
+        if let Some(entry) = find(&*self.cache.read().await) {
+            return Some(entry);
+        }
 
-        Ok(count)
+        for attempt in 1..=MISS_RETRY_ATTEMPTS {
+            tokio::time::sleep(MISS_RETRY_DELAY).await;
+            // Wait out a refresh in flight on another task
+            drop(self.refresh_semaphore.acquire().await);
+
+            if let Some(entry) = find(&*self.cache.read().await) {
+                info!("{} found after retry {}", what, attempt);
+                return Some(entry);
+            }
+        }
+        None
     }
 
     /// Get address for a symbol (convenience method).
@@ -386,65 +686,157 @@ impl TokenRegistry {
 #[async_trait]
 impl TokenRegistryTrait for TokenRegistry {
     async fn resolve_symbol(&self, symbol: &str) -> Option<TokenEntry> {
-        // First, ensure cache is fresh
-        if let Err(e) = self.ensure_fresh().await {
-            warn!("Failed to refresh token list: {}", e);
-        }
+        let key = (self.chain_id, normalize_symbol(symbol));
+        self.lookup_with_refresh(&format!("Token '{}'", symbol), |cache| {
+            cache.by_symbol.get(&key).cloned()
+        })
+        .await
+    }
 
-        let key = (self.chain_id, symbol.to_uppercase());
+    async fn lookup_address(&self, address: Address) -> Option<TokenEntry> {
+        let key = (self.chain_id, address);
+        self.lookup_with_refresh(&format!("Token address {:?}", address), |cache| {
+            cache.by_address.get(&key).cloned()
+        })
+        .await
+    }
 
-        // Try to find in cache
-        {
-            let cache_guard = self.cache.read().await;
-            if let Some(entry) = cache_guard.by_symbol.get(&key) {
-                return Some(entry.clone());
-            }
-        }
+    async fn token_list_diff(&self) -> Result<TokenListDiff> {
+        self.refresh_with_diff().await
+    }
+}
 
-        // Not found - force refresh and retry
-        info!("Token '{}' not found in cache, forcing refresh", symbol);
-        if let Err(e) = self.refresh().await {
-            warn!("Failed to refresh token list on cache miss: {}", e);
-            return None;
+impl From<&TokenEntry> for ListedToken {
+    fn from(entry: &TokenEntry) -> Self {
+        Self {
+            address: format!("{:?}", entry.address),
+            symbol: entry.symbol.clone(),
+            name: entry.name.clone(),
+            decimals: entry.decimals,
         }
-
-        // Retry after refresh
-        let cache_guard = self.cache.read().await;
-        cache_guard.by_symbol.get(&key).cloned()
     }
+}
 
-    async fn lookup_address(&self, address: Address) -> Option<TokenEntry> {
-        // First, ensure cache is fresh
-        if let Err(e) = self.ensure_fresh().await {
-            warn!("Failed to refresh token list: {}", e);
+/// Diff two token list snapshots. Each list is sorted by symbol, then address.
+fn diff_snapshots(previous: Option<&ListSnapshot>, current: &ListSnapshot) -> TokenListDiff {
+    let mut diff = TokenListDiff {
+        source: current.source.origin.clone(),
+        list_name: current.source.list_name.clone(),
+        list_version: current.source.list_version.map(|v| v.to_string()),
+        token_count: current.tokens.len(),
+        has_previous_snapshot: previous.is_some(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        message: None,
+    };
+
+    let Some(previous) = previous else {
+        diff.message = Some(
+            "No previous snapshot to compare against; this refresh is the baseline for the next \
+             diff."
+                .to_string(),
+        );
+        return diff;
+    };
+
+    let mut added: Vec<&TokenEntry> = Vec::new();
+    let mut changed: Vec<(&TokenEntry, &TokenEntry)> = Vec::new();
+    for (address, entry) in &current.tokens {
+        match previous.tokens.get(address) {
+            None => added.push(entry),
+            Some(old) if old.symbol != entry.symbol || old.decimals != entry.decimals => {
+                changed.push((old, entry))
+            }
+            Some(_) => {}
         }
+    }
+    let mut removed: Vec<&TokenEntry> = previous
+        .tokens
+        .iter()
+        .filter(|(address, _)| !current.tokens.contains_key(*address))
+        .map(|(_, entry)| entry)
+        .collect();
+
+    let by_symbol = |a: &&TokenEntry, b: &&TokenEntry| {
+        a.symbol.cmp(&b.symbol).then_with(|| a.address.cmp(&b.address))
+    };
+    added.sort_by(by_symbol);
+    removed.sort_by(by_symbol);
+    changed.sort_by(|(_, a), (_, b)| by_symbol(a, b));
+
+    diff.added = added.into_iter().map(ListedToken::from).collect();
+    diff.removed = removed.into_iter().map(ListedToken::from).collect();
+    diff.changed = changed
+        .into_iter()
+        .map(|(old, new)| ChangedToken {
+            address: format!("{:?}", new.address),
+            symbol: new.symbol.clone(),
+            decimals: new.decimals,
+            previous_symbol: (old.symbol != new.symbol).then(|| old.symbol.clone()),
+            previous_decimals: (old.decimals != new.decimals).then_some(old.decimals),
+        })
+        .collect();
+    diff
+}
 
-        let key = (self.chain_id, address);
+/// Characters that render as nothing and are dropped from symbols.
+const ZERO_WIDTH_CHARS: [char; 5] = ['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
 
-        // Try to find in cache
-        {
-            let cache_guard = self.cache.read().await;
-            if let Some(entry) = cache_guard.by_address.get(&key) {
-                return Some(entry.clone());
-            }
-        }
+/// Normalize a token symbol for lookup.
+///
+/// Applies NFKC, so fullwidth and other compatibility forms (e.g. "ＵＳＤＣ") become
+/// their plain equivalents, drops zero-width characters, trims whitespace and
+/// uppercases. Lookalikes from other scripts, such as Cyrillic letters, are kept.
+pub fn normalize_symbol(symbol: &str) -> String {
+    ComposingNormalizerBorrowed::new_nfkc()
+        .normalize(symbol)
+        .chars()
+        .filter(|c| !ZERO_WIDTH_CHARS.contains(c))
+        .collect::<String>()
+        .trim()
+        .to_uppercase()
+}
 
-        // Not found - force refresh and retry
-        info!("Token address {:?} not found in cache, forcing refresh", address);
-        if let Err(e) = self.refresh().await {
-            warn!("Failed to refresh token list on cache miss: {}", e);
-            return None;
-        }
+/// Build the HTTP client for token list fetches, routed through `proxy`.
+fn http_client(proxy: &HttpProxy) -> Result<reqwest::Client> {
+    proxy
+        .apply(reqwest::Client::builder().timeout(Duration::from_secs(30)))?
+        .build()
+        .map_err(|e| AppError::Transport(format!("Failed to create HTTP client: {}", e)))
+}
 
-        // Retry after refresh
-        let cache_guard = self.cache.read().await;
-        cache_guard.by_address.get(&key).cloned()
+/// Read a response body, failing once it exceeds `limit` bytes.
+///
+/// Checks the declared `Content-Length` up front, then counts bytes as they
+/// arrive so chunked responses cannot grow past the limit either.
+async fn read_capped_body(mut response: reqwest::Response, limit: usize) -> Result<Vec<u8>> {
+    let too_large =
+        || AppError::Transport(format!("Token list response exceeds the {} byte limit", limit));
+
+    if response.content_length().is_some_and(|len| len > limit as u64) {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AppError::Transport(format!("Failed to read token list: {}", e)))?
+    {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
     }
+
+    Ok(body)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ethereum::SEPOLIA_CHAIN_ID;
 
     // ============================================================================
     // CacheState Tests
@@ -484,6 +876,7 @@ mod tests {
             name: "Wrapped Ether".to_string(),
             decimals: 18,
             chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+            source: TokenSource::builtin(),
         };
 
         state.insert(entry.clone());
@@ -507,6 +900,7 @@ mod tests {
             name: "Wrapped Ether".to_string(),
             decimals: 18,
             chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+            source: TokenSource::builtin(),
         };
 
         let usdc = TokenEntry {
@@ -515,6 +909,7 @@ mod tests {
             name: "USD Coin".to_string(),
             decimals: 6,
             chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+            source: TokenSource::builtin(),
         };
 
         state.insert(weth);
@@ -534,6 +929,7 @@ mod tests {
             name: "USD Coin".to_string(),
             decimals: 6,
             chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+            source: TokenSource::builtin(),
         };
 
         state.insert(entry);
@@ -543,6 +939,115 @@ mod tests {
         assert!(state.by_symbol.contains_key(&key));
     }
 
+    fn synthetic_token_list(count: usize) -> TokenListResponse {
+        let tokens = (0..count)
+            .map(|i| TokenListToken {
+                chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+                address: Address::with_last_byte((i % 256) as u8)
+                    .create((i / 256) as u64)
+                    .to_string(),
+                symbol: format!("TKN{}", i),
+                name: format!("Token {}", i),
+                decimals: 18,
+                logo_uri: None,
+            })
+            .collect();
+        TokenListResponse { name: "Synthetic".to_string(), version: None, tokens }
+    }
+
+    #[test]
+    fn test_load_token_list_stops_at_max_tokens() {
+        let mut state = CacheState::new();
+        let list = synthetic_token_list(1_000);
+        let source = TokenSource::token_list("https://example.com", &list);
+
+        let count = state.load_token_list(list, &source, ETHEREUM_MAINNET_CHAIN_ID, 100);
+
+        assert_eq!(count, 100);
+        assert_eq!(state.by_symbol.len(), 100);
+        assert_eq!(state.by_address.len(), 100);
+        assert!(state.by_symbol.contains_key(&(ETHEREUM_MAINNET_CHAIN_ID, "TKN99".to_string())));
+        assert!(!state.by_symbol.contains_key(&(ETHEREUM_MAINNET_CHAIN_ID, "TKN100".to_string())));
+    }
+
+    #[test]
+    fn test_load_token_list_skips_other_chains_and_bad_addresses() {
+        let mut state = CacheState::new();
+        let mut list = synthetic_token_list(3);
+        list.tokens[0].chain_id = 11155111;
+        list.tokens[1].address = "not-an-address".to_string();
+        let source = TokenSource::token_list("https://example.com", &list);
+
+        let count = state.load_token_list(list, &source, ETHEREUM_MAINNET_CHAIN_ID, 2);
+
+        // Skipped tokens do not count towards the cap
+        assert_eq!(count, 1);
+        assert!(state.by_symbol.contains_key(&(ETHEREUM_MAINNET_CHAIN_ID, "TKN2".to_string())));
+    }
+
+    fn snapshot(tokens: &[(u8, &str, u8)]) -> ListSnapshot {
+        let tokens = tokens
+            .iter()
+            .map(|&(byte, symbol, decimals)| {
+                let entry = TokenEntry {
+                    address: Address::repeat_byte(byte),
+                    symbol: symbol.to_string(),
+                    name: symbol.to_string(),
+                    decimals,
+                    chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+                    source: TokenSource::builtin(),
+                };
+                (entry.address, entry)
+            })
+            .collect();
+        ListSnapshot { source: TokenSource::builtin(), tokens }
+    }
+
+    #[test]
+    fn test_diff_snapshots_reports_added_removed_and_changed() {
+        let previous = snapshot(&[(1, "AAA", 18), (2, "BBB", 18), (3, "CCC", 6)]);
+        let current = snapshot(&[(1, "AAA", 18), (3, "CCC2", 8), (5, "ZZZ", 18), (4, "EEE", 18)]);
+
+        let diff = diff_snapshots(Some(&previous), &current);
+
+        assert!(diff.has_previous_snapshot);
+        assert_eq!(diff.token_count, 4);
+        let added: Vec<_> = diff.added.iter().map(|t| t.symbol.as_str()).collect();
+        assert_eq!(added, ["EEE", "ZZZ"]);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].symbol, "BBB");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].symbol, "CCC2");
+        assert_eq!(diff.changed[0].previous_symbol.as_deref(), Some("CCC"));
+        assert_eq!(diff.changed[0].previous_decimals, Some(6));
+        assert!(diff.message.is_none());
+    }
+
+    #[test]
+    fn test_diff_snapshots_without_previous_snapshot() {
+        let current = snapshot(&[(1, "AAA", 18)]);
+
+        let diff = diff_snapshots(None, &current);
+
+        assert!(!diff.has_previous_snapshot);
+        assert_eq!(diff.token_count, 1);
+        assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty());
+        assert!(diff.message.unwrap().contains("baseline"));
+    }
+
+    #[test]
+    fn test_load_token_list_replaces_snapshot() {
+        let mut state = CacheState::new();
+        let list = synthetic_token_list(5);
+        let source = TokenSource::token_list("https://example.com", &list);
+
+        state.load_token_list(list, &source, ETHEREUM_MAINNET_CHAIN_ID, 3);
+
+        let snapshot = state.list_snapshot.unwrap();
+        assert_eq!(snapshot.tokens.len(), 3);
+        assert_eq!(snapshot.source, source);
+    }
+
     // ============================================================================
     // TokenEntry Tests
     // ============================================================================
@@ -555,6 +1060,7 @@ mod tests {
             name: "Wrapped Ether".to_string(),
             decimals: 18,
             chain_id: 1,
+            source: TokenSource::builtin(),
         };
 
         assert_eq!(entry.symbol, "WETH");
@@ -570,6 +1076,7 @@ mod tests {
             name: "USD Coin".to_string(),
             decimals: 6,
             chain_id: 1,
+            source: TokenSource::builtin(),
         };
 
         let cloned = entry.clone();
@@ -585,6 +1092,7 @@ mod tests {
             name: "Wrapped BTC".to_string(),
             decimals: 8,
             chain_id: 1,
+            source: TokenSource::builtin(),
         };
 
         let debug_str = format!("{:?}", entry);
@@ -657,6 +1165,260 @@ mod tests {
         let response: TokenListResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.name, "Uniswap Labs Default");
         assert_eq!(response.tokens.len(), 2);
+        assert!(response.version.is_none());
+    }
+
+    #[test]
+    fn test_token_list_response_with_version() {
+        let json = r#"{
+            "name": "Uniswap Labs Default",
+            "version": { "major": 12, "minor": 3, "patch": 0 },
+            "tokens": []
+        }"#;
+
+        let response: TokenListResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.version, Some(TokenListVersion { major: 12, minor: 3, patch: 0 }));
+        assert_eq!(response.version.unwrap().to_string(), "12.3.0");
+    }
+
+    // ============================================================================
+    // TokenSource / Provenance Tests
+    // ============================================================================
+
+    #[test]
+    fn test_token_source_from_list() {
+        let list = TokenListResponse {
+            name: "Uniswap Labs Default".to_string(),
+            version: Some(TokenListVersion { major: 1, minor: 2, patch: 3 }),
+            tokens: vec![],
+        };
+
+        let source = TokenSource::token_list(UNISWAP_TOKEN_LIST_URL, &list);
+        assert_eq!(source.origin, UNISWAP_TOKEN_LIST_URL);
+        assert_eq!(source.list_name.as_deref(), Some("Uniswap Labs Default"));
+        assert_eq!(source.list_version.unwrap().to_string(), "1.2.3");
+    }
+
+    #[test]
+    fn test_token_source_is_curated() {
+        assert!(TokenSource::builtin().is_curated());
+
+        let list = TokenListResponse { name: "List".to_string(), version: None, tokens: vec![] };
+        assert!(TokenSource::token_list(UNISWAP_TOKEN_LIST_URL, &list).is_curated());
+        assert!(!TokenSource::token_list(ONE_INCH_TOKEN_LIST_URL, &list).is_curated());
+    }
+
+    #[test]
+    fn test_token_entry_provenance() {
+        let entry = TokenEntry {
+            address: USDC_ADDRESS,
+            symbol: "USDC".to_string(),
+            name: "USD Coin".to_string(),
+            decimals: 6,
+            chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+            source: TokenSource {
+                origin: UNISWAP_TOKEN_LIST_URL.to_string(),
+                list_name: Some("Uniswap Labs Default".to_string()),
+                list_version: Some(TokenListVersion { major: 12, minor: 0, patch: 1 }),
+            },
+        };
+
+        let provenance = entry.provenance("usdc");
+        assert_eq!(provenance.symbol, "usdc");
+        assert_eq!(provenance.address, format!("{:?}", USDC_ADDRESS));
+        assert_eq!(provenance.chain_id, ETHEREUM_MAINNET_CHAIN_ID);
+        assert_eq!(provenance.source, UNISWAP_TOKEN_LIST_URL);
+        assert_eq!(provenance.list_version.as_deref(), Some("12.0.1"));
+    }
+
+    #[test]
+    fn test_builtin_provenance() {
+        let entry = TokenEntry {
+            address: WETH_ADDRESS,
+            symbol: "WETH".to_string(),
+            name: "Wrapped Ether".to_string(),
+            decimals: 18,
+            chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+            source: TokenSource::builtin(),
+        };
+
+        let provenance = entry.provenance("WETH");
+        assert_eq!(provenance.source, BUILTIN_TOKEN_SOURCE);
+        assert!(provenance.list_name.is_none());
+        assert!(provenance.list_version.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_uses_explicit_proxy() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let proxy = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_url = format!("http://{}", proxy.local_addr().unwrap());
+        let proxied = tokio::spawn(async move {
+            let (mut stream, _) = proxy.accept().await.unwrap();
+            let mut request = vec![0u8; 4096];
+            let n = stream.read(&mut request).await.unwrap();
+            let body = r#"{"name":"Proxied","tokens":[]}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..n]).into_owned()
+        });
+
+        // The list host does not resolve, so only the proxy can serve it
+        let registry = TokenRegistry::with_options(
+            ETHEREUM_MAINNET_CHAIN_ID,
+            "http://tokens.invalid/list.json".to_string(),
+            DEFAULT_CACHE_TTL,
+        )
+        .unwrap()
+        .with_http_proxy(&HttpProxy::Url(proxy_url))
+        .unwrap();
+
+        assert_eq!(registry.refresh().await.unwrap(), 0);
+        let request = proxied.await.unwrap();
+        assert!(request.starts_with("GET http://tokens.invalid/list.json HTTP/1.1"));
+    }
+
+    #[test]
+    fn test_normalize_symbol() {
+        assert_eq!(normalize_symbol("  usdc\n"), "USDC");
+        assert_eq!(normalize_symbol("WE\u{200B}TH\u{FEFF}"), "WETH");
+        // Fullwidth forms and the non-breaking space are compatibility characters
+        assert_eq!(normalize_symbol("\u{00A0}ＵＳＤＣ"), "USDC");
+        assert_eq!(normalize_symbol("\u{1D5E8}\u{1D5E1}\u{1D5DC}"), "UNI");
+        // Cyrillic "А" is a different letter, not a compatibility form
+        assert_ne!(normalize_symbol("\u{0410}MPL"), "AMPL");
+    }
+
+    #[tokio::test]
+    async fn test_prewarm_failure_keeps_fallback_tokens() {
+        // Nothing listens on the discard port, so the fetch fails
+        let registry = Arc::new(
+            TokenRegistry::with_options(
+                ETHEREUM_MAINNET_CHAIN_ID,
+                "http://127.0.0.1:9/tokens.json".to_string(),
+                DEFAULT_CACHE_TTL,
+            )
+            .unwrap(),
+        );
+
+        registry.spawn_prewarm().await.unwrap();
+
+        let cache = registry.cache.read().await;
+        assert!(cache.last_updated.is_none());
+        assert!(cache.by_symbol.contains_key(&(ETHEREUM_MAINNET_CHAIN_ID, "WETH".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_prewarm_skips_fresh_cache() {
+        let registry = Arc::new(
+            TokenRegistry::with_options(
+                ETHEREUM_MAINNET_CHAIN_ID,
+                "http://127.0.0.1:9/tokens.json".to_string(),
+                DEFAULT_CACHE_TTL,
+            )
+            .unwrap(),
+        );
+        let loaded = Instant::now();
+        registry.cache.write().await.last_updated = Some(loaded);
+
+        registry.spawn_prewarm().await.unwrap();
+
+        assert_eq!(registry.cache.read().await.last_updated, Some(loaded));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_symbol_normalizes_input() {
+        let registry = TokenRegistry::with_options(
+            ETHEREUM_MAINNET_CHAIN_ID,
+            UNISWAP_TOKEN_LIST_URL.to_string(),
+            DEFAULT_CACHE_TTL,
+        )
+        .unwrap();
+        // Mark the built-in tokens fresh so lookups do not fetch the list
+        registry.cache.write().await.last_updated = Some(Instant::now());
+
+        for symbol in [" weth ", "\tWETH\u{200B}", "ＷＥＴＨ", "\u{FEFF}wEtH\u{00A0}"] {
+            let entry = registry.resolve_symbol(symbol).await;
+            assert_eq!(entry.map(|e| e.address), Some(WETH_ADDRESS), "{:?}", symbol);
+        }
+    }
+
+    /// A registry whose list fetches fail, with an empty but fresh cache.
+    async fn unreachable_registry() -> TokenRegistry {
+        // Nothing listens on the discard port; Sepolia has no built-in tokens
+        let registry = TokenRegistry::with_options(
+            SEPOLIA_CHAIN_ID,
+            "http://127.0.0.1:9/tokens.json".to_string(),
+            DEFAULT_CACHE_TTL,
+        )
+        .unwrap();
+        registry.cache.write().await.last_updated = Some(Instant::now());
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_resolve_symbol_picks_up_concurrent_refresh() {
+        let registry = Arc::new(unreachable_registry().await);
+
+        // Another task is mid-refresh: it holds the semaphore
+        let permit = registry.refresh_semaphore.acquire().await.unwrap();
+        let lookup = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.resolve_symbol("NEW").await }
+        });
+
+        // Our own forced refresh fails; the concurrent one then lists the token
+        tokio::time::sleep(MISS_RETRY_DELAY * 3).await;
+        let token = Address::repeat_byte(0x42);
+        registry.cache.write().await.insert(TokenEntry {
+            address: token,
+            symbol: "NEW".to_string(),
+            name: "Newly Listed".to_string(),
+            decimals: 18,
+            chain_id: SEPOLIA_CHAIN_ID,
+            source: TokenSource::builtin(),
+        });
+        drop(permit);
+
+        assert_eq!(lookup.await.unwrap().map(|e| e.address), Some(token));
+        assert!(registry.lookup_address(token).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_symbol_miss_is_bounded() {
+        let registry = unreachable_registry().await;
+
+        let started = Instant::now();
+        assert!(registry.resolve_symbol("MISSING").await.is_none());
+        assert!(started.elapsed() >= MISS_RETRY_DELAY * MISS_RETRY_ATTEMPTS);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_token_entry_non_canonical() {
+        let impostor = Address::repeat_byte(0x42);
+        let mut entry = TokenEntry {
+            address: impostor,
+            symbol: "usdc".to_string(),
+            name: "USD Coin".to_string(),
+            decimals: 6,
+            chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+            source: TokenSource::builtin(),
+        };
+        assert_eq!(entry.non_canonical(), Some(USDC_ADDRESS));
+
+        entry.address = USDC_ADDRESS;
+        assert_eq!(entry.non_canonical(), None);
+
+        // Symbols outside the core set have no canonical address
+        entry.address = impostor;
+        entry.symbol = "PEPE".to_string();
+        assert_eq!(entry.non_canonical(), None);
     }
 
     // ============================================================================
@@ -775,4 +1537,46 @@ mod tests {
         // Age should be None (fallback doesn't set last_updated)
         assert!(age.is_none());
     }
+
+    #[tokio::test]
+    async fn test_auto_refresh_survives_failures() {
+        let registry = Arc::new(
+            TokenRegistry::with_options(
+                ETHEREUM_MAINNET_CHAIN_ID,
+                "http://127.0.0.1:1/tokens.json".to_string(),
+                DEFAULT_CACHE_TTL,
+            )
+            .unwrap(),
+        );
+
+        let handle = registry.spawn_auto_refresh(Duration::from_millis(10));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Failed refreshes are logged, not fatal
+        assert!(!handle.is_finished());
+        // Fallback tokens are untouched
+        assert!(registry.get_address("WETH").await.is_some());
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_auto_refresh_stops_when_registry_dropped() {
+        let registry = Arc::new(
+            TokenRegistry::with_options(
+                ETHEREUM_MAINNET_CHAIN_ID,
+                "http://127.0.0.1:1/tokens.json".to_string(),
+                DEFAULT_CACHE_TTL,
+            )
+            .unwrap(),
+        );
+
+        let handle = registry.spawn_auto_refresh(Duration::from_millis(10));
+        drop(registry);
+
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("auto-refresh task should exit after the registry is dropped")
+            .unwrap();
+    }
 }
@@ -0,0 +1,363 @@
+//! Token verification service.
+//!
+//! Performs a basic ERC20 conformance screen on an arbitrary address.
+
+use alloy::primitives::{Address, U256};
+use std::sync::Arc;
+
+use crate::{
+    error::Result,
+    ethereum::{
+        contracts::{
+            compliance::{compliance_interface, ComplianceInterface, IFiatToken, ITetherToken},
+            erc20::IERC20,
+        },
+        EthereumClient, EIP1967_IMPLEMENTATION_SLOT,
+    },
+    types::{ComplianceStatus, TokenVerificationReport, VerificationCheck, VerifiedTokenMetadata},
+};
+
+/// Service for verifying that an address is a well-behaved ERC20 token.
+#[derive(Clone)]
+pub struct TokenVerificationService {
+    client: Arc<EthereumClient>,
+}
+
+impl TokenVerificationService {
+    /// Create a new token verification service.
+    pub fn new(client: Arc<EthereumClient>) -> Self {
+        Self { client }
+    }
+
+    /// Verify that a token address has contract code and answers core ERC20 calls.
+    ///
+    /// Checks performed:
+    /// - Contract code is deployed (`eth_getCode` is non-empty)
+    /// - `decimals()`, `symbol()`, `name()` and `totalSupply()` succeed
+    /// - `balanceOf(address(0))` succeeds
+    ///
+    /// Also reads the EIP-1967 implementation slot to flag upgradeable proxies, and,
+    /// for known tokens with pause and blacklist controls (USDC, USDT), whether the
+    /// token is paused and whether `holder` is blacklisted.
+    /// Individual call failures are reported in the result rather than returned as errors.
+    /// An address without code has no token metadata.
+    pub async fn verify_token(
+        &self,
+        token: Address,
+        holder: Option<Address>,
+    ) -> Result<TokenVerificationReport> {
+        tracing::debug!(token = %token, "Verifying token contract");
+
+        let code = self.client.get_code(token).await?;
+        let is_contract = !code.is_empty();
+
+        let (checks, metadata) = if is_contract {
+            let (checks, metadata) = self.run_erc20_checks(token).await;
+            (checks, Some(metadata))
+        } else {
+            (vec![VerificationCheck::fail("code", "No contract code at address (EOA)")], None)
+        };
+
+        let implementation =
+            if is_contract { self.get_proxy_implementation(token).await } else { None };
+
+        let compliance = match compliance_interface(token) {
+            Some(interface) if is_contract => {
+                Some(self.get_compliance_status(token, interface, holder).await)
+            }
+            _ => None,
+        };
+
+        let mut warnings = Self::collect_warnings(is_contract, &checks);
+        if let Some(implementation) = implementation {
+            warnings.push(format!(
+                "Token is an upgradeable proxy (EIP-1967, implementation {:?}) - its logic can be changed by the admin",
+                implementation
+            ));
+        }
+        if let Some(compliance) = &compliance {
+            warnings.extend(Self::compliance_warnings(compliance));
+        }
+
+        Ok(TokenVerificationReport {
+            token: metadata,
+            is_contract,
+            is_erc20: is_contract && checks.iter().all(|c| c.passed),
+            is_proxy: implementation.is_some(),
+            proxy_implementation: implementation.map(|a| format!("{:?}", a)),
+            compliance,
+            checks,
+            warnings,
+        })
+    }
+
+    /// Read the EIP-1967 implementation slot, returning the implementation address if set.
+    ///
+    /// Best-effort: a failed storage read is treated as "not a proxy".
+    async fn get_proxy_implementation(&self, token: Address) -> Option<Address> {
+        match self.client.get_storage_at(token, EIP1967_IMPLEMENTATION_SLOT).await {
+            Ok(value) => Self::implementation_from_slot(value),
+            Err(e) => {
+                tracing::debug!(token = %token, error = %e, "Failed to read EIP-1967 slot");
+                None
+            }
+        }
+    }
+
+    /// Extract the implementation address from a raw slot value (zero means unset).
+    fn implementation_from_slot(value: U256) -> Option<Address> {
+        let address = Address::from_word(value.to_be_bytes::<32>().into());
+        (address != Address::ZERO).then_some(address)
+    }
+
+    /// Read the pause status, and the blacklist status of `holder` if given.
+    ///
+    /// Best-effort: a failed call leaves the corresponding field unset.
+    async fn get_compliance_status(
+        &self,
+        token: Address,
+        interface: ComplianceInterface,
+        holder: Option<Address>,
+    ) -> ComplianceStatus {
+        let provider = self.client.provider().clone();
+        let (name, paused, blacklisted) = match interface {
+            ComplianceInterface::FiatToken => {
+                let contract = IFiatToken::new(token, provider);
+                let blacklisted = match holder {
+                    Some(holder) => contract.isBlacklisted(holder).call().await.ok(),
+                    None => None,
+                };
+                ("fiat_token", contract.paused().call().await.ok(), blacklisted)
+            }
+            ComplianceInterface::Tether => {
+                let contract = ITetherToken::new(token, provider);
+                let blacklisted = match holder {
+                    Some(holder) => contract.getBlackListStatus(holder).call().await.ok(),
+                    None => None,
+                };
+                ("tether", contract.paused().call().await.ok(), blacklisted)
+            }
+        };
+
+        ComplianceStatus {
+            interface: name.to_string(),
+            paused,
+            address: holder.map(|holder| format!("{:?}", holder)),
+            blacklisted,
+        }
+    }
+
+    /// Build warnings for a paused token or a blacklisted address.
+    fn compliance_warnings(status: &ComplianceStatus) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if status.paused == Some(true) {
+            warnings.push("Token is paused - transfers and swaps will revert".to_string());
+        }
+        if let (Some(true), Some(address)) = (status.blacklisted, &status.address) {
+            warnings.push(format!(
+                "Address {} is blacklisted by the token issuer - transfers to or from it will revert",
+                address
+            ));
+        }
+        warnings
+    }
+
+    /// Call each core ERC20 function and record whether it succeeded.
+    ///
+    /// Each call is made once; its result is both a check and token metadata.
+    async fn run_erc20_checks(
+        &self,
+        token: Address,
+    ) -> (Vec<VerificationCheck>, VerifiedTokenMetadata) {
+        let contract = IERC20::new(token, self.client.provider().clone());
+
+        let decimals_result = contract.decimals().call().await;
+        let symbol_result = contract.symbol().call().await;
+        let metadata = VerifiedTokenMetadata::new(
+            token,
+            symbol_result.as_ref().ok().cloned(),
+            decimals_result.as_ref().ok().copied(),
+        );
+
+        let decimals = match decimals_result {
+            Ok(v) => VerificationCheck::pass("decimals()", v),
+            Err(e) => VerificationCheck::fail("decimals()", e),
+        };
+        let symbol = match symbol_result {
+            Ok(v) => VerificationCheck::pass("symbol()", v),
+            Err(e) => VerificationCheck::fail("symbol()", e),
+        };
+        let name = match contract.name().call().await {
+            Ok(v) => VerificationCheck::pass("name()", v),
+            Err(e) => VerificationCheck::fail("name()", e),
+        };
+        let total_supply = match contract.totalSupply().call().await {
+            Ok(v) => VerificationCheck::pass("totalSupply()", v),
+            Err(e) => VerificationCheck::fail("totalSupply()", e),
+        };
+        let balance_of = match contract.balanceOf(Address::ZERO).call().await {
+            Ok(v) => VerificationCheck::pass("balanceOf(address(0))", v),
+            Err(e) => VerificationCheck::fail("balanceOf(address(0))", e),
+        };
+
+        (vec![decimals, symbol, name, total_supply, balance_of], metadata)
+    }
+
+    /// Build warnings for an EOA or for each failed check.
+    fn collect_warnings(is_contract: bool, checks: &[VerificationCheck]) -> Vec<String> {
+        if !is_contract {
+            return vec!["Address has no contract code (EOA) - this is not a token".to_string()];
+        }
+
+        checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| format!("{} failed - token may be non-standard or malicious", c.name))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{primitives::Bytes, sol_types::SolValue, transports::mock::Asserter};
+
+    #[tokio::test]
+    async fn test_verify_token_reads_metadata_from_checks() {
+        let asserter = Asserter::new();
+        let service =
+            TokenVerificationService::new(Arc::new(EthereumClient::mocked(asserter.clone())));
+        let token = Address::repeat_byte(0x11);
+
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80]));
+        asserter.push_success(&Bytes::from(U256::from(6).abi_encode()));
+        asserter.push_success(&Bytes::from("TKN".to_string().abi_encode()));
+        asserter.push_success(&Bytes::from("Token".to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(1000).abi_encode()));
+        asserter.push_success(&Bytes::from(U256::ZERO.abi_encode()));
+        asserter.push_success(&U256::ZERO);
+
+        let report = service.verify_token(token, None).await.unwrap();
+
+        assert!(report.is_erc20);
+        let info = report.token.unwrap();
+        assert_eq!(info.symbol.as_deref(), Some("TKN"));
+        assert_eq!(info.decimals, Some(6));
+        // Every response was used exactly once
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_token_omits_failed_metadata() {
+        let asserter = Asserter::new();
+        let service =
+            TokenVerificationService::new(Arc::new(EthereumClient::mocked(asserter.clone())));
+
+        asserter.push_success(&Bytes::from_static(&[0x60, 0x80]));
+        asserter.push_success(&Bytes::from(U256::from(6).abi_encode()));
+        asserter.push_failure_msg("execution reverted");
+        asserter.push_success(&Bytes::from("Token".to_string().abi_encode()));
+        asserter.push_success(&Bytes::from(U256::from(1000).abi_encode()));
+        asserter.push_success(&Bytes::from(U256::ZERO.abi_encode()));
+        asserter.push_success(&U256::ZERO);
+
+        let report = service.verify_token(Address::repeat_byte(0x11), None).await.unwrap();
+
+        assert!(!report.is_erc20);
+        let info = report.token.unwrap();
+        assert_eq!(info.symbol, None);
+        assert_eq!(info.decimals, Some(6));
+        assert!(report.checks.iter().any(|c| c.name == "symbol()" && !c.passed));
+    }
+
+    #[tokio::test]
+    async fn test_verify_token_eoa_has_no_metadata() {
+        let asserter = Asserter::new();
+        let service =
+            TokenVerificationService::new(Arc::new(EthereumClient::mocked(asserter.clone())));
+
+        asserter.push_success(&Bytes::new());
+
+        let report = service.verify_token(Address::repeat_byte(0x22), None).await.unwrap();
+
+        assert!(!report.is_contract);
+        assert!(report.token.is_none());
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[test]
+    fn test_implementation_from_slot_unset() {
+        assert_eq!(TokenVerificationService::implementation_from_slot(U256::ZERO), None);
+    }
+
+    #[test]
+    fn test_implementation_from_slot_set() {
+        let implementation = Address::repeat_byte(0xab);
+        let value = U256::from_be_slice(implementation.as_slice());
+
+        assert_eq!(TokenVerificationService::implementation_from_slot(value), Some(implementation));
+    }
+
+    fn compliance(paused: Option<bool>, blacklisted: Option<bool>) -> ComplianceStatus {
+        ComplianceStatus {
+            interface: "fiat_token".to_string(),
+            paused,
+            address: Some(format!("{:?}", Address::repeat_byte(1))),
+            blacklisted,
+        }
+    }
+
+    #[test]
+    fn test_compliance_warnings_clear() {
+        let warnings =
+            TokenVerificationService::compliance_warnings(&compliance(Some(false), Some(false)));
+        assert!(warnings.is_empty());
+
+        // Unreadable status is not reported
+        assert!(TokenVerificationService::compliance_warnings(&compliance(None, None)).is_empty());
+    }
+
+    #[test]
+    fn test_compliance_warnings_paused_and_blacklisted() {
+        let warnings =
+            TokenVerificationService::compliance_warnings(&compliance(Some(true), Some(true)));
+
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("paused"));
+        assert!(warnings[1].contains("blacklisted"));
+    }
+
+    #[test]
+    fn test_collect_warnings_eoa() {
+        let checks = vec![VerificationCheck::fail("code", "No contract code at address (EOA)")];
+        let warnings = TokenVerificationService::collect_warnings(false, &checks);
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("EOA"));
+    }
+
+    #[test]
+    fn test_collect_warnings_all_passed() {
+        let checks = vec![
+            VerificationCheck::pass("decimals()", 6),
+            VerificationCheck::pass("symbol()", "X"),
+        ];
+        let warnings = TokenVerificationService::collect_warnings(true, &checks);
+
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_collect_warnings_failed_checks() {
+        let checks = vec![
+            VerificationCheck::pass("decimals()", 18),
+            VerificationCheck::fail("name()", "reverted"),
+            VerificationCheck::fail("totalSupply()", "reverted"),
+        ];
+        let warnings = TokenVerificationService::collect_warnings(true, &checks);
+
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("name()"));
+        assert!(warnings[1].starts_with("totalSupply()"));
+    }
+}
@@ -0,0 +1,1184 @@
+//! Trade cost breakdown service.
+//!
+//! Combines a swap simulation with USD prices into a single summary of what a
+//! trade really costs: AMM fees, price impact and gas. Also reports the all-in
+//! exchange rate of a swap without the USD valuation, ranks swaps into several
+//! candidate output tokens, and nets out the result of a buy-and-sell round trip.
+
+use alloy::primitives::{Address, U256};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::{cmp::Ordering, str::FromStr};
+
+use crate::{
+    config::DEFAULT_FAN_OUT_CONCURRENCY,
+    error::{AppError, Result},
+    services::{balance::fan_out_with_progress, BalanceService, PriceService, SwapService},
+    types::{
+        format_units, parse_units, BestSwapCandidate, BestSwapResult, EffectiveRate,
+        ExactOutputQuote, PriceInfo, QuoteCurrency, RoundTripPnl, SwapParams, SwapPathLeg,
+        SwapRankingMetric, SwapRoute, SwapSimulationResult, TokenInfo, TradeCostBreakdown,
+        UsdOutputQuote, Warning, WarningCode,
+    },
+};
+
+/// USD prices used to value a trade (`None` when a token could not be priced).
+#[derive(Debug, Clone, Copy, Default)]
+struct UsdPrices {
+    from: Option<Decimal>,
+    to: Option<Decimal>,
+    eth: Option<Decimal>,
+}
+
+/// Service for estimating the total cost of a swap.
+#[derive(Clone)]
+pub struct TradeCostService {
+    swap_service: SwapService,
+    price_service: PriceService,
+    balance_service: BalanceService,
+    /// Maximum concurrent swap simulations within one best_swap request.
+    fan_out_concurrency: usize,
+}
+
+impl TradeCostService {
+    /// Create a new trade cost service.
+    pub fn new(
+        swap_service: SwapService,
+        price_service: PriceService,
+        balance_service: BalanceService,
+    ) -> Self {
+        Self {
+            swap_service,
+            price_service,
+            balance_service,
+            fan_out_concurrency: DEFAULT_FAN_OUT_CONCURRENCY,
+        }
+    }
+
+    /// Set how many swaps one best_swap request may simulate concurrently.
+    pub fn with_fan_out_concurrency(mut self, limit: usize) -> Self {
+        self.fan_out_concurrency = limit.max(1);
+        self
+    }
+
+    /// Simulate a swap and break down its cost.
+    pub async fn breakdown(&self, params: SwapParams) -> Result<TradeCostBreakdown> {
+        let from_token = params.from_token;
+        let to_token = params.to_token;
+
+        let simulation = self.swap_service.simulate_swap(params).await?;
+
+        let from_metadata = self.balance_service.get_token_metadata(from_token).await?;
+        let to_metadata = self.balance_service.get_token_metadata(to_token).await?;
+
+        let (from, to, eth) = tokio::join!(
+            self.usd_price(from_token),
+            self.usd_price(to_token),
+            self.eth_usd_price(),
+        );
+
+        Self::build_breakdown(
+            TokenInfo::erc20(from_token, from_metadata.symbol, from_metadata.decimals),
+            TokenInfo::erc20(to_token, to_metadata.symbol, to_metadata.decimals),
+            simulation,
+            UsdPrices { from, to, eth },
+        )
+    }
+
+    /// Simulate a swap and report its all-in exchange rate in both directions.
+    pub async fn effective_rate(&self, params: SwapParams) -> Result<EffectiveRate> {
+        let from_token = params.from_token;
+        let to_token = params.to_token;
+
+        let simulation = self.swap_service.simulate_swap(params).await?;
+
+        let from_metadata = self.balance_service.get_token_metadata(from_token).await?;
+        let to_metadata = self.balance_service.get_token_metadata(to_token).await?;
+
+        Self::build_effective_rate(
+            TokenInfo::erc20(from_token, from_metadata.symbol, from_metadata.decimals),
+            TokenInfo::erc20(to_token, to_metadata.symbol, to_metadata.decimals),
+            simulation,
+        )
+    }
+
+    /// Simulate buying `params.to_token` and selling the proceeds straight back.
+    ///
+    /// The sell leg is quoted at current pool prices. With `exit_price` (input
+    /// tokens per output token), its output is rescaled to that pool price while
+    /// keeping its fee and price impact. A sell leg that cannot be routed, or gas
+    /// that cannot be priced in the input token, is explained in `notes` rather
+    /// than failing the request.
+    pub async fn round_trip_pnl(
+        &self,
+        params: SwapParams,
+        exit_price: Option<Decimal>,
+    ) -> Result<RoundTripPnl> {
+        let from_token = params.from_token;
+        let to_token = params.to_token;
+        let slippage_tolerance = params.slippage_tolerance;
+        let from_address = params.from_address;
+
+        let buy = self.swap_service.simulate_swap(params).await?;
+
+        let from_metadata = self.balance_service.get_token_metadata(from_token).await?;
+        let to_metadata = self.balance_service.get_token_metadata(to_token).await?;
+
+        // The sell leg spends exactly the buy leg's expected output
+        let amount_bought =
+            parse_units(&buy.amount_out_expected, to_metadata.decimals).map_err(AppError::Parse)?;
+        let sell_params = SwapParams {
+            from_token: to_token,
+            to_token: from_token,
+            amount_in: amount_bought,
+            slippage_tolerance,
+            from_address,
+            ..Default::default()
+        };
+
+        let (sell, from_eth) = tokio::join!(
+            self.swap_service.simulate_swap(sell_params),
+            self.price_in(from_token, QuoteCurrency::ETH),
+        );
+        let sell = match sell {
+            Ok(sell) => Ok(sell),
+            Err(e @ AppError::Wallet(_)) => return Err(e),
+            Err(e) => {
+                tracing::warn!(error = %e, "Round trip sell leg could not be routed");
+                Err(e.to_string())
+            }
+        };
+
+        Self::build_round_trip(
+            TokenInfo::erc20(from_token, from_metadata.symbol, from_metadata.decimals),
+            TokenInfo::erc20(to_token, to_metadata.symbol, to_metadata.decimals),
+            buy,
+            sell,
+            exit_price,
+            from_eth,
+        )
+    }
+
+    /// Simulate swapping `amount_in` of `from_token` into each candidate and rank them.
+    ///
+    /// Simulations run concurrently, at most `fan_out_concurrency` at a time.
+    /// Candidates that cannot be routed or valued are listed last with a reason.
+    /// `on_progress(done, total)` is called as each candidate's simulation completes.
+    pub async fn best_swap(
+        &self,
+        from_token: Address,
+        to_tokens: Vec<Address>,
+        amount_in: U256,
+        slippage_tolerance: Decimal,
+        metric: SwapRankingMetric,
+        on_progress: impl Fn(usize, usize),
+    ) -> Result<BestSwapResult> {
+        let from_metadata = self.balance_service.get_token_metadata(from_token).await?;
+        let (from_usd, eth_usd) = tokio::join!(self.usd_price(from_token), self.eth_usd_price());
+
+        let candidates = fan_out_with_progress(
+            self.fan_out_concurrency,
+            to_tokens,
+            |to_token| {
+                let service = self.clone();
+                async move {
+                    let params = SwapParams {
+                        from_token,
+                        to_token,
+                        amount_in,
+                        slippage_tolerance,
+                        ..Default::default()
+                    };
+                    service.best_swap_candidate(params, eth_usd).await
+                }
+            },
+            on_progress,
+        )
+        .await?;
+
+        let amount_in = format_units(amount_in, from_metadata.decimals);
+        let input_value_usd = from_usd
+            .zip(Decimal::from_str(&amount_in).ok())
+            .map(|(price, amount)| format_usd(price * amount));
+
+        Ok(BestSwapResult {
+            from_token: TokenInfo::erc20(from_token, from_metadata.symbol, from_metadata.decimals),
+            amount_in,
+            input_value_usd,
+            metric,
+            candidates: rank_candidates(candidates, metric),
+        })
+    }
+
+    /// Simulate one best_swap candidate.
+    ///
+    /// A candidate that cannot be routed is returned with the reason rather than
+    /// failing the whole request; only a missing wallet is an error.
+    async fn best_swap_candidate(
+        &self,
+        params: SwapParams,
+        eth_usd: Option<Decimal>,
+    ) -> Result<BestSwapCandidate> {
+        let to_token = params.to_token;
+        let to_metadata = self.balance_service.get_token_metadata(to_token).await?;
+        let to_info = TokenInfo::erc20(to_token, to_metadata.symbol, to_metadata.decimals);
+
+        let (simulation, to_usd) =
+            tokio::join!(self.swap_service.simulate_swap(params), self.usd_price(to_token));
+        match simulation {
+            Ok(simulation) => Ok(build_candidate(to_info, simulation, to_usd, eth_usd)),
+            Err(e @ AppError::Wallet(_)) => Err(e),
+            Err(e) => Ok(BestSwapCandidate {
+                rank: 0,
+                to_token: to_info,
+                ranked: false,
+                amount_out_expected: None,
+                output_value_usd: None,
+                net_output_value_usd: None,
+                price_impact_percent: None,
+                gas_cost_eth: None,
+                route: None,
+                reason: Some(e.to_string()),
+                warnings: Vec::new(),
+            }),
+        }
+    }
+
+    /// Quote the input required to receive `target_usd` worth of `to_token`.
+    ///
+    /// Unlike the other USD figures here, the output token's price is required:
+    /// a token that cannot be priced in USD fails the request.
+    pub async fn quote_usd_output(
+        &self,
+        from_token: Address,
+        to_token: Address,
+        target_usd: Decimal,
+    ) -> Result<UsdOutputQuote> {
+        let to_metadata = self.balance_service.get_token_metadata(to_token).await?;
+        let price =
+            self.price_service.get_price(to_token, QuoteCurrency::USD).await.map_err(|e| {
+                AppError::PriceOracle(format!(
+                    "Cannot price {} in USD, so ${} cannot be converted to an amount of it: {}",
+                    to_metadata.symbol, target_usd, e
+                ))
+            })?;
+
+        let usd_price = parse_decimal("price", &price.price)?;
+        let amount_out = usd_to_token_amount(target_usd, usd_price, to_metadata.decimals)?;
+        if amount_out.is_zero() {
+            return Err(AppError::Parse(format!(
+                "${} is worth less than the smallest unit of {}",
+                target_usd, to_metadata.symbol
+            )));
+        }
+
+        let quote = self.swap_service.quote_exact_output(from_token, to_token, amount_out).await?;
+        Self::build_usd_output_quote(target_usd, price, quote)
+    }
+
+    /// Get the USD price of a token, or `None` if it cannot be priced.
+    async fn usd_price(&self, token: Address) -> Option<Decimal> {
+        self.price_in(token, QuoteCurrency::USD).await
+    }
+
+    /// Get the price of a token in `quote`, or `None` if it cannot be priced.
+    async fn price_in(&self, token: Address, quote: QuoteCurrency) -> Option<Decimal> {
+        match self.price_service.get_price(token, quote).await {
+            Ok(info) => Decimal::from_str(&info.price).ok(),
+            Err(e) => {
+                tracing::debug!(token = %token, error = %e, "Could not price token in {:?}", quote);
+                None
+            }
+        }
+    }
+
+    /// USD price of ETH, via the chain's wrapped native token.
+    async fn eth_usd_price(&self) -> Option<Decimal> {
+        let Some(wrapped) = self.price_service.wrapped_native_token() else {
+            tracing::debug!("No wrapped native token known, cannot price ETH in USD");
+            return None;
+        };
+        self.usd_price(wrapped).await
+    }
+
+    /// Combine a simulation result with USD prices into a cost breakdown.
+    fn build_breakdown(
+        from_token: TokenInfo,
+        to_token: TokenInfo,
+        simulation: SwapSimulationResult,
+        prices: UsdPrices,
+    ) -> Result<TradeCostBreakdown> {
+        let amount_in = parse_decimal("amount_in", &simulation.amount_in)?;
+        let amount_out = parse_decimal("amount_out_expected", &simulation.amount_out_expected)?;
+        let amount_out_minimum =
+            parse_decimal("amount_out_minimum", &simulation.amount_out_minimum)?;
+        let price_impact = parse_decimal("price_impact", &simulation.price_impact)?;
+        let gas_cost_eth = parse_decimal("gas_cost_eth", &simulation.gas_cost_eth)?;
+
+        if amount_in.is_zero() {
+            return Err(AppError::Parse(format!(
+                "Invalid amount_in '{}': must be greater than zero",
+                simulation.amount_in
+            )));
+        }
+
+        let fee_fraction = route_fee_fraction(&simulation.route)?;
+        let amm_fee = amount_in * fee_fraction;
+
+        // Output that would have been received at the spot rate, minus actual output
+        let price_impact_cost = if price_impact < Decimal::ONE_HUNDRED {
+            amount_out * price_impact / (Decimal::ONE_HUNDRED - price_impact)
+        } else {
+            Decimal::ZERO
+        };
+
+        let mut notes = Vec::new();
+        if prices.from.is_none() {
+            notes.push(format!(
+                "{} could not be priced in USD; input value and AMM fee in USD are omitted",
+                from_token.symbol
+            ));
+        }
+        if prices.to.is_none() {
+            notes.push(format!(
+                "{} could not be priced in USD; output value, price impact cost in USD and net effective rate are omitted",
+                to_token.symbol
+            ));
+        }
+        if prices.eth.is_none() {
+            notes.push(
+                "ETH could not be priced in USD; gas cost in USD and net effective rate are omitted"
+                    .to_string(),
+            );
+        }
+        if simulation.gas_estimate_is_fallback {
+            notes.push("Gas cost is based on a fallback gas estimate".to_string());
+        }
+        if !simulation.simulation_success {
+            notes.push(format!(
+                "Swap simulation failed{}; figures reflect the quoted route only",
+                simulation.simulation_error.map(|e| format!(" ({})", e)).unwrap_or_default()
+            ));
+        }
+
+        let input_value_usd = prices.from.map(|p| amount_in * p);
+        let output_value_usd = prices.to.map(|p| amount_out * p);
+        let gas_cost_usd = prices.eth.map(|p| gas_cost_eth * p);
+
+        let total_cost_usd = match (input_value_usd, output_value_usd, gas_cost_usd) {
+            (Some(input), Some(output), Some(gas)) => Some(input - output + gas),
+            _ => None,
+        };
+
+        // Pay for gas out of the output to get the net rate
+        let net_effective_rate = match (gas_cost_usd, prices.to) {
+            (Some(gas), Some(to_price)) if !to_price.is_zero() => {
+                Some((amount_out - gas / to_price) / amount_in)
+            }
+            _ => None,
+        };
+
+        Ok(TradeCostBreakdown {
+            from_token,
+            to_token,
+            amount_in: simulation.amount_in,
+            amount_out_expected: simulation.amount_out_expected,
+            effective_rate: format_decimal(amount_out / amount_in),
+            net_effective_rate: net_effective_rate.map(format_decimal),
+            amm_fee_percent: format_decimal(fee_fraction * Decimal::ONE_HUNDRED),
+            amm_fee: format_decimal(amm_fee),
+            price_impact_percent: simulation.price_impact,
+            price_impact_cost: format_decimal(price_impact_cost),
+            gas_cost_eth: simulation.gas_cost_eth,
+            gas_estimate_is_fallback: simulation.gas_estimate_is_fallback,
+            input_value_usd: input_value_usd.map(format_usd),
+            output_value_usd: output_value_usd.map(format_usd),
+            minimum_output_value_usd: prices.to.map(|p| format_usd(amount_out_minimum * p)),
+            amm_fee_usd: prices.from.map(|p| format_usd(amm_fee * p)),
+            price_impact_cost_usd: prices.to.map(|p| format_usd(price_impact_cost * p)),
+            gas_cost_usd: gas_cost_usd.map(format_usd),
+            total_cost_usd: total_cost_usd.map(format_usd),
+            simulation_success: simulation.simulation_success,
+            route: simulation.route,
+            notes,
+        })
+    }
+
+    /// Derive execution and spot rates from a simulation result.
+    ///
+    /// The simulation quotes the spot rate with a tiny reference trade to measure
+    /// price impact, so the spot rate is recovered as `rate / (1 - impact)`.
+    /// When the impact could not be calculated there is no spot rate, and a
+    /// warning says so.
+    fn build_effective_rate(
+        from_token: TokenInfo,
+        to_token: TokenInfo,
+        simulation: SwapSimulationResult,
+    ) -> Result<EffectiveRate> {
+        let amount_in = parse_decimal("amount_in", &simulation.amount_in)?;
+        let amount_out = parse_decimal("amount_out_expected", &simulation.amount_out_expected)?;
+        let price_impact = parse_decimal("price_impact", &simulation.price_impact)?;
+
+        if amount_in.is_zero() || amount_out.is_zero() {
+            return Err(AppError::RouteNotFound(format!(
+                "Swap of {} {} returns {} {}; no rate available",
+                simulation.amount_in,
+                from_token.symbol,
+                simulation.amount_out_expected,
+                to_token.symbol
+            )));
+        }
+
+        let rate = amount_out / amount_in;
+        let impact_unavailable = simulation.price_impact_unavailable();
+        let spot_rate = (!impact_unavailable && price_impact < Decimal::ONE_HUNDRED)
+            .then(|| rate * Decimal::ONE_HUNDRED / (Decimal::ONE_HUNDRED - price_impact));
+
+        let mut warnings = simulation.warnings;
+        if impact_unavailable {
+            warnings.push(Warning::new(
+                WarningCode::PriceImpactUnavailable,
+                "Spot rate is not reported because the price impact could not be calculated",
+            ));
+        }
+
+        Ok(EffectiveRate {
+            from_token,
+            to_token,
+            amount_in: simulation.amount_in,
+            amount_out_expected: simulation.amount_out_expected,
+            rate: format_decimal(rate),
+            inverse_rate: format_decimal(amount_in / amount_out),
+            spot_rate: spot_rate.map(format_decimal),
+            inverse_spot_rate: spot_rate.map(|spot| format_decimal(Decimal::ONE / spot)),
+            price_impact_percent: simulation.price_impact,
+            simulation_success: simulation.simulation_success,
+            route: simulation.route,
+            warnings,
+        })
+    }
+
+    /// Combine an exact output quote with the USD price its output amount came from.
+    fn build_usd_output_quote(
+        target_usd: Decimal,
+        price: PriceInfo,
+        quote: ExactOutputQuote,
+    ) -> Result<UsdOutputQuote> {
+        let amount_in = parse_decimal("amount_in", &quote.amount_in)?;
+        if amount_in.is_zero() {
+            return Err(AppError::RouteNotFound(format!(
+                "Quote for {} {} requires no {}; no rate available",
+                quote.amount_out, quote.to_token.symbol, quote.from_token.symbol
+            )));
+        }
+
+        Ok(UsdOutputQuote {
+            from_token: quote.from_token,
+            to_token: quote.to_token,
+            target_usd: format_usd(target_usd),
+            to_token_usd_price: price.price,
+            price_source: price.source,
+            amount_in: quote.amount_in,
+            amount_in_raw: quote.amount_in_raw,
+            amount_out: quote.amount_out,
+            amount_out_raw: quote.amount_out_raw,
+            price: quote.price,
+            input_usd_rate: format_decimal(target_usd / amount_in),
+            route: quote.route,
+            warnings: price.warnings,
+        })
+    }
+
+    /// Net out a buy leg and the sell leg back into the input token.
+    ///
+    /// `sell` is the sell leg at current pool prices, or why it failed.
+    /// `from_eth_price` is the input token's price in ETH, used to charge gas.
+    fn build_round_trip(
+        from_token: TokenInfo,
+        to_token: TokenInfo,
+        buy: SwapSimulationResult,
+        sell: std::result::Result<SwapSimulationResult, String>,
+        exit_price: Option<Decimal>,
+        from_eth_price: Option<Decimal>,
+    ) -> Result<RoundTripPnl> {
+        let amount_in = parse_decimal("amount_in", &buy.amount_in)?;
+        let bought = parse_decimal("amount_out_expected", &buy.amount_out_expected)?;
+        let bought_minimum = parse_decimal("amount_out_minimum", &buy.amount_out_minimum)?;
+        let buy_fee = route_fee_fraction(&buy.route)?;
+        let buy_impact = parse_decimal("price_impact", &buy.price_impact)?;
+        let mut gas_cost_eth = parse_decimal("gas_cost_eth", &buy.gas_cost_eth)?;
+
+        if amount_in.is_zero() {
+            return Err(AppError::Parse(format!(
+                "Invalid amount_in '{}': must be greater than zero",
+                buy.amount_in
+            )));
+        }
+
+        let mut notes = Vec::new();
+        let sell = match sell {
+            Ok(sell) => Some(sell),
+            Err(e) => {
+                notes.push(format!(
+                    "Selling {} back to {} could not be simulated ({}); only the buy leg is reported",
+                    to_token.symbol, from_token.symbol, e
+                ));
+                None
+            }
+        };
+
+        let entry_price = pool_price(amount_in, bought, buy_fee, buy_impact);
+        let buy_impact_cost =
+            impact_cost(bought, buy_impact).zip(entry_price).map(|(cost, price)| cost * price);
+
+        // (returned, returned_minimum, fee, impact) of the sell leg at current prices
+        let mut exit = None;
+        if let Some(sell) = &sell {
+            gas_cost_eth += parse_decimal("gas_cost_eth", &sell.gas_cost_eth)?;
+            exit = Some((
+                parse_decimal("amount_out_expected", &sell.amount_out_expected)?,
+                parse_decimal("amount_out_minimum", &sell.amount_out_minimum)?,
+                route_fee_fraction(&sell.route)?,
+                parse_decimal("price_impact", &sell.price_impact)?,
+            ));
+        }
+
+        // The sell leg's pool price is quoted in output tokens, so invert it
+        let current_exit_price = exit
+            .and_then(|(returned, _, fee, impact)| pool_price(bought, returned, fee, impact))
+            .and_then(|price| Decimal::ONE.checked_div(price));
+        let scale = match (exit_price, current_exit_price) {
+            (Some(exit_price), Some(current)) => exit_price.checked_div(current),
+            (None, Some(_)) => Some(Decimal::ONE),
+            _ => None,
+        };
+        if sell.is_some() && scale.is_none() {
+            notes.push(
+                "The exit pool price could not be derived from the sell leg; P&L is omitted"
+                    .to_string(),
+            );
+        }
+        let exit_price = exit_price.or(current_exit_price);
+
+        let returned = exit.zip(scale).map(|((returned, ..), scale)| returned * scale);
+        // Slippage on the buy leg shrinks the sell leg's input proportionally
+        let returned_minimum = exit.zip(scale).and_then(|((_, minimum, ..), scale)| {
+            (minimum * scale * bought_minimum).checked_div(bought)
+        });
+
+        let amm_fees = exit
+            .zip(exit_price)
+            .map(|((_, _, fee, _), price)| amount_in * buy_fee + bought * fee * price);
+        let price_impact_cost = exit
+            .zip(returned)
+            .and_then(|((_, _, _, impact), returned)| impact_cost(returned, impact))
+            .zip(buy_impact_cost)
+            .map(|(sell_cost, buy_cost)| sell_cost + buy_cost);
+
+        let gas_cost = from_eth_price
+            .filter(|price| *price > Decimal::ZERO)
+            .and_then(|price| gas_cost_eth.checked_div(price));
+        if gas_cost.is_none() {
+            notes.push(format!(
+                "{} could not be priced in ETH; net P&L excludes gas",
+                from_token.symbol
+            ));
+        }
+        if buy.gas_estimate_is_fallback
+            || sell.as_ref().is_some_and(|sell| sell.gas_estimate_is_fallback)
+        {
+            notes.push("Gas cost is based on a fallback gas estimate".to_string());
+        }
+
+        let net = |returned: Decimal| returned - amount_in - gas_cost.unwrap_or_default();
+        let net_pnl = returned.map(net);
+
+        Ok(RoundTripPnl {
+            amount_in: buy.amount_in.clone(),
+            amount_bought: buy.amount_out_expected.clone(),
+            amount_returned: returned.map(format_decimal),
+            entry_price: entry_price.map(format_decimal),
+            exit_price: exit_price.map(format_decimal),
+            exit_price_is_hypothetical: scale.is_some() && exit_price != current_exit_price,
+            amm_fees: amm_fees.map(format_decimal),
+            price_impact_cost: price_impact_cost.map(format_decimal),
+            gas_cost_eth: format_decimal(gas_cost_eth),
+            gas_cost: gas_cost.map(format_decimal),
+            net_pnl_percent: net_pnl
+                .map(|net| format_decimal((net * Decimal::ONE_HUNDRED / amount_in).round_dp(4))),
+            net_pnl: net_pnl.map(format_decimal),
+            net_pnl_includes_gas: gas_cost.is_some(),
+            worst_case_net_pnl: returned_minimum.map(net).map(format_decimal),
+            buy: SwapPathLeg {
+                from_token: from_token.clone(),
+                to_token: to_token.clone(),
+                simulation: buy,
+            },
+            sell: sell.map(|simulation| SwapPathLeg {
+                from_token: to_token.clone(),
+                to_token: from_token.clone(),
+                simulation,
+            }),
+            from_token,
+            to_token,
+            notes,
+        })
+    }
+}
+
+/// Pool price of the output token in input tokens, before the AMM fee and price impact.
+fn pool_price(
+    amount_in: Decimal,
+    amount_out: Decimal,
+    fee_fraction: Decimal,
+    price_impact: Decimal,
+) -> Option<Decimal> {
+    let undiscounted =
+        amount_in * (Decimal::ONE - fee_fraction) * (Decimal::ONE_HUNDRED - price_impact)
+            / Decimal::ONE_HUNDRED;
+    undiscounted.checked_div(amount_out).filter(|price| *price > Decimal::ZERO)
+}
+
+/// Output lost to price impact: what the spot rate would have paid, minus `amount_out`.
+fn impact_cost(amount_out: Decimal, price_impact: Decimal) -> Option<Decimal> {
+    (price_impact < Decimal::ONE_HUNDRED)
+        .then(|| amount_out * price_impact / (Decimal::ONE_HUNDRED - price_impact))
+}
+
+/// Summarize a candidate's simulation, valuing its output in USD where possible.
+fn build_candidate(
+    to_token: TokenInfo,
+    simulation: SwapSimulationResult,
+    to_usd: Option<Decimal>,
+    eth_usd: Option<Decimal>,
+) -> BestSwapCandidate {
+    let amount_out = Decimal::from_str(&simulation.amount_out_expected).ok();
+    let gas_cost_eth = Decimal::from_str(&simulation.gas_cost_eth).ok();
+
+    let output_value_usd = to_usd.zip(amount_out).map(|(price, amount)| price * amount);
+    let net_output_value_usd = output_value_usd
+        .zip(eth_usd.zip(gas_cost_eth))
+        .map(|(output, (eth_price, gas))| output - eth_price * gas);
+
+    BestSwapCandidate {
+        rank: 0,
+        ranked: false,
+        amount_out_expected: Some(simulation.amount_out_expected),
+        output_value_usd: output_value_usd.map(format_usd),
+        net_output_value_usd: net_output_value_usd.map(format_usd),
+        price_impact_percent: Some(simulation.price_impact),
+        gas_cost_eth: Some(simulation.gas_cost_eth),
+        route: Some(simulation.route),
+        reason: None,
+        warnings: simulation.warnings,
+        to_token,
+    }
+}
+
+/// Value of a candidate for the ranking metric, if it has one.
+fn metric_value(candidate: &BestSwapCandidate, metric: SwapRankingMetric) -> Option<Decimal> {
+    let value = match metric {
+        SwapRankingMetric::OutputUsd => &candidate.output_value_usd,
+        SwapRankingMetric::NetOutputUsd => &candidate.net_output_value_usd,
+        SwapRankingMetric::PriceImpact => &candidate.price_impact_percent,
+    };
+    value.as_deref().and_then(|v| Decimal::from_str(v).ok())
+}
+
+/// Order candidates best first and number them.
+///
+/// Candidates without a value for the metric keep their input order at the
+/// bottom, with a reason when the simulation itself succeeded.
+fn rank_candidates(
+    candidates: Vec<BestSwapCandidate>,
+    metric: SwapRankingMetric,
+) -> Vec<BestSwapCandidate> {
+    let mut keyed: Vec<_> = candidates
+        .into_iter()
+        .map(|candidate| (metric_value(&candidate, metric), candidate))
+        .collect();
+
+    // Stable sort: ranked candidates first, best value first
+    keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => match metric {
+            SwapRankingMetric::PriceImpact => a.cmp(b),
+            _ => b.cmp(a),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+
+    keyed
+        .into_iter()
+        .enumerate()
+        .map(|(index, (value, mut candidate))| {
+            candidate.rank = index + 1;
+            candidate.ranked = value.is_some();
+            if !candidate.ranked && candidate.reason.is_none() {
+                candidate.reason = Some(match metric {
+                    SwapRankingMetric::NetOutputUsd if candidate.output_value_usd.is_some() => {
+                        "ETH could not be priced in USD, so the gas cost cannot be deducted"
+                            .to_string()
+                    }
+                    _ => format!("{} could not be priced in USD", candidate.to_token.symbol),
+                });
+            }
+            candidate
+        })
+        .collect()
+}
+
+/// Combined pool fee along a route as a fraction of the input.
+///
+/// Each hop charges its fee on what is left after the previous hop, so the
+/// combined fee is `1 - Π(1 - fee)`.
+/// Amount of a token, in its smallest units, worth `usd` at `usd_price`.
+///
+/// Rounds down, so the amount is never worth more than requested.
+fn usd_to_token_amount(usd: Decimal, usd_price: Decimal, decimals: u8) -> Result<U256> {
+    if usd_price <= Decimal::ZERO {
+        return Err(AppError::PriceOracle(format!("Invalid USD price: {}", usd_price)));
+    }
+    let amount = usd
+        .checked_div(usd_price)
+        .ok_or_else(|| AppError::NumericOverflow(format!("${} at ${}", usd, usd_price)))?
+        .round_dp_with_strategy(u32::from(decimals), RoundingStrategy::ToZero);
+    parse_units(&amount.normalize().to_string(), decimals).map_err(AppError::Parse)
+}
+
+fn route_fee_fraction(route: &SwapRoute) -> Result<Decimal> {
+    // Fees are in hundredths of a bip
+    let hop_fee_ppm = route.hop_fee_ppm().ok_or_else(|| {
+        AppError::RouteNotFound("V3 route has no fee tier, so its pool fee is unknown".to_string())
+    })?;
+    let hop_fee = Decimal::from(hop_fee_ppm) / Decimal::from(1_000_000);
+    let hops = route.path.len().saturating_sub(1);
+
+    let remaining = (0..hops).fold(Decimal::ONE, |acc, _| acc * (Decimal::ONE - hop_fee));
+    Ok(Decimal::ONE - remaining)
+}
+
+fn parse_decimal(field: &str, value: &str) -> Result<Decimal> {
+    Decimal::from_str(value)
+        .map_err(|e| AppError::Parse(format!("Invalid {} '{}': {}", field, value, e)))
+}
+
+fn format_decimal(value: Decimal) -> String {
+    value.round_dp(18).normalize().to_string()
+}
+
+fn format_usd(value: Decimal) -> String {
+    format!("{:.2}", value.round_dp(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ethereum::contracts::WETH_ADDRESS,
+        types::{UniswapVersion, Venue},
+    };
+
+    fn route(protocol: UniswapVersion, hops: usize, fee_tier: Option<u32>) -> SwapRoute {
+        SwapRoute {
+            protocol,
+            path: (0..=hops).map(|i| format!("0xToken{}", i)).collect(),
+            fee_tier,
+            venue: Venue::Uniswap,
+        }
+    }
+
+    fn simulation(route: SwapRoute) -> SwapSimulationResult {
+        SwapSimulationResult {
+            amount_out_expected: "2970".to_string(),
+            amount_out_minimum: "2955.15".to_string(),
+            price_impact: "1".to_string(),
+            gas_price: "20000000000".to_string(),
+            gas_cost_eth: "0.003".to_string(),
+            route,
+            ..SwapSimulationResult::fixture()
+        }
+    }
+
+    fn tokens() -> (TokenInfo, TokenInfo) {
+        (
+            TokenInfo::erc20(WETH_ADDRESS, "WETH".to_string(), 18),
+            TokenInfo::erc20(Address::repeat_byte(1), "USDC".to_string(), 6),
+        )
+    }
+
+    #[test]
+    fn test_route_fee_fraction() {
+        assert_eq!(
+            route_fee_fraction(&route(UniswapVersion::V2, 1, None)).unwrap(),
+            Decimal::new(3, 3)
+        );
+        assert_eq!(
+            route_fee_fraction(&route(UniswapVersion::V3, 1, Some(500))).unwrap(),
+            Decimal::new(5, 4)
+        );
+        // Two 0.3% hops: 1 - 0.997^2 = 0.005991
+        assert_eq!(
+            route_fee_fraction(&route(UniswapVersion::V2, 2, None)).unwrap(),
+            Decimal::new(5991, 6)
+        );
+        // A V3 route needs its fee tier
+        assert!(route_fee_fraction(&route(UniswapVersion::V3, 1, None)).is_err());
+    }
+
+    #[test]
+    fn test_breakdown_with_prices() {
+        let (from, to) = tokens();
+        let prices = UsdPrices {
+            from: Some(Decimal::from(3000)),
+            to: Some(Decimal::ONE),
+            eth: Some(Decimal::from(3000)),
+        };
+
+        let breakdown = TradeCostService::build_breakdown(
+            from,
+            to,
+            simulation(route(UniswapVersion::V3, 1, Some(3000))),
+            prices,
+        )
+        .unwrap();
+
+        assert_eq!(breakdown.effective_rate, "2970");
+        assert_eq!(breakdown.amm_fee_percent, "0.3");
+        assert_eq!(breakdown.amm_fee, "0.003");
+        assert_eq!(breakdown.amm_fee_usd.as_deref(), Some("9.00"));
+        // 2970 * 1 / 99 = 30
+        assert_eq!(breakdown.price_impact_cost, "30");
+        assert_eq!(breakdown.input_value_usd.as_deref(), Some("3000.00"));
+        assert_eq!(breakdown.output_value_usd.as_deref(), Some("2970.00"));
+        assert_eq!(breakdown.minimum_output_value_usd.as_deref(), Some("2955.15"));
+        assert_eq!(breakdown.gas_cost_usd.as_deref(), Some("9.00"));
+        assert_eq!(breakdown.total_cost_usd.as_deref(), Some("39.00"));
+        assert_eq!(breakdown.net_effective_rate.as_deref(), Some("2961"));
+        assert!(breakdown.notes.is_empty());
+    }
+
+    #[test]
+    fn test_breakdown_unpriceable_token() {
+        let (from, to) = tokens();
+        let prices = UsdPrices { from: Some(Decimal::from(3000)), to: None, eth: None };
+
+        let mut sim = simulation(route(UniswapVersion::V2, 1, None));
+        sim.gas_estimate_is_fallback = true;
+
+        let breakdown = TradeCostService::build_breakdown(from, to, sim, prices).unwrap();
+
+        assert_eq!(breakdown.input_value_usd.as_deref(), Some("3000.00"));
+        assert!(breakdown.output_value_usd.is_none());
+        assert!(breakdown.minimum_output_value_usd.is_none());
+        assert!(breakdown.gas_cost_usd.is_none());
+        assert!(breakdown.total_cost_usd.is_none());
+        assert!(breakdown.net_effective_rate.is_none());
+        assert_eq!(breakdown.notes.len(), 3);
+        assert!(breakdown.notes[0].contains("USDC"));
+
+        let json = serde_json::to_value(&breakdown).unwrap();
+        assert!(json.get("total_cost_usd").is_none());
+        assert!(json.get("input_value_usd").is_some());
+    }
+
+    #[test]
+    fn test_effective_rate_both_directions() {
+        let (from, to) = tokens();
+        let mut sim = simulation(route(UniswapVersion::V3, 1, Some(3000)));
+        sim.amount_in = "2".to_string();
+        sim.amount_out_expected = "5000".to_string();
+        sim.price_impact = "20".to_string();
+
+        let rate = TradeCostService::build_effective_rate(from, to, sim).unwrap();
+
+        assert_eq!(rate.rate, "2500");
+        assert_eq!(rate.inverse_rate, "0.0004");
+        // 2500 / (1 - 0.2) = 3125
+        assert_eq!(rate.spot_rate.as_deref(), Some("3125"));
+        assert_eq!(rate.inverse_spot_rate.as_deref(), Some("0.00032"));
+        assert_eq!(rate.price_impact_percent, "20");
+    }
+
+    #[test]
+    fn test_effective_rate_rounding() {
+        let (from, to) = tokens();
+        let mut sim = simulation(route(UniswapVersion::V2, 1, None));
+        sim.amount_out_expected = "3".to_string();
+        sim.price_impact = "100".to_string();
+
+        let rate = TradeCostService::build_effective_rate(from, to, sim).unwrap();
+
+        assert_eq!(rate.inverse_rate, "0.333333333333333333");
+        assert!(rate.spot_rate.is_none());
+        assert!(rate.inverse_spot_rate.is_none());
+    }
+
+    #[test]
+    fn test_effective_rate_without_price_impact() {
+        let (from, to) = tokens();
+        let mut sim = simulation(route(UniswapVersion::V3, 1, Some(3000)));
+        sim.amount_in = "2".to_string();
+        sim.amount_out_expected = "5000".to_string();
+        sim.price_impact = "0".to_string();
+        sim.warnings.push(Warning::new(WarningCode::PriceImpactUnavailable, "no spot quote"));
+
+        let rate = TradeCostService::build_effective_rate(from, to, sim).unwrap();
+
+        // The spot rate is unknown rather than equal to the execution rate
+        assert_eq!(rate.rate, "2500");
+        assert!(rate.spot_rate.is_none());
+        assert!(rate.inverse_spot_rate.is_none());
+        assert_eq!(rate.warnings.len(), 2);
+        assert!(rate.warnings[1].message.contains("Spot rate"));
+    }
+
+    #[test]
+    fn test_effective_rate_zero_output() {
+        let (from, to) = tokens();
+        let mut sim = simulation(route(UniswapVersion::V2, 1, None));
+        sim.amount_out_expected = "0".to_string();
+
+        let result = TradeCostService::build_effective_rate(from, to, sim);
+
+        assert!(matches!(result, Err(AppError::RouteNotFound(_))));
+    }
+
+    #[test]
+    fn test_usd_to_token_amount() {
+        // $500 of USDC at $1.0002 rounds down to whole micro-USDC
+        let amount = usd_to_token_amount(Decimal::from(500), Decimal::new(10002, 4), 6).unwrap();
+        assert_eq!(amount, U256::from(499_900_019u64));
+
+        // $1000 of WETH at $2500
+        let amount = usd_to_token_amount(Decimal::from(1000), Decimal::from(2500), 18).unwrap();
+        assert_eq!(amount, U256::from(400_000_000_000_000_000u64));
+
+        assert!(matches!(
+            usd_to_token_amount(Decimal::ONE, Decimal::ZERO, 18),
+            Err(AppError::PriceOracle(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_usd_output_quote() {
+        let (from, to) = tokens();
+        let price = PriceInfo {
+            token: to.clone(),
+            price: "1".to_string(),
+            quote_currency: QuoteCurrency::USD,
+            source: crate::types::PriceSource::Peg,
+            timestamp: 0,
+            confidence: None,
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
+        };
+        let quote = ExactOutputQuote {
+            from_token: from,
+            to_token: to,
+            amount_in: "0.2".to_string(),
+            amount_in_raw: "200000000000000000".to_string(),
+            amount_out: "500".to_string(),
+            amount_out_raw: "500000000".to_string(),
+            price: "0.0004".to_string(),
+            route: route(UniswapVersion::V3, 1, Some(500)),
+        };
+
+        let result =
+            TradeCostService::build_usd_output_quote(Decimal::from(500), price, quote).unwrap();
+
+        assert_eq!(result.target_usd, "500.00");
+        assert_eq!(result.amount_in, "0.2");
+        assert_eq!(result.amount_out, "500");
+        assert_eq!(result.price, "0.0004");
+        assert_eq!(result.input_usd_rate, "2500");
+        assert_eq!(result.route.fee_tier, Some(500));
+    }
+
+    #[test]
+    fn test_breakdown_failed_simulation_is_noted() {
+        let (from, to) = tokens();
+        let mut sim = simulation(route(UniswapVersion::V3, 1, Some(500)));
+        sim.simulation_success = false;
+        sim.simulation_error = Some("insufficient balance".to_string());
+
+        let breakdown =
+            TradeCostService::build_breakdown(from, to, sim, UsdPrices::default()).unwrap();
+
+        assert!(!breakdown.simulation_success);
+        assert!(breakdown.notes.iter().any(|n| n.contains("insufficient balance")));
+    }
+
+    /// Buy 0.9801 WETH with 3000 USDC and sell it back for 2881.78803 USDC, with a
+    /// 1% fee and 1% price impact per leg at a pool price of 3000.
+    fn round_trip_legs() -> (SwapSimulationResult, SwapSimulationResult) {
+        let mut buy = simulation(route(UniswapVersion::V3, 1, Some(10000)));
+        buy.amount_in = "3000".to_string();
+        buy.amount_out_expected = "0.9801".to_string();
+        buy.amount_out_minimum = "0.975199".to_string();
+
+        let mut sell = simulation(route(UniswapVersion::V3, 1, Some(10000)));
+        sell.amount_in = "0.9801".to_string();
+        sell.amount_out_expected = "2881.78803".to_string();
+        sell.amount_out_minimum = "2867.379089".to_string();
+        (buy, sell)
+    }
+
+    #[test]
+    fn test_round_trip_at_current_price() {
+        let (weth, usdc) = tokens();
+        let (buy, sell) = round_trip_legs();
+
+        let pnl = TradeCostService::build_round_trip(
+            usdc,
+            weth,
+            buy,
+            Ok(sell),
+            None,
+            Some(Decimal::new(5, 4)),
+        )
+        .unwrap();
+
+        assert_eq!(pnl.entry_price.as_deref(), Some("3000"));
+        assert_eq!(pnl.exit_price.as_deref(), Some("3000"));
+        assert!(!pnl.exit_price_is_hypothetical);
+        assert_eq!(pnl.amount_returned.as_deref(), Some("2881.78803"));
+        // 30 USDC on the buy leg, 0.009801 WETH at 3000 on the sell leg
+        assert_eq!(pnl.amm_fees.as_deref(), Some("59.403"));
+        // 0.0099 WETH at 3000 on the buy leg, 2881.78803 / 99 on the sell leg
+        assert_eq!(pnl.price_impact_cost.as_deref(), Some("58.80897"));
+        // 0.006 ETH at 0.0005 ETH per USDC
+        assert_eq!(pnl.gas_cost_eth, "0.006");
+        assert_eq!(pnl.gas_cost.as_deref(), Some("12"));
+        assert_eq!(pnl.net_pnl.as_deref(), Some("-130.21197"));
+        assert_eq!(pnl.net_pnl_percent.as_deref(), Some("-4.3404"));
+        assert!(pnl.net_pnl_includes_gas);
+        let worst: Decimal = pnl.worst_case_net_pnl.unwrap().parse().unwrap();
+        assert!(worst < Decimal::from(-130));
+        assert!(pnl.notes.is_empty());
+        assert_eq!(pnl.sell.unwrap().from_token.symbol, "WETH");
+    }
+
+    #[test]
+    fn test_round_trip_at_hypothetical_exit_price() {
+        let (weth, usdc) = tokens();
+        let (buy, sell) = round_trip_legs();
+
+        let pnl = TradeCostService::build_round_trip(
+            usdc,
+            weth,
+            buy,
+            Ok(sell),
+            Some(Decimal::from(3300)),
+            Some(Decimal::new(5, 4)),
+        )
+        .unwrap();
+
+        assert!(pnl.exit_price_is_hypothetical);
+        assert_eq!(pnl.exit_price.as_deref(), Some("3300"));
+        // 10% more than at the current price
+        assert_eq!(pnl.amount_returned.as_deref(), Some("3169.966833"));
+        assert_eq!(pnl.net_pnl.as_deref(), Some("157.966833"));
+        assert_eq!(pnl.net_pnl_percent.as_deref(), Some("5.2656"));
+    }
+
+    #[test]
+    fn test_round_trip_unpriceable_gas_and_failed_sell() {
+        let (weth, usdc) = tokens();
+        let (buy, sell) = round_trip_legs();
+
+        let pnl = TradeCostService::build_round_trip(
+            usdc.clone(),
+            weth.clone(),
+            buy.clone(),
+            Ok(sell),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(!pnl.net_pnl_includes_gas);
+        assert!(pnl.gas_cost.is_none());
+        assert_eq!(pnl.net_pnl.as_deref(), Some("-118.21197"));
+        assert!(pnl.notes[0].contains("USDC could not be priced in ETH"));
+
+        let pnl = TradeCostService::build_round_trip(
+            usdc,
+            weth,
+            buy,
+            Err("No route found".to_string()),
+            Some(Decimal::from(3300)),
+            Some(Decimal::new(5, 4)),
+        )
+        .unwrap();
+        assert!(pnl.sell.is_none());
+        assert!(pnl.amount_returned.is_none());
+        assert!(pnl.net_pnl.is_none());
+        assert!(pnl.worst_case_net_pnl.is_none());
+        assert_eq!(pnl.entry_price.as_deref(), Some("3000"));
+        assert_eq!(pnl.gas_cost_eth, "0.003");
+        assert!(pnl.notes[0].contains("No route found"));
+
+        let json = serde_json::to_value(&pnl).unwrap();
+        assert!(json.get("net_pnl").is_none());
+        assert!(json.get("sell").is_none());
+        assert_eq!(json["buy"]["amount_out_expected"], "0.9801");
+    }
+
+    fn candidate(symbol: &str, amount_out: &str, to_usd: Option<Decimal>) -> BestSwapCandidate {
+        let token = TokenInfo::erc20(Address::repeat_byte(2), symbol.to_string(), 18);
+        let mut sim = simulation(route(UniswapVersion::V3, 1, Some(3000)));
+        sim.amount_out_expected = amount_out.to_string();
+        build_candidate(token, sim, to_usd, Some(Decimal::from(3000)))
+    }
+
+    #[test]
+    fn test_build_candidate_values_output() {
+        let candidate = candidate("UNI", "300", Some(Decimal::from(10)));
+
+        assert_eq!(candidate.output_value_usd.as_deref(), Some("3000.00"));
+        // 0.003 ETH of gas at $3000
+        assert_eq!(candidate.net_output_value_usd.as_deref(), Some("2991.00"));
+        assert_eq!(candidate.price_impact_percent.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_rank_candidates_by_output_usd() {
+        let failed = BestSwapCandidate {
+            reason: Some("No route found".to_string()),
+            route: None,
+            amount_out_expected: None,
+            price_impact_percent: None,
+            ..candidate("XYZ", "0", None)
+        };
+        let candidates = vec![
+            failed,
+            candidate("UNI", "300", Some(Decimal::from(10))),
+            candidate("FOO", "5", None),
+            candidate("LINK", "200", Some(Decimal::from(20))),
+        ];
+
+        let ranked = rank_candidates(candidates, SwapRankingMetric::OutputUsd);
+
+        let symbols: Vec<_> = ranked.iter().map(|c| c.to_token.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["LINK", "UNI", "XYZ", "FOO"]);
+        assert_eq!(ranked.iter().map(|c| c.rank).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(ranked[0].ranked && ranked[1].ranked);
+        assert!(!ranked[2].ranked && !ranked[3].ranked);
+        assert_eq!(ranked[2].reason.as_deref(), Some("No route found"));
+        assert_eq!(ranked[3].reason.as_deref(), Some("FOO could not be priced in USD"));
+    }
+
+    #[test]
+    fn test_rank_candidates_by_price_impact() {
+        let mut low = candidate("UNI", "300", None);
+        low.price_impact_percent = Some("0.2".to_string());
+        let high = candidate("LINK", "200", None);
+
+        let ranked = rank_candidates(vec![high, low], SwapRankingMetric::PriceImpact);
+
+        assert_eq!(ranked[0].to_token.symbol, "UNI");
+        assert!(ranked.iter().all(|c| c.ranked && c.reason.is_none()));
+    }
+}
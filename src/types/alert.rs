@@ -0,0 +1,122 @@
+//! Price alert types.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::PriceInfo;
+
+/// Comparison operator for a price alert condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComparisonOperator {
+    /// Price equals the threshold (within tolerance).
+    #[serde(rename = "==")]
+    Eq,
+    /// Price is strictly above the threshold.
+    #[serde(rename = ">")]
+    Gt,
+    /// Price is strictly below the threshold.
+    #[serde(rename = "<")]
+    Lt,
+    /// Price is at or above the threshold.
+    #[serde(rename = ">=")]
+    Gte,
+    /// Price is at or below the threshold.
+    #[serde(rename = "<=")]
+    Lte,
+}
+
+impl ComparisonOperator {
+    /// Evaluate `price <op> threshold`.
+    ///
+    /// `tolerance` is a percentage of the threshold and only applies to `==`.
+    pub fn evaluate(&self, price: Decimal, threshold: Decimal, tolerance: Decimal) -> bool {
+        match self {
+            ComparisonOperator::Eq => {
+                let allowed = (threshold * tolerance / Decimal::from(100)).abs();
+                (price - threshold).abs() <= allowed
+            }
+            ComparisonOperator::Gt => price > threshold,
+            ComparisonOperator::Lt => price < threshold,
+            ComparisonOperator::Gte => price >= threshold,
+            ComparisonOperator::Lte => price <= threshold,
+        }
+    }
+}
+
+impl std::str::FromStr for ComparisonOperator {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "==" | "=" => Ok(ComparisonOperator::Eq),
+            ">" => Ok(ComparisonOperator::Gt),
+            "<" => Ok(ComparisonOperator::Lt),
+            ">=" => Ok(ComparisonOperator::Gte),
+            "<=" => Ok(ComparisonOperator::Lte),
+            _ => Err(format!("Invalid operator: {} (expected ==, >, <, >= or <=)", s)),
+        }
+    }
+}
+
+/// Result of a price alert check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceAlertResult {
+    /// Current price information.
+    pub price: PriceInfo,
+    /// Operator the price was compared with.
+    pub operator: ComparisonOperator,
+    /// Threshold the price was compared against.
+    pub threshold: String,
+    /// Equality tolerance as a percentage of the threshold (only used for `==`).
+    pub tolerance: String,
+    /// Whether the condition is met.
+    pub triggered: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comparison_operator_from_str() {
+        assert_eq!("==".parse::<ComparisonOperator>().unwrap(), ComparisonOperator::Eq);
+        assert_eq!("=".parse::<ComparisonOperator>().unwrap(), ComparisonOperator::Eq);
+        assert_eq!(">".parse::<ComparisonOperator>().unwrap(), ComparisonOperator::Gt);
+        assert_eq!("<".parse::<ComparisonOperator>().unwrap(), ComparisonOperator::Lt);
+        assert_eq!(" >= ".parse::<ComparisonOperator>().unwrap(), ComparisonOperator::Gte);
+        assert_eq!("<=".parse::<ComparisonOperator>().unwrap(), ComparisonOperator::Lte);
+        assert!("!=".parse::<ComparisonOperator>().is_err());
+        assert!("".parse::<ComparisonOperator>().is_err());
+    }
+
+    #[test]
+    fn test_comparison_operator_serialization() {
+        assert_eq!(serde_json::to_string(&ComparisonOperator::Gte).unwrap(), "\">=\"");
+        let op: ComparisonOperator = serde_json::from_str("\"<\"").unwrap();
+        assert_eq!(op, ComparisonOperator::Lt);
+    }
+
+    #[test]
+    fn test_evaluate_ordering() {
+        let price = Decimal::from(3000);
+        let tolerance = Decimal::ZERO;
+
+        assert!(ComparisonOperator::Gt.evaluate(price, Decimal::from(2999), tolerance));
+        assert!(!ComparisonOperator::Gt.evaluate(price, Decimal::from(3000), tolerance));
+        assert!(ComparisonOperator::Gte.evaluate(price, Decimal::from(3000), tolerance));
+        assert!(ComparisonOperator::Lt.evaluate(price, Decimal::from(3001), tolerance));
+        assert!(!ComparisonOperator::Lt.evaluate(price, Decimal::from(3000), tolerance));
+        assert!(ComparisonOperator::Lte.evaluate(price, Decimal::from(3000), tolerance));
+    }
+
+    #[test]
+    fn test_evaluate_equality_tolerance() {
+        let threshold = Decimal::from(3000);
+        let tolerance = Decimal::new(1, 1); // 0.1% = 3.0
+
+        assert!(ComparisonOperator::Eq.evaluate(Decimal::from(3000), threshold, Decimal::ZERO));
+        assert!(ComparisonOperator::Eq.evaluate(Decimal::from(3003), threshold, tolerance));
+        assert!(ComparisonOperator::Eq.evaluate(Decimal::from(2997), threshold, tolerance));
+        assert!(!ComparisonOperator::Eq.evaluate(Decimal::new(30031, 1), threshold, tolerance));
+    }
+}
@@ -0,0 +1,96 @@
+//! Server capability types.
+
+use serde::{Deserialize, Serialize};
+
+use super::{PriceSource, ProtocolPreference, QuoteCurrency, UniswapVersion, Venue};
+
+/// What the server can do with its current configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Configured chain ID.
+    pub chain_id: u64,
+    /// Network name ("mainnet", "sepolia", or "unknown").
+    pub network: String,
+    /// Networks with built-in contract addresses and tokens.
+    pub supported_networks: Vec<SupportedNetwork>,
+    /// Currencies prices can be quoted in.
+    pub quote_currencies: Vec<QuoteCurrency>,
+    /// Price sources available on the configured network.
+    pub price_sources: Vec<PriceSource>,
+    /// Uniswap versions swaps are routed through.
+    pub swap_protocols: Vec<UniswapVersion>,
+    /// Venues V2 routes and prices are quoted on.
+    pub v2_venues: Vec<Venue>,
+    /// Features enabled by the configuration.
+    pub features: CapabilityFeatures,
+    /// Tools this server exposes, sorted.
+    pub enabled_tools: Vec<String>,
+}
+
+/// A network with built-in contract addresses and tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupportedNetwork {
+    /// Chain ID.
+    pub chain_id: u64,
+    /// Network name.
+    pub network: String,
+}
+
+/// Configuration-dependent features.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityFeatures {
+    /// Whether a wallet is configured; without one the server is read-only.
+    pub wallet_configured: bool,
+    /// Whether swap simulations can run (they need a wallet as sender).
+    pub swap_simulation: bool,
+    /// Whether transactions are signed and broadcast. Always false: swaps are only simulated.
+    pub execution: bool,
+    /// Whether prices in ETH are available (a wrapped native token is known).
+    pub eth_pricing: bool,
+    /// Whether trading tools refuse tokens not on a curated token list.
+    pub trusted_tokens_only: bool,
+    /// Whether a failed price falls back to the last known price.
+    pub stale_price_fallback: bool,
+    /// Default protocol order for automatic route selection.
+    pub protocol_preference: ProtocolPreference,
+    /// Blocks behind the latest that balances and reserves are read at.
+    pub confirmations: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_serialization() {
+        let capabilities = Capabilities {
+            chain_id: 1,
+            network: "mainnet".to_string(),
+            supported_networks: vec![SupportedNetwork {
+                chain_id: 1,
+                network: "mainnet".to_string(),
+            }],
+            quote_currencies: vec![QuoteCurrency::USD, QuoteCurrency::ETH],
+            price_sources: vec![PriceSource::Chainlink, PriceSource::UniswapV3],
+            swap_protocols: vec![UniswapVersion::V2, UniswapVersion::V3],
+            v2_venues: vec![Venue::Uniswap, Venue::Sushiswap],
+            features: CapabilityFeatures {
+                wallet_configured: false,
+                swap_simulation: false,
+                execution: false,
+                eth_pricing: true,
+                trusted_tokens_only: false,
+                stale_price_fallback: false,
+                protocol_preference: ProtocolPreference::V3First,
+                confirmations: 0,
+            },
+            enabled_tools: vec!["get_capabilities".to_string()],
+        };
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(json["quote_currencies"], serde_json::json!(["USD", "ETH"]));
+        assert_eq!(json["price_sources"], serde_json::json!(["chainlink", "uniswap_v3"]));
+        assert_eq!(json["swap_protocols"], serde_json::json!(["v2", "v3"]));
+        assert_eq!(json["v2_venues"], serde_json::json!(["uniswap", "sushiswap"]));
+        assert_eq!(json["features"]["protocol_preference"], "v3_first");
+    }
+}
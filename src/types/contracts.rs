@@ -0,0 +1,96 @@
+//! Contract address and function selector types.
+
+use serde::{Deserialize, Serialize};
+
+/// Contract addresses the server uses on the configured network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractAddresses {
+    /// Configured chain ID.
+    pub chain_id: u64,
+    /// Network name ("mainnet", "sepolia", or "unknown").
+    pub network: String,
+    /// Uniswap V2 Router02 address.
+    pub uniswap_v2_router: String,
+    /// Uniswap V2 Factory address.
+    pub uniswap_v2_factory: String,
+    /// Uniswap V3 SwapRouter address.
+    pub uniswap_v3_router: String,
+    /// Uniswap V3 Factory address.
+    pub uniswap_v3_factory: String,
+    /// Uniswap V3 QuoterV2 address.
+    pub uniswap_v3_quoter: String,
+    /// Sushiswap V2 router address, when Sushiswap is a configured venue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sushiswap_v2_router: Option<String>,
+    /// Sushiswap V2 factory address, when Sushiswap is a configured venue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sushiswap_v2_factory: Option<String>,
+    /// Wrapped native token (WETH) address, if known for the network.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weth: Option<String>,
+    /// Chainlink price feeds used for token prices.
+    pub chainlink_feeds: Vec<ChainlinkFeedAddress>,
+}
+
+/// A Chainlink price feed and the pair it prices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainlinkFeedAddress {
+    /// Pair priced by the feed (e.g., "ETH/USD").
+    pub pair: String,
+    /// Feed (aggregator proxy) address.
+    pub address: String,
+}
+
+/// Function selector looked up among the known contract ABIs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecodedSelector {
+    /// The 4-byte selector (0x-prefixed hex).
+    pub selector: String,
+    /// Whether a known function has this selector.
+    pub known: bool,
+    /// Matching functions; more than one only on a selector collision.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<SelectorMatch>,
+    /// Bytes of calldata after the selector, when full calldata was given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub argument_bytes: Option<usize>,
+    /// Explanation when the selector is unknown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// A known function matching a selector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectorMatch {
+    /// Canonical function signature, e.g. `transfer(address,uint256)`.
+    pub signature: String,
+    /// Known interfaces declaring the function (e.g., "IERC20", "IUniswapV2Pair").
+    pub interfaces: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contract_addresses_omits_unknown_weth() {
+        let addresses = ContractAddresses {
+            chain_id: 10,
+            network: "unknown".to_string(),
+            uniswap_v2_router: "0x01".to_string(),
+            uniswap_v2_factory: "0x02".to_string(),
+            uniswap_v3_router: "0x03".to_string(),
+            uniswap_v3_factory: "0x04".to_string(),
+            uniswap_v3_quoter: "0x05".to_string(),
+            sushiswap_v2_router: None,
+            sushiswap_v2_factory: None,
+            weth: None,
+            chainlink_feeds: Vec::new(),
+        };
+        let json = serde_json::to_value(&addresses).unwrap();
+        assert!(json.get("weth").is_none());
+        assert!(json.get("sushiswap_v2_router").is_none());
+        assert_eq!(json["network"], "unknown");
+        assert_eq!(json["chainlink_feeds"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,59 @@
+//! Rendering of tool results.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::Warning;
+
+/// How a tool result is returned to the client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseFormat {
+    /// The full result as pretty-printed JSON.
+    #[default]
+    Json,
+    /// A concise human-readable summary.
+    Text,
+}
+
+impl std::str::FromStr for ResponseFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Ok(ResponseFormat::Json),
+            "text" => Ok(ResponseFormat::Text),
+            _ => Err(format!("Invalid format: {} (expected \"json\" or \"text\")", s)),
+        }
+    }
+}
+
+/// Write each warning of a text summary on its own line.
+pub(crate) fn write_warnings(f: &mut fmt::Formatter<'_>, warnings: &[Warning]) -> fmt::Result {
+    for warning in warnings {
+        write!(f, "\nWarning: {}", warning.message)?;
+    }
+    Ok(())
+}
+
+/// Display adapter for warnings appended to a text summary, one per line.
+pub(crate) struct Warnings<'a>(pub(crate) &'a [Warning]);
+
+impl fmt::Display for Warnings<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_warnings(f, self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_format_parsing() {
+        assert_eq!("json".parse::<ResponseFormat>().unwrap(), ResponseFormat::Json);
+        assert_eq!(" Text ".parse::<ResponseFormat>().unwrap(), ResponseFormat::Text);
+        assert!("markdown".parse::<ResponseFormat>().is_err());
+        assert_eq!(ResponseFormat::default(), ResponseFormat::Json);
+    }
+}
@@ -0,0 +1,114 @@
+//! Liquidity provision types.
+
+use alloy::primitives::{Address, U256};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{TokenInfo, TransactionData, Warning};
+
+/// Parameters for adding liquidity to a Uniswap V2 pair.
+#[derive(Debug, Clone, Default)]
+pub struct AddLiquidityParams {
+    /// First token address.
+    pub token_a: Address,
+    /// Second token address.
+    pub token_b: Address,
+    /// Most of `token_a` to deposit, in smallest units.
+    pub amount_a_desired: U256,
+    /// Most of `token_b` to deposit, in smallest units.
+    pub amount_b_desired: U256,
+    /// Slippage tolerance as a percentage (e.g., 0.5 for 0.5%).
+    pub slippage_tolerance: Decimal,
+    /// Receiver of the LP tokens.
+    pub recipient: Address,
+    /// Transaction deadline (Unix timestamp).
+    pub deadline: Option<u64>,
+}
+
+/// Expected outcome of adding liquidity to a Uniswap V2 pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddLiquiditySimulation {
+    /// Pair address; omitted when the pair does not exist yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pair: Option<String>,
+    /// First token.
+    pub token_a: TokenInfo,
+    /// Second token.
+    pub token_b: TokenInfo,
+    /// The pair's current `token_a` reserve (human-readable).
+    pub reserve_a: String,
+    /// The pair's current `token_b` reserve (human-readable).
+    pub reserve_b: String,
+    /// `token_a` deposited at the pair's current ratio (human-readable).
+    pub amount_a: String,
+    /// `token_b` deposited at the pair's current ratio (human-readable).
+    pub amount_b: String,
+    /// Least `token_a` the router may deposit after slippage (human-readable).
+    pub amount_a_min: String,
+    /// Least `token_b` the router may deposit after slippage (human-readable).
+    pub amount_b_min: String,
+    /// LP tokens expected to be minted (human-readable, 18 decimals).
+    pub lp_tokens: String,
+    /// LP token supply before the deposit (human-readable, 18 decimals).
+    pub total_supply: String,
+    /// Share of the pair owned by the minted LP tokens after the deposit.
+    pub share_of_pool_percent: String,
+    /// Unsigned `addLiquidity` call to the V2 router.
+    pub transaction: TransactionData,
+    /// Non-fatal advisories about this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+/// Parameters for removing liquidity from a Uniswap V2 pair.
+#[derive(Debug, Clone, Default)]
+pub struct RemoveLiquidityParams {
+    /// First token address.
+    pub token_a: Address,
+    /// Second token address.
+    pub token_b: Address,
+    /// LP tokens to burn, in smallest units (`None` burns the owner's whole balance).
+    pub liquidity: Option<U256>,
+    /// Slippage tolerance as a percentage (e.g., 0.5 for 0.5%).
+    pub slippage_tolerance: Decimal,
+    /// Holder of the LP tokens, who also receives the withdrawn tokens.
+    pub owner: Address,
+    /// Transaction deadline (Unix timestamp).
+    pub deadline: Option<u64>,
+}
+
+/// Expected outcome of removing liquidity from a Uniswap V2 pair.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveLiquiditySimulation {
+    /// Pair address.
+    pub pair: String,
+    /// First token.
+    pub token_a: TokenInfo,
+    /// Second token.
+    pub token_b: TokenInfo,
+    /// The pair's current `token_a` reserve (human-readable).
+    pub reserve_a: String,
+    /// The pair's current `token_b` reserve (human-readable).
+    pub reserve_b: String,
+    /// LP tokens burned (human-readable, 18 decimals).
+    pub lp_tokens: String,
+    /// The owner's LP token balance (human-readable, 18 decimals).
+    pub lp_balance: String,
+    /// LP token supply before the withdrawal (human-readable, 18 decimals).
+    pub total_supply: String,
+    /// Share of the pair withdrawn.
+    pub share_of_pool_percent: String,
+    /// `token_a` expected back (human-readable).
+    pub amount_a: String,
+    /// `token_b` expected back (human-readable).
+    pub amount_b: String,
+    /// Least `token_a` the router may return after slippage (human-readable).
+    pub amount_a_min: String,
+    /// Least `token_b` the router may return after slippage (human-readable).
+    pub amount_b_min: String,
+    /// Unsigned `removeLiquidity` call to the V2 router.
+    pub transaction: TransactionData,
+    /// Non-fatal advisories about this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
@@ -2,8 +2,30 @@
 //!
 //! Contains shared types used across the application.
 
+pub mod alert;
+pub mod capabilities;
+pub mod contracts;
+pub mod format;
+pub mod liquidity;
+pub mod pool_activity;
+pub mod rpc;
 pub mod swap;
 pub mod token;
+pub mod token_list;
+pub mod trade_cost;
+pub mod verification;
+pub mod warning;
 
+pub use alert::*;
+pub use capabilities::*;
+pub use contracts::*;
+pub use format::*;
+pub use liquidity::*;
+pub use pool_activity::*;
+pub use rpc::*;
 pub use swap::*;
 pub use token::*;
+pub use token_list::*;
+pub use trade_cost::*;
+pub use verification::*;
+pub use warning::*;
@@ -0,0 +1,213 @@
+//! Pool activity types.
+
+use alloy::primitives::Address;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::{TokenInfo, UniswapVersion, Venue, V2_POOL_FEE_PPM};
+
+/// Which way a swap moved through a pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapDirection {
+    /// token0 in, token1 out.
+    Token0ToToken1,
+    /// token1 in, token0 out.
+    Token1ToToken0,
+}
+
+/// A decoded `Swap` event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSwap {
+    /// Block the swap was included in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Hash of the transaction that emitted the event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<String>,
+    /// Direction of the swap.
+    pub direction: SwapDirection,
+    /// Symbol of the token sent into the pool.
+    pub token_in: String,
+    /// Symbol of the token taken out of the pool.
+    pub token_out: String,
+    /// Amount sent into the pool (human-readable).
+    pub amount_in: String,
+    /// Amount taken out of the pool (human-readable).
+    pub amount_out: String,
+    /// Address that called the pool (usually a router).
+    pub sender: String,
+    /// Address that received the output.
+    pub recipient: String,
+}
+
+/// Recent swaps of a pool, newest first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentPoolSwaps {
+    /// Pool address.
+    pub pool: String,
+    /// Protocol version of the pool.
+    pub protocol: UniswapVersion,
+    /// The pool's token0.
+    pub token0: TokenInfo,
+    /// The pool's token1.
+    pub token1: TokenInfo,
+    /// First block searched.
+    pub from_block: u64,
+    /// Last block searched.
+    pub to_block: u64,
+    /// Swaps found, newest first.
+    pub swaps: Vec<PoolSwap>,
+}
+
+/// Trading fee of one Uniswap pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolFee {
+    /// DEX the pool belongs to.
+    pub venue: Venue,
+    /// Protocol version of the pool.
+    pub protocol: UniswapVersion,
+    /// Pair or pool address.
+    pub pool: String,
+    /// Fee tier in hundredths of a bip (only for V3).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
+    /// Fee in basis points (e.g., "30").
+    pub fee_bps: String,
+    /// Fee as a percentage of the input (e.g., "0.3").
+    pub fee_percent: String,
+}
+
+impl PoolFee {
+    /// The fixed 0.30% fee of a V2 pair on `venue`.
+    pub fn v2(venue: Venue, pair: Address) -> Self {
+        Self { venue, ..Self::new(UniswapVersion::V2, pair, V2_POOL_FEE_PPM) }
+    }
+
+    /// The fee of a V3 pool at `fee_tier` (hundredths of a bip).
+    pub fn v3(pool: Address, fee_tier: u32) -> Self {
+        Self { fee_tier: Some(fee_tier), ..Self::new(UniswapVersion::V3, pool, fee_tier) }
+    }
+
+    fn new(protocol: UniswapVersion, pool: Address, fee_ppm: u32) -> Self {
+        let fee = Decimal::from(fee_ppm);
+        Self {
+            venue: Venue::Uniswap,
+            protocol,
+            pool: format!("{pool:?}"),
+            fee_tier: None,
+            fee_bps: (fee / Decimal::ONE_HUNDRED).normalize().to_string(),
+            fee_percent: (fee / Decimal::from(10_000)).normalize().to_string(),
+        }
+    }
+}
+
+/// Uniswap pools of a token pair and their trading fees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairPoolFees {
+    /// First token of the pair, as requested.
+    pub token_a: TokenInfo,
+    /// Second token of the pair, as requested.
+    pub token_b: TokenInfo,
+    /// Existing pools: the V2 pair of each venue first, then V3 pools by
+    /// ascending fee tier.
+    /// Empty when the pair has no pool.
+    pub pools: Vec<PoolFee>,
+}
+
+/// Estimated annualized fee yield of a pool, extrapolated from a recent window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolFeeApr {
+    /// The pool and its trading fee.
+    #[serde(flatten)]
+    pub fee: PoolFee,
+    /// The pool's token0.
+    pub token0: TokenInfo,
+    /// The pool's token1.
+    pub token1: TokenInfo,
+    /// First block of the window.
+    pub from_block: u64,
+    /// Last block of the window.
+    pub to_block: u64,
+    /// Duration of the window in seconds.
+    pub window_secs: u64,
+    /// Number of swaps in the window.
+    pub swap_count: usize,
+    /// token0 sent into the pool by swaps in the window (human-readable).
+    pub volume_token0: String,
+    /// token1 sent into the pool by swaps in the window (human-readable).
+    pub volume_token1: String,
+    /// Fees paid in token0 over the window (human-readable).
+    pub fees_token0: String,
+    /// Fees paid in token1 over the window (human-readable).
+    pub fees_token1: String,
+    /// The pool's current token0 balance (human-readable).
+    pub tvl_token0: String,
+    /// The pool's current token1 balance (human-readable).
+    pub tvl_token1: String,
+    /// Estimated fee APR as a percentage of TVL; omitted for an empty pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_apr_percent: Option<String>,
+    /// How the estimate was made and what it leaves out.
+    pub assumptions: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_fee_units() {
+        let v2 = PoolFee::v2(Venue::Sushiswap, Address::repeat_byte(1));
+        assert_eq!(v2.venue, Venue::Sushiswap);
+        assert_eq!(v2.protocol, UniswapVersion::V2);
+        assert_eq!(v2.fee_tier, None);
+        assert_eq!(v2.fee_bps, "30");
+        assert_eq!(v2.fee_percent, "0.3");
+
+        let lowest = PoolFee::v3(Address::repeat_byte(2), 100);
+        assert_eq!(lowest.venue, Venue::Uniswap);
+        assert_eq!(lowest.fee_tier, Some(100));
+        assert_eq!(lowest.fee_bps, "1");
+        assert_eq!(lowest.fee_percent, "0.01");
+
+        let low = PoolFee::v3(Address::repeat_byte(3), 500);
+        assert_eq!(low.fee_bps, "5");
+        assert_eq!(low.fee_percent, "0.05");
+    }
+
+    #[test]
+    fn test_pool_fee_serialization_omits_v2_fee_tier() {
+        let json = serde_json::to_value(PoolFee::v2(Venue::Uniswap, Address::ZERO)).unwrap();
+        assert!(json.get("fee_tier").is_none());
+        assert_eq!(json["venue"], "uniswap");
+        assert_eq!(json["protocol"], "v2");
+    }
+
+    #[test]
+    fn test_pool_fee_apr_flattens_fee() {
+        let apr = PoolFeeApr {
+            fee: PoolFee::v3(Address::repeat_byte(1), 500),
+            token0: TokenInfo::erc20(Address::repeat_byte(2), "USDC".to_string(), 6),
+            token1: TokenInfo::erc20(Address::repeat_byte(3), "WETH".to_string(), 18),
+            from_block: 1,
+            to_block: 7_200,
+            window_secs: 86_400,
+            swap_count: 0,
+            volume_token0: "0".to_string(),
+            volume_token1: "0".to_string(),
+            fees_token0: "0".to_string(),
+            fees_token1: "0".to_string(),
+            tvl_token0: "0".to_string(),
+            tvl_token1: "0".to_string(),
+            estimated_apr_percent: None,
+            assumptions: Vec::new(),
+        };
+
+        let json = serde_json::to_value(&apr).unwrap();
+        assert_eq!(json["protocol"], "v3");
+        assert_eq!(json["fee_tier"], 500);
+        assert_eq!(json["pool"], format!("{:?}", Address::repeat_byte(1)));
+        assert!(json.get("estimated_apr_percent").is_none());
+    }
+}
@@ -0,0 +1,184 @@
+//! RPC endpoint health types.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Warning, WarningCode};
+
+/// How far the RPC node's latest block trails the server's clock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcFreshness {
+    /// Chain ID reported by the node.
+    pub chain_id: u64,
+    /// Latest block number reported by the node.
+    pub latest_block: u64,
+    /// Timestamp of the node's latest block (Unix seconds).
+    pub block_timestamp: u64,
+    /// The server's clock when the block was read (Unix seconds).
+    pub server_time: u64,
+    /// `server_time - block_timestamp`; negative when the block is ahead of the
+    /// server's clock, which points at clock skew on the server.
+    pub lag_secs: i64,
+    /// `lag_secs` in whole blocks at the network's block time (0 when negative).
+    pub lag_blocks: u64,
+    /// Lag in seconds above which the node is reported as lagging.
+    pub max_lag_secs: u64,
+    /// Whether `lag_secs` exceeds `max_lag_secs`.
+    pub lagging: bool,
+    /// Non-fatal advisories about this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl RpcFreshness {
+    /// Compare the latest block's timestamp with the server's clock.
+    ///
+    /// A healthy node trails by less than one block time.
+    pub fn new(
+        chain_id: u64,
+        latest_block: u64,
+        block_timestamp: u64,
+        server_time: u64,
+        seconds_per_block: u64,
+        max_lag_secs: u64,
+    ) -> Self {
+        let lag_secs = i64::try_from(server_time)
+            .unwrap_or(i64::MAX)
+            .saturating_sub(i64::try_from(block_timestamp).unwrap_or(i64::MAX));
+        let lag = u64::try_from(lag_secs).unwrap_or(0);
+        let lag_blocks = lag / seconds_per_block.max(1);
+        let lagging = lag > max_lag_secs;
+
+        let mut warnings = Vec::new();
+        if lagging {
+            warnings.push(Warning::new(
+                WarningCode::RpcLagging,
+                format!(
+                    "The RPC node's latest block ({}) is {}s (about {} blocks) old, more than \
+                     {}s; prices, balances and swap deadlines may be based on stale state. The \
+                     endpoint may be out of sync or serving archived data",
+                    latest_block, lag, lag_blocks, max_lag_secs
+                ),
+            ));
+        }
+
+        Self {
+            chain_id,
+            latest_block,
+            block_timestamp,
+            server_time,
+            lag_secs,
+            lag_blocks,
+            max_lag_secs,
+            lagging,
+            warnings,
+        }
+    }
+}
+
+/// How many blocks remain before a swap deadline expires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadlineEstimate {
+    /// Deadline being estimated (Unix seconds).
+    pub deadline: u64,
+    /// Latest block number reported by the node.
+    pub latest_block: u64,
+    /// Timestamp of the node's latest block (Unix seconds).
+    pub block_timestamp: u64,
+    /// `deadline - block_timestamp`; negative once the deadline has passed.
+    pub seconds_remaining: i64,
+    /// Average block time the estimate assumes, in seconds.
+    pub seconds_per_block: u64,
+    /// Blocks expected to be produced at or before the deadline (0 when negative).
+    pub blocks_remaining: u64,
+    /// Estimated last block a transaction with this deadline can be included in.
+    pub last_block: u64,
+    /// Whether no further block is expected before the deadline.
+    pub expired: bool,
+}
+
+impl DeadlineEstimate {
+    /// Estimate the blocks left before `deadline`, measured from the latest block.
+    ///
+    /// Routers compare the deadline with the including block's timestamp, so the
+    /// chain's clock is used rather than the server's.
+    pub fn new(
+        deadline: u64,
+        latest_block: u64,
+        block_timestamp: u64,
+        seconds_per_block: u64,
+    ) -> Self {
+        let seconds_remaining = i64::try_from(deadline)
+            .unwrap_or(i64::MAX)
+            .saturating_sub(i64::try_from(block_timestamp).unwrap_or(i64::MAX));
+        let blocks_remaining =
+            u64::try_from(seconds_remaining).unwrap_or(0) / seconds_per_block.max(1);
+
+        Self {
+            deadline,
+            latest_block,
+            block_timestamp,
+            seconds_remaining,
+            seconds_per_block,
+            blocks_remaining,
+            last_block: latest_block.saturating_add(blocks_remaining),
+            expired: blocks_remaining == 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_freshness_lag() {
+        let fresh = RpcFreshness::new(1, 100, 1_700_000_000, 1_700_000_008, 12, 60);
+        assert_eq!(fresh.lag_secs, 8);
+        assert_eq!(fresh.lag_blocks, 0);
+        assert!(!fresh.lagging);
+        assert!(fresh.warnings.is_empty());
+
+        let stale = RpcFreshness::new(1, 100, 1_700_000_000, 1_700_000_300, 12, 60);
+        assert_eq!(stale.lag_secs, 300);
+        assert_eq!(stale.lag_blocks, 25);
+        assert!(stale.lagging);
+        assert_eq!(stale.warnings[0].code, WarningCode::RpcLagging);
+    }
+
+    #[test]
+    fn test_deadline_estimate() {
+        // 20 minutes ahead at 12s blocks
+        let estimate = DeadlineEstimate::new(1_700_001_200, 100, 1_700_000_000, 12);
+        assert_eq!(estimate.seconds_remaining, 1_200);
+        assert_eq!(estimate.blocks_remaining, 100);
+        assert_eq!(estimate.last_block, 200);
+        assert!(!estimate.expired);
+
+        // Partial blocks do not count
+        let estimate = DeadlineEstimate::new(1_700_000_030, 100, 1_700_000_000, 12);
+        assert_eq!(estimate.blocks_remaining, 2);
+
+        // Less than one block left
+        let estimate = DeadlineEstimate::new(1_700_000_005, 100, 1_700_000_000, 12);
+        assert_eq!(estimate.blocks_remaining, 0);
+        assert_eq!(estimate.last_block, 100);
+        assert!(estimate.expired);
+
+        // Already passed
+        let estimate = DeadlineEstimate::new(1_699_999_940, 100, 1_700_000_000, 12);
+        assert_eq!(estimate.seconds_remaining, -60);
+        assert_eq!(estimate.blocks_remaining, 0);
+        assert!(estimate.expired);
+
+        // A zero block time is treated as one second
+        assert_eq!(DeadlineEstimate::new(1_700_000_010, 1, 1_700_000_000, 0).blocks_remaining, 10);
+    }
+
+    #[test]
+    fn test_rpc_freshness_block_ahead_of_clock() {
+        let skewed = RpcFreshness::new(1, 100, 1_700_000_030, 1_700_000_000, 12, 60);
+        assert_eq!(skewed.lag_secs, -30);
+        assert_eq!(skewed.lag_blocks, 0);
+        assert!(!skewed.lagging);
+    }
+}
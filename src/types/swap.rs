@@ -2,9 +2,12 @@
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{format::write_warnings, TokenInfo, Warning, WarningCode};
 
 /// Parameters for a swap operation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SwapParams {
     /// Input token address.
     pub from_token: alloy::primitives::Address,
@@ -16,6 +19,168 @@ pub struct SwapParams {
     pub slippage_tolerance: Decimal,
     /// Transaction deadline (Unix timestamp).
     pub deadline: Option<u64>,
+    /// Explicit route to use instead of automatic route discovery.
+    pub forced_route: Option<ForcedRoute>,
+    /// Gas limit to use instead of estimating gas.
+    pub gas_limit: Option<u64>,
+    /// Format of the returned transaction.
+    pub transaction_format: TransactionFormat,
+    /// Protocol order for automatic route selection (`None` uses the server default).
+    pub protocol_preference: Option<ProtocolPreference>,
+    /// Sender of the simulated call and gas estimate instead of the wallet.
+    ///
+    /// The wallet stays the recipient and the signer of any transaction.
+    pub from_address: Option<alloy::primitives::Address>,
+    /// Extra slippage tolerances (percentages) to compute the minimum output at.
+    pub slippage_levels: Vec<Decimal>,
+    /// Whether to re-quote the selected route from pool state and compare the outputs.
+    pub cross_check_quote: bool,
+    /// Whether to include a decoded view of the transaction calldata.
+    pub decode_transaction: bool,
+    /// Absolute minimum output in the output token's smallest unit. The
+    /// stricter of this and the slippage-derived minimum is sent.
+    pub min_amount_out: Option<alloy::primitives::U256>,
+}
+
+/// Order in which automatic route selection tries the Uniswap protocols.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolPreference {
+    /// Try V3, and V2 only if no V3 pool qualifies.
+    #[default]
+    V3First,
+    /// Try V2, and V3 only if no V2 route qualifies.
+    V2First,
+    /// Try both and use the route with the larger output.
+    Best,
+}
+
+impl std::str::FromStr for ProtocolPreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "v3_first" | "v3" => Ok(ProtocolPreference::V3First),
+            "v2_first" | "v2" => Ok(ProtocolPreference::V2First),
+            "best" => Ok(ProtocolPreference::Best),
+            _ => Err(format!(
+                "Invalid protocol preference: {} (expected \"v3_first\", \"v2_first\" or \"best\")",
+                s
+            )),
+        }
+    }
+}
+
+/// DEX whose contracts a route trades through.
+///
+/// Sushiswap is a Uniswap V2 fork, so it only offers V2 routes. The venue names
+/// only the DEX: a route's pool type is its `protocol`, so a Uniswap V3 route is
+/// `"uniswap"` with protocol `"v3"` rather than a combined `"uniswap_v3"`. The
+/// same names configure `ETHEREUM_V2_VENUES`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Venue {
+    /// Uniswap (V2 or V3).
+    #[default]
+    Uniswap,
+    /// Sushiswap (V2 only).
+    Sushiswap,
+}
+
+impl std::fmt::Display for Venue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Venue::Uniswap => write!(f, "Uniswap"),
+            Venue::Sushiswap => write!(f, "Sushiswap"),
+        }
+    }
+}
+
+impl std::str::FromStr for Venue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "uniswap" => Ok(Venue::Uniswap),
+            "sushiswap" | "sushi" => Ok(Venue::Sushiswap),
+            _ => Err(format!("Invalid venue: {} (expected \"uniswap\" or \"sushiswap\")", s)),
+        }
+    }
+}
+
+/// Format of the transaction returned with a swap simulation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionFormat {
+    /// Only `to`, `data` and `value` (and the gas limit, when set).
+    #[default]
+    Minimal,
+    /// Also a complete EIP-1559 transaction for a wallet or external signer.
+    Eip1559,
+}
+
+impl std::str::FromStr for TransactionFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "minimal" => Ok(TransactionFormat::Minimal),
+            "eip1559" | "eip-1559" => Ok(TransactionFormat::Eip1559),
+            _ => Err(format!(
+                "Invalid transaction format: {} (expected \"minimal\" or \"eip1559\")",
+                s
+            )),
+        }
+    }
+}
+
+/// Which limit set a swap's minimum output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MinimumOutSource {
+    /// The expected output less the slippage tolerance.
+    #[default]
+    Slippage,
+    /// The caller's absolute `min_amount_out`, stricter than the slippage minimum.
+    MinAmountOut,
+}
+
+/// Token of a swap to express its gas cost in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasCostToken {
+    /// The input token.
+    FromToken,
+    /// The output token.
+    ToToken,
+}
+
+impl std::str::FromStr for GasCostToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "from_token" | "from" => Ok(GasCostToken::FromToken),
+            "to_token" | "to" => Ok(GasCostToken::ToToken),
+            _ => Err(format!(
+                "Invalid gas cost token: {} (expected \"from_token\" or \"to_token\")",
+                s
+            )),
+        }
+    }
+}
+
+/// A caller-specified swap route that bypasses automatic route discovery.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForcedRoute {
+    /// Protocol version to route through.
+    pub protocol: UniswapVersion,
+    /// Fee tier applied to every hop (required for V3, ignored for V2).
+    pub fee_tier: Option<u32>,
+    /// Intermediary tokens between the input and output token, in order.
+    pub intermediaries: Vec<alloy::primitives::Address>,
+    /// Venue a V2 route trades on (V3 routes are always on Uniswap).
+    pub venue: Venue,
 }
 
 /// Uniswap protocol version.
@@ -28,6 +193,18 @@ pub enum UniswapVersion {
     V3,
 }
 
+impl std::str::FromStr for UniswapVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "v2" => Ok(UniswapVersion::V2),
+            "v3" => Ok(UniswapVersion::V3),
+            _ => Err(format!("Invalid protocol: {} (expected \"v2\" or \"v3\")", s)),
+        }
+    }
+}
+
 /// Swap route information.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapRoute {
@@ -38,6 +215,44 @@ pub struct SwapRoute {
     /// Fee tier (only for V3, in basis points).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee_tier: Option<u32>,
+    /// DEX the route trades on, without the protocol version.
+    #[serde(default)]
+    pub venue: Venue,
+}
+
+/// Uniswap V2 pool fee, in hundredths of a bip (0.3%).
+pub const V2_POOL_FEE_PPM: u32 = 3000;
+
+impl SwapRoute {
+    /// Fee charged by each pool on the route, in hundredths of a bip.
+    ///
+    /// V2 pairs always charge 0.3%; V3 routes use their fee tier, and are
+    /// `None` without one.
+    pub fn hop_fee_ppm(&self) -> Option<u32> {
+        match self.protocol {
+            UniswapVersion::V2 => Some(V2_POOL_FEE_PPM),
+            UniswapVersion::V3 => self.fee_tier,
+        }
+    }
+
+    /// Pool fees paid on `amount_in`, in input token units.
+    ///
+    /// Each hop charges its fee on what is left after the previous hop. Fees
+    /// charged by later hops are converted back to the input token at the same
+    /// rate, so the total is `amount_in * (1 - Π(1 - fee))`, rounded down.
+    ///
+    /// `None` for a V3 route without a fee tier, or when the amount overflows.
+    pub fn fee_amount(
+        &self,
+        amount_in: alloy::primitives::U256,
+    ) -> Option<alloy::primitives::U256> {
+        let scale = alloy::primitives::U256::from(1_000_000u32);
+        let keep = alloy::primitives::U256::from(1_000_000 - self.hop_fee_ppm()?.min(1_000_000));
+        let hops = self.path.len().saturating_sub(1);
+        let remaining = (0..hops)
+            .try_fold(amount_in, |amount, _| amount.checked_mul(keep).map(|kept| kept / scale))?;
+        Some(amount_in - remaining)
+    }
 }
 
 /// Raw transaction data for inspection.
@@ -49,11 +264,83 @@ pub struct TransactionData {
     pub data: String,
     /// Value in wei (hex encoded).
     pub value: String,
+    /// Gas limit, when set by the caller.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
 }
 
+/// Router call a swap's calldata encodes, decoded from `transaction.data`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedTransaction {
+    /// Router function called (e.g., "exactInputSingle").
+    pub function: String,
+    /// 4-byte function selector (hex encoded).
+    pub selector: String,
+    /// Input amount in the input token's smallest unit.
+    pub amount_in: String,
+    /// Minimum output in the output token's smallest unit; the swap reverts below it.
+    pub amount_out_min: String,
+    /// Token addresses from input to output.
+    pub path: Vec<String>,
+    /// Fee tier of each V3 hop (empty for V2).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fee_tiers: Vec<u32>,
+    /// Address the output tokens are sent to.
+    pub recipient: String,
+    /// Unix timestamp after which the router rejects the swap.
+    pub deadline: u64,
+}
+
+/// An EIP-1559 transaction ready for `eth_sendTransaction` or a wallet.
+///
+/// Field names and hex-encoded quantities follow the JSON-RPC transaction object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletTransaction {
+    /// Transaction type (`0x2` for EIP-1559).
+    #[serde(rename = "type")]
+    pub tx_type: String,
+    /// Sender (the configured wallet).
+    pub from: String,
+    /// Target contract address.
+    pub to: String,
+    /// Calldata (hex encoded).
+    pub data: String,
+    /// Value in wei.
+    pub value: String,
+    /// Chain ID reported by the node.
+    pub chain_id: String,
+    /// Gas limit (the caller's gas limit or the estimate).
+    pub gas: String,
+    /// Maximum total fee per gas in wei.
+    pub max_fee_per_gas: String,
+    /// Maximum priority fee per gas in wei.
+    pub max_priority_fee_per_gas: String,
+    /// Next nonce of the sender, counting pending transactions.
+    pub nonce: String,
+}
+
+/// Minimum output of a swap at one slippage tolerance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlippageLevel {
+    /// Slippage tolerance as a percentage.
+    pub slippage: String,
+    /// Minimum output after this slippage (human-readable).
+    pub min_out: String,
+}
+
+/// Note carried by every swap simulation result.
+pub const SIMULATION_NOTE: &str = "This is a simulation; no transaction was broadcast";
+
 /// Result of a swap simulation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapSimulationResult {
+    /// Whether the swap was executed on chain. Always false: swaps are only simulated.
+    #[serde(default)]
+    pub executed: bool,
+    /// States that no transaction was broadcast ([`SIMULATION_NOTE`]).
+    #[serde(default)]
+    pub note: String,
     /// Whether the simulation was successful (transaction would execute).
     pub simulation_success: bool,
     /// Error message if simulation failed.
@@ -61,22 +348,416 @@ pub struct SwapSimulationResult {
     pub simulation_error: Option<String>,
     /// Input amount (human-readable).
     pub amount_in: String,
+    /// Input amount in the token's smallest unit, as sent on-chain.
+    pub amount_in_raw: String,
+    /// Decimals of the input token, as reported by its contract (`None` when
+    /// its `decimals()` call failed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_token_decimals: Option<u8>,
     /// Expected output amount (human-readable).
     pub amount_out_expected: String,
     /// Minimum output after slippage (human-readable).
     pub amount_out_minimum: String,
+    /// Whether `amount_out_minimum` comes from the slippage tolerance or the
+    /// caller's absolute `min_amount_out`.
+    #[serde(default)]
+    pub amount_out_minimum_source: MinimumOutSource,
+    /// `amount_out_minimum` at each requested slippage level, from the same quote.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slippage_table: Vec<SlippageLevel>,
+    /// Value of `amount_out_expected` in USD, when the output token can be priced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_out_expected_usd: Option<String>,
+    /// Value of `amount_out_minimum` in USD: the worst case if the slippage
+    /// tolerance is fully consumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_out_minimum_usd: Option<String>,
+    /// Pool fees paid on the input, in input token units (human-readable).
+    #[serde(default)]
+    pub amm_fee_amount: String,
+    /// `amount_out_expected - amount_out_minimum`: output given up to the slippage
+    /// tolerance, in output token units (human-readable). Excludes the AMM fee,
+    /// which is already deducted from `amount_out_expected`.
+    #[serde(default)]
+    pub slippage_buffer_amount: String,
     /// Price impact as a percentage.
     pub price_impact: String,
+    /// Pool mid price after the swap, in output tokens per input token (fee excluded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_trade_price: Option<String>,
     /// Estimated gas units.
     pub gas_estimate: String,
+    /// Whether `gas_estimate` is a fallback value because estimation failed.
+    pub gas_estimate_is_fallback: bool,
+    /// Why gas estimation failed and which fallback was used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_estimate_fallback_reason: Option<String>,
+    /// Whether `gas_estimate` is a caller-provided gas limit rather than an estimate.
+    #[serde(default)]
+    pub gas_limit_is_override: bool,
     /// Current gas price in wei.
     pub gas_price: String,
     /// Gas cost in ETH (human-readable).
     pub gas_cost_eth: String,
+    /// Gas cost in `gas_cost_token`, when requested and the token could be priced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_cost_in_token: Option<String>,
+    /// Symbol of the token `gas_cost_in_token` is expressed in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_cost_token: Option<String>,
     /// Swap route used.
     pub route: SwapRoute,
+    /// Better-priced pools skipped because their liquidity is below the minimum.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_pools: Vec<SkippedPool>,
+    /// Whether `amount_out_expected` was compared against an independent quote.
+    #[serde(default)]
+    pub quote_cross_checked: bool,
+    /// The independent quote, when a cross-check was requested and could be computed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_cross_check: Option<QuoteCrossCheck>,
     /// Raw transaction data.
     pub transaction: TransactionData,
+    /// Decoded view of `transaction.data`, when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded_transaction: Option<DecodedTransaction>,
+    /// Complete EIP-1559 transaction, when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_transaction: Option<WalletTransaction>,
+    /// Non-fatal advisories about this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl SwapSimulationResult {
+    /// Flag a mismatch between the decimals the input amount was parsed with
+    /// and the decimals the token contract reports.
+    ///
+    /// Nothing is checked when the contract's decimals are unknown.
+    pub fn check_parsed_decimals(&mut self, symbol: &str, parsed_decimals: u8) {
+        let Some(reported_decimals) = self.from_token_decimals else { return };
+        if parsed_decimals == reported_decimals {
+            return;
+        }
+        let message = format!(
+            "Amount was parsed with {} decimals from the token list, but the {} contract \
+             reports {} decimals; the swap spends {} {} ({} base units)",
+            parsed_decimals, symbol, reported_decimals, self.amount_in, symbol, self.amount_in_raw
+        );
+        self.warnings.push(Warning::new(WarningCode::DecimalsMismatch, message));
+    }
+
+    /// A one-line summary of the swap, followed by any failure and warnings.
+    ///
+    /// The result only records token addresses, so the caller supplies the symbols.
+    pub fn summary<'a>(&'a self, from_symbol: &'a str, to_symbol: &'a str) -> SwapSummary<'a> {
+        SwapSummary { result: self, from_symbol, to_symbol }
+    }
+
+    /// The price impact, if it is above `max_percent`.
+    ///
+    /// An impact that could not be calculated (see [`Self::price_impact_unavailable`])
+    /// is never above.
+    pub fn price_impact_above(&self, max_percent: Decimal) -> Option<Decimal> {
+        self.price_impact.parse::<Decimal>().ok().filter(|&impact| impact > max_percent)
+    }
+
+    /// Whether the price impact could not be calculated, so `price_impact` is not a real value.
+    pub fn price_impact_unavailable(&self) -> bool {
+        self.price_impact.parse::<Decimal>().is_err()
+            || self.warnings.iter().any(|w| w.code == WarningCode::PriceImpactUnavailable)
+    }
+
+    /// Value the expected and minimum outputs in USD at `usd_price` per output token.
+    pub fn value_output_in_usd(&mut self, usd_price: Decimal) {
+        let value = |amount: &str| {
+            amount
+                .parse::<Decimal>()
+                .ok()
+                .map(|amount| format!("{:.2}", (amount * usd_price).round_dp(2)))
+        };
+        self.amount_out_expected_usd = value(&self.amount_out_expected);
+        self.amount_out_minimum_usd = value(&self.amount_out_minimum);
+    }
+
+    /// Express the gas cost in a token worth `eth_price` ETH, rounded to its decimals.
+    ///
+    /// Leaves the gas cost in ETH only if the price is not positive.
+    pub fn value_gas_in_token(&mut self, symbol: &str, decimals: u8, eth_price: Decimal) {
+        if eth_price <= Decimal::ZERO {
+            return;
+        }
+        let Ok(gas_cost_eth) = self.gas_cost_eth.parse::<Decimal>() else {
+            return;
+        };
+        let Some(cost) = gas_cost_eth.checked_div(eth_price) else {
+            return;
+        };
+        self.gas_cost_in_token = Some(cost.round_dp(decimals.into()).normalize().to_string());
+        self.gas_cost_token = Some(symbol.to_string());
+    }
+}
+
+/// Text summary of a [`SwapSimulationResult`],
+/// e.g. `1 WETH → 2994.5 USDC (V3 0.3%, impact 0.2%, gas ~0.004 ETH)`.
+pub struct SwapSummary<'a> {
+    result: &'a SwapSimulationResult,
+    from_symbol: &'a str,
+    to_symbol: &'a str,
+}
+
+impl fmt::Display for SwapSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = self.result;
+        write!(
+            f,
+            "{} {} → {} {} (",
+            result.amount_in, self.from_symbol, result.amount_out_expected, self.to_symbol
+        )?;
+        match result.route.venue {
+            Venue::Uniswap => write!(f, "{:?}", result.route.protocol)?,
+            venue => write!(f, "{} {:?}", venue, result.route.protocol)?,
+        }
+        if let Some(fee_tier) = result.route.fee_tier {
+            write!(f, " {}%", Decimal::new(fee_tier.into(), 4).normalize())?;
+        }
+        if result.route.path.len() > 2 {
+            write!(f, ", {} hops", result.route.path.len() - 1)?;
+        }
+        write!(
+            f,
+            ", impact {}%, gas ~{} ETH)\nMinimum received: {} {}",
+            result.price_impact, result.gas_cost_eth, result.amount_out_minimum, self.to_symbol
+        )?;
+        if let Some(error) = &result.simulation_error {
+            write!(f, "\nSimulation failed: {}", error)?;
+        }
+        write_warnings(f, &result.warnings)
+    }
+}
+
+#[cfg(test)]
+impl SwapSimulationResult {
+    /// A successful simulation of 1 WETH for 3000 USDC through a V3 pool, for
+    /// tests to override field by field.
+    pub(crate) fn fixture() -> Self {
+        Self {
+            executed: false,
+            note: SIMULATION_NOTE.to_string(),
+            simulation_success: true,
+            simulation_error: None,
+            amount_in: "1".to_string(),
+            amount_in_raw: "1000000000000000000".to_string(),
+            from_token_decimals: Some(18),
+            amount_out_expected: "3000".to_string(),
+            amount_out_minimum: "2985".to_string(),
+            slippage_table: Vec::new(),
+            amount_out_expected_usd: None,
+            amount_out_minimum_usd: None,
+            amount_out_minimum_source: MinimumOutSource::Slippage,
+            amm_fee_amount: "0".to_string(),
+            slippage_buffer_amount: "0".to_string(),
+            price_impact: "0".to_string(),
+            post_trade_price: None,
+            gas_estimate: "150000".to_string(),
+            gas_estimate_is_fallback: false,
+            gas_estimate_fallback_reason: None,
+            gas_limit_is_override: false,
+            gas_price: "30000000000".to_string(),
+            gas_cost_eth: "0.0045".to_string(),
+            gas_cost_in_token: None,
+            gas_cost_token: None,
+            route: SwapRoute {
+                protocol: UniswapVersion::V3,
+                path: vec!["WETH".to_string(), "USDC".to_string()],
+                fee_tier: Some(3000),
+                venue: Venue::Uniswap,
+            },
+            skipped_pools: Vec::new(),
+            quote_cross_checked: false,
+            quote_cross_check: None,
+            transaction: TransactionData {
+                to: "0xRouter".to_string(),
+                data: "0x".to_string(),
+                value: "0".to_string(),
+                gas_limit: None,
+            },
+            wallet_transaction: None,
+            decoded_transaction: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// One leg of a simulated swap path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapPathLeg {
+    /// Input token of this leg.
+    pub from_token: TokenInfo,
+    /// Output token of this leg.
+    pub to_token: TokenInfo,
+    /// Simulation of this leg.
+    #[serde(flatten)]
+    pub simulation: SwapSimulationResult,
+}
+
+/// Result of simulating a sequence of dependent swaps.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapPathResult {
+    /// Starting input amount (human-readable).
+    pub amount_in: String,
+    /// Expected output of the last simulated leg (human-readable).
+    pub final_amount_out: String,
+    /// Token of `final_amount_out`.
+    pub final_token: TokenInfo,
+    /// Whether every leg could be routed.
+    pub completed: bool,
+    /// Whether every leg's on-chain simulation succeeded.
+    pub all_simulations_succeeded: bool,
+    /// Index of the leg that could not be routed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_leg: Option<usize>,
+    /// Why the failed leg could not be routed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Sum of the legs' gas estimates.
+    pub total_gas_estimate: String,
+    /// Sum of the legs' gas costs in ETH.
+    pub total_gas_cost_eth: String,
+    /// Per-leg results, in execution order.
+    pub legs: Vec<SwapPathLeg>,
+}
+
+/// A pool that quoted a better price but was skipped for low liquidity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedPool {
+    /// Protocol version of the pool.
+    pub protocol: UniswapVersion,
+    /// Venue of the pool.
+    pub venue: Venue,
+    /// Fee tier (only for V3, in hundredths of a bip).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
+    /// Output the pool quoted (human-readable).
+    pub amount_out: String,
+    /// Pool liquidity (V3 `liquidity()`, or `sqrt(reserve0 * reserve1)` for V2).
+    pub liquidity: String,
+}
+
+/// How an independent quote was computed from pool state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteCrossCheckMethod {
+    /// Constant-product formula on each V2 pair's reserves, bypassing the router.
+    V2Reserves,
+    /// Single-tick math on each V3 pool's price and active liquidity, bypassing the quoter.
+    V3SingleTick,
+}
+
+/// The selected route re-quoted independently of the quoter and router.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteCrossCheck {
+    /// How the independent quote was computed.
+    pub method: QuoteCrossCheckMethod,
+    /// Output of the independent quote (human-readable).
+    pub amount_out: String,
+    /// Difference between the two quotes as a percentage of `amount_out_expected`.
+    pub discrepancy_percent: String,
+    /// Largest discrepancy accepted without a warning, as a percentage.
+    pub tolerance_percent: String,
+    /// Whether `discrepancy_percent` is within `tolerance_percent`.
+    pub within_tolerance: bool,
+}
+
+/// Quote for receiving an exact output amount.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExactOutputQuote {
+    /// Input token.
+    pub from_token: TokenInfo,
+    /// Output token.
+    pub to_token: TokenInfo,
+    /// Required input amount (human-readable).
+    pub amount_in: String,
+    /// Required input amount in smallest units.
+    pub amount_in_raw: String,
+    /// Desired output amount (human-readable).
+    pub amount_out: String,
+    /// Desired output amount in `to_token`'s smallest units.
+    pub amount_out_raw: String,
+    /// Implied price: input tokens paid per output token.
+    pub price: String,
+    /// Venue requiring the least input.
+    pub route: SwapRoute,
+}
+
+/// Routes that exist for a token pair, found by looking up pools without quoting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairTradability {
+    /// Input token.
+    pub from_token: TokenInfo,
+    /// Output token.
+    pub to_token: TokenInfo,
+    /// Whether any route exists.
+    pub tradable: bool,
+    /// Existing routes: V3 fee tiers first, then V2 routes by venue.
+    pub routes: Vec<SwapRoute>,
+}
+
+impl PairTradability {
+    /// Report the `routes` found between two tokens.
+    pub fn new(from_token: TokenInfo, to_token: TokenInfo, routes: Vec<SwapRoute>) -> Self {
+        Self { from_token, to_token, tradable: !routes.is_empty(), routes }
+    }
+}
+
+/// A venue considered by a swap preview.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewVenue {
+    /// Protocol version.
+    pub protocol: UniswapVersion,
+    /// DEX the venue belongs to.
+    #[serde(default)]
+    pub venue: Venue,
+    /// Fee tier (only for V3, in hundredths of a bip).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_tier: Option<u32>,
+    /// Token addresses along the venue's path.
+    pub path: Vec<String>,
+    /// Liquidity (V3 `liquidity()`, or the thinnest `sqrt(reserve0 * reserve1)` for V2),
+    /// if it could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub liquidity: Option<String>,
+    /// Quoted output (human-readable), if the venue could be quoted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_out: Option<String>,
+}
+
+/// Cheap pre-check of a swap: resolved tokens, venues and a rough quote.
+///
+/// No gas estimation or `eth_call` simulation is run, so this is an estimate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapPreview {
+    /// Input token.
+    pub from_token: TokenInfo,
+    /// Output token.
+    pub to_token: TokenInfo,
+    /// Input amount (human-readable).
+    pub amount_in: String,
+    /// Input amount in smallest units.
+    pub amount_in_raw: String,
+    /// Every existing pool for the pair, and the V2 route if there is one.
+    pub venues: Vec<PreviewVenue>,
+    /// Route `swap_tokens` would pick, if any venue qualifies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<SwapRoute>,
+    /// Quoted output on `route` (human-readable), before slippage and gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_out_estimate: Option<String>,
+    /// Whether a full `swap_tokens` simulation is likely to succeed.
+    pub likely_to_succeed: bool,
+    /// Reasons the simulation is likely to fail.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub issues: Vec<String>,
 }
 
 #[cfg(test)]
@@ -97,6 +778,30 @@ mod tests {
         assert_eq!(serialized, "\"v3\"");
     }
 
+    #[test]
+    fn test_route_fee_amount() {
+        let route = |protocol, hops: usize, fee_tier| SwapRoute {
+            protocol,
+            path: vec![String::new(); hops + 1],
+            fee_tier,
+            venue: Venue::Uniswap,
+        };
+        let amount_in = U256::from(1_000_000u64);
+
+        let fee = |route: SwapRoute, amount_in| route.fee_amount(amount_in);
+
+        assert_eq!(fee(route(UniswapVersion::V2, 1, None), amount_in), Some(U256::from(3000)));
+        assert_eq!(fee(route(UniswapVersion::V3, 1, Some(500)), amount_in), Some(U256::from(500)));
+        // The second hop charges its fee on what is left after the first
+        assert_eq!(fee(route(UniswapVersion::V2, 2, None), amount_in), Some(U256::from(5991)));
+        assert_eq!(fee(route(UniswapVersion::V3, 1, Some(500)), U256::ZERO), Some(U256::ZERO));
+
+        // A V3 route without a fee tier has no known fee
+        assert_eq!(fee(route(UniswapVersion::V3, 1, None), amount_in), None);
+        // Overflow is reported rather than wrapped
+        assert_eq!(fee(route(UniswapVersion::V2, 1, None), U256::MAX), None);
+    }
+
     #[test]
     fn test_uniswap_version_deserialization() {
         let v2: UniswapVersion = serde_json::from_str("\"v2\"").unwrap();
@@ -119,6 +824,7 @@ mod tests {
             protocol: UniswapVersion::V2,
             path: vec!["0xToken1".to_string(), "0xToken2".to_string()],
             fee_tier: None,
+            venue: Venue::Uniswap,
         };
 
         assert_eq!(route.protocol, UniswapVersion::V2);
@@ -132,6 +838,7 @@ mod tests {
             protocol: UniswapVersion::V3,
             path: vec!["0xWETH".to_string(), "0xUSDC".to_string()],
             fee_tier: Some(3000), // 0.3%
+            venue: Venue::Uniswap,
         };
 
         assert_eq!(route.protocol, UniswapVersion::V3);
@@ -144,6 +851,7 @@ mod tests {
             protocol: UniswapVersion::V2,
             path: vec!["0xToken1".to_string(), "0xWETH".to_string(), "0xToken2".to_string()],
             fee_tier: None,
+            venue: Venue::Uniswap,
         };
 
         assert_eq!(route.path.len(), 3);
@@ -155,16 +863,47 @@ mod tests {
             protocol: UniswapVersion::V3,
             path: vec!["0xA".to_string(), "0xB".to_string()],
             fee_tier: Some(500),
+            venue: Venue::Uniswap,
         };
 
         let json = serde_json::to_string(&route).unwrap();
         assert!(json.contains("\"protocol\":\"v3\""));
         assert!(json.contains("\"fee_tier\":500"));
+        assert!(json.contains("\"venue\":\"uniswap\""));
 
         // Deserialize and verify
         let parsed: SwapRoute = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.protocol, route.protocol);
         assert_eq!(parsed.fee_tier, route.fee_tier);
+        assert_eq!(parsed.venue, route.venue);
+    }
+
+    #[test]
+    fn test_swap_route_venue_serialization() {
+        let route = SwapRoute {
+            protocol: UniswapVersion::V2,
+            path: vec!["0xA".to_string(), "0xB".to_string()],
+            fee_tier: None,
+            venue: Venue::Sushiswap,
+        };
+
+        let json = serde_json::to_value(&route).unwrap();
+        assert_eq!(json["venue"], "sushiswap");
+        assert_eq!(json["protocol"], "v2");
+
+        // Routes serialized before venues existed were all Uniswap
+        let parsed: SwapRoute =
+            serde_json::from_str(r#"{"protocol":"v3","path":["0xA","0xB"],"fee_tier":500}"#)
+                .unwrap();
+        assert_eq!(parsed.venue, Venue::Uniswap);
+    }
+
+    #[test]
+    fn test_venue_from_str() {
+        assert_eq!("uniswap".parse::<Venue>().unwrap(), Venue::Uniswap);
+        assert_eq!("Sushiswap".parse::<Venue>().unwrap(), Venue::Sushiswap);
+        assert_eq!("sushi".parse::<Venue>().unwrap(), Venue::Sushiswap);
+        assert!("curve".parse::<Venue>().is_err());
     }
 
     #[test]
@@ -173,6 +912,7 @@ mod tests {
             protocol: UniswapVersion::V2,
             path: vec!["0xA".to_string(), "0xB".to_string()],
             fee_tier: None,
+            venue: Venue::Uniswap,
         };
 
         let json = serde_json::to_string(&route).unwrap();
@@ -180,12 +920,34 @@ mod tests {
         assert!(!json.contains("fee_tier"));
     }
 
+    #[test]
+    fn test_pair_tradability() {
+        let weth = TokenInfo::erc20(Address::repeat_byte(1), "WETH".to_string(), 18);
+        let usdc = TokenInfo::erc20(Address::repeat_byte(2), "USDC".to_string(), 6);
+
+        let none = PairTradability::new(weth.clone(), usdc.clone(), Vec::new());
+        assert!(!none.tradable);
+
+        let route = SwapRoute {
+            protocol: UniswapVersion::V3,
+            path: vec!["0xA".to_string(), "0xB".to_string()],
+            fee_tier: Some(500),
+            venue: Venue::Uniswap,
+        };
+        let some = PairTradability::new(weth, usdc, vec![route]);
+        let json = serde_json::to_value(&some).unwrap();
+        assert_eq!(json["tradable"], true);
+        assert_eq!(json["routes"][0]["protocol"], "v3");
+        assert_eq!(json["routes"][0]["fee_tier"], 500);
+    }
+
     #[test]
     fn test_transaction_data_creation() {
         let tx = TransactionData {
             to: "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D".to_string(),
             data: "0x38ed1739".to_string(),
             value: "0".to_string(),
+            gas_limit: None,
         };
 
         assert!(!tx.to.is_empty());
@@ -198,9 +960,12 @@ mod tests {
             to: "0xRouter".to_string(),
             data: "0xcalldata".to_string(),
             value: "1000000000000000000".to_string(),
+            gas_limit: None,
         };
 
         let json = serde_json::to_string(&tx).unwrap();
+        // gas_limit should be omitted when None
+        assert!(!json.contains("gas_limit"));
         let parsed: TransactionData = serde_json::from_str(&json).unwrap();
 
         assert_eq!(parsed.to, tx.to);
@@ -216,6 +981,7 @@ mod tests {
             amount_in: U256::from(1_000_000u64),
             slippage_tolerance: Decimal::new(5, 1), // 0.5%
             deadline: Some(1700000000),
+            ..Default::default()
         };
 
         assert_eq!(params.slippage_tolerance, Decimal::new(5, 1));
@@ -230,37 +996,210 @@ mod tests {
             amount_in: U256::from(100u64),
             slippage_tolerance: Decimal::ONE,
             deadline: None,
+            ..Default::default()
         };
 
         assert!(params.deadline.is_none());
+        assert!(params.forced_route.is_none());
+    }
+
+    #[test]
+    fn test_uniswap_version_from_str() {
+        assert_eq!("v2".parse::<UniswapVersion>().unwrap(), UniswapVersion::V2);
+        assert_eq!("V3".parse::<UniswapVersion>().unwrap(), UniswapVersion::V3);
+        assert!("v4".parse::<UniswapVersion>().is_err());
+        assert!("".parse::<UniswapVersion>().is_err());
+    }
+
+    #[test]
+    fn test_protocol_preference_from_str() {
+        assert_eq!("v3_first".parse::<ProtocolPreference>().unwrap(), ProtocolPreference::V3First);
+        assert_eq!("V2".parse::<ProtocolPreference>().unwrap(), ProtocolPreference::V2First);
+        assert_eq!("best".parse::<ProtocolPreference>().unwrap(), ProtocolPreference::Best);
+        assert!("v4".parse::<ProtocolPreference>().is_err());
+        assert_eq!(ProtocolPreference::default(), ProtocolPreference::V3First);
+    }
+
+    #[test]
+    fn test_transaction_format_from_str() {
+        assert_eq!("minimal".parse::<TransactionFormat>().unwrap(), TransactionFormat::Minimal);
+        assert_eq!("EIP1559".parse::<TransactionFormat>().unwrap(), TransactionFormat::Eip1559);
+        assert_eq!("eip-1559".parse::<TransactionFormat>().unwrap(), TransactionFormat::Eip1559);
+        assert!("legacy".parse::<TransactionFormat>().is_err());
+        assert_eq!(TransactionFormat::default(), TransactionFormat::Minimal);
+    }
+
+    #[test]
+    fn test_swap_params_with_forced_route() {
+        let params = SwapParams {
+            from_token: Address::ZERO,
+            to_token: Address::ZERO,
+            amount_in: U256::from(100u64),
+            slippage_tolerance: Decimal::ONE,
+            deadline: None,
+            forced_route: Some(ForcedRoute {
+                protocol: UniswapVersion::V3,
+                fee_tier: Some(500),
+                intermediaries: vec![Address::ZERO],
+                venue: Venue::Uniswap,
+            }),
+            gas_limit: None,
+            transaction_format: TransactionFormat::Minimal,
+            protocol_preference: None,
+            from_address: None,
+            slippage_levels: Vec::new(),
+            cross_check_quote: false,
+            decode_transaction: false,
+            min_amount_out: None,
+        };
+
+        let route = params.forced_route.unwrap();
+        assert_eq!(route.protocol, UniswapVersion::V3);
+        assert_eq!(route.fee_tier, Some(500));
+        assert_eq!(route.intermediaries.len(), 1);
     }
 
     #[test]
     fn test_swap_simulation_result_success() {
         let result = SwapSimulationResult {
-            simulation_success: true,
-            simulation_error: None,
             amount_in: "1.0".to_string(),
             amount_out_expected: "3000.0".to_string(),
             amount_out_minimum: "2985.0".to_string(),
             price_impact: "0.05".to_string(),
-            gas_estimate: "150000".to_string(),
-            gas_price: "30000000000".to_string(),
-            gas_cost_eth: "0.0045".to_string(),
+            ..SwapSimulationResult::fixture()
+        };
+
+        assert!(result.simulation_success);
+        assert!(result.simulation_error.is_none());
+    }
+
+    #[test]
+    fn test_check_parsed_decimals() {
+        let mut result = SwapSimulationResult {
+            amount_in: "0.0000000000015".to_string(),
+            amount_in_raw: "1500000".to_string(),
+            amount_out_expected: "0".to_string(),
+            amount_out_minimum: "0".to_string(),
             route: SwapRoute {
                 protocol: UniswapVersion::V3,
-                path: vec!["WETH".to_string(), "USDC".to_string()],
+                path: vec!["FOO".to_string(), "USDC".to_string()],
                 fee_tier: Some(3000),
+                venue: Venue::Uniswap,
             },
-            transaction: TransactionData {
-                to: "0xRouter".to_string(),
-                data: "0x".to_string(),
-                value: "0".to_string(),
+            ..SwapSimulationResult::fixture()
+        };
+
+        result.check_parsed_decimals("FOO", 18);
+        assert!(result.warnings.is_empty());
+
+        result.check_parsed_decimals("FOO", 6);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].code, WarningCode::DecimalsMismatch);
+        let warning = &result.warnings[0].message;
+        assert!(warning.contains("parsed with 6 decimals"));
+        assert!(warning.contains("reports 18 decimals"));
+        assert!(warning.contains("1500000 base units"));
+
+        // Unknown contract decimals are not compared
+        result.warnings.clear();
+        result.from_token_decimals = None;
+        result.check_parsed_decimals("FOO", 6);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_value_output_in_usd() {
+        let mut result = SwapSimulationResult {
+            amount_out_expected: "2500.5".to_string(),
+            amount_out_minimum: "2488.0025".to_string(),
+            price_impact: "0.01".to_string(),
+            route: SwapRoute {
+                protocol: UniswapVersion::V3,
+                path: vec!["WETH".to_string(), "USDC".to_string()],
+                fee_tier: Some(500),
+                venue: Venue::Uniswap,
             },
+            ..SwapSimulationResult::fixture()
         };
 
-        assert!(result.simulation_success);
-        assert!(result.simulation_error.is_none());
+        let json = serde_json::to_value(&result).unwrap();
+        assert!(json.get("amount_out_expected_usd").is_none());
+        assert!(json.get("amount_out_minimum_usd").is_none());
+
+        result.value_output_in_usd(Decimal::new(9998, 4));
+        assert_eq!(result.amount_out_expected_usd.as_deref(), Some("2500.00"));
+        assert_eq!(result.amount_out_minimum_usd.as_deref(), Some("2487.50"));
+
+        assert!(json.get("gas_cost_in_token").is_none());
+        result.value_gas_in_token("USDC", 6, Decimal::ZERO);
+        assert!(result.gas_cost_in_token.is_none());
+
+        // 0.0045 ETH at 0.0004 ETH per USDC (2500 USDC/ETH)
+        result.value_gas_in_token("USDC", 6, Decimal::new(4, 4));
+        assert_eq!(result.gas_cost_in_token.as_deref(), Some("11.25"));
+        assert_eq!(result.gas_cost_token.as_deref(), Some("USDC"));
+
+        // Rounded to the token's decimals
+        result.value_gas_in_token("GUSD", 2, Decimal::new(3, 4));
+        assert_eq!(result.gas_cost_in_token.as_deref(), Some("15"));
+
+        assert_eq!(result.price_impact_above(Decimal::from(50)), None);
+        assert!(!result.price_impact_unavailable());
+        result.price_impact = "50".to_string();
+        assert_eq!(result.price_impact_above(Decimal::from(50)), None);
+        result.price_impact = "72.5".to_string();
+        assert_eq!(result.price_impact_above(Decimal::from(50)), Some(Decimal::new(725, 1)));
+
+        // An impact reported as 0 because it could not be calculated is not a real value
+        result.price_impact = "0".to_string();
+        result.warnings.push(Warning::new(WarningCode::PriceImpactUnavailable, "no spot quote"));
+        assert!(result.price_impact_unavailable());
+        assert_eq!(result.price_impact_above(Decimal::from(50)), None);
+    }
+
+    #[test]
+    fn test_swap_summary() {
+        let mut result = SwapSimulationResult {
+            amount_out_expected: "2994.5".to_string(),
+            amount_out_minimum: "2979.52".to_string(),
+            amm_fee_amount: "0.003".to_string(),
+            slippage_buffer_amount: "14.98".to_string(),
+            price_impact: "0.2".to_string(),
+            gas_cost_eth: "0.004".to_string(),
+            route: SwapRoute {
+                protocol: UniswapVersion::V3,
+                path: vec!["0xWETH".to_string(), "0xUSDC".to_string()],
+                fee_tier: Some(3000),
+                venue: Venue::Uniswap,
+            },
+            ..SwapSimulationResult::fixture()
+        };
+
+        assert_eq!(
+            result.summary("WETH", "USDC").to_string(),
+            "1 WETH → 2994.5 USDC (V3 0.3%, impact 0.2%, gas ~0.004 ETH)\n\
+             Minimum received: 2979.52 USDC"
+        );
+
+        result.route = SwapRoute {
+            protocol: UniswapVersion::V2,
+            path: vec!["0xA".to_string(), "0xWETH".to_string(), "0xB".to_string()],
+            fee_tier: None,
+            venue: Venue::Sushiswap,
+        };
+        result.simulation_error = Some("STF".to_string());
+        result.warnings.push(Warning::new(WarningCode::HighPriceImpact, "Price impact is high"));
+        let summary = result.summary("A", "B").to_string();
+        assert!(summary.starts_with("1 A → 2994.5 B (Sushiswap V2, 2 hops, impact"));
+        assert!(summary.contains("\nSimulation failed: STF"));
+        assert!(summary.ends_with("\nWarning: Price impact is high"));
+    }
+
+    #[test]
+    fn test_gas_cost_token_from_str() {
+        assert_eq!("from_token".parse::<GasCostToken>(), Ok(GasCostToken::FromToken));
+        assert_eq!("TO".parse::<GasCostToken>(), Ok(GasCostToken::ToToken));
+        assert!("eth".parse::<GasCostToken>().is_err());
     }
 
     #[test]
@@ -269,22 +1208,26 @@ mod tests {
             simulation_success: false,
             simulation_error: Some("Insufficient liquidity".to_string()),
             amount_in: "1000.0".to_string(),
+            amount_in_raw: "1000000000000000000000".to_string(),
             amount_out_expected: "0".to_string(),
             amount_out_minimum: "0".to_string(),
-            price_impact: "0".to_string(),
             gas_estimate: "200000".to_string(),
-            gas_price: "30000000000".to_string(),
+            gas_estimate_is_fallback: true,
+            gas_estimate_fallback_reason: Some("execution reverted".to_string()),
             gas_cost_eth: "0.006".to_string(),
             route: SwapRoute {
                 protocol: UniswapVersion::V2,
                 path: vec!["TokenA".to_string(), "TokenB".to_string()],
                 fee_tier: None,
+                venue: Venue::Uniswap,
             },
             transaction: TransactionData {
                 to: "0x".to_string(),
                 data: "0x".to_string(),
                 value: "0".to_string(),
+                gas_limit: None,
             },
+            ..SwapSimulationResult::fixture()
         };
 
         assert!(!result.simulation_success);
@@ -295,8 +1238,6 @@ mod tests {
     #[test]
     fn test_swap_simulation_result_serialization() {
         let result = SwapSimulationResult {
-            simulation_success: true,
-            simulation_error: None,
             amount_in: "1.0".to_string(),
             amount_out_expected: "100.0".to_string(),
             amount_out_minimum: "99.5".to_string(),
@@ -308,18 +1249,27 @@ mod tests {
                 protocol: UniswapVersion::V3,
                 path: vec!["A".to_string(), "B".to_string()],
                 fee_tier: Some(500),
+                venue: Venue::Uniswap,
             },
             transaction: TransactionData {
                 to: "0xRouter".to_string(),
                 data: "0xdata".to_string(),
                 value: "0".to_string(),
+                gas_limit: None,
             },
+            ..SwapSimulationResult::fixture()
         };
 
         let json = serde_json::to_string(&result).unwrap();
 
         // simulation_error should be omitted when None
         assert!(!json.contains("simulation_error"));
+        assert!(!json.contains("gas_estimate_fallback_reason"));
+        assert!(!json.contains("warnings"));
+        assert!(!json.contains("slippage_table"));
+        assert!(json.contains("\"executed\":false"));
+        assert!(json.contains(SIMULATION_NOTE));
+        assert!(json.contains("\"gas_estimate_is_fallback\":false"));
 
         // Other fields should be present
         assert!(json.contains("simulation_success"));
@@ -3,6 +3,9 @@
 use alloy::primitives::{Address, U256};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+use super::{format::write_warnings, Warning, WarningCode};
 
 /// Information about a token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +31,35 @@ impl TokenInfo {
     }
 }
 
+/// How a token symbol was resolved to an address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolProvenance {
+    /// Symbol as provided by the caller.
+    pub symbol: String,
+    /// Resolved token address.
+    pub address: String,
+    /// Chain ID the symbol was resolved on.
+    pub chain_id: u64,
+    /// Token list URL the entry came from, or "builtin" for built-in fallback tokens.
+    pub source: String,
+    /// Token list name, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_name: Option<String>,
+    /// Token list version (major.minor.patch), if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_version: Option<String>,
+}
+
+/// A tool response with symbol resolution provenance attached.
+#[derive(Debug, Clone, Serialize)]
+pub struct WithProvenance<T> {
+    /// The underlying tool result.
+    #[serde(flatten)]
+    pub result: T,
+    /// How each symbol in the request was resolved.
+    pub provenance: Vec<SymbolProvenance>,
+}
+
 /// Balance information response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceInfo {
@@ -39,10 +71,174 @@ pub struct BalanceInfo {
     pub balance: String,
     /// Raw balance in smallest unit.
     pub balance_raw: String,
+    /// Block the balance was read at, when reading behind the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Value of the balance in the requested base currency, when valued.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Non-fatal advisories about this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl BalanceInfo {
+    /// Round the human-readable `balance` to `decimals` places; `balance_raw` stays exact.
+    ///
+    /// Leaves the balance unchanged if it does not fit a `Decimal`.
+    pub fn round_balance(&mut self, decimals: u32) {
+        if let Ok(balance) = self.balance.parse::<Decimal>() {
+            self.balance = balance.round_dp(decimals).to_string();
+        }
+    }
+}
+
+impl fmt::Display for BalanceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} {}", self.address, self.balance, self.token.symbol)?;
+        if let Some(value) = &self.value {
+            write!(f, " (worth {})", value)?;
+        }
+        if let Some(block) = self.block_number {
+            write!(f, " at block {}", block)?;
+        }
+        write_warnings(f, &self.warnings)
+    }
+}
+
+/// ERC1155 balance response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Erc1155BalanceInfo {
+    /// Account address.
+    pub address: String,
+    /// ERC1155 contract address.
+    pub token_address: String,
+    /// Token ID within the contract (decimal).
+    pub token_id: String,
+    /// Raw balance (ERC1155 has no decimals).
+    pub balance_raw: String,
+    /// Block the balance was read at, when reading behind the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+}
+
+/// Confirmed and pending nonces of an address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonceInfo {
+    /// Checksummed address.
+    pub address: String,
+    /// Nonce at the latest block: the number of confirmed transactions.
+    pub latest_nonce: u64,
+    /// Next nonce counting the node's pending transactions.
+    pub pending_nonce: u64,
+    /// Transactions sent but not yet mined (`pending_nonce - latest_nonce`).
+    pub in_flight_transactions: u64,
+}
+
+impl NonceInfo {
+    /// Build from the nonces at the latest block and the pending state.
+    ///
+    /// A node can answer `pending` from a block older than its `latest` answer,
+    /// so a pending nonce below the latest one counts as no transactions in flight.
+    pub fn new(address: Address, latest_nonce: u64, pending_nonce: u64) -> Self {
+        Self {
+            address: address.to_checksum(None),
+            latest_nonce,
+            pending_nonce,
+            in_flight_transactions: pending_nonce.saturating_sub(latest_nonce),
+        }
+    }
+}
+
+/// Information about the configured wallet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletInfo {
+    /// Whether a wallet is configured (false in read-only mode).
+    pub configured: bool,
+    /// Checksummed wallet address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Native ETH balance, when requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eth_balance: Option<BalanceInfo>,
+    /// Balances of the requested tokens.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub token_balances: Vec<BalanceInfo>,
+    /// Number of requested tokens left out of `token_balances` because their balance is zero.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub zero_balance_tokens_skipped: usize,
+    /// Currency of `total_value` and the per-balance values, when valued.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_currency: Option<QuoteCurrency>,
+    /// Total value of the priced balances in `base_currency`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_value: Option<String>,
+    /// Symbols of balances that could not be priced and are left out of `total_value`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unpriced_tokens: Vec<String>,
+    /// Non-fatal advisories about the valuation as a whole.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+    /// Explanation when no wallet is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl WalletInfo {
+    /// Wallet info for read-only mode.
+    pub fn not_configured() -> Self {
+        Self {
+            configured: false,
+            address: None,
+            eth_balance: None,
+            token_balances: Vec::new(),
+            zero_balance_tokens_skipped: 0,
+            base_currency: None,
+            total_value: None,
+            unpriced_tokens: Vec::new(),
+            warnings: Vec::new(),
+            message: Some(
+                "No wallet configured (read-only mode). Set ETHEREUM_PRIVATE_KEY or \
+                 ETHEREUM_MNEMONIC to enable one."
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for WalletInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(address) = &self.address else {
+            return write!(f, "{}", self.message.as_deref().unwrap_or("No wallet configured"));
+        };
+        write!(f, "Wallet {}", address)?;
+        for balance in self.eth_balance.iter().chain(&self.token_balances) {
+            write!(f, "\n{} {}", balance.balance, balance.token.symbol)?;
+            if let Some(value) = &balance.value {
+                write!(f, " (worth {})", value)?;
+            }
+            write_warnings(f, &balance.warnings)?;
+        }
+        if self.zero_balance_tokens_skipped > 0 {
+            write!(f, "\n{} zero balances not listed", self.zero_balance_tokens_skipped)?;
+        }
+        if let (Some(total), Some(base)) = (&self.total_value, self.base_currency) {
+            write!(f, "\nTotal: {} {:?}", total, base)?;
+        }
+        write_warnings(f, &self.warnings)
+    }
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 /// Quote currency for price queries.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum QuoteCurrency {
     /// US Dollar.
@@ -52,6 +248,11 @@ pub enum QuoteCurrency {
     ETH,
 }
 
+impl QuoteCurrency {
+    /// Every supported quote currency.
+    pub const ALL: [QuoteCurrency; 2] = [QuoteCurrency::USD, QuoteCurrency::ETH];
+}
+
 impl std::str::FromStr for QuoteCurrency {
     type Err = String;
 
@@ -74,6 +275,50 @@ pub enum PriceSource {
     UniswapV2,
     /// Uniswap V3 pool.
     UniswapV3,
+    /// Liquidity-weighted average across Uniswap V2 and V3 pools.
+    UniswapAggregate,
+    /// Fixed 1:1 peg (stablecoin in USD, wrapped native token in ETH).
+    Peg,
+    /// The token is the quote token itself, so its price is exactly 1.
+    Identity,
+}
+
+impl fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriceSource::Chainlink => write!(f, "Chainlink"),
+            PriceSource::UniswapV2 => write!(f, "Uniswap V2"),
+            PriceSource::UniswapV3 => write!(f, "Uniswap V3"),
+            PriceSource::UniswapAggregate => write!(f, "Uniswap aggregate"),
+            PriceSource::Peg => write!(f, "peg"),
+            PriceSource::Identity => write!(f, "identity"),
+        }
+    }
+}
+
+/// How far a price can be trusted.
+///
+/// Derived from the source, the depth of the pool it was read from and, for
+/// aggregate prices, how closely the venues agree. See [`PriceInfo::confidence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceConfidence {
+    /// Thin pool, diverging venues or a stale price: weight it lightly.
+    Low,
+    /// A single pool or an assumed peg.
+    Medium,
+    /// Chainlink oracle, or several deep venues that agree.
+    High,
+}
+
+impl fmt::Display for PriceConfidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PriceConfidence::Low => write!(f, "low"),
+            PriceConfidence::Medium => write!(f, "medium"),
+            PriceConfidence::High => write!(f, "high"),
+        }
+    }
 }
 
 /// Price information response.
@@ -89,6 +334,189 @@ pub struct PriceInfo {
     pub source: PriceSource,
     /// Timestamp of price data.
     pub timestamp: u64,
+    /// How far the price can be trusted, when it could be judged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<PriceConfidence>,
+    /// Block the pool reserves were read at, when reading behind the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Whether every price source failed and this is the last price fetched instead.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub stale: bool,
+    /// Age of a stale price in seconds, measured from `timestamp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_secs: Option<u64>,
+    /// Non-fatal advisories about this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl fmt::Display for PriceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "1 {} = {} {:?} ({}",
+            self.token.symbol, self.price, self.quote_currency, self.source
+        )?;
+        if let Some(confidence) = self.confidence {
+            write!(f, ", {} confidence", confidence)?;
+        }
+        if self.stale {
+            match self.age_secs {
+                Some(age) => write!(f, ", stale: {}s old", age)?,
+                None => write!(f, ", stale")?,
+            }
+        }
+        write!(f, ")")?;
+        write_warnings(f, &self.warnings)
+    }
+}
+
+/// A token's price in one quote currency, as part of a [`MultiPriceInfo`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotePrice {
+    /// Current price.
+    pub price: String,
+    /// Price data source.
+    pub source: PriceSource,
+    /// Timestamp of price data.
+    pub timestamp: u64,
+    /// How far the price can be trusted, when it could be judged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<PriceConfidence>,
+    /// Block the pool reserves were read at, when reading behind the latest block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// Whether every price source failed and this is the last price fetched instead.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub stale: bool,
+    /// Age of a stale price in seconds, measured from `timestamp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_secs: Option<u64>,
+    /// Non-fatal advisories about this price.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl From<PriceInfo> for QuotePrice {
+    fn from(info: PriceInfo) -> Self {
+        Self {
+            price: info.price,
+            source: info.source,
+            timestamp: info.timestamp,
+            confidence: info.confidence,
+            block_number: info.block_number,
+            stale: info.stale,
+            age_secs: info.age_secs,
+            warnings: info.warnings,
+        }
+    }
+}
+
+/// A token's price in several quote currencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiPriceInfo {
+    /// Token information.
+    pub token: TokenInfo,
+    /// Price in each requested quote currency.
+    pub prices: BTreeMap<QuoteCurrency, QuotePrice>,
+}
+
+impl fmt::Display for MultiPriceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (quote_currency, quote)) in self.prices.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "1 {} = {} {:?} ({}",
+                self.token.symbol, quote.price, quote_currency, quote.source
+            )?;
+            if let Some(confidence) = quote.confidence {
+                write!(f, ", {} confidence", confidence)?;
+            }
+            if quote.stale {
+                write!(f, ", stale")?;
+            }
+            write!(f, ")")?;
+            write_warnings(f, &quote.warnings)?;
+        }
+        Ok(())
+    }
+}
+
+/// A token's Chainlink and Uniswap USD prices side by side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSourceSpread {
+    /// Token information.
+    pub token: TokenInfo,
+    /// Price from the token's Chainlink feed.
+    pub chainlink: PriceInfo,
+    /// Price from Uniswap (V3, falling back to V2).
+    pub uniswap: PriceInfo,
+    /// `(uniswap - chainlink) / chainlink` in basis points; positive when
+    /// Uniswap is higher.
+    pub spread_bps: String,
+    /// Spread, in basis points either way, above which the sources diverge.
+    pub max_spread_bps: u32,
+    /// Whether the spread exceeds `max_spread_bps`.
+    pub diverging: bool,
+    /// Non-fatal advisories about this result.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl PriceSourceSpread {
+    /// Compare a Chainlink and a Uniswap price of `token`.
+    ///
+    /// Fails if either price is not a number or the Chainlink price is zero.
+    pub fn new(
+        token: TokenInfo,
+        chainlink: PriceInfo,
+        uniswap: PriceInfo,
+        max_spread_bps: u32,
+    ) -> Result<Self, String> {
+        let parse = |info: &PriceInfo| {
+            info.price
+                .parse::<Decimal>()
+                .map_err(|e| format!("Invalid {} price '{}': {}", info.source, info.price, e))
+        };
+        let chainlink_price = parse(&chainlink)?;
+        let uniswap_price = parse(&uniswap)?;
+        if chainlink_price.is_zero() {
+            return Err("Chainlink price is zero".to_string());
+        }
+
+        let spread_bps = ((uniswap_price - chainlink_price) / chainlink_price
+            * Decimal::from(10_000))
+        .round_dp(2)
+        .normalize();
+        let diverging = spread_bps.abs() > Decimal::from(max_spread_bps);
+
+        let warnings = if diverging {
+            vec![Warning::new(
+                WarningCode::PriceSourceDivergence,
+                format!(
+                    "Uniswap ({}) and Chainlink ({}) prices of {} differ by {} bps, more than {}; \
+                     the oracle may be stale or the pool thin or manipulated",
+                    uniswap.price, chainlink.price, token.symbol, spread_bps, max_spread_bps
+                ),
+            )]
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            token,
+            chainlink,
+            uniswap,
+            spread_bps: spread_bps.to_string(),
+            max_spread_bps,
+            diverging,
+            warnings,
+        })
+    }
 }
 
 /// Format a U256 value with decimals to a human-readable string.
@@ -155,6 +583,12 @@ pub fn parse_units(amount: &str, decimals: u8) -> Result<U256, String> {
             let integer = parts[0];
             let mut fraction = parts[1].to_string();
 
+            // Validate the digits before truncating, which may drop all of them
+            // (always, for a token with 0 decimals)
+            if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(format!("Invalid fraction part: {}", parts[1]));
+            }
+
             // Pad or truncate fraction to match decimals
             if fraction.len() > decimals {
                 fraction.truncate(decimals);
@@ -190,6 +624,7 @@ pub fn u256_to_decimal(value: U256, decimals: u8) -> Decimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::WarningCode;
     use alloy::primitives::address;
 
     // ============================================================================
@@ -204,6 +639,18 @@ mod tests {
         assert!(info.address.is_none());
     }
 
+    #[test]
+    fn test_nonce_info_in_flight_transactions() {
+        let addr = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+
+        let info = NonceInfo::new(addr, 7, 9);
+        assert_eq!(info.address, "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert_eq!(info.in_flight_transactions, 2);
+
+        assert_eq!(NonceInfo::new(addr, 7, 7).in_flight_transactions, 0);
+        assert_eq!(NonceInfo::new(addr, 7, 6).in_flight_transactions, 0);
+    }
+
     #[test]
     fn test_token_info_erc20() {
         let addr = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
@@ -260,6 +707,9 @@ mod tests {
             token: TokenInfo::eth(),
             balance: "1.5".to_string(),
             balance_raw: "1500000000000000000".to_string(),
+            block_number: None,
+            value: None,
+            warnings: Vec::new(),
         };
 
         assert_eq!(info.balance, "1.5");
@@ -273,15 +723,46 @@ mod tests {
             token: TokenInfo::eth(),
             balance: "10".to_string(),
             balance_raw: "10000000000000000000".to_string(),
+            block_number: None,
+            value: None,
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&info).unwrap();
+        // value should be omitted when not valued
+        assert!(!json.contains("value"));
+        assert!(!json.contains("warnings"));
         let parsed: BalanceInfo = serde_json::from_str(&json).unwrap();
 
         assert_eq!(parsed.address, info.address);
         assert_eq!(parsed.balance, info.balance);
     }
 
+    #[test]
+    fn test_balance_info_round_balance() {
+        let mut info = BalanceInfo {
+            address: "0xABC".to_string(),
+            token: TokenInfo::eth(),
+            balance: "1.234567890123456789".to_string(),
+            balance_raw: "1234567890123456789".to_string(),
+            block_number: None,
+            value: None,
+            warnings: Vec::new(),
+        };
+
+        info.round_balance(4);
+        assert_eq!(info.balance, "1.2346");
+        assert_eq!(info.balance_raw, "1234567890123456789");
+
+        info.round_balance(0);
+        assert_eq!(info.balance, "1");
+
+        // Fewer places than requested are kept as they are
+        info.balance = "2.5".to_string();
+        info.round_balance(6);
+        assert_eq!(info.balance, "2.5");
+    }
+
     // ============================================================================
     // QuoteCurrency Tests
     // ============================================================================
@@ -337,6 +818,12 @@ mod tests {
         assert_eq!(serde_json::to_string(&PriceSource::Chainlink).unwrap(), "\"chainlink\"");
         assert_eq!(serde_json::to_string(&PriceSource::UniswapV2).unwrap(), "\"uniswap_v2\"");
         assert_eq!(serde_json::to_string(&PriceSource::UniswapV3).unwrap(), "\"uniswap_v3\"");
+        assert_eq!(
+            serde_json::to_string(&PriceSource::UniswapAggregate).unwrap(),
+            "\"uniswap_aggregate\""
+        );
+        assert_eq!(serde_json::to_string(&PriceSource::Peg).unwrap(), "\"peg\"");
+        assert_eq!(serde_json::to_string(&PriceSource::Identity).unwrap(), "\"identity\"");
     }
 
     #[test]
@@ -360,6 +847,11 @@ mod tests {
             quote_currency: QuoteCurrency::USD,
             source: PriceSource::Chainlink,
             timestamp: 1700000000,
+            confidence: None,
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
         };
 
         assert_eq!(info.price, "3000.50");
@@ -375,6 +867,11 @@ mod tests {
             quote_currency: QuoteCurrency::USD,
             source: PriceSource::UniswapV3,
             timestamp: 1234567890,
+            confidence: None,
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -383,6 +880,42 @@ mod tests {
         assert!(json.contains("\"source\":\"uniswap_v3\""));
     }
 
+    // ============================================================================
+    // WithProvenance Tests
+    // ============================================================================
+
+    #[test]
+    fn test_with_provenance_flattens_result() {
+        let response = WithProvenance {
+            result: PriceInfo {
+                token: TokenInfo::eth(),
+                price: "2500".to_string(),
+                quote_currency: QuoteCurrency::USD,
+                source: PriceSource::Chainlink,
+                timestamp: 1234567890,
+                confidence: None,
+                block_number: None,
+                stale: false,
+                age_secs: None,
+                warnings: Vec::new(),
+            },
+            provenance: vec![SymbolProvenance {
+                symbol: "weth".to_string(),
+                address: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".to_string(),
+                chain_id: 1,
+                source: "builtin".to_string(),
+                list_name: None,
+                list_version: None,
+            }],
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["price"], "2500");
+        assert_eq!(json["provenance"][0]["symbol"], "weth");
+        assert_eq!(json["provenance"][0]["source"], "builtin");
+        assert!(json["provenance"][0].get("list_version").is_none());
+    }
+
     // ============================================================================
     // format_units Tests
     // ============================================================================
@@ -523,6 +1056,18 @@ mod tests {
         assert_eq!(result, U256::from(100u64));
     }
 
+    #[test]
+    fn test_parse_units_zero_decimals_fraction() {
+        // The fraction cannot be represented, so it is truncated like any excess digits
+        assert_eq!(parse_units("7.9", 0).unwrap(), U256::from(7u64));
+        assert_eq!(parse_units("7.", 0).unwrap(), U256::from(7u64));
+        assert_eq!(parse_units(".9", 0).unwrap(), U256::ZERO);
+
+        // ...but it must still be a number
+        assert!(parse_units("7.9x", 0).is_err());
+        assert!(parse_units("7.1234567x", 6).is_err());
+    }
+
     #[test]
     fn test_parse_units_invalid_format() {
         // Multiple decimal points
@@ -593,4 +1138,209 @@ mod tests {
         let parsed = parse_units(&formatted, 6).unwrap();
         assert_eq!(original, parsed);
     }
+
+    #[test]
+    fn test_format_parse_roundtrip_zero_decimals() {
+        for value in [0u64, 1, 7, 1_000, 123_456_789] {
+            let original = U256::from(value);
+            let formatted = format_units(original, 0);
+            assert!(!formatted.contains('.'));
+            assert_eq!(parse_units(&formatted, 0).unwrap(), original);
+        }
+    }
+
+    #[test]
+    fn test_u256_to_decimal_zero_decimals() {
+        assert_eq!(u256_to_decimal(U256::from(42u64), 0), Decimal::from(42));
+        assert_eq!(u256_to_decimal(U256::from(1u64), 0), Decimal::ONE);
+        assert_eq!(u256_to_decimal(U256::ZERO, 0), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_wallet_info_not_configured() {
+        let info = WalletInfo::not_configured();
+        assert!(!info.configured);
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["configured"], false);
+        assert!(json.get("address").is_none());
+        assert!(json.get("token_balances").is_none());
+        assert!(json["message"].as_str().unwrap().contains("read-only"));
+    }
+
+    #[test]
+    fn test_price_info_display() {
+        let mut info = PriceInfo {
+            token: TokenInfo::erc20(Address::ZERO, "WETH".to_string(), 18),
+            price: "2994.5".to_string(),
+            quote_currency: QuoteCurrency::USD,
+            source: PriceSource::Chainlink,
+            timestamp: 1,
+            confidence: Some(PriceConfidence::High),
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
+        };
+        assert_eq!(info.to_string(), "1 WETH = 2994.5 USD (Chainlink, high confidence)");
+
+        info.source = PriceSource::UniswapV3;
+        info.confidence = None;
+        info.stale = true;
+        info.age_secs = Some(120);
+        info.warnings.push(Warning::new(WarningCode::StalePrice, "All sources failed"));
+        assert_eq!(
+            info.to_string(),
+            "1 WETH = 2994.5 USD (Uniswap V3, stale: 120s old)\nWarning: All sources failed"
+        );
+    }
+
+    #[test]
+    fn test_balance_info_display() {
+        let mut info = BalanceInfo {
+            address: "0xabc".to_string(),
+            token: TokenInfo::eth(),
+            balance: "1.5".to_string(),
+            balance_raw: "1500000000000000000".to_string(),
+            block_number: None,
+            value: None,
+            warnings: Vec::new(),
+        };
+        assert_eq!(info.to_string(), "0xabc: 1.5 ETH");
+
+        info.value = Some("4500".to_string());
+        info.block_number = Some(19_000_000);
+        assert_eq!(info.to_string(), "0xabc: 1.5 ETH (worth 4500) at block 19000000");
+    }
+
+    #[test]
+    fn test_wallet_info_display() {
+        assert!(WalletInfo::not_configured().to_string().starts_with("No wallet configured"));
+
+        let balance = |token, balance: &str, value: &str| BalanceInfo {
+            address: "0xabc".to_string(),
+            token,
+            balance: balance.to_string(),
+            balance_raw: "0".to_string(),
+            block_number: None,
+            value: Some(value.to_string()),
+            warnings: Vec::new(),
+        };
+        let wallet = WalletInfo {
+            configured: true,
+            address: Some("0xabc".to_string()),
+            eth_balance: Some(balance(TokenInfo::eth(), "1.5", "4500.00")),
+            token_balances: vec![balance(
+                TokenInfo::erc20(Address::ZERO, "USDC".to_string(), 6),
+                "100",
+                "100.00",
+            )],
+            zero_balance_tokens_skipped: 2,
+            base_currency: Some(QuoteCurrency::USD),
+            total_value: Some("4600.00".to_string()),
+            unpriced_tokens: Vec::new(),
+            warnings: vec![Warning::new(WarningCode::ValueUnavailable, "No ETH/USD rate")],
+            message: None,
+        };
+
+        assert_eq!(
+            wallet.to_string(),
+            "Wallet 0xabc\n1.5 ETH (worth 4500.00)\n100 USDC (worth 100.00)\n\
+             2 zero balances not listed\nTotal: 4600.00 USD\nWarning: No ETH/USD rate"
+        );
+    }
+
+    #[test]
+    fn test_price_source_spread() {
+        let price = |price: &str, source| PriceInfo {
+            token: TokenInfo::erc20(Address::ZERO, "WETH".to_string(), 18),
+            price: price.to_string(),
+            quote_currency: QuoteCurrency::USD,
+            source,
+            timestamp: 1,
+            confidence: None,
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
+        };
+        let token = TokenInfo::erc20(Address::ZERO, "WETH".to_string(), 18);
+
+        // Uniswap 0.3% above Chainlink
+        let spread = PriceSourceSpread::new(
+            token.clone(),
+            price("3000", PriceSource::Chainlink),
+            price("3009", PriceSource::UniswapV3),
+            100,
+        )
+        .unwrap();
+        assert_eq!(spread.spread_bps, "30");
+        assert!(!spread.diverging);
+        assert!(spread.warnings.is_empty());
+
+        // Uniswap 2.5% below Chainlink
+        let spread = PriceSourceSpread::new(
+            token.clone(),
+            price("2000", PriceSource::Chainlink),
+            price("1950", PriceSource::UniswapV2),
+            100,
+        )
+        .unwrap();
+        assert_eq!(spread.spread_bps, "-250");
+        assert!(spread.diverging);
+        assert_eq!(spread.warnings[0].code, WarningCode::PriceSourceDivergence);
+
+        let json = serde_json::to_value(&spread).unwrap();
+        assert_eq!(json["chainlink"]["source"], "chainlink");
+        assert_eq!(json["uniswap"]["source"], "uniswap_v2");
+        assert_eq!(json["max_spread_bps"], 100);
+
+        // Fractional basis points are kept to two places
+        let spread = PriceSourceSpread::new(
+            token.clone(),
+            price("3", PriceSource::Chainlink),
+            price("3.0001", PriceSource::UniswapV3),
+            100,
+        )
+        .unwrap();
+        assert_eq!(spread.spread_bps, "0.33");
+
+        assert!(PriceSourceSpread::new(
+            token,
+            price("0", PriceSource::Chainlink),
+            price("1", PriceSource::UniswapV3),
+            100,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_multi_price_info_keys_by_currency() {
+        let quote = |price: &str| QuotePrice {
+            price: price.to_string(),
+            source: PriceSource::UniswapV3,
+            timestamp: 1,
+            confidence: None,
+            block_number: None,
+            stale: false,
+            age_secs: None,
+            warnings: Vec::new(),
+        };
+        let info = MultiPriceInfo {
+            token: TokenInfo::erc20(Address::ZERO, "UNI".to_string(), 18),
+            prices: BTreeMap::from([
+                (QuoteCurrency::ETH, quote("0.003")),
+                (QuoteCurrency::USD, quote("7.5")),
+            ]),
+        };
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["prices"]["USD"]["price"], "7.5");
+        assert_eq!(json["prices"]["ETH"]["source"], "uniswap_v3");
+        assert!(json["prices"]["ETH"].get("block_number").is_none());
+        assert_eq!(
+            info.to_string(),
+            "1 UNI = 7.5 USD (Uniswap V3)\n1 UNI = 0.003 ETH (Uniswap V3)"
+        );
+    }
 }
@@ -0,0 +1,79 @@
+//! Token list diff types.
+
+use serde::{Deserialize, Serialize};
+
+/// A token as listed in a token list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListedToken {
+    /// Token contract address.
+    pub address: String,
+    /// Token symbol.
+    pub symbol: String,
+    /// Token name.
+    pub name: String,
+    /// Number of decimals.
+    pub decimals: u8,
+}
+
+/// A token whose symbol or decimals changed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangedToken {
+    /// Token contract address.
+    pub address: String,
+    /// Current token symbol.
+    pub symbol: String,
+    /// Current number of decimals.
+    pub decimals: u8,
+    /// Symbol in the previous snapshot, if it changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_symbol: Option<String>,
+    /// Decimals in the previous snapshot, if they changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_decimals: Option<u8>,
+}
+
+/// Changes to the token list since the previous refresh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenListDiff {
+    /// Token list URL.
+    pub source: String,
+    /// Token list name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_name: Option<String>,
+    /// Token list version, if the list declares one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_version: Option<String>,
+    /// Number of tokens in the current snapshot.
+    pub token_count: usize,
+    /// Whether a previous snapshot existed to compare against.
+    pub has_previous_snapshot: bool,
+    /// Tokens listed now but not in the previous snapshot.
+    pub added: Vec<ListedToken>,
+    /// Tokens in the previous snapshot that are no longer listed.
+    pub removed: Vec<ListedToken>,
+    /// Tokens whose symbol or decimals changed.
+    pub changed: Vec<ChangedToken>,
+    /// Explanation when there was nothing to compare against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_token_omits_unchanged_fields() {
+        let changed = ChangedToken {
+            address: "0x1f9840a85d5af5bf1d1762f925bdaddc4201f984".to_string(),
+            symbol: "UNI".to_string(),
+            decimals: 18,
+            previous_symbol: Some("UNI-V1".to_string()),
+            previous_decimals: None,
+        };
+
+        let json = serde_json::to_value(&changed).unwrap();
+        assert_eq!(json["previous_symbol"], "UNI-V1");
+        assert!(json.get("previous_decimals").is_none());
+    }
+}
@@ -0,0 +1,444 @@
+//! Trade cost breakdown types.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::{format::write_warnings, PriceSource, SwapPathLeg, SwapRoute, TokenInfo, Warning};
+
+/// Total cost of a swap, broken down by component.
+///
+/// Token-denominated figures are always present. USD figures are omitted when a
+/// token involved could not be priced, with an explanation in `notes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeCostBreakdown {
+    /// Input token.
+    pub from_token: TokenInfo,
+    /// Output token.
+    pub to_token: TokenInfo,
+    /// Input amount (human-readable).
+    pub amount_in: String,
+    /// Expected output amount (human-readable).
+    pub amount_out_expected: String,
+    /// Output tokens received per input token, before gas.
+    pub effective_rate: String,
+    /// Output tokens received per input token after paying gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_effective_rate: Option<String>,
+    /// Combined AMM fee along the route as a percentage of the input.
+    pub amm_fee_percent: String,
+    /// AMM fee paid, in input tokens.
+    pub amm_fee: String,
+    /// Price impact as a percentage.
+    pub price_impact_percent: String,
+    /// Output lost to price impact, in output tokens.
+    pub price_impact_cost: String,
+    /// Gas cost in ETH.
+    pub gas_cost_eth: String,
+    /// Whether the gas cost is based on a fallback gas estimate.
+    pub gas_estimate_is_fallback: bool,
+    /// Value of the input in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_value_usd: Option<String>,
+    /// Value of the expected output in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_value_usd: Option<String>,
+    /// Value of the minimum output after slippage in USD: the worst case.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_output_value_usd: Option<String>,
+    /// AMM fee in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amm_fee_usd: Option<String>,
+    /// Price impact cost in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_impact_cost_usd: Option<String>,
+    /// Gas cost in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_cost_usd: Option<String>,
+    /// Total cost in USD: input value minus output value, plus gas.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_cost_usd: Option<String>,
+    /// Whether the swap simulation succeeded.
+    pub simulation_success: bool,
+    /// Swap route used.
+    pub route: SwapRoute,
+    /// Explanations for omitted or approximate figures.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+impl fmt::Display for TradeCostBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let usd = |value: &Option<String>| value.as_ref().map(|v| format!(" (${})", v));
+        let (from, to) = (&self.from_token.symbol, &self.to_token.symbol);
+
+        write!(
+            f,
+            "{} {} → {} {} at {} {} per {}",
+            self.amount_in, from, self.amount_out_expected, to, self.effective_rate, to, from
+        )?;
+        write!(
+            f,
+            "\nAMM fee: {} {} ({}%){}",
+            self.amm_fee,
+            from,
+            self.amm_fee_percent,
+            usd(&self.amm_fee_usd).unwrap_or_default()
+        )?;
+        write!(
+            f,
+            "\nPrice impact: {} {} ({}%){}",
+            self.price_impact_cost,
+            to,
+            self.price_impact_percent,
+            usd(&self.price_impact_cost_usd).unwrap_or_default()
+        )?;
+        write!(
+            f,
+            "\nGas: {} ETH{}",
+            self.gas_cost_eth,
+            usd(&self.gas_cost_usd).unwrap_or_default()
+        )?;
+        if let Some(total) = &self.total_cost_usd {
+            write!(f, "\nTotal cost: ${}", total)?;
+        }
+        for note in &self.notes {
+            write!(f, "\nNote: {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+/// All-in exchange rate of a swap at a specific size.
+///
+/// Rates include the AMM fee and price impact but not gas. Both directions are
+/// reported so callers do not have to invert them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectiveRate {
+    /// Input token.
+    pub from_token: TokenInfo,
+    /// Output token.
+    pub to_token: TokenInfo,
+    /// Input amount (human-readable).
+    pub amount_in: String,
+    /// Expected output amount (human-readable).
+    pub amount_out_expected: String,
+    /// Output tokens received per input token at this size.
+    pub rate: String,
+    /// Input tokens paid per output token at this size.
+    pub inverse_rate: String,
+    /// Marginal output tokens per input token for a tiny trade, fee included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spot_rate: Option<String>,
+    /// Marginal input tokens per output token for a tiny trade, fee included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inverse_spot_rate: Option<String>,
+    /// Price impact as a percentage: how much worse `rate` is than `spot_rate`.
+    pub price_impact_percent: String,
+    /// Whether the swap simulation succeeded.
+    pub simulation_success: bool,
+    /// Swap route used.
+    pub route: SwapRoute,
+    /// Non-fatal advisories from the simulation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl fmt::Display for EffectiveRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (from, to) = (&self.from_token.symbol, &self.to_token.symbol);
+
+        write!(
+            f,
+            "1 {} = {} {} for {} {} (impact {}%",
+            from, self.rate, to, self.amount_in, from, self.price_impact_percent
+        )?;
+        if let Some(spot_rate) = &self.spot_rate {
+            write!(f, ", spot {}", spot_rate)?;
+        }
+        write!(f, ")\n1 {} = {} {}", to, self.inverse_rate, from)?;
+        write_warnings(f, &self.warnings)
+    }
+}
+
+/// How the best_swap tool ranks its candidates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapRankingMetric {
+    /// Highest USD value of the expected output.
+    #[default]
+    OutputUsd,
+    /// Highest USD value of the expected output after paying gas.
+    NetOutputUsd,
+    /// Lowest price impact.
+    PriceImpact,
+}
+
+impl std::str::FromStr for SwapRankingMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "output_usd" => Ok(SwapRankingMetric::OutputUsd),
+            "net_output_usd" => Ok(SwapRankingMetric::NetOutputUsd),
+            "price_impact" => Ok(SwapRankingMetric::PriceImpact),
+            _ => Err(format!(
+                "Invalid metric: {} (expected \"output_usd\", \"net_output_usd\" or \"price_impact\")",
+                s
+            )),
+        }
+    }
+}
+
+/// Swaps of one input into several candidate output tokens, best first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BestSwapResult {
+    /// Input token.
+    pub from_token: TokenInfo,
+    /// Input amount (human-readable).
+    pub amount_in: String,
+    /// Value of the input in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_value_usd: Option<String>,
+    /// Metric the candidates are ranked by.
+    pub metric: SwapRankingMetric,
+    /// Candidates, best first. Candidates that could not be ranked come last.
+    pub candidates: Vec<BestSwapCandidate>,
+}
+
+/// One candidate output token of a best_swap ranking.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BestSwapCandidate {
+    /// Position in the ranking, starting at 1.
+    pub rank: usize,
+    /// Output token.
+    pub to_token: TokenInfo,
+    /// Whether the candidate has a value for the ranking metric.
+    pub ranked: bool,
+    /// Expected output amount (human-readable).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_out_expected: Option<String>,
+    /// Value of the expected output in USD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_value_usd: Option<String>,
+    /// Value of the expected output in USD, minus the gas cost.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_output_value_usd: Option<String>,
+    /// Price impact as a percentage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_impact_percent: Option<String>,
+    /// Gas cost in ETH.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_cost_eth: Option<String>,
+    /// Swap route used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route: Option<SwapRoute>,
+    /// Why the candidate could not be ranked (no route, or no USD price).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Non-fatal advisories from the simulation.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+/// Net result of buying a token and selling it straight back, in the input token.
+///
+/// Prices are in input tokens per output token. Figures that depend on the sell
+/// leg or on pricing gas are omitted when unavailable, with an explanation in
+/// `notes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundTripPnl {
+    /// Input token: spent on the buy leg and received on the sell leg.
+    pub from_token: TokenInfo,
+    /// Token bought and sold back.
+    pub to_token: TokenInfo,
+    /// Input amount (human-readable).
+    pub amount_in: String,
+    /// Output tokens expected from the buy leg (human-readable).
+    pub amount_bought: String,
+    /// Input tokens expected back from the sell leg at `exit_price` (human-readable).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_returned: Option<String>,
+    /// Pool price at entry, before fees and price impact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_price: Option<String>,
+    /// Pool price at exit, before fees and price impact.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_price: Option<String>,
+    /// Whether `exit_price` was supplied by the caller rather than read from the pools.
+    pub exit_price_is_hypothetical: bool,
+    /// AMM fees of both legs, in input tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amm_fees: Option<String>,
+    /// Output lost to price impact on both legs, in input tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_impact_cost: Option<String>,
+    /// Gas cost of both legs in ETH.
+    pub gas_cost_eth: String,
+    /// Gas cost of both legs in input tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_cost: Option<String>,
+    /// Input tokens returned minus input tokens spent, minus gas when it could be priced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_pnl: Option<String>,
+    /// `net_pnl` as a percentage of the input amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub net_pnl_percent: Option<String>,
+    /// Whether `net_pnl` includes the gas cost.
+    pub net_pnl_includes_gas: bool,
+    /// `net_pnl` if both legs fill at their minimum output after slippage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worst_case_net_pnl: Option<String>,
+    /// Simulation of the buy leg.
+    pub buy: SwapPathLeg,
+    /// Simulation of the sell leg at current pool prices, if it could be routed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sell: Option<SwapPathLeg>,
+    /// Explanations for omitted or approximate figures.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+/// Input required to receive a target USD value of an output token.
+///
+/// The target is converted to an output amount at the output token's current USD
+/// price, and that amount is then quoted as an exact output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsdOutputQuote {
+    /// Input token.
+    pub from_token: TokenInfo,
+    /// Output token.
+    pub to_token: TokenInfo,
+    /// Requested output value in USD.
+    pub target_usd: String,
+    /// USD price of one output token used for the conversion.
+    pub to_token_usd_price: String,
+    /// Where the output token's USD price came from.
+    pub price_source: PriceSource,
+    /// Required input amount (human-readable).
+    pub amount_in: String,
+    /// Required input amount in smallest units.
+    pub amount_in_raw: String,
+    /// Output amount worth `target_usd` (human-readable).
+    pub amount_out: String,
+    /// Output amount worth `target_usd` in `to_token`'s smallest units.
+    pub amount_out_raw: String,
+    /// Implied price: input tokens paid per output token.
+    pub price: String,
+    /// Implied price of the input token in USD: `target_usd` per input token.
+    pub input_usd_rate: String,
+    /// Venue requiring the least input.
+    pub route: SwapRoute,
+    /// Non-fatal advisories about the output token's price.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{UniswapVersion, Venue, WarningCode};
+    use alloy::primitives::Address;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_swap_ranking_metric_from_str() {
+        assert_eq!(
+            SwapRankingMetric::from_str("output_usd").unwrap(),
+            SwapRankingMetric::OutputUsd
+        );
+        assert_eq!(
+            SwapRankingMetric::from_str("NET_OUTPUT_USD").unwrap(),
+            SwapRankingMetric::NetOutputUsd
+        );
+        assert_eq!(
+            SwapRankingMetric::from_str("price_impact").unwrap(),
+            SwapRankingMetric::PriceImpact
+        );
+        assert!(SwapRankingMetric::from_str("liquidity").is_err());
+    }
+
+    fn route() -> SwapRoute {
+        SwapRoute {
+            protocol: UniswapVersion::V3,
+            path: vec!["0xWETH".to_string(), "0xUSDC".to_string()],
+            fee_tier: Some(3000),
+            venue: Venue::Uniswap,
+        }
+    }
+
+    fn tokens() -> (TokenInfo, TokenInfo) {
+        (
+            TokenInfo::erc20(Address::ZERO, "WETH".to_string(), 18),
+            TokenInfo::erc20(Address::ZERO, "USDC".to_string(), 6),
+        )
+    }
+
+    #[test]
+    fn test_effective_rate_display() {
+        let (from_token, to_token) = tokens();
+        let mut rate = EffectiveRate {
+            from_token,
+            to_token,
+            amount_in: "2".to_string(),
+            amount_out_expected: "5000".to_string(),
+            rate: "2500".to_string(),
+            inverse_rate: "0.0004".to_string(),
+            spot_rate: Some("3125".to_string()),
+            inverse_spot_rate: Some("0.00032".to_string()),
+            price_impact_percent: "20".to_string(),
+            simulation_success: true,
+            route: route(),
+            warnings: Vec::new(),
+        };
+        assert_eq!(
+            rate.to_string(),
+            "1 WETH = 2500 USDC for 2 WETH (impact 20%, spot 3125)\n1 USDC = 0.0004 WETH"
+        );
+
+        rate.spot_rate = None;
+        rate.warnings.push(Warning::new(WarningCode::PriceImpactUnavailable, "No spot quote"));
+        assert_eq!(
+            rate.to_string(),
+            "1 WETH = 2500 USDC for 2 WETH (impact 20%)\n1 USDC = 0.0004 WETH\n\
+             Warning: No spot quote"
+        );
+    }
+
+    #[test]
+    fn test_trade_cost_breakdown_display() {
+        let (from_token, to_token) = tokens();
+        let breakdown = TradeCostBreakdown {
+            from_token,
+            to_token,
+            amount_in: "1".to_string(),
+            amount_out_expected: "2990".to_string(),
+            effective_rate: "2990".to_string(),
+            net_effective_rate: None,
+            amm_fee_percent: "0.3".to_string(),
+            amm_fee: "0.003".to_string(),
+            price_impact_percent: "0.1".to_string(),
+            price_impact_cost: "3".to_string(),
+            gas_cost_eth: "0.004".to_string(),
+            gas_estimate_is_fallback: false,
+            input_value_usd: Some("3000.00".to_string()),
+            output_value_usd: None,
+            minimum_output_value_usd: None,
+            amm_fee_usd: Some("9.00".to_string()),
+            price_impact_cost_usd: None,
+            gas_cost_usd: Some("12.00".to_string()),
+            total_cost_usd: None,
+            simulation_success: true,
+            route: route(),
+            notes: vec!["USDC could not be priced in USD".to_string()],
+        };
+
+        assert_eq!(
+            breakdown.to_string(),
+            "1 WETH → 2990 USDC at 2990 USDC per WETH\n\
+             AMM fee: 0.003 WETH (0.3%) ($9.00)\n\
+             Price impact: 3 USDC (0.1%)\n\
+             Gas: 0.004 ETH ($12.00)\n\
+             Note: USDC could not be priced in USD"
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Token verification types.
+
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a single ERC20 conformance check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationCheck {
+    /// Name of the check (e.g., "decimals()").
+    pub name: String,
+    /// Whether the check passed.
+    pub passed: bool,
+    /// Value returned by the call, if it succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Error message, if the check failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl VerificationCheck {
+    /// Create a passing check with the returned value.
+    pub fn pass(name: &str, value: impl ToString) -> Self {
+        Self { name: name.to_string(), passed: true, value: Some(value.to_string()), error: None }
+    }
+
+    /// Create a failing check with an error message.
+    pub fn fail(name: &str, error: impl ToString) -> Self {
+        Self { name: name.to_string(), passed: false, value: None, error: Some(error.to_string()) }
+    }
+}
+
+/// Pause and blacklist status of a token with known compliance controls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceStatus {
+    /// Interface the status was read through ("fiat_token" or "tether").
+    pub interface: String,
+    /// Whether the token is paused, if `paused()` could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    /// Address whose blacklist status was checked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Whether `address` is blacklisted, if it could be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blacklisted: Option<bool>,
+}
+
+/// Token metadata read by the verify_token checks.
+///
+/// A field is omitted when its call failed, rather than filled with a default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifiedTokenMetadata {
+    /// Token contract address.
+    pub address: String,
+    /// Symbol returned by `symbol()`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Decimals returned by `decimals()`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+}
+
+impl VerifiedTokenMetadata {
+    /// Create metadata for `address` from the calls that succeeded.
+    pub fn new(address: Address, symbol: Option<String>, decimals: Option<u8>) -> Self {
+        Self { address: format!("{address:?}"), symbol, decimals }
+    }
+}
+
+/// Report produced by the verify_token tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenVerificationReport {
+    /// Token metadata as reported by the contract.
+    ///
+    /// Omitted for an address without contract code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<VerifiedTokenMetadata>,
+    /// Whether the address has deployed contract code.
+    pub is_contract: bool,
+    /// Whether all core ERC20 checks passed.
+    pub is_erc20: bool,
+    /// Whether the contract is an EIP-1967 upgradeable proxy (best-effort, based on
+    /// the implementation storage slot only).
+    pub is_proxy: bool,
+    /// Implementation contract address, if the EIP-1967 slot is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_implementation: Option<String>,
+    /// Pause and blacklist status, for known tokens that expose them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<ComplianceStatus>,
+    /// Individual check results.
+    pub checks: Vec<VerificationCheck>,
+    /// Human-readable warnings for failed checks.
+    pub warnings: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_check_pass() {
+        let check = VerificationCheck::pass("decimals()", 6u8);
+        assert!(check.passed);
+        assert_eq!(check.value, Some("6".to_string()));
+        assert!(check.error.is_none());
+    }
+
+    #[test]
+    fn test_verification_check_fail() {
+        let check = VerificationCheck::fail("symbol()", "execution reverted");
+        assert!(!check.passed);
+        assert!(check.value.is_none());
+        assert_eq!(check.error, Some("execution reverted".to_string()));
+    }
+
+    #[test]
+    fn test_verification_check_serialization_skips_none() {
+        let check = VerificationCheck::pass("name()", "USD Coin");
+        let json = serde_json::to_string(&check).unwrap();
+        assert!(json.contains("\"value\":\"USD Coin\""));
+        assert!(!json.contains("error"));
+    }
+
+    #[test]
+    fn test_token_verification_report_serialization() {
+        let report = TokenVerificationReport {
+            token: None,
+            is_contract: false,
+            is_erc20: false,
+            is_proxy: false,
+            proxy_implementation: None,
+            compliance: None,
+            checks: vec![VerificationCheck::fail("code", "no contract code")],
+            warnings: vec!["Address is an EOA".to_string()],
+        };
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"is_contract\":false"));
+        assert!(json.contains("\"is_erc20\":false"));
+        assert!(json.contains("Address is an EOA"));
+        assert!(json.contains("\"is_proxy\":false"));
+        assert!(!json.contains("proxy_implementation"));
+        assert!(!json.contains("compliance"));
+        assert!(!json.contains("\"token\""));
+    }
+
+    #[test]
+    fn test_verified_token_metadata_omits_failed_calls() {
+        let metadata = VerifiedTokenMetadata::new(Address::repeat_byte(0x11), None, Some(6));
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["decimals"], 6);
+        assert!(json.get("symbol").is_none());
+    }
+}
@@ -0,0 +1,85 @@
+//! Non-fatal advisories attached to tool responses.
+
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable kind of a [`Warning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCode {
+    /// Price impact is at or above [`HIGH_PRICE_IMPACT_PERCENT`](crate::services::swap::HIGH_PRICE_IMPACT_PERCENT).
+    HighPriceImpact,
+    /// Price impact could not be calculated and is reported as zero.
+    PriceImpactUnavailable,
+    /// Gas estimation failed and a typical value for the route was used.
+    GasEstimateFallback,
+    /// The gas price could not be read and a default was used.
+    GasPriceFallback,
+    /// Better-priced pools were skipped because their liquidity is below the minimum.
+    LowLiquidityPoolsSkipped,
+    /// The input amount was parsed with decimals other than the token contract reports.
+    DecimalsMismatch,
+    /// The oracle answer is still accepted but is approaching the staleness limit.
+    OracleAging,
+    /// The price is an assumed 1:1 peg rather than a market price.
+    PeggedPrice,
+    /// Token metadata could not be read and defaults were used.
+    TokenMetadataFallback,
+    /// The balance could not be valued in the requested currency.
+    ValueUnavailable,
+    /// The token rebases, so balances and amounts change without transfers.
+    RebasingToken,
+    /// Every price source failed and the last known price was returned instead.
+    StalePrice,
+    /// Rounding the minimum output to whole raw units drops a significant share of it.
+    PrecisionLoss,
+    /// The V3 quoter failed and the output was computed from the pool's current tick.
+    ApproximateQuote,
+    /// The requested slippage tolerance was below the configured floor and was raised to it.
+    SlippageRaised,
+    /// The pair has no liquidity yet, so the deposit sets its price.
+    NewPair,
+    /// The owner holds fewer tokens than the transaction spends, so it would revert.
+    InsufficientBalance,
+    /// The RPC node now reports a different chain ID than at the start of the session.
+    ChainIdChanged,
+    /// An independent quote from pool state differs from the quote by more than the tolerance.
+    QuoteMismatch,
+    /// A quote cross-check was requested but could not be computed.
+    QuoteCrossCheckUnavailable,
+    /// The RPC node's latest block is far behind the server's clock.
+    RpcLagging,
+    /// A core token symbol resolved to an address other than its canonical one.
+    NonCanonicalToken,
+    /// The Chainlink and Uniswap prices of a token differ by more than the allowed spread.
+    PriceSourceDivergence,
+    /// The caller's absolute minimum output is above the expected output.
+    MinimumAboveQuote,
+}
+
+/// A non-fatal advisory about a tool response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Warning {
+    /// Warning kind.
+    pub code: WarningCode,
+    /// Human-readable explanation.
+    pub message: String,
+}
+
+impl Warning {
+    /// Create a warning.
+    pub fn new(code: WarningCode, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_serialization() {
+        let warning = Warning::new(WarningCode::GasEstimateFallback, "estimation failed");
+        let json = serde_json::to_string(&warning).unwrap();
+        assert_eq!(json, r#"{"code":"gas_estimate_fallback","message":"estimation failed"}"#);
+    }
+}
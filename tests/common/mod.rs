@@ -20,6 +20,7 @@ pub fn create_test_server() -> Option<EthereumTradingServer> {
         private_key,
         log_level: "warn".to_string(),
         chain_id: ETHEREUM_MAINNET_CHAIN_ID,
+        ..Default::default()
     };
 
     EthereumTradingServer::new(config).ok()
@@ -0,0 +1,137 @@
+//! Integration tests for the best_swap tool.
+//!
+//! Run with: `cargo test --test test_best_swap -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::BestSwapInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test ranking WETH into several tokens by USD value of the output.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_best_swap_ranks_by_output_usd() {
+    let server = skip_if_no_server!();
+
+    let input = BestSwapInput {
+        from_token: "WETH".to_string(),
+        amount: "1".to_string(),
+        to_tokens: vec!["USDC".to_string(), "UNI".to_string(), "LINK".to_string()],
+        ..Default::default()
+    };
+
+    let result = server.best_swap(Parameters(input)).await;
+
+    assert!(result.is_ok(), "best_swap should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["from_token"]["symbol"], "WETH");
+    assert_eq!(parsed["metric"], "output_usd");
+
+    let candidates = parsed["candidates"].as_array().unwrap();
+    assert_eq!(candidates.len(), 3);
+
+    let values: Vec<f64> = candidates
+        .iter()
+        .filter(|c| c["ranked"].as_bool().unwrap())
+        .map(|c| c["output_value_usd"].as_str().unwrap().parse().unwrap())
+        .collect();
+    assert!(!values.is_empty(), "at least one candidate should be ranked");
+    assert!(values.windows(2).all(|w| w[0] >= w[1]), "candidates should be best first");
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        assert_eq!(candidate["rank"], index + 1);
+        if !candidate["ranked"].as_bool().unwrap() {
+            assert!(candidate["reason"].is_string(), "unranked candidates need a reason");
+        }
+    }
+
+    println!("WETH Best Swap: {}", json_str);
+}
+
+/// Test ranking by lowest price impact.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_best_swap_ranks_by_price_impact() {
+    let server = skip_if_no_server!();
+
+    let input = BestSwapInput {
+        from_token: "WETH".to_string(),
+        amount: "1".to_string(),
+        to_tokens: vec!["USDC".to_string(), "DAI".to_string()],
+        metric: Some("price_impact".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.best_swap(Parameters(input)).await;
+
+    assert!(result.is_ok(), "best_swap should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["metric"], "price_impact");
+
+    let impacts: Vec<f64> = parsed["candidates"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|c| c["ranked"].as_bool().unwrap())
+        .map(|c| c["price_impact_percent"].as_str().unwrap().parse().unwrap())
+        .collect();
+    assert!(impacts.windows(2).all(|w| w[0] <= w[1]), "lowest price impact should come first");
+}
+
+/// Test that from_token cannot be a candidate.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_best_swap_from_token_candidate_error() {
+    let server = skip_if_no_server!();
+
+    let input = BestSwapInput {
+        from_token: "WETH".to_string(),
+        amount: "1".to_string(),
+        to_tokens: vec!["USDC".to_string(), "WETH".to_string()],
+        ..Default::default()
+    };
+
+    let result = server.best_swap(Parameters(input)).await;
+
+    assert!(result.is_err(), "best_swap should reject from_token as a candidate");
+}
+
+/// Test that an empty candidate list is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_best_swap_no_candidates_error() {
+    let server = skip_if_no_server!();
+
+    let input = BestSwapInput {
+        from_token: "WETH".to_string(),
+        amount: "1".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.best_swap(Parameters(input)).await;
+
+    assert!(result.is_err(), "best_swap should fail without candidates");
+}
+
+/// Test that an unknown metric is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_best_swap_invalid_metric_error() {
+    let server = skip_if_no_server!();
+
+    let input = BestSwapInput {
+        from_token: "WETH".to_string(),
+        amount: "1".to_string(),
+        to_tokens: vec!["USDC".to_string()],
+        metric: Some("liquidity".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.best_swap(Parameters(input)).await;
+
+    assert!(result.is_err(), "best_swap should fail for an unknown metric");
+}
@@ -0,0 +1,62 @@
+//! Integration tests for the decode_selector tool.
+//!
+//! Run with: `cargo test --test test_decode_selector`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::DecodeSelectorInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test that a bare ERC20 selector resolves to its signature.
+#[tokio::test]
+async fn test_decode_erc20_transfer_selector() {
+    let server = skip_if_no_server!();
+
+    let input = DecodeSelectorInput { data: "0xa9059cbb".to_string() };
+
+    let result = server.decode_selector(Parameters(input)).await;
+
+    assert!(result.is_ok(), "decode_selector should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    assert_eq!(parsed["selector"], "0xa9059cbb");
+    assert_eq!(parsed["known"], true);
+    assert_eq!(parsed["functions"][0]["signature"], "transfer(address,uint256)");
+    assert!(parsed.get("argument_bytes").is_none());
+}
+
+/// Test that full calldata is resolved by its first 4 bytes.
+#[tokio::test]
+async fn test_decode_router_calldata() {
+    let server = skip_if_no_server!();
+
+    // swapExactTokensForTokens selector followed by one 32-byte word
+    let data = format!("0x38ed1739{}", "00".repeat(32));
+    let input = DecodeSelectorInput { data };
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.decode_selector(Parameters(input)).await.unwrap()).unwrap();
+
+    assert_eq!(parsed["known"], true);
+    assert_eq!(parsed["functions"][0]["interfaces"][0], "IUniswapV2Router02");
+    assert_eq!(parsed["argument_bytes"], 32);
+}
+
+/// Test that an unknown selector is reported as unknown, and bad input fails.
+#[tokio::test]
+async fn test_decode_unknown_and_invalid_selectors() {
+    let server = skip_if_no_server!();
+
+    let input = DecodeSelectorInput { data: "0xdeadbeef".to_string() };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.decode_selector(Parameters(input)).await.unwrap()).unwrap();
+    assert_eq!(parsed["known"], false);
+    assert!(parsed.get("functions").is_none());
+    assert!(parsed["message"].as_str().unwrap().contains("0xdeadbeef"));
+
+    for data in ["0xa9059c", "0xzzzzzzzz", ""] {
+        let input = DecodeSelectorInput { data: data.to_string() };
+        assert!(server.decode_selector(Parameters(input)).await.is_err(), "{} should fail", data);
+    }
+}
@@ -0,0 +1,79 @@
+//! Integration tests for the effective_rate tool.
+//!
+//! Run with: `cargo test --test test_effective_rate -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::EffectiveRateInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test a WETH -> USDC rate in both directions.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_effective_rate_weth_to_usdc() {
+    let server = skip_if_no_server!();
+
+    let input = EffectiveRateInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.effective_rate(Parameters(input)).await;
+
+    assert!(result.is_ok(), "effective_rate should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["from_token"]["symbol"], "WETH");
+    assert_eq!(parsed["to_token"]["symbol"], "USDC");
+
+    let rate: f64 = parsed["rate"].as_str().unwrap().parse().unwrap();
+    let inverse_rate: f64 = parsed["inverse_rate"].as_str().unwrap().parse().unwrap();
+    assert!(rate > 0.0, "rate should be positive");
+    assert!((rate * inverse_rate - 1.0).abs() < 1e-9, "inverse_rate should invert rate");
+
+    // Fees and price impact only make the all-in rate worse than the spot rate
+    let spot_rate: f64 = parsed["spot_rate"].as_str().unwrap().parse().unwrap();
+    assert!(spot_rate >= rate, "spot_rate should be at least the all-in rate");
+
+    println!("WETH -> USDC Effective Rate: {}", json_str);
+}
+
+/// Test same token (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_effective_rate_same_token_error() {
+    let server = skip_if_no_server!();
+
+    let input = EffectiveRateInput {
+        from_token: "WETH".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.effective_rate(Parameters(input)).await;
+
+    assert!(result.is_err(), "effective_rate should fail for same token");
+}
+
+/// Test zero amount (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_effective_rate_zero_amount_error() {
+    let server = skip_if_no_server!();
+
+    let input = EffectiveRateInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.effective_rate(Parameters(input)).await;
+
+    assert!(result.is_err(), "effective_rate should fail for zero amount");
+}
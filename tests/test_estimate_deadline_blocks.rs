@@ -0,0 +1,54 @@
+//! Integration tests for the estimate_deadline_blocks tool.
+//!
+//! Run with: `cargo test --test test_estimate_deadline_blocks -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::EstimateDeadlineBlocksInput;
+use rmcp::handler::server::wrapper::Parameters;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+/// Test that a deadline 20 minutes out is about 100 mainnet blocks away.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_estimate_deadline_blocks_future() {
+    let server = skip_if_no_server!();
+
+    let input = EstimateDeadlineBlocksInput { deadline: now_secs() + 1_200 };
+    let result = server.estimate_deadline_blocks(Parameters(input)).await;
+
+    assert!(result.is_ok(), "estimate_deadline_blocks should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["seconds_per_block"], 12);
+    assert_eq!(parsed["expired"], false);
+
+    let blocks = parsed["blocks_remaining"].as_u64().unwrap();
+    assert!((95..=110).contains(&blocks), "unexpected blocks_remaining: {}", blocks);
+    assert_eq!(
+        parsed["last_block"].as_u64().unwrap(),
+        parsed["latest_block"].as_u64().unwrap() + blocks
+    );
+    println!("Deadline in {}s ({} blocks)", parsed["seconds_remaining"], blocks);
+}
+
+/// Test that a deadline in the past is reported as expired.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_estimate_deadline_blocks_expired() {
+    let server = skip_if_no_server!();
+
+    let input = EstimateDeadlineBlocksInput { deadline: now_secs() - 3_600 };
+    let result = server.estimate_deadline_blocks(Parameters(input)).await;
+
+    assert!(result.is_ok(), "estimate_deadline_blocks should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["expired"], true);
+    assert_eq!(parsed["blocks_remaining"], 0);
+    assert!(parsed["seconds_remaining"].as_i64().unwrap() < 0);
+}
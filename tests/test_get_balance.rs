@@ -18,6 +18,7 @@ async fn test_get_eth_balance() {
     let input = GetBalanceInput {
         address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
         token_address: None,
+        ..Default::default()
     };
 
     let result = server.get_balance(Parameters(input)).await;
@@ -55,6 +56,7 @@ async fn test_get_erc20_balance() {
     let input = GetBalanceInput {
         address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
         token_address: Some(usdc_address),
+        ..Default::default()
     };
 
     let result = server.get_balance(Parameters(input)).await;
@@ -85,7 +87,11 @@ async fn test_get_erc20_balance() {
 async fn test_get_balance_invalid_address() {
     let server = skip_if_no_server!();
 
-    let input = GetBalanceInput { address: "not-a-valid-address".to_string(), token_address: None };
+    let input = GetBalanceInput {
+        address: "not-a-valid-address".to_string(),
+        token_address: None,
+        ..Default::default()
+    };
 
     let result = server.get_balance(Parameters(input)).await;
 
@@ -99,9 +105,91 @@ async fn test_get_balance_invalid_address() {
 async fn test_get_balance_empty_address() {
     let server = skip_if_no_server!();
 
-    let input = GetBalanceInput { address: "".to_string(), token_address: None };
+    let input = GetBalanceInput { address: "".to_string(), ..Default::default() };
 
     let result = server.get_balance(Parameters(input)).await;
 
     assert!(result.is_err(), "get_balance should fail for empty address");
 }
+
+/// Test that display_decimals rounds the balance but not the raw balance.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_balance_display_decimals() {
+    let server = skip_if_no_server!();
+
+    let input = GetBalanceInput {
+        address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+        token_address: None,
+        display_decimals: Some(4),
+        ..Default::default()
+    };
+
+    let result = server.get_balance(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_balance should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    let balance = parsed["balance"].as_str().unwrap();
+    let places = balance.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+    assert!(places <= 4, "balance {} should have at most 4 decimal places", balance);
+    assert!(parsed["balance_raw"].as_str().unwrap().chars().all(|c| c.is_ascii_digit()));
+}
+
+/// Test querying a token balance by symbol.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_balance_by_symbol() {
+    let server = skip_if_no_server!();
+
+    let input = GetBalanceInput {
+        address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+        token_symbol: Some("usdc".to_string()),
+        include_provenance: Some(true),
+        ..Default::default()
+    };
+
+    let result = server.get_balance(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_balance by symbol should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["token"]["symbol"], "USDC");
+    assert_eq!(
+        parsed["token"]["address"].as_str().unwrap().to_lowercase(),
+        "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+    );
+    assert_eq!(parsed["provenance"][0]["symbol"], "usdc");
+
+    // ETH is the native balance
+    let input = GetBalanceInput {
+        address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+        token_symbol: Some("ETH".to_string()),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.get_balance(Parameters(input)).await.unwrap()).unwrap();
+    assert_eq!(parsed["token"]["symbol"], "ETH");
+}
+
+/// Test that unknown symbols and a symbol together with an address are rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_balance_symbol_errors() {
+    let server = skip_if_no_server!();
+
+    let input = GetBalanceInput {
+        address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+        token_symbol: Some("NOTATOKEN123".to_string()),
+        ..Default::default()
+    };
+    assert!(server.get_balance(Parameters(input)).await.is_err());
+
+    let input = GetBalanceInput {
+        address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+        token_address: Some("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string()),
+        token_symbol: Some("USDC".to_string()),
+        ..Default::default()
+    };
+    assert!(server.get_balance(Parameters(input)).await.is_err());
+}
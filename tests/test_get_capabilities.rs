@@ -0,0 +1,59 @@
+//! Integration tests for the get_capabilities tool.
+//!
+//! Run with: `cargo test --test test_get_capabilities`
+
+use ethereum_trading_mcp::{Config, EthereumTradingServer, SEPOLIA_CHAIN_ID};
+
+/// Test the capabilities of a read-only mainnet server.
+#[tokio::test]
+async fn test_capabilities_read_only_mainnet() {
+    let config = Config { rpc_url: "http://localhost:8545".to_string(), ..Default::default() };
+    let server = EthereumTradingServer::new(config).unwrap();
+
+    let result = server.get_capabilities().await;
+
+    assert!(result.is_ok(), "get_capabilities should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    assert_eq!(parsed["chain_id"], 1);
+    assert_eq!(parsed["network"], "mainnet");
+    assert_eq!(parsed["quote_currencies"], serde_json::json!(["USD", "ETH"]));
+    let sources = parsed["price_sources"].as_array().unwrap();
+    assert!(sources.contains(&"chainlink".into()) && sources.contains(&"uniswap_v3".into()));
+    let networks = parsed["supported_networks"].as_array().unwrap();
+    assert!(networks.iter().any(|network| network["network"] == "sepolia"));
+
+    let features = &parsed["features"];
+    assert_eq!(features["wallet_configured"], false);
+    assert_eq!(features["swap_simulation"], false);
+    assert_eq!(features["execution"], false);
+    assert_eq!(features["eth_pricing"], true);
+    assert_eq!(features["protocol_preference"], "v3_first");
+
+    let tools = parsed["enabled_tools"].as_array().unwrap();
+    assert!(tools.contains(&"get_capabilities".into()));
+}
+
+/// Test that capabilities follow the configuration.
+#[tokio::test]
+async fn test_capabilities_follow_config() {
+    let config = Config {
+        rpc_url: "http://localhost:8545".to_string(),
+        chain_id: SEPOLIA_CHAIN_ID,
+        trusted_tokens_only: true,
+        stale_price_fallback: true,
+        enabled_tools: Some(vec!["get_capabilities".to_string(), "get_balance".to_string()]),
+        ..Default::default()
+    };
+    let server = EthereumTradingServer::new(config).unwrap();
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.get_capabilities().await.unwrap()).unwrap();
+
+    assert_eq!(parsed["network"], "sepolia");
+    assert!(!parsed["price_sources"].as_array().unwrap().contains(&"chainlink".into()));
+    assert_eq!(parsed["features"]["trusted_tokens_only"], true);
+    assert_eq!(parsed["features"]["stale_price_fallback"], true);
+    assert_eq!(parsed["enabled_tools"], serde_json::json!(["get_balance", "get_capabilities"]));
+}
@@ -0,0 +1,29 @@
+//! Integration tests for the get_contract_addresses tool.
+//!
+//! Run with: `cargo test --test test_get_contract_addresses`
+
+mod common;
+
+use ethereum_trading_mcp::{UNISWAP_V2_ROUTER, UNISWAP_V3_QUOTER, WETH_ADDRESS};
+
+/// Test that mainnet contract addresses are reported with the chain.
+#[tokio::test]
+async fn test_contract_addresses_mainnet() {
+    let server = skip_if_no_server!();
+
+    let result = server.get_contract_addresses().await;
+
+    assert!(result.is_ok(), "get_contract_addresses should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    assert_eq!(parsed["chain_id"], 1);
+    assert_eq!(parsed["network"], "mainnet");
+    assert_eq!(parsed["uniswap_v2_router"], format!("{:?}", UNISWAP_V2_ROUTER));
+    assert_eq!(parsed["uniswap_v3_quoter"], format!("{:?}", UNISWAP_V3_QUOTER));
+    assert_eq!(parsed["weth"], format!("{:?}", WETH_ADDRESS));
+
+    let feeds = parsed["chainlink_feeds"].as_array().unwrap();
+    assert!(feeds.iter().any(|feed| feed["pair"] == "ETH/USD"));
+    assert!(feeds.iter().all(|feed| feed["address"].as_str().unwrap().starts_with("0x")));
+}
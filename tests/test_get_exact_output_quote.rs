@@ -0,0 +1,125 @@
+//! Integration tests for the get_exact_output_quote tool.
+//!
+//! Run with: `cargo test --test test_get_exact_output_quote -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::GetExactOutputQuoteInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test quoting how much WETH is needed for exactly 1000 USDC.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_exact_output_weth_for_usdc() {
+    let server = skip_if_no_server!();
+
+    let input = GetExactOutputQuoteInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount_out: "1000".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_exact_output_quote(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_exact_output_quote should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["amount_out"], "1000");
+    // Parsed with USDC's 6 decimals, not WETH's 18
+    assert_eq!(parsed["amount_out_raw"], "1000000000");
+    assert_eq!(parsed["from_token"]["symbol"], "WETH");
+    assert_eq!(parsed["to_token"]["symbol"], "USDC");
+
+    let amount_in: f64 = parsed["amount_in"].as_str().unwrap().parse().unwrap();
+    assert!(amount_in > 0.0 && amount_in < 10.0, "Unexpected WETH input: {}", amount_in);
+
+    let price: f64 = parsed["price"].as_str().unwrap().parse().unwrap();
+    assert!(price > 0.0);
+    assert!(parsed["route"].get("protocol").is_some());
+
+    println!("WETH for 1000 USDC Quote: {}", json_str);
+}
+
+/// Test quoting in the other direction (USDC for exactly 0.1 WETH).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_exact_output_usdc_for_weth() {
+    let server = skip_if_no_server!();
+
+    let input = GetExactOutputQuoteInput {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount_out: "0.1".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_exact_output_quote(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_exact_output_quote should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    // Parsed with WETH's 18 decimals, not USDC's 6
+    assert_eq!(parsed["amount_out_raw"], "100000000000000000");
+    let amount_in: f64 = parsed["amount_in"].as_str().unwrap().parse().unwrap();
+    assert!(amount_in > 10.0, "0.1 WETH should cost more than 10 USDC");
+}
+
+/// Test that amount_out is parsed with to_token's decimals, not from_token's.
+///
+/// 0.0000001 is below one unit of USDC (6 decimals), so it parses to zero and is
+/// rejected. Parsed with WETH's 18 decimals it would be a valid amount.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_exact_output_uses_to_token_decimals() {
+    let server = skip_if_no_server!();
+
+    let input = GetExactOutputQuoteInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount_out: "0.0000001".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_exact_output_quote(Parameters(input)).await;
+
+    assert!(result.is_err(), "amount_out below one USDC unit should be rejected");
+}
+
+/// Test zero output amount (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_exact_output_zero_amount_error() {
+    let server = skip_if_no_server!();
+
+    let input = GetExactOutputQuoteInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount_out: "0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_exact_output_quote(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_exact_output_quote should fail for zero amount");
+}
+
+/// Test same token (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_exact_output_same_token_error() {
+    let server = skip_if_no_server!();
+
+    let input = GetExactOutputQuoteInput {
+        from_token: "USDC".to_string(),
+        to_token: "USDC".to_string(),
+        amount_out: "100".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_exact_output_quote(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_exact_output_quote should fail for same token");
+}
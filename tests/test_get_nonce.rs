@@ -0,0 +1,60 @@
+//! Integration tests for the get_nonce tool.
+//!
+//! Run with: `cargo test --test test_get_nonce -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::{GetNonceInput, GetWalletInfoInput};
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test that the configured wallet's nonces are returned by default.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_nonce_defaults_to_wallet() {
+    let server = skip_if_no_server!();
+
+    let result = server.get_nonce(Parameters(GetNonceInput::default())).await;
+
+    assert!(result.is_ok(), "get_nonce should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    let wallet: serde_json::Value = serde_json::from_str(
+        &server.get_wallet_info(Parameters(GetWalletInfoInput::default())).await.unwrap(),
+    )
+    .unwrap();
+    assert_eq!(parsed["address"], wallet["address"]);
+}
+
+/// Test the nonces of an address with many transactions.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_nonce_for_address() {
+    let server = skip_if_no_server!();
+
+    let input =
+        GetNonceInput { address: Some("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()) };
+
+    let result = server.get_nonce(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_nonce should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["address"], "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+    let latest = parsed["latest_nonce"].as_u64().unwrap();
+    let pending = parsed["pending_nonce"].as_u64().unwrap();
+    assert!(latest > 0);
+    assert_eq!(parsed["in_flight_transactions"].as_u64().unwrap(), pending.saturating_sub(latest));
+}
+
+/// Test that an invalid address is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_nonce_invalid_address_error() {
+    let server = skip_if_no_server!();
+
+    let input = GetNonceInput { address: Some("0xinvalid".to_string()) };
+
+    let result = server.get_nonce(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_nonce should reject an invalid address");
+}
@@ -0,0 +1,68 @@
+//! Integration tests for the get_pool_fee tool.
+//!
+//! Run with: `cargo test --test test_get_pool_fee -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::GetPoolFeeInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test that WETH/USDC lists its V2 pair and V3 pools with their fees.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_pool_fee_weth_usdc() {
+    let server = skip_if_no_server!();
+
+    let input = GetPoolFeeInput {
+        token_a: "WETH".to_string(),
+        token_b: "USDC".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_pool_fee(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_pool_fee should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    let pools = parsed["pools"].as_array().unwrap();
+
+    let v2 = pools.iter().find(|pool| pool["protocol"] == "v2").expect("WETH/USDC has a V2 pair");
+    assert_eq!(v2["fee_bps"], "30");
+    assert_eq!(v2["fee_percent"], "0.3");
+
+    let v3 =
+        pools.iter().find(|pool| pool["fee_tier"] == 500).expect("WETH/USDC has a 0.05% V3 pool");
+    assert_eq!(v3["protocol"], "v3");
+    assert_eq!(v3["fee_bps"], "5");
+    assert!(v3["pool"].as_str().unwrap().starts_with("0x"));
+}
+
+/// Test that an unknown symbol is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_pool_fee_unknown_token_error() {
+    let server = skip_if_no_server!();
+
+    let input = GetPoolFeeInput {
+        token_a: "WETH".to_string(),
+        token_b: "NOT_A_REAL_TOKEN_XYZ".to_string(),
+        ..Default::default()
+    };
+
+    assert!(server.get_pool_fee(Parameters(input)).await.is_err());
+}
+
+/// Test that a pair of the same token is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_pool_fee_same_token_error() {
+    let server = skip_if_no_server!();
+
+    let input = GetPoolFeeInput {
+        token_a: "USDC".to_string(),
+        token_b: "USDC".to_string(),
+        ..Default::default()
+    };
+
+    assert!(server.get_pool_fee(Parameters(input)).await.is_err());
+}
@@ -13,8 +13,11 @@ use rmcp::handler::server::wrapper::Parameters;
 async fn test_get_weth_price_usd() {
     let server = skip_if_no_server!();
 
-    let input =
-        GetTokenPriceInput { token: "WETH".to_string(), quote_currency: Some("USD".to_string()) };
+    let input = GetTokenPriceInput {
+        token: "WETH".to_string(),
+        quote_currency: Some("USD".to_string()),
+        ..Default::default()
+    };
 
     let result = server.get_token_price(Parameters(input)).await;
 
@@ -36,6 +39,9 @@ async fn test_get_weth_price_usd() {
     assert!(price > 100.0, "ETH price should be > $100, got {}", price);
     assert!(price < 100000.0, "ETH price should be < $100,000, got {}", price);
 
+    // WETH is priced from the Chainlink ETH/USD feed, not a Uniswap pool
+    assert_eq!(parsed["source"], "chainlink");
+
     println!("WETH Price (USD): {}", json_str);
 }
 
@@ -45,8 +51,11 @@ async fn test_get_weth_price_usd() {
 async fn test_get_weth_price_eth() {
     let server = skip_if_no_server!();
 
-    let input =
-        GetTokenPriceInput { token: "WETH".to_string(), quote_currency: Some("ETH".to_string()) };
+    let input = GetTokenPriceInput {
+        token: "WETH".to_string(),
+        quote_currency: Some("ETH".to_string()),
+        ..Default::default()
+    };
 
     let result = server.get_token_price(Parameters(input)).await;
 
@@ -68,8 +77,11 @@ async fn test_get_weth_price_eth() {
 async fn test_get_usdc_price_usd() {
     let server = skip_if_no_server!();
 
-    let input =
-        GetTokenPriceInput { token: "USDC".to_string(), quote_currency: Some("USD".to_string()) };
+    let input = GetTokenPriceInput {
+        token: "USDC".to_string(),
+        quote_currency: Some("USD".to_string()),
+        ..Default::default()
+    };
 
     let result = server.get_token_price(Parameters(input)).await;
 
@@ -93,8 +105,11 @@ async fn test_get_usdc_price_usd() {
 async fn test_get_uni_price() {
     let server = skip_if_no_server!();
 
-    let input =
-        GetTokenPriceInput { token: "UNI".to_string(), quote_currency: Some("USD".to_string()) };
+    let input = GetTokenPriceInput {
+        token: "UNI".to_string(),
+        quote_currency: Some("USD".to_string()),
+        ..Default::default()
+    };
 
     let result = server.get_token_price(Parameters(input)).await;
 
@@ -117,7 +132,11 @@ async fn test_get_uni_price() {
 async fn test_get_price_default_quote() {
     let server = skip_if_no_server!();
 
-    let input = GetTokenPriceInput { token: "WETH".to_string(), quote_currency: None };
+    let input = GetTokenPriceInput {
+        token: "WETH".to_string(),
+        quote_currency: None,
+        ..Default::default()
+    };
 
     let result = server.get_token_price(Parameters(input)).await;
 
@@ -132,6 +151,33 @@ async fn test_get_price_default_quote() {
     println!("WETH Price (default): {}", json_str);
 }
 
+/// Test aggregated (liquidity-weighted) Uniswap price.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_aggregate() {
+    let server = skip_if_no_server!();
+
+    let input = GetTokenPriceInput {
+        token: "UNI".to_string(),
+        quote_currency: Some("USD".to_string()),
+        aggregate: Some(true),
+        ..Default::default()
+    };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_token_price should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["source"], "uniswap_aggregate");
+    let price: f64 = parsed["price"].as_str().unwrap().parse().unwrap();
+    assert!(price > 0.0, "UNI aggregate price should be > 0, got {}", price);
+
+    println!("UNI Aggregate Price (USD): {}", json_str);
+}
+
 /// Test unknown token handling.
 #[tokio::test]
 #[ignore = "Requires network access and environment variables"]
@@ -141,6 +187,7 @@ async fn test_get_price_unknown_token() {
     let input = GetTokenPriceInput {
         token: "NOTAREALTOKEN123".to_string(),
         quote_currency: Some("USD".to_string()),
+        ..Default::default()
     };
 
     let result = server.get_token_price(Parameters(input)).await;
@@ -158,6 +205,7 @@ async fn test_get_price_invalid_quote() {
     let input = GetTokenPriceInput {
         token: "WETH".to_string(),
         quote_currency: Some("INVALID".to_string()),
+        ..Default::default()
     };
 
     let result = server.get_token_price(Parameters(input)).await;
@@ -165,3 +213,165 @@ async fn test_get_price_invalid_quote() {
     // Should return an error for invalid quote currency
     assert!(result.is_err(), "get_token_price should fail for invalid quote currency");
 }
+
+/// Test that symbol provenance is included when requested.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_with_provenance() {
+    let server = skip_if_no_server!();
+
+    let input = GetTokenPriceInput {
+        token: "usdc".to_string(),
+        include_provenance: Some(true),
+        ..Default::default()
+    };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_token_price should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    // The regular price fields are still present at the top level
+    assert!(parsed["price"].is_string());
+
+    let provenance = parsed["provenance"].as_array().unwrap();
+    assert_eq!(provenance.len(), 1);
+    assert_eq!(provenance[0]["symbol"], "usdc");
+    assert_eq!(provenance[0]["chain_id"], 1);
+    assert!(provenance[0]["source"].is_string());
+}
+
+/// Test that provenance is omitted by default.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_without_provenance() {
+    let server = skip_if_no_server!();
+
+    let input = GetTokenPriceInput { token: "WETH".to_string(), ..Default::default() };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_token_price should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert!(parsed.get("provenance").is_none());
+}
+
+/// Test pricing a token in several quote currencies in one call.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_multiple_quote_currencies() {
+    let server = skip_if_no_server!();
+
+    let input = GetTokenPriceInput {
+        token: "UNI".to_string(),
+        quote_currencies: Some(vec!["USD".to_string(), "eth".to_string(), "USD".to_string()]),
+        ..Default::default()
+    };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_token_price should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    assert_eq!(parsed["token"]["symbol"], "UNI");
+    let prices = parsed["prices"].as_object().unwrap();
+    assert_eq!(prices.len(), 2, "Duplicate currencies should be quoted once");
+    for currency in ["USD", "ETH"] {
+        let quote = &prices[currency];
+        let price: f64 = quote["price"].as_str().unwrap().parse().unwrap();
+        assert!(price > 0.0, "{} price should be positive", currency);
+        assert!(quote["source"].is_string());
+        assert!(quote["timestamp"].as_u64().unwrap() > 0);
+    }
+}
+
+/// Test that quote_currency and quote_currencies cannot be combined.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_conflicting_quote_currencies() {
+    let server = skip_if_no_server!();
+
+    let input = GetTokenPriceInput {
+        token: "WETH".to_string(),
+        quote_currency: Some("USD".to_string()),
+        quote_currencies: Some(vec!["ETH".to_string()]),
+        ..Default::default()
+    };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_token_price should reject both quote fields");
+
+    let input = GetTokenPriceInput {
+        token: "WETH".to_string(),
+        quote_currencies: Some(Vec::new()),
+        ..Default::default()
+    };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_token_price should reject an empty quote_currencies");
+}
+
+/// Test pricing at a custom reference amount.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_with_reference_amount() {
+    let server = skip_if_no_server!();
+
+    let input = GetTokenPriceInput {
+        token: "UNI".to_string(),
+        reference_amount: Some("100".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_token_price should succeed: {:?}", result.err());
+
+    // The price is still per token, not per reference amount
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    let price: f64 = parsed["price"].as_str().unwrap().parse().unwrap();
+    assert!(price > 0.1 && price < 1000.0, "UNI price out of range: {}", price);
+}
+
+/// Test that a non-positive reference amount is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_invalid_reference_amount() {
+    let server = skip_if_no_server!();
+
+    for amount in ["0", "-1", "abc"] {
+        let input = GetTokenPriceInput {
+            token: "UNI".to_string(),
+            reference_amount: Some(amount.to_string()),
+            ..Default::default()
+        };
+
+        let result = server.get_token_price(Parameters(input)).await;
+        assert!(result.is_err(), "reference_amount {} should be rejected", amount);
+    }
+}
+
+/// Test the human-readable text format.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_get_price_text_format() {
+    let server = skip_if_no_server!();
+
+    let input = GetTokenPriceInput {
+        token: "WETH".to_string(),
+        format: Some("text".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.get_token_price(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_token_price should succeed: {:?}", result.err());
+    let text = result.unwrap();
+    assert!(text.starts_with("1 WETH = "), "unexpected summary: {}", text);
+    assert!(serde_json::from_str::<serde_json::Value>(&text).is_err());
+}
@@ -0,0 +1,82 @@
+//! Integration tests for the get_usd_output_quote tool.
+//!
+//! Run with: `cargo test --test test_get_usd_output_quote -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::GetUsdOutputQuoteInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test quoting how much WETH is needed to receive $500 of USDC.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_usd_output_weth_for_usdc() {
+    let server = skip_if_no_server!();
+
+    let input = GetUsdOutputQuoteInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        usd_amount: "500".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_usd_output_quote(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_usd_output_quote should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["target_usd"], "500.00");
+    assert_eq!(parsed["from_token"]["symbol"], "WETH");
+    assert_eq!(parsed["to_token"]["symbol"], "USDC");
+
+    // USDC trades close to $1, so $500 is close to 500 USDC
+    let amount_out: f64 = parsed["amount_out"].as_str().unwrap().parse().unwrap();
+    assert!(amount_out > 450.0 && amount_out < 550.0, "Unexpected USDC output: {}", amount_out);
+
+    let amount_in: f64 = parsed["amount_in"].as_str().unwrap().parse().unwrap();
+    assert!(amount_in > 0.0 && amount_in < 5.0, "Unexpected WETH input: {}", amount_in);
+
+    let input_usd_rate: f64 = parsed["input_usd_rate"].as_str().unwrap().parse().unwrap();
+    assert!(input_usd_rate > 100.0, "Unexpected WETH USD rate: {}", input_usd_rate);
+    assert!(parsed["route"].get("protocol").is_some());
+
+    println!("WETH for $500 of USDC Quote: {}", json_str);
+}
+
+/// Test that a zero USD amount is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_usd_output_zero_amount() {
+    let server = skip_if_no_server!();
+
+    let input = GetUsdOutputQuoteInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        usd_amount: "0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_usd_output_quote(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_usd_output_quote should fail for zero amount");
+}
+
+/// Test that a malformed USD amount is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_usd_output_invalid_amount() {
+    let server = skip_if_no_server!();
+
+    let input = GetUsdOutputQuoteInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        usd_amount: "$500".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.get_usd_output_quote(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_usd_output_quote should fail for a malformed amount");
+}
@@ -0,0 +1,158 @@
+//! Integration tests for the get_wallet_info tool.
+//!
+//! Run with: `cargo test --test test_get_wallet_info -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::GetWalletInfoInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test that the wallet address is returned without balances by default.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_wallet_info_address_only() {
+    let server = skip_if_no_server!();
+
+    let result = server.get_wallet_info(Parameters(GetWalletInfoInput::default())).await;
+
+    assert!(result.is_ok(), "get_wallet_info should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["configured"], true);
+    let address = parsed["address"].as_str().unwrap();
+    assert!(address.starts_with("0x") && address.len() == 42);
+    assert!(parsed.get("eth_balance").is_none());
+
+    // The private key must never leak into the response
+    let private_key = std::env::var("ETHEREUM_PRIVATE_KEY").unwrap();
+    let key_hex = private_key.trim_start_matches("0x").to_lowercase();
+    assert!(!json_str.to_lowercase().contains(&key_hex));
+}
+
+/// Test wallet info with ETH and token balances.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_wallet_info_with_balances() {
+    let server = skip_if_no_server!();
+
+    let input = GetWalletInfoInput {
+        include_eth_balance: Some(true),
+        tokens: vec!["USDC".to_string(), "WETH".to_string()],
+        include_zero_balances: Some(true),
+        ..Default::default()
+    };
+
+    let result = server.get_wallet_info(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_wallet_info should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    assert_eq!(parsed["eth_balance"]["token"]["symbol"], "ETH");
+    let balances = parsed["token_balances"].as_array().unwrap();
+    assert_eq!(balances.len(), 2);
+    assert_eq!(balances[0]["token"]["symbol"], "USDC");
+    assert_eq!(balances[1]["token"]["symbol"], "WETH");
+}
+
+/// Test that zero balances are counted rather than listed by default.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_wallet_info_skips_zero_balances() {
+    let server = skip_if_no_server!();
+
+    let tokens = vec!["USDC".to_string(), "WETH".to_string(), "UNI".to_string()];
+    let input = GetWalletInfoInput { tokens: tokens.clone(), ..Default::default() };
+
+    let result = server.get_wallet_info(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_wallet_info should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    let listed = parsed.get("token_balances").and_then(|b| b.as_array()).map_or(0, Vec::len);
+    let skipped = parsed.get("zero_balance_tokens_skipped").and_then(|c| c.as_u64()).unwrap_or(0);
+    assert_eq!(listed + skipped as usize, tokens.len());
+    for balance in parsed.get("token_balances").and_then(|b| b.as_array()).into_iter().flatten() {
+        assert_ne!(balance["balance_raw"], "0");
+    }
+}
+
+/// Test valuing balances in ETH.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_wallet_info_valued_in_eth() {
+    let server = skip_if_no_server!();
+
+    let input = GetWalletInfoInput {
+        include_eth_balance: Some(true),
+        tokens: vec!["USDC".to_string(), "WETH".to_string()],
+        base_currency: Some("ETH".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.get_wallet_info(Parameters(input)).await;
+
+    assert!(result.is_ok(), "get_wallet_info should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+
+    assert_eq!(parsed["base_currency"], "ETH");
+    assert!(parsed["total_value"].as_str().is_some());
+    // ETH is worth its balance when valued in ETH (up to rounding)
+    let eth_balance: f64 = parsed["eth_balance"]["balance"].as_str().unwrap().parse().unwrap();
+    let eth_value: f64 = parsed["eth_balance"]["value"].as_str().unwrap().parse().unwrap();
+    assert!((eth_balance - eth_value).abs() < 1e-6);
+    for balance in parsed["token_balances"].as_array().unwrap() {
+        assert!(balance["value"].as_str().is_some());
+    }
+}
+
+/// Test unsupported base currency (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_wallet_info_invalid_base_currency_error() {
+    let server = skip_if_no_server!();
+
+    let input = GetWalletInfoInput {
+        tokens: vec!["USDC".to_string()],
+        base_currency: Some("JPY".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.get_wallet_info(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_wallet_info should fail for an unsupported base currency");
+}
+
+/// Test unknown token symbol (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_wallet_info_unknown_token_error() {
+    let server = skip_if_no_server!();
+
+    let input =
+        GetWalletInfoInput { tokens: vec!["NOTAREALTOKEN123".to_string()], ..Default::default() };
+
+    let result = server.get_wallet_info(Parameters(input)).await;
+
+    assert!(result.is_err(), "get_wallet_info should fail for unknown token");
+}
+
+/// Test read-only mode (no private key) reports that no wallet is configured.
+#[tokio::test]
+async fn test_wallet_info_read_only_mode() {
+    use ethereum_trading_mcp::{Config, EthereumTradingServer};
+
+    let config = Config { rpc_url: "http://localhost:8545".to_string(), ..Default::default() };
+    let server = EthereumTradingServer::new(config).unwrap();
+
+    let result = server.get_wallet_info(Parameters(GetWalletInfoInput::default())).await;
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["configured"], false);
+    assert!(parsed.get("address").is_none());
+    assert!(parsed["message"].as_str().unwrap().contains("read-only"));
+}
@@ -0,0 +1,72 @@
+//! Integration tests for the pool_fee_apr tool.
+//!
+//! Run with: `cargo test --test test_pool_fee_apr -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::PoolFeeAprInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Uniswap V3 USDC/WETH 0.05% pool.
+const V3_USDC_WETH_POOL: &str = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640";
+
+/// Uniswap V2 USDC/WETH pair.
+const V2_USDC_WETH_PAIR: &str = "0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc";
+
+/// Test the fee APR estimate of a busy V3 pool.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_pool_fee_apr_v3_pool() {
+    let server = skip_if_no_server!();
+
+    let input = PoolFeeAprInput { pool_address: V3_USDC_WETH_POOL.to_string(), blocks: Some(300) };
+
+    let result = server.pool_fee_apr(Parameters(input)).await;
+
+    assert!(result.is_ok(), "pool_fee_apr should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["protocol"], "v3");
+    assert_eq!(parsed["fee_tier"], 500);
+    assert_eq!(parsed["to_block"].as_u64().unwrap() - parsed["from_block"].as_u64().unwrap(), 299);
+    assert!(parsed["window_secs"].as_u64().unwrap() > 0);
+    assert!(parsed["swap_count"].as_u64().unwrap() > 0, "a busy pool should have swaps");
+
+    let apr: f64 = parsed["estimated_apr_percent"].as_str().unwrap().parse().unwrap();
+    assert!(apr > 0.0 && apr < 1_000.0, "implausible APR: {}", apr);
+    assert!(!parsed["assumptions"].as_array().unwrap().is_empty());
+}
+
+/// Test the fee APR estimate of a V2 pair.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_pool_fee_apr_v2_pair() {
+    let server = skip_if_no_server!();
+
+    let input = PoolFeeAprInput { pool_address: V2_USDC_WETH_PAIR.to_string(), blocks: None };
+
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.pool_fee_apr(Parameters(input)).await.unwrap()).unwrap();
+
+    assert_eq!(parsed["protocol"], "v2");
+    assert_eq!(parsed["fee_bps"], "30");
+    assert!(parsed.get("fee_tier").is_none());
+}
+
+/// Test that a non-pool address and an out-of-range window are rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_pool_fee_apr_invalid_input() {
+    let server = skip_if_no_server!();
+
+    // USDC token contract, not a pool
+    let input = PoolFeeAprInput {
+        pool_address: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_string(),
+        blocks: Some(10),
+    };
+    assert!(server.pool_fee_apr(Parameters(input)).await.is_err());
+
+    let input =
+        PoolFeeAprInput { pool_address: V3_USDC_WETH_POOL.to_string(), blocks: Some(100_000) };
+    assert!(server.pool_fee_apr(Parameters(input)).await.is_err());
+}
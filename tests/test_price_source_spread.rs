@@ -0,0 +1,62 @@
+//! Integration tests for the price_source_spread tool.
+//!
+//! Run with: `cargo test --test test_price_source_spread -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::PriceSourceSpreadInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test that WETH's Chainlink and Uniswap prices are close.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_price_source_spread_weth() {
+    let server = skip_if_no_server!();
+
+    let input = PriceSourceSpreadInput { token: "WETH".to_string(), ..Default::default() };
+    let result = server.price_source_spread(Parameters(input)).await;
+
+    assert!(result.is_ok(), "price_source_spread should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["chainlink"]["source"], "chainlink");
+    assert!(parsed["uniswap"]["source"].as_str().unwrap().starts_with("uniswap"));
+    assert_eq!(parsed["max_spread_bps"], 100);
+
+    let spread: f64 = parsed["spread_bps"].as_str().unwrap().parse().unwrap();
+    // A deep pool tracks the oracle within its deviation threshold and fee
+    assert!(spread.abs() < 100.0, "unexpected WETH spread: {} bps", spread);
+    assert_eq!(parsed["diverging"], false);
+    println!("WETH Chainlink/Uniswap spread: {} bps", spread);
+}
+
+/// Test that a zero allowed spread flags any difference.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_price_source_spread_zero_tolerance() {
+    let server = skip_if_no_server!();
+
+    let input = PriceSourceSpreadInput {
+        token: "WBTC".to_string(),
+        max_spread_bps: Some(0),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.price_source_spread(Parameters(input)).await.unwrap())
+            .unwrap();
+
+    let diverging = parsed["diverging"].as_bool().unwrap();
+    assert_eq!(diverging, parsed["spread_bps"] != "0");
+    assert_eq!(diverging, parsed.get("warnings").is_some());
+}
+
+/// Test that a token without a Chainlink feed is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_price_source_spread_no_feed() {
+    let server = skip_if_no_server!();
+
+    let input = PriceSourceSpreadInput { token: "UNI".to_string(), ..Default::default() };
+    let err = server.price_source_spread(Parameters(input)).await.unwrap_err();
+    assert!(err.message.contains("No Chainlink feed"), "unexpected error: {}", err.message);
+}
@@ -0,0 +1,107 @@
+//! Integration tests for the recent_pool_swaps tool.
+//!
+//! Run with: `cargo test --test test_recent_pool_swaps -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::RecentPoolSwapsInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Uniswap V3 USDC/WETH 0.05% pool.
+const V3_USDC_WETH_POOL: &str = "0x88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640";
+
+/// Uniswap V2 USDC/WETH pair.
+const V2_USDC_WETH_PAIR: &str = "0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc";
+
+/// Test recent swaps of a busy V3 pool.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_recent_swaps_v3_pool() {
+    let server = skip_if_no_server!();
+
+    let input = RecentPoolSwapsInput {
+        pool_address: V3_USDC_WETH_POOL.to_string(),
+        blocks: Some(100),
+        limit: Some(5),
+    };
+
+    let result = server.recent_pool_swaps(Parameters(input)).await;
+
+    assert!(result.is_ok(), "recent_pool_swaps should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["protocol"], "v3");
+    assert_eq!(parsed["token0"]["symbol"], "USDC");
+    assert_eq!(parsed["token1"]["symbol"], "WETH");
+
+    let swaps = parsed["swaps"].as_array().unwrap();
+    assert!(!swaps.is_empty(), "a busy pool should have swaps in 100 blocks");
+    assert!(swaps.len() <= 5);
+
+    // Newest first
+    let blocks: Vec<u64> = swaps.iter().map(|s| s["block_number"].as_u64().unwrap()).collect();
+    assert!(blocks.windows(2).all(|w| w[0] >= w[1]));
+
+    println!("V3 Pool Swaps: {}", json_str);
+}
+
+/// Test recent swaps of a V2 pair.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_recent_swaps_v2_pair() {
+    let server = skip_if_no_server!();
+
+    let input = RecentPoolSwapsInput {
+        pool_address: V2_USDC_WETH_PAIR.to_string(),
+        blocks: Some(2_000),
+        limit: Some(3),
+    };
+
+    let result = server.recent_pool_swaps(Parameters(input)).await;
+
+    assert!(result.is_ok(), "recent_pool_swaps should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["protocol"], "v2");
+    for swap in parsed["swaps"].as_array().unwrap() {
+        assert!(swap.get("direction").is_some());
+        assert!(swap.get("amount_in").is_some());
+        assert!(swap.get("amount_out").is_some());
+    }
+}
+
+/// Test that an address that is not a pool is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_recent_swaps_not_a_pool_error() {
+    let server = skip_if_no_server!();
+
+    let input = RecentPoolSwapsInput {
+        // Vitalik's EOA
+        pool_address: "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.recent_pool_swaps(Parameters(input)).await;
+
+    assert!(result.is_err(), "recent_pool_swaps should fail for a non-pool address");
+}
+
+/// Test that an oversized block range is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_recent_swaps_block_range_too_large_error() {
+    let server = skip_if_no_server!();
+
+    let input = RecentPoolSwapsInput {
+        pool_address: V3_USDC_WETH_POOL.to_string(),
+        blocks: Some(1_000_000),
+        ..Default::default()
+    };
+
+    let result = server.recent_pool_swaps(Parameters(input)).await;
+
+    assert!(result.is_err(), "recent_pool_swaps should fail for too many blocks");
+}
@@ -0,0 +1,27 @@
+//! Integration tests for the rpc_freshness tool.
+//!
+//! Run with: `cargo test --test test_rpc_freshness -- --ignored`
+
+mod common;
+
+/// Test that the connected node's lag behind the server's clock is reported.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_rpc_freshness() {
+    let server = skip_if_no_server!();
+
+    let result = server.rpc_freshness().await;
+
+    assert!(result.is_ok(), "rpc_freshness should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["chain_id"], 1);
+    assert!(parsed["latest_block"].as_u64().unwrap() > 0);
+    assert_eq!(parsed["max_lag_secs"], 60);
+
+    let lag_secs = parsed["lag_secs"].as_i64().unwrap();
+    let lagging = parsed["lagging"].as_bool().unwrap();
+    assert_eq!(lagging, lag_secs > 60);
+    assert_eq!(lagging, parsed.get("warnings").is_some());
+    println!("RPC lag: {}s ({} blocks)", lag_secs, parsed["lag_blocks"]);
+}
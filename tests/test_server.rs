@@ -4,8 +4,12 @@
 
 mod common;
 
+use ethereum_trading_mcp::mcp::{GetBalanceInput, GetTokenPriceInput, SwapTokensInput};
+use ethereum_trading_mcp::{Config, EthereumTradingServer};
+use rmcp::handler::server::common::schema_for_type;
 use rmcp::model::ServerInfo;
 use rmcp::ServerHandler;
+use serde_json::Value;
 
 /// Test server info.
 #[test]
@@ -16,3 +20,106 @@ fn test_server_info() {
     assert_eq!(info.server_info.name, "ethereum-trading-mcp");
     assert!(!info.server_info.version.is_empty());
 }
+
+/// Test that tool input schemas carry examples and the address format.
+#[test]
+fn test_input_schema_examples() {
+    let schema = schema_for_type::<GetBalanceInput>();
+    let properties = &schema["properties"];
+    assert_eq!(schema["examples"].as_array().map(Vec::len), Some(2));
+    assert_eq!(properties["address"]["pattern"], "^0x[0-9a-fA-F]{40}$");
+    assert_eq!(properties["token_address"]["pattern"], "^0x[0-9a-fA-F]{40}$");
+    assert!(properties["address"]["description"].is_string());
+    assert!(properties["address"]["examples"].is_array());
+    let required = schema["required"].as_array().unwrap();
+    assert_eq!(required, &vec![Value::from("address")]);
+
+    let schema = schema_for_type::<GetTokenPriceInput>();
+    assert!(schema["examples"].is_array());
+    assert_eq!(
+        schema["properties"]["quote_currency"]["examples"],
+        serde_json::json!(["USD", "ETH"])
+    );
+    assert_eq!(
+        schema["properties"]["quote_currencies"]["examples"],
+        serde_json::json!([["USD", "ETH"]])
+    );
+
+    let schema = schema_for_type::<SwapTokensInput>();
+    assert_eq!(schema["examples"][0]["from_token"], "WETH");
+    assert_eq!(
+        schema["properties"]["slippage_tolerance"]["examples"],
+        serde_json::json!(["0.1", "0.5", "1"])
+    );
+}
+
+/// Test that MCP_ENABLED_TOOLS limits the tools the server exposes.
+#[test]
+fn test_enabled_tools_allowlist() {
+    let config = Config {
+        rpc_url: "http://localhost:8545".to_string(),
+        enabled_tools: Some(vec!["get_token_price".to_string(), "get_balance".to_string()]),
+        ..Default::default()
+    };
+    let server = EthereumTradingServer::new(config).unwrap();
+
+    assert_eq!(server.enabled_tool_names(), vec!["get_balance", "get_token_price"]);
+    let instructions = server.get_info().instructions.unwrap();
+    assert!(instructions.ends_with("only enables: get_balance, get_token_price."));
+
+    // Without an allowlist every tool is exposed
+    let config = Config { rpc_url: "http://localhost:8545".to_string(), ..Default::default() };
+    let server = EthereumTradingServer::new(config).unwrap();
+    assert_eq!(server.enabled_tool_names(), EthereumTradingServer::tool_names());
+    assert!(EthereumTradingServer::tool_names().contains(&"swap_tokens".to_string()));
+    let instructions = server.get_info().instructions.unwrap();
+    assert!(!instructions.contains("only enables"));
+    // The tool summary is built from the registered tools
+    let tools = EthereumTradingServer::tool_names().join(", ");
+    assert!(instructions.ends_with(&format!(" Tools: {}.", tools)));
+    assert!(instructions.contains("get_erc1155_balance"));
+}
+
+/// Test that the server instructions describe this instance's configuration.
+#[test]
+fn test_instructions_describe_configuration() {
+    let config = Config { rpc_url: "http://localhost:8545".to_string(), ..Default::default() };
+    let instructions = EthereumTradingServer::new(config).unwrap().get_info().instructions.unwrap();
+
+    assert!(instructions.starts_with("Ethereum Trading MCP Server on mainnet (chain ID 1)."));
+    assert!(instructions.contains("Prices are quoted in USD or ETH from Chainlink feeds"));
+    assert!(instructions.contains("V2 pools on Uniswap and Sushiswap."));
+    assert!(instructions.contains("No wallet is configured, so the server is read-only"));
+    assert!(!instructions.contains("curated token list"));
+
+    let config = Config {
+        rpc_url: "http://localhost:8545".to_string(),
+        chain_id: 8453,
+        trusted_tokens_only: true,
+        stale_price_fallback: true,
+        ..Default::default()
+    };
+    let instructions = EthereumTradingServer::new(config).unwrap().get_info().instructions.unwrap();
+
+    assert!(instructions.starts_with("Ethereum Trading MCP Server on unknown (chain ID 8453)."));
+    assert!(instructions.contains("no built-in contract addresses or tokens"));
+    // Without a wrapped native token there are no ETH prices
+    assert!(instructions.contains("Prices are quoted in USD from Uniswap pools."));
+    assert!(instructions.contains("V2 pools on Uniswap."));
+    assert!(instructions.contains("falls back to the last known price"));
+    assert!(instructions.contains(
+        "Swap, quote, trade cost and liquidity tools only accept tokens from a curated token list."
+    ));
+
+    // A well-known development key; no funds are involved
+    let config = Config {
+        rpc_url: "http://localhost:8545".to_string(),
+        private_key: "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+            .to_string(),
+        ..Default::default()
+    };
+    let instructions = EthereumTradingServer::new(config).unwrap().get_info().instructions.unwrap();
+    assert!(instructions.contains(
+        "The configured wallet is the default sender and the recipient of simulated swaps"
+    ));
+}
@@ -0,0 +1,83 @@
+//! Integration tests for the simulate_add_liquidity tool.
+//!
+//! Run with: `cargo test --test test_simulate_add_liquidity -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::SimulateAddLiquidityInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Uniswap V2 Router02.
+const V2_ROUTER: &str = "0x7a250d5630b4cf539739df2c5dacb4c659f2488d";
+
+/// `addLiquidity(address,address,uint256,uint256,uint256,uint256,address,uint256)` selector.
+const ADD_LIQUIDITY_SELECTOR: &str = "0xe8e33700";
+
+/// Recipient used so the tests do not depend on a configured wallet.
+const RECIPIENT: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+/// Test a deposit into the WETH/USDC pair with more USDC than the ratio needs.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_simulate_add_liquidity_weth_usdc() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateAddLiquidityInput {
+        token_a: "WETH".to_string(),
+        token_b: "USDC".to_string(),
+        amount_a: "1".to_string(),
+        // Far more than 1 WETH is worth, so the WETH side decides
+        amount_b: "1000000".to_string(),
+        recipient: Some(RECIPIENT.to_string()),
+        ..Default::default()
+    };
+
+    let result = server.simulate_add_liquidity(Parameters(input)).await;
+
+    assert!(result.is_ok(), "simulate_add_liquidity should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert!(parsed["pair"].is_string());
+    assert_eq!(parsed["amount_a"], "1");
+
+    let amount_b: f64 = parsed["amount_b"].as_str().unwrap().parse().unwrap();
+    assert!(amount_b > 100.0 && amount_b < 1_000_000.0, "amount_b should follow the ratio");
+    let amount_b_min: f64 = parsed["amount_b_min"].as_str().unwrap().parse().unwrap();
+    assert!(amount_b_min < amount_b);
+
+    let lp_tokens: f64 = parsed["lp_tokens"].as_str().unwrap().parse().unwrap();
+    assert!(lp_tokens > 0.0);
+    let share: f64 = parsed["share_of_pool_percent"].as_str().unwrap().parse().unwrap();
+    assert!(share > 0.0 && share < 100.0);
+
+    assert_eq!(parsed["transaction"]["to"].as_str().unwrap().to_lowercase(), V2_ROUTER);
+    assert!(parsed["transaction"]["data"].as_str().unwrap().starts_with(ADD_LIQUIDITY_SELECTOR));
+    assert_eq!(parsed["transaction"]["value"], "0");
+}
+
+/// Test that invalid inputs are rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_simulate_add_liquidity_invalid_input() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateAddLiquidityInput {
+        token_a: "WETH".to_string(),
+        token_b: "WETH".to_string(),
+        amount_a: "1".to_string(),
+        amount_b: "1".to_string(),
+        recipient: Some(RECIPIENT.to_string()),
+        ..Default::default()
+    };
+    assert!(server.simulate_add_liquidity(Parameters(input)).await.is_err());
+
+    let input = SimulateAddLiquidityInput {
+        token_a: "WETH".to_string(),
+        token_b: "USDC".to_string(),
+        amount_a: "0".to_string(),
+        amount_b: "2000".to_string(),
+        recipient: Some(RECIPIENT.to_string()),
+        ..Default::default()
+    };
+    assert!(server.simulate_add_liquidity(Parameters(input)).await.is_err());
+}
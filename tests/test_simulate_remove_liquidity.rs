@@ -0,0 +1,76 @@
+//! Integration tests for the simulate_remove_liquidity tool.
+//!
+//! Run with: `cargo test --test test_simulate_remove_liquidity -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::SimulateRemoveLiquidityInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Uniswap V2 Router02.
+const V2_ROUTER: &str = "0x7a250d5630b4cf539739df2c5dacb4c659f2488d";
+
+/// `removeLiquidity(address,address,uint256,uint256,uint256,address,uint256)` selector.
+const REMOVE_LIQUIDITY_SELECTOR: &str = "0xbaa2abde";
+
+/// Owner used so the tests do not depend on a configured wallet.
+const OWNER: &str = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045";
+
+/// Test burning an explicit amount of WETH/USDC LP tokens.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_simulate_remove_liquidity_weth_usdc() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateRemoveLiquidityInput {
+        token_a: "WETH".to_string(),
+        token_b: "USDC".to_string(),
+        liquidity: Some("0.00001".to_string()),
+        owner: Some(OWNER.to_string()),
+        ..Default::default()
+    };
+
+    let result = server.simulate_remove_liquidity(Parameters(input)).await;
+
+    assert!(result.is_ok(), "simulate_remove_liquidity should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert!(parsed["pair"].is_string());
+    assert_eq!(parsed["lp_tokens"], "0.00001");
+
+    for (amount, min) in [("amount_a", "amount_a_min"), ("amount_b", "amount_b_min")] {
+        let amount: f64 = parsed[amount].as_str().unwrap().parse().unwrap();
+        let min: f64 = parsed[min].as_str().unwrap().parse().unwrap();
+        assert!(amount > 0.0 && min < amount);
+    }
+    let share: f64 = parsed["share_of_pool_percent"].as_str().unwrap().parse().unwrap();
+    assert!(share > 0.0 && share < 100.0);
+
+    assert_eq!(parsed["transaction"]["to"].as_str().unwrap().to_lowercase(), V2_ROUTER);
+    assert!(parsed["transaction"]["data"].as_str().unwrap().starts_with(REMOVE_LIQUIDITY_SELECTOR));
+}
+
+/// Test that invalid inputs are rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_simulate_remove_liquidity_invalid_input() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateRemoveLiquidityInput {
+        token_a: "WETH".to_string(),
+        token_b: "WETH".to_string(),
+        liquidity: Some("1".to_string()),
+        owner: Some(OWNER.to_string()),
+        ..Default::default()
+    };
+    assert!(server.simulate_remove_liquidity(Parameters(input)).await.is_err());
+
+    let input = SimulateRemoveLiquidityInput {
+        token_a: "WETH".to_string(),
+        token_b: "USDC".to_string(),
+        liquidity: Some("0".to_string()),
+        owner: Some(OWNER.to_string()),
+        ..Default::default()
+    };
+    assert!(server.simulate_remove_liquidity(Parameters(input)).await.is_err());
+}
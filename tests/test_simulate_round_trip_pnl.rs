@@ -0,0 +1,91 @@
+//! Integration tests for the simulate_round_trip_pnl tool.
+//!
+//! Run with: `cargo test --test test_simulate_round_trip_pnl -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::SimulateRoundTripPnlInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test a USDC -> WETH -> USDC round trip at the current price.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_round_trip_usdc_weth_current_price() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateRoundTripPnlInput {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1000".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.simulate_round_trip_pnl(Parameters(input)).await;
+
+    assert!(result.is_ok(), "simulate_round_trip_pnl should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["from_token"]["symbol"], "USDC");
+    assert_eq!(parsed["to_token"]["symbol"], "WETH");
+    assert_eq!(parsed["exit_price_is_hypothetical"], false);
+    assert_eq!(parsed["buy"]["amount_out_expected"], parsed["amount_bought"]);
+    assert_eq!(parsed["sell"]["amount_in"], parsed["amount_bought"]);
+
+    // Exiting at the entry price can only lose fees, price impact and gas
+    let net_pnl: f64 = parsed["net_pnl"].as_str().unwrap().parse().unwrap();
+    assert!(net_pnl < 0.0, "round trip at the current price should lose money");
+
+    println!("USDC -> WETH -> USDC Round Trip: {}", json_str);
+}
+
+/// Test a round trip exiting at a much higher hypothetical price.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_round_trip_hypothetical_exit_price() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateRoundTripPnlInput {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1000".to_string(),
+        exit_price: Some("1000000".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.simulate_round_trip_pnl(Parameters(input)).await;
+
+    assert!(result.is_ok(), "simulate_round_trip_pnl should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["exit_price_is_hypothetical"], true);
+    assert_eq!(parsed["exit_price"], "1000000");
+
+    let net_pnl: f64 = parsed["net_pnl"].as_str().unwrap().parse().unwrap();
+    assert!(net_pnl > 0.0, "exiting far above the current price should be profitable");
+}
+
+/// Test invalid input (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_round_trip_invalid_input() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateRoundTripPnlInput {
+        from_token: "WETH".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+    assert!(server.simulate_round_trip_pnl(Parameters(input)).await.is_err());
+
+    let input = SimulateRoundTripPnlInput {
+        from_token: "USDC".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1000".to_string(),
+        exit_price: Some("0".to_string()),
+        ..Default::default()
+    };
+    assert!(server.simulate_round_trip_pnl(Parameters(input)).await.is_err());
+}
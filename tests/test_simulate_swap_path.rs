@@ -0,0 +1,97 @@
+//! Integration tests for the simulate_swap_path tool.
+//!
+//! Run with: `cargo test --test test_simulate_swap_path -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::SimulateSwapPathInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+fn tokens(symbols: &[&str]) -> Vec<String> {
+    symbols.iter().map(|s| s.to_string()).collect()
+}
+
+/// Test a two-leg path where the first leg's output feeds the second leg.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_path_weth_usdc_uni() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateSwapPathInput {
+        tokens: tokens(&["WETH", "USDC", "UNI"]),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.simulate_swap_path(Parameters(input)).await;
+
+    assert!(result.is_ok(), "simulate_swap_path should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["completed"], true);
+    assert_eq!(parsed["final_token"]["symbol"], "UNI");
+
+    let legs = parsed["legs"].as_array().unwrap();
+    assert_eq!(legs.len(), 2);
+    // The second leg spends exactly what the first leg is expected to return
+    assert_eq!(legs[1]["amount_in"], legs[0]["amount_out_expected"]);
+    assert_eq!(parsed["final_amount_out"], legs[1]["amount_out_expected"]);
+
+    let total_gas: u64 = parsed["total_gas_estimate"].as_str().unwrap().parse().unwrap();
+    assert!(total_gas > 0);
+
+    println!("WETH -> USDC -> UNI Path: {}", json_str);
+}
+
+/// Test that a single token is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_path_too_short_error() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateSwapPathInput {
+        tokens: tokens(&["WETH"]),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.simulate_swap_path(Parameters(input)).await;
+
+    assert!(result.is_err(), "simulate_swap_path should fail for a single token");
+}
+
+/// Test that repeating a token in consecutive positions is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_path_repeated_token_error() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateSwapPathInput {
+        tokens: tokens(&["WETH", "WETH", "USDC"]),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.simulate_swap_path(Parameters(input)).await;
+
+    assert!(result.is_err(), "simulate_swap_path should fail for a repeated token");
+}
+
+/// Test unknown token in the path (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_path_unknown_token_error() {
+    let server = skip_if_no_server!();
+
+    let input = SimulateSwapPathInput {
+        tokens: tokens(&["WETH", "NOTAREALTOKEN123"]),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.simulate_swap_path(Parameters(input)).await;
+
+    assert!(result.is_err(), "simulate_swap_path should fail for an unknown token");
+}
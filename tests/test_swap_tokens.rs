@@ -4,7 +4,12 @@
 
 mod common;
 
-use ethereum_trading_mcp::mcp::SwapTokensInput;
+use alloy::primitives::U256;
+use ethereum_trading_mcp::{
+    ethereum::{quoter, EthereumClient},
+    mcp::{ForceRouteInput, SwapTokensInput},
+    UNISWAP_V3_QUOTER, UNI_ADDRESS, USDC_ADDRESS, WETH_ADDRESS,
+};
 use rmcp::handler::server::wrapper::Parameters;
 
 /// Test simulating WETH to USDC swap.
@@ -18,6 +23,7 @@ async fn test_swap_weth_to_usdc() {
         to_token: "USDC".to_string(),
         amount: "0.1".to_string(),
         slippage_tolerance: Some("0.5".to_string()),
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
@@ -28,12 +34,15 @@ async fn test_swap_weth_to_usdc() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     // Verify response structure
+    assert_eq!(parsed["executed"], false);
+    assert!(parsed["note"].as_str().unwrap().contains("no transaction was broadcast"));
     assert!(parsed.get("simulation_success").is_some());
     assert!(parsed.get("amount_in").is_some());
     assert!(parsed.get("amount_out_expected").is_some());
     assert!(parsed.get("amount_out_minimum").is_some());
     assert!(parsed.get("price_impact").is_some());
     assert!(parsed.get("gas_estimate").is_some());
+    assert!(parsed["gas_estimate_is_fallback"].is_boolean());
     assert!(parsed.get("gas_price").is_some());
     assert!(parsed.get("gas_cost_eth").is_some());
     assert!(parsed.get("route").is_some());
@@ -42,10 +51,16 @@ async fn test_swap_weth_to_usdc() {
     // Verify amount_in matches input
     assert_eq!(parsed["amount_in"], "0.1");
 
-    // Verify route contains protocol and path
+    // USDC can be priced, so the expected and worst-case outputs are valued in USD
+    let expected_usd: f64 = parsed["amount_out_expected_usd"].as_str().unwrap().parse().unwrap();
+    let minimum_usd: f64 = parsed["amount_out_minimum_usd"].as_str().unwrap().parse().unwrap();
+    assert!(minimum_usd > 0.0 && minimum_usd <= expected_usd);
+
+    // Verify route contains protocol, path and venue
     let route = &parsed["route"];
     assert!(route.get("protocol").is_some());
     assert!(route.get("path").is_some());
+    assert!(route.get("venue").is_some());
 
     // Verify transaction has required fields
     let tx = &parsed["transaction"];
@@ -66,6 +81,7 @@ async fn test_swap_usdc_to_weth() {
         to_token: "WETH".to_string(),
         amount: "100".to_string(), // 100 USDC
         slippage_tolerance: Some("1.0".to_string()),
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
@@ -99,6 +115,7 @@ async fn test_swap_default_slippage() {
         to_token: "USDC".to_string(),
         amount: "0.05".to_string(),
         slippage_tolerance: None, // Should default to 0.5%
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
@@ -131,6 +148,7 @@ async fn test_swap_uni_to_weth() {
         to_token: "WETH".to_string(),
         amount: "10".to_string(), // 10 UNI
         slippage_tolerance: Some("1.0".to_string()),
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
@@ -160,6 +178,7 @@ async fn test_swap_same_token_error() {
         to_token: "WETH".to_string(),
         amount: "1".to_string(),
         slippage_tolerance: None,
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
@@ -167,6 +186,40 @@ async fn test_swap_same_token_error() {
     assert!(result.is_err(), "swap_tokens should fail for same token");
 }
 
+/// Test that native ETH is rejected on either side of a swap, and that token
+/// swaps send no ETH value.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_native_eth_value_handling() {
+    let server = skip_if_no_server!();
+
+    for (from_token, to_token, field) in
+        [("ETH", "USDC", "from_token"), ("USDC", "eth", "to_token")]
+    {
+        let input = SwapTokensInput {
+            from_token: from_token.to_string(),
+            to_token: to_token.to_string(),
+            amount: "1".to_string(),
+            ..Default::default()
+        };
+        let err = server.swap_tokens(Parameters(input)).await.unwrap_err();
+        assert!(err.message.contains("Native ETH"), "unexpected error: {}", err.message);
+        assert!(err.message.contains(field), "error should name {}: {}", field, err.message);
+    }
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        transaction_format: Some("eip1559".to_string()),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+    assert_eq!(parsed["transaction"]["value"], "0");
+    assert_eq!(parsed["wallet_transaction"]["value"], "0x0");
+}
+
 /// Test swap with zero amount (should fail).
 #[tokio::test]
 #[ignore = "Requires network access and environment variables"]
@@ -178,6 +231,7 @@ async fn test_swap_zero_amount_error() {
         to_token: "USDC".to_string(),
         amount: "0".to_string(),
         slippage_tolerance: None,
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
@@ -196,6 +250,7 @@ async fn test_swap_invalid_slippage_error() {
         to_token: "USDC".to_string(),
         amount: "1".to_string(),
         slippage_tolerance: Some("100".to_string()), // 100% is too high
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
@@ -214,9 +269,629 @@ async fn test_swap_unknown_token_error() {
         to_token: "USDC".to_string(),
         amount: "1".to_string(),
         slippage_tolerance: None,
+        ..Default::default()
     };
 
     let result = server.swap_tokens(Parameters(input)).await;
 
     assert!(result.is_err(), "swap_tokens should fail for unknown token");
 }
+
+/// Test forcing a V2 route through WETH.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_forced_v2_route_via_weth() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "UNI".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "10".to_string(),
+        slippage_tolerance: Some("1.0".to_string()),
+        force_route: Some(ForceRouteInput {
+            protocol: "V2".to_string(),
+            fee_tier: None,
+            via: vec!["WETH".to_string()],
+            venue: None,
+        }),
+        include_provenance: Some(true),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_ok(), "forced V2 route should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["route"]["protocol"], "v2");
+    assert_eq!(parsed["route"]["path"].as_array().unwrap().len(), 3);
+    assert_eq!(parsed["route"]["venue"], "uniswap");
+
+    // Provenance covers from_token, to_token and the intermediary
+    let provenance = parsed["provenance"].as_array().unwrap();
+    assert_eq!(provenance.len(), 3);
+    assert_eq!(provenance[2]["symbol"], "WETH");
+}
+
+/// Test forcing a V2 route on Sushiswap.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_forced_v2_route_on_sushiswap() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        force_route: Some(ForceRouteInput {
+            protocol: "V2".to_string(),
+            venue: Some("sushiswap".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_ok(), "forced Sushiswap route should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["route"]["protocol"], "v2");
+    assert_eq!(parsed["route"]["venue"], "sushiswap");
+}
+
+/// Test forcing a V3 route at a specific fee tier.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_forced_v3_route_fee_tier() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        slippage_tolerance: Some("0.5".to_string()),
+        force_route: Some(ForceRouteInput {
+            protocol: "V3".to_string(),
+            fee_tier: Some(500),
+            via: vec![],
+            venue: None,
+        }),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_ok(), "forced V3 route should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["route"]["protocol"], "v3");
+    assert_eq!(parsed["route"]["fee_tier"], 500);
+    assert_eq!(parsed["route"]["venue"], "uniswap");
+
+    // The pool price after the trade is reported for V3 routes
+    let post_trade_price: f64 = parsed["post_trade_price"].as_str().unwrap().parse().unwrap();
+    assert!(post_trade_price > 0.0, "post_trade_price should be positive");
+}
+
+/// Test forced V3 route without a fee tier (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_forced_v3_route_missing_fee_error() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        force_route: Some(ForceRouteInput { protocol: "V3".to_string(), ..Default::default() }),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_err(), "forced V3 route without fee_tier should fail");
+}
+
+/// Test forced route through an intermediary that repeats an endpoint (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_forced_route_invalid_path_error() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        force_route: Some(ForceRouteInput {
+            protocol: "V2".to_string(),
+            fee_tier: None,
+            via: vec!["USDC".to_string()],
+            venue: None,
+        }),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_err(), "forced route repeating a token should fail");
+}
+
+/// Test that a caller-provided gas limit replaces gas estimation.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_gas_limit_override() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        gas_limit: Some(250_000),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_ok(), "swap_tokens should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["gas_estimate"], "250000");
+    assert_eq!(parsed["gas_limit_is_override"], true);
+    assert_eq!(parsed["gas_estimate_is_fallback"], false);
+    assert_eq!(parsed["transaction"]["gas_limit"], "250000");
+}
+
+/// Test that a gas limit outside 21000..=30000000 is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_gas_limit_out_of_range_error() {
+    let server = skip_if_no_server!();
+
+    for gas_limit in [20_999, 30_000_001] {
+        let input = SwapTokensInput {
+            from_token: "WETH".to_string(),
+            to_token: "USDC".to_string(),
+            amount: "0.1".to_string(),
+            gas_limit: Some(gas_limit),
+            ..Default::default()
+        };
+
+        let result = server.swap_tokens(Parameters(input)).await;
+
+        assert!(result.is_err(), "gas_limit {} should be rejected", gas_limit);
+    }
+}
+
+/// Test that an EIP-1559 wallet transaction is returned when requested.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_eip1559_wallet_transaction() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        gas_limit: Some(250_000),
+        transaction_format: Some("eip1559".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_ok(), "swap_tokens should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    let wallet_tx = &parsed["wallet_transaction"];
+    assert_eq!(wallet_tx["type"], "0x2");
+    assert_eq!(wallet_tx["chainId"], "0x1");
+    assert_eq!(wallet_tx["gas"], "0x3d090");
+    assert_eq!(wallet_tx["to"], parsed["transaction"]["to"]);
+    assert_eq!(wallet_tx["data"], parsed["transaction"]["data"]);
+    for field in ["from", "value", "maxFeePerGas", "maxPriorityFeePerGas", "nonce"] {
+        assert!(wallet_tx[field].as_str().unwrap().starts_with("0x"), "{} should be hex", field);
+    }
+
+    // The minimal transaction is still returned, and is the only one by default
+    assert!(parsed["transaction"]["data"].is_string());
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+    assert!(parsed.get("wallet_transaction").is_none());
+}
+
+/// Test that an unknown transaction format is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_invalid_transaction_format_error() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        transaction_format: Some("legacy".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_err(), "swap_tokens should reject an unknown transaction_format");
+}
+
+/// Test that a V2-first preference picks the V2 route for a pair with a V2 pool.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_v2_first_protocol_preference() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        protocol_preference: Some("v2_first".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_ok(), "swap_tokens should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["route"]["protocol"], "v2");
+}
+
+/// Test that the gas cost is expressed in the output token when requested.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_gas_cost_in_output_token() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        gas_cost_token: Some("to_token".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_ok(), "swap_tokens should succeed: {:?}", result.err());
+
+    let parsed: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert_eq!(parsed["gas_cost_token"], "USDC");
+    let gas_cost: f64 = parsed["gas_cost_in_token"].as_str().unwrap().parse().unwrap();
+    assert!(gas_cost > 0.0);
+
+    // Not requested by default, and rejected for anything but the traded tokens
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+    assert!(parsed.get("gas_cost_in_token").is_none());
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        gas_cost_token: Some("DAI".to_string()),
+        ..Default::default()
+    };
+    assert!(server.swap_tokens(Parameters(input)).await.is_err());
+}
+
+/// Test that an unknown protocol preference is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_invalid_protocol_preference_error() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        protocol_preference: Some("v4_first".to_string()),
+        ..Default::default()
+    };
+
+    let result = server.swap_tokens(Parameters(input)).await;
+
+    assert!(result.is_err(), "swap_tokens should reject an unknown protocol_preference");
+}
+
+/// Test that an invalid from_address, or one combined with a wallet transaction, is rejected.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_from_address_errors() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        from_address: Some("0x1234".to_string()),
+        ..Default::default()
+    };
+    assert!(server.swap_tokens(Parameters(input)).await.is_err());
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        from_address: Some("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".to_string()),
+        transaction_format: Some("eip1559".to_string()),
+        ..Default::default()
+    };
+    assert!(server.swap_tokens(Parameters(input)).await.is_err());
+}
+
+/// Test that zero slippage is raised to the floor unless allow_low_slippage is set.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_zero_slippage_floor() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.05".to_string(),
+        slippage_tolerance: Some("0".to_string()),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+
+    assert_ne!(parsed["amount_out_minimum"], parsed["amount_out_expected"]);
+    let warnings = parsed["warnings"].as_array().unwrap();
+    assert!(warnings.iter().any(|w| w["code"] == "slippage_raised"));
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.05".to_string(),
+        slippage_tolerance: Some("0".to_string()),
+        allow_low_slippage: Some(true),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+
+    assert_eq!(parsed["amount_out_minimum"], parsed["amount_out_expected"]);
+    assert!(parsed.get("warnings").is_none_or(|warnings| {
+        warnings.as_array().unwrap().iter().all(|w| w["code"] != "slippage_raised")
+    }));
+}
+
+/// Test the slippage table computed from the swap's quote.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_slippage_table() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        slippage_levels: Some(vec!["0.1".to_string(), "0.5".to_string(), "1".to_string()]),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+
+    let table = parsed["slippage_table"].as_array().unwrap();
+    let slippages: Vec<_> = table.iter().map(|level| level["slippage"].as_str().unwrap()).collect();
+    assert_eq!(slippages, ["0.1", "0.5", "1"]);
+
+    // Higher slippage, lower minimum; the default 0.5% matches amount_out_minimum
+    let min_outs: Vec<f64> =
+        table.iter().map(|level| level["min_out"].as_str().unwrap().parse().unwrap()).collect();
+    assert!(min_outs[0] > min_outs[1] && min_outs[1] > min_outs[2]);
+    assert_eq!(table[1]["min_out"], parsed["amount_out_minimum"]);
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        slippage_levels: Some(vec!["60".to_string()]),
+        ..Default::default()
+    };
+    assert!(server.swap_tokens(Parameters(input)).await.is_err());
+}
+
+/// Test cross-checking the quote against pool state on both protocols.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_cross_check_quote() {
+    let server = skip_if_no_server!();
+
+    for (protocol, fee_tier, method) in
+        [("V3", Some(500), "v3_single_tick"), ("V2", None, "v2_reserves")]
+    {
+        let input = SwapTokensInput {
+            from_token: "WETH".to_string(),
+            to_token: "USDC".to_string(),
+            amount: "0.1".to_string(),
+            force_route: Some(ForceRouteInput {
+                protocol: protocol.to_string(),
+                fee_tier,
+                via: Vec::new(),
+                venue: None,
+            }),
+            cross_check_quote: Some(true),
+            ..Default::default()
+        };
+        let parsed: serde_json::Value =
+            serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+
+        assert_eq!(parsed["quote_cross_checked"], true, "{} route", protocol);
+        let check = &parsed["quote_cross_check"];
+        assert_eq!(check["method"], method);
+        // A small swap on a deep pool matches its pool-state quote
+        assert_eq!(check["within_tolerance"], true, "{} cross-check: {}", protocol, check);
+    }
+
+    // Off by default
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+    assert_eq!(parsed["quote_cross_checked"], false);
+    assert!(parsed.get("quote_cross_check").is_none());
+}
+
+/// Test that the decoded calldata matches the simulated swap.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_decode_transaction() {
+    let server = skip_if_no_server!();
+
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        force_route: Some(ForceRouteInput {
+            protocol: "V3".to_string(),
+            fee_tier: Some(500),
+            via: Vec::new(),
+            venue: None,
+        }),
+        decode_transaction: Some(true),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+
+    let decoded = &parsed["decoded_transaction"];
+    assert_eq!(decoded["function"], "exactInputSingle");
+    assert_eq!(decoded["amount_in"], parsed["amount_in_raw"]);
+    assert_eq!(decoded["fee_tiers"], serde_json::json!([500]));
+    assert_eq!(decoded["path"].as_array().unwrap().len(), 2);
+    assert!(parsed["transaction"]["data"].as_str().unwrap().starts_with("0x414bf389"));
+
+    // Off by default
+    let input = SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        ..Default::default()
+    };
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(input)).await.unwrap()).unwrap();
+    assert!(parsed.get("decoded_transaction").is_none());
+}
+
+/// Test that min_amount_out replaces the slippage minimum only when stricter,
+/// and that the calldata carries the effective minimum.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_swap_min_amount_out() {
+    let server = skip_if_no_server!();
+
+    let swap = |min_amount_out: Option<&str>| SwapTokensInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0.1".to_string(),
+        min_amount_out: min_amount_out.map(str::to_string),
+        decode_transaction: Some(true),
+        ..Default::default()
+    };
+
+    // A floor of 1 USDC is looser than the slippage minimum
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(swap(Some("1")))).await.unwrap())
+            .unwrap();
+    assert_eq!(parsed["amount_out_minimum_source"], "slippage");
+    let raw_min = |parsed: &serde_json::Value| {
+        parsed["decoded_transaction"]["amount_out_min"].as_str().unwrap().parse::<u128>().unwrap()
+    };
+    let slippage_min = raw_min(&parsed);
+
+    // A floor at the expected output is stricter and is sent in the calldata
+    let expected = parsed["amount_out_expected"].as_str().unwrap().to_string();
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(swap(Some(&expected)))).await.unwrap())
+            .unwrap();
+    assert_eq!(parsed["amount_out_minimum_source"], "min_amount_out");
+    assert_eq!(parsed["amount_out_minimum"], expected);
+    assert!(raw_min(&parsed) > slippage_min);
+
+    // Far above the quote: the swap reverts and is flagged
+    let parsed: serde_json::Value =
+        serde_json::from_str(&server.swap_tokens(Parameters(swap(Some("1000000")))).await.unwrap())
+            .unwrap();
+    assert_eq!(parsed["simulation_success"], false);
+    let warnings = parsed["warnings"].as_array().unwrap();
+    assert!(warnings.iter().any(|w| w["code"] == "minimum_above_quote"));
+
+    // Malformed amounts are rejected
+    assert!(server.swap_tokens(Parameters(swap(Some("abc")))).await.is_err());
+}
+
+/// Test that a two-hop V3 path quote accounts for price impact on every hop,
+/// so it falls short of multiplying the per-hop spot rates.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_v3_path_quote_differs_from_per_hop_rates() {
+    let _ = dotenvy::dotenv();
+    let Ok(rpc_url) = std::env::var("ETHEREUM_RPC_URL") else {
+        eprintln!("Skipping test: ETHEREUM_RPC_URL not set");
+        return;
+    };
+    let client = EthereumClient::new(&rpc_url).unwrap();
+    let provider = client.provider();
+
+    let path = [UNI_ADDRESS, WETH_ADDRESS, USDC_ADDRESS];
+    let fees = [3000, 500];
+    let one_uni = U256::from(10u64).pow(U256::from(18u64));
+    let amount_in = one_uni * U256::from(50_000u64);
+
+    let quote =
+        quoter::quote_exact_input_path(provider, UNISWAP_V3_QUOTER, &path, &fees, amount_in)
+            .await
+            .unwrap();
+    assert_eq!(quote.sqrt_price_x96_after.len(), 2);
+    assert_eq!(quote.initialized_ticks_crossed.len(), 2);
+
+    // Per-hop rates from 1 UNI, scaled up without any price impact
+    let weth_per_uni = quoter::quote_exact_input_single(
+        provider,
+        UNISWAP_V3_QUOTER,
+        path[0],
+        path[1],
+        fees[0],
+        one_uni,
+    )
+    .await
+    .unwrap();
+    let usdc_per_weth = quoter::quote_exact_input_single(
+        provider,
+        UNISWAP_V3_QUOTER,
+        path[1],
+        path[2],
+        fees[1],
+        U256::from(10u64).pow(U256::from(18u64)),
+    )
+    .await
+    .unwrap();
+    let naive = weth_per_uni * usdc_per_weth * U256::from(50_000u64)
+        / U256::from(10u64).pow(U256::from(18u64));
+
+    println!("Path quote: {}, naive per-hop: {}", quote.amount_out, naive);
+    assert!(quote.amount_out > U256::ZERO);
+    assert!(quote.amount_out < naive, "path quote should include price impact");
+}
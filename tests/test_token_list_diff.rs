@@ -0,0 +1,28 @@
+//! Integration tests for the token_list_diff tool.
+//!
+//! Run with: `cargo test --test test_token_list_diff -- --ignored`
+
+mod common;
+
+/// Test that the first diff records a baseline and the next one compares against it.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_token_list_diff_baseline_then_compare() {
+    let server = skip_if_no_server!();
+
+    let result = server.token_list_diff().await;
+
+    assert!(result.is_ok(), "token_list_diff should succeed: {:?}", result.err());
+
+    let first: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+    assert!(first["token_count"].as_u64().unwrap() > 0);
+    assert!(first["source"].as_str().unwrap().starts_with("https://"));
+
+    let second: serde_json::Value =
+        serde_json::from_str(&server.token_list_diff().await.unwrap()).unwrap();
+    assert_eq!(second["has_previous_snapshot"], true);
+    assert!(second.get("message").is_none());
+    assert!(second["added"].is_array());
+    assert!(second["removed"].is_array());
+    assert!(second["changed"].is_array());
+}
@@ -0,0 +1,79 @@
+//! Integration tests for the trade_cost_breakdown tool.
+//!
+//! Run with: `cargo test --test test_trade_cost_breakdown -- --ignored`
+
+mod common;
+
+use ethereum_trading_mcp::mcp::TradeCostBreakdownInput;
+use rmcp::handler::server::wrapper::Parameters;
+
+/// Test a WETH -> USDC cost breakdown with USD figures.
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_trade_cost_weth_to_usdc() {
+    let server = skip_if_no_server!();
+
+    let input = TradeCostBreakdownInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.trade_cost_breakdown(Parameters(input)).await;
+
+    assert!(result.is_ok(), "trade_cost_breakdown should succeed: {:?}", result.err());
+
+    let json_str = result.unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
+
+    assert_eq!(parsed["from_token"]["symbol"], "WETH");
+    assert_eq!(parsed["to_token"]["symbol"], "USDC");
+    assert!(parsed.get("effective_rate").is_some());
+    assert!(parsed.get("amm_fee_percent").is_some());
+    assert!(parsed.get("price_impact_cost").is_some());
+
+    // Both tokens and ETH are priceable, so every USD figure should be present
+    for field in ["input_value_usd", "output_value_usd", "gas_cost_usd", "total_cost_usd"] {
+        let value: f64 = parsed[field].as_str().unwrap().parse().unwrap();
+        assert!(value >= 0.0, "{} should be non-negative", field);
+    }
+
+    println!("WETH -> USDC Cost Breakdown: {}", json_str);
+}
+
+/// Test same token (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_trade_cost_same_token_error() {
+    let server = skip_if_no_server!();
+
+    let input = TradeCostBreakdownInput {
+        from_token: "WETH".to_string(),
+        to_token: "WETH".to_string(),
+        amount: "1.0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.trade_cost_breakdown(Parameters(input)).await;
+
+    assert!(result.is_err(), "trade_cost_breakdown should fail for same token");
+}
+
+/// Test zero amount (should fail).
+#[tokio::test]
+#[ignore = "Requires network access and environment variables"]
+async fn test_trade_cost_zero_amount_error() {
+    let server = skip_if_no_server!();
+
+    let input = TradeCostBreakdownInput {
+        from_token: "WETH".to_string(),
+        to_token: "USDC".to_string(),
+        amount: "0".to_string(),
+        ..Default::default()
+    };
+
+    let result = server.trade_cost_breakdown(Parameters(input)).await;
+
+    assert!(result.is_err(), "trade_cost_breakdown should fail for zero amount");
+}